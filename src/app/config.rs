@@ -0,0 +1,266 @@
+//! # Config
+//!
+//! Configuração do shell, lida de `/state/config/shell.conf`.
+//!
+//! ## Formato
+//!
+//! TOML (ver [`super::toml`]), com uma tabela `[wallpaper]`:
+//!
+//! ```toml
+//! [wallpaper]
+//! path = "/system/resources/wallpapers/default.webp"
+//! mode = "fill"              # fill, fit, stretch, center ou tile
+//! background = "#1A1A2E"     # #RRGGBB ou #AARRGGBB
+//! gradient_top = "#FF4500"
+//! gradient_bottom = "#CC3500"
+//! # Slideshow (opcional): diretório, segundos por imagem e cross-fade em
+//! # ms (0 = troca direta)
+//! slideshow = "/system/resources/wallpapers"
+//! interval = 300
+//! fade = 1000
+//! ```
+//!
+//! Arquivo ausente usa os padrões, assim como um arquivo que não é TOML
+//! válido (o erro é reportado com o número da linha). Chaves desconhecidas
+//! ou com valor inválido são ignoradas com um aviso; o resto do arquivo
+//! continua valendo.
+
+use alloc::string::{String, ToString};
+use gfx_types::color::Color;
+
+use super::toml::{Document, Value};
+use crate::ui::{SlideshowConfig, WallpaperConfig, WallpaperMode};
+use crate::util::fs::{read_file, ReadError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Caminho do arquivo de configuração.
+pub const SHELL_CONFIG_PATH: &str = "/state/config/shell.conf";
+
+/// Maior arquivo de configuração aceito.
+const MAX_CONFIG_SIZE: usize = 16 * 1024;
+
+// =============================================================================
+// CONFIGURAÇÃO
+// =============================================================================
+
+/// Configuração do shell.
+#[derive(Debug, Clone, Default)]
+pub struct ShellConfig {
+    /// Papel de parede.
+    pub wallpaper: WallpaperConfig,
+}
+
+impl ShellConfig {
+    /// Lê a configuração de [`SHELL_CONFIG_PATH`] (padrões se ausente).
+    pub fn load() -> Self {
+        let mut config = Self::default();
+
+        let Some(content) = read_config(SHELL_CONFIG_PATH) else {
+            redpowder::println!("[Config] {} ausente, usando padroes", SHELL_CONFIG_PATH);
+            return config;
+        };
+
+        config.parse(&content);
+        redpowder::println!("[Config] {} carregado", SHELL_CONFIG_PATH);
+        config
+    }
+
+    /// Aplica o conteúdo de `content` sobre a configuração atual.
+    fn parse(&mut self, content: &str) {
+        let document = match Document::parse(content) {
+            Ok(document) => document,
+            Err(e) => {
+                redpowder::println!(
+                    "[Config] WARN: Linha {}: {:?}, usando padroes",
+                    e.line,
+                    e.kind
+                );
+                return;
+            }
+        };
+
+        let mut slideshow: Option<SlideshowConfig> = None;
+        let mut interval_ms = None;
+        let mut fade_ms = None;
+
+        for table in &document.tables {
+            for (key, value) in &table.entries {
+                let result = match (table.name.as_str(), key.as_str()) {
+                    ("wallpaper", "path") => {
+                        string(value).map(|path| self.wallpaper.path = path.to_string())
+                    }
+                    ("wallpaper", "mode") => string(value)
+                        .and_then(|name| WallpaperMode::from_name(name).ok_or("modo invalido"))
+                        .map(|mode| self.wallpaper.mode = mode),
+                    ("wallpaper", "background") => string(value)
+                        .and_then(parse_color)
+                        .map(|color| self.wallpaper.background = color),
+                    ("wallpaper", "gradient_top") => string(value)
+                        .and_then(parse_color)
+                        .map(|color| self.wallpaper.gradient_top = color),
+                    ("wallpaper", "gradient_bottom") => string(value)
+                        .and_then(parse_color)
+                        .map(|color| self.wallpaper.gradient_bottom = color),
+                    ("wallpaper", "slideshow") => string(value).map(|dir| {
+                        slideshow = (!dir.is_empty()).then(|| SlideshowConfig::new(dir.to_string()))
+                    }),
+                    ("wallpaper", "interval") => integer(value)
+                        .and_then(|seconds| {
+                            u64::try_from(seconds)
+                                .ok()
+                                .filter(|&seconds| seconds > 0)
+                                .ok_or("intervalo invalido")
+                        })
+                        .map(|seconds| interval_ms = Some(seconds.saturating_mul(1000))),
+                    ("wallpaper", "fade") => integer(value)
+                        .and_then(|ms| u64::try_from(ms).map_err(|_| "fade invalido"))
+                        .map(|ms| fade_ms = Some(ms)),
+                    _ => Err("chave desconhecida"),
+                };
+
+                if let Err(reason) = result {
+                    redpowder::println!("[Config] WARN: [{}] {}: {}", table.name, key, reason);
+                }
+            }
+        }
+
+        // Intervalo e fade podem vir antes do diretório
+        if let Some(ref mut slideshow) = slideshow {
+            if let Some(ms) = interval_ms {
+                slideshow.interval_ms = ms;
+            }
+            if let Some(ms) = fade_ms {
+                slideshow.fade_ms = ms;
+            }
+        }
+        self.wallpaper.slideshow = slideshow;
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Lê o arquivo de configuração inteiro.
+fn read_config(path: &str) -> Option<String> {
+    let data = match read_file(path, MAX_CONFIG_SIZE) {
+        Ok(data) => data,
+        Err(ReadError::Open) => return None,
+        Err(ReadError::TooLarge) => {
+            redpowder::println!("[Config] ERRO: {} grande demais", path);
+            return None;
+        }
+        Err(ReadError::Read) => {
+            redpowder::println!("[Config] ERRO: Falha ao ler {}", path);
+            return None;
+        }
+    };
+
+    match String::from_utf8(data) {
+        Ok(content) => Some(content),
+        Err(_) => {
+            redpowder::println!("[Config] ERRO: {} nao e UTF-8 valido", path);
+            None
+        }
+    }
+}
+
+/// Valor string.
+fn string(value: &Value) -> Result<&str, &'static str> {
+    value.as_str().ok_or("esperado string")
+}
+
+/// Valor inteiro.
+fn integer(value: &Value) -> Result<i64, &'static str> {
+    value.as_integer().ok_or("esperado inteiro")
+}
+
+/// Lê uma cor `#RRGGBB` (opaca) ou `#AARRGGBB`.
+fn parse_color(value: &str) -> Result<Color, &'static str> {
+    let hex = value.strip_prefix('#').ok_or("cor sem #")?;
+    // `from_str_radix` aceitaria um `+` inicial
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("cor invalida");
+    }
+    let argb = u32::from_str_radix(hex, 16).map_err(|_| "cor invalida")?;
+    match hex.len() {
+        6 => Ok(Color(0xFF00_0000 | argb)),
+        8 => Ok(Color(argb)),
+        _ => Err("cor invalida"),
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> WallpaperConfig {
+        let mut config = ShellConfig::default();
+        config.parse(content);
+        config.wallpaper
+    }
+
+    #[test]
+    fn reads_wallpaper_table() {
+        let wallpaper = parse(
+            "[wallpaper]\n\
+             path = \"/img/a.png\"\n\
+             mode = \"tile\"\n\
+             background = \"#80102030\"\n\
+             gradient_top = \"#FF4500\"\n\
+             fade = 0\n\
+             interval = 10\n\
+             slideshow = \"/img\"\n",
+        );
+
+        assert_eq!(wallpaper.path, "/img/a.png");
+        assert_eq!(wallpaper.mode, WallpaperMode::Tile);
+        assert_eq!(wallpaper.background.0, 0x8010_2030);
+        assert_eq!(wallpaper.gradient_top.0, 0xFFFF_4500);
+
+        let slideshow = wallpaper.slideshow.unwrap();
+        assert_eq!(slideshow.dir, "/img");
+        assert_eq!((slideshow.interval_ms, slideshow.fade_ms), (10_000, 0));
+    }
+
+    #[test]
+    fn skips_invalid_values() {
+        let defaults = WallpaperConfig::default();
+        let wallpaper = parse(
+            "path = \"/fora/da/tabela.png\"\n\
+             [wallpaper]\n\
+             path = 42\n\
+             mode = \"zoom\"\n\
+             background = \"#12345G\"\n\
+             gradient_top = \"#+12345\"\n\
+             gradient_bottom = \"#FFF\"\n\
+             interval = 0\n\
+             fade = -1\n\
+             slideshow = \"/img\"\n",
+        );
+
+        assert_eq!(wallpaper.path, defaults.path);
+        assert_eq!(wallpaper.mode, defaults.mode);
+        assert_eq!(wallpaper.background.0, defaults.background.0);
+        assert_eq!(wallpaper.gradient_top.0, defaults.gradient_top.0);
+        assert_eq!(wallpaper.gradient_bottom.0, defaults.gradient_bottom.0);
+
+        let slideshow = wallpaper.slideshow.unwrap();
+        let expected = SlideshowConfig::new(String::from("/img"));
+        assert_eq!(slideshow.interval_ms, expected.interval_ms);
+        assert_eq!(slideshow.fade_ms, expected.fade_ms);
+    }
+
+    #[test]
+    fn invalid_toml_keeps_defaults() {
+        let wallpaper = parse("[wallpaper]\nmode = \"tile\"\npath = /sem/aspas\n");
+        assert_eq!(wallpaper.mode, WallpaperMode::default());
+    }
+}
@@ -1,16 +1,35 @@
 //! # Desktop
 //!
 //! Desktop Environment principal do Shell.
+//!
+//! ## Controle via IPC
+//!
+//! Além dos eventos de lifecycle do compositor, a porta `shell.taskbar`
+//! aceita mensagens de controle:
+//!
+//! - [`OP_RELOAD_CONFIG`]: relê `shell.conf` e aplica o wallpaper
+//! - [`OP_SET_WALLPAPER`]: troca a imagem e/ou o modo ([`SetWallpaperRequest`])
+//! - [`OP_REFRESH_APPS`]: relê a lista de apps (enviada pelo instalador de
+//!   pacotes após instalar ou remover um app)
+//!
+//! A lista de apps também pode ser relida pelo botão "Atualizar" do menu
+//! iniciar.
 
-use alloc::string::ToString;
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::string::String;
 use alloc::vec::Vec;
-use gfx_types::geometry::Size;
+use gfx_types::geometry::{Rect, Size};
 use gfx_types::window::WindowFlags;
 
-use crate::app::{discover_apps, launch_app, AppInfo};
+use crate::app::{
+    discover_apps, launch_app_info, rescan_apps, AppInfo, ShellConfig, SHELL_CONFIG_PATH,
+};
+use crate::render::{font, Canvas, DamageRegion, FontSet};
 use crate::ui::panels::StartMenuAction;
 use crate::ui::{
-    Panel, QuickSettingsPanel, StartMenuPanel, Taskbar, TaskbarAction, Wallpaper, WidgetPanel,
+    IconCache, Panel, QuickSettingsPanel, StartMenuPanel, Taskbar, TaskbarAction, Wallpaper,
+    WallpaperMode, WidgetPanel,
 };
 
 use redpowder::event::event_type;
@@ -29,10 +48,53 @@ use redpowder::window::{
 const LISTENER_PORT_NAME: &str = "shell.taskbar";
 const FRAME_INTERVAL_MS: u64 = 16;
 
+/// Tempo para um app `single_instance` abrir a janela depois do spawn.
+const INSTANCE_WINDOW_TIMEOUT_MS: u64 = 10_000;
+
+// =============================================================================
+// PROTOCOLO DE CONTROLE
+// =============================================================================
+
+/// Relê a configuração (mensagem só com o opcode).
+pub const OP_RELOAD_CONFIG: u32 = 0x5348_0001;
+
+/// Troca o wallpaper ([`SetWallpaperRequest`]).
+pub const OP_SET_WALLPAPER: u32 = 0x5348_0002;
+
+/// Relê a lista de apps (mensagem só com o opcode).
+pub const OP_REFRESH_APPS: u32 = 0x5348_0003;
+
+/// Pedido de troca de wallpaper.
+///
+/// Desliga o slideshow. `mode` 0 mantém o modo atual (1 = fill ...
+/// 5 = tile); `path` vazio mantém a imagem atual.
+#[repr(C)]
+pub struct SetWallpaperRequest {
+    pub op: u32,
+    pub mode: u32,
+    /// Caminho UTF-8, completado com zeros.
+    pub path: [u8; 128],
+}
+
 // =============================================================================
 // DESKTOP
 // =============================================================================
 
+/// Instância de um app `single_instance` lançada pelo shell.
+///
+/// O evento de lifecycle não traz o processo dono da janela; uma janela
+/// nova é atribuída à instância pendente do app cujo nome é igual ao
+/// título (mesma regra da taskbar). Janelas de outros títulos deixam a
+/// instância pendente.
+struct AppInstance {
+    /// Id do app.
+    app_id: String,
+    /// Janela do app (`None` até ela ser criada).
+    window_id: Option<u32>,
+    /// Momento do spawn (ms).
+    launched_at: u64,
+}
+
 /// Desktop Environment principal.
 pub struct Desktop {
     /// Janela principal.
@@ -59,9 +121,16 @@ pub struct Desktop {
 
     /// Apps descobertos.
     available_apps: Vec<AppInfo>,
+    /// Cache de ícones compartilhado.
+    icons: Rc<IconCache>,
+    /// Instâncias de apps `single_instance` em execução.
+    instances: Vec<AppInstance>,
 
-    /// Flag de dirty.
-    dirty: bool,
+    /// Fontes da UI.
+    fonts: FontSet,
+
+    /// Regiões a redesenhar no próximo frame.
+    damage: DamageRegion,
     /// Contador de frames.
     frame_count: u64,
 }
@@ -84,13 +153,20 @@ impl Desktop {
         let window = Window::create_with_flags(0, 0, screen_width, screen_height, flags, "Shell")?;
         println!("[Shell] Janela desktop criada");
 
+        // Carregar fontes
+        let fonts = FontSet::load();
+
+        // Carregar configuração
+        let config = ShellConfig::load();
+
         // Criar componentes visuais
-        let wallpaper = Wallpaper::new(screen_width, screen_height);
-        let mut taskbar = Taskbar::new(screen_width, screen_height);
+        let wallpaper = Wallpaper::new(screen_width, screen_height, config.wallpaper);
+        let icons = Rc::new(IconCache::new());
+        let mut taskbar = Taskbar::new(screen_width, screen_height, icons.clone());
 
         // Criar painéis
         let widget_panel = WidgetPanel::new(screen_width, screen_height);
-        let mut start_menu = StartMenuPanel::new(screen_width, screen_height);
+        let mut start_menu = StartMenuPanel::new(screen_width, screen_height, icons.clone());
         let quick_settings = QuickSettingsPanel::new(screen_width, screen_height);
 
         // Descobrir apps
@@ -109,6 +185,10 @@ impl Desktop {
         // Registrar como taskbar
         Self::register_with_compositor();
 
+        // Primeiro frame desenha a tela inteira
+        let mut damage = DamageRegion::new(Rect::new(0, 0, screen_width, screen_height));
+        damage.add_all();
+
         Ok(Self {
             window,
             listener_port,
@@ -119,7 +199,10 @@ impl Desktop {
             start_menu,
             quick_settings,
             available_apps,
-            dirty: true,
+            icons,
+            instances: Vec::new(),
+            fonts,
+            damage,
             frame_count: 0,
         })
     }
@@ -164,27 +247,40 @@ impl Desktop {
         loop {
             self.frame_count += 1;
 
-            // Heartbeat periódico
             if let Ok(ms) = redpowder::time::clock() {
+                // Heartbeat periódico
                 if ms - last_heartbeat > 10000 {
-                    println!("[Shell] Frame {}, dirty={}", self.frame_count, self.dirty);
+                    println!(
+                        "[Shell] Frame {}, damage={}",
+                        self.frame_count,
+                        self.damage.rects().len()
+                    );
                     last_heartbeat = ms;
                 }
+
+                // Slideshow e cross-fade do wallpaper
+                if self.wallpaper.update(ms) {
+                    self.damage.add_all();
+                }
             }
 
-            // Processar eventos do compositor
-            self.process_lifecycle_events(&mut msg_buf);
+            // Processar eventos do compositor e mensagens de controle
+            self.process_messages(&mut msg_buf);
 
             // Processar input
             self.process_input();
 
+            // Relógio da taskbar
+            if self.taskbar.update_uptime() {
+                self.damage.add(self.taskbar.status_bounds());
+            }
+
             // Atualizar animações
-            let animating = self.update_animations();
+            self.update_animations();
 
-            // Redesenhar se necessário
-            if self.dirty || animating {
+            // Redesenhar apenas o que mudou
+            if !self.damage.is_empty() {
                 self.redraw();
-                self.dirty = false;
             }
 
             // Estabilizar framerate
@@ -192,8 +288,8 @@ impl Desktop {
         }
     }
 
-    /// Processa eventos de lifecycle do compositor.
-    fn process_lifecycle_events(&mut self, buf: &mut [u8; 256]) {
+    /// Processa eventos de lifecycle do compositor e mensagens de controle.
+    fn process_messages(&mut self, buf: &mut [u8; 256]) {
         while let Ok(size) = self.listener_port.recv(buf, 0) {
             if size < 4 {
                 break;
@@ -201,42 +297,143 @@ impl Desktop {
 
             let opcode = unsafe { *(buf.as_ptr() as *const u32) };
 
-            if opcode == opcodes::EVENT_WINDOW_LIFECYCLE {
-                let evt = unsafe { &*(buf.as_ptr() as *const WindowLifecycleEvent) };
-                self.handle_lifecycle_event(evt);
+            match opcode {
+                opcodes::EVENT_WINDOW_LIFECYCLE => {
+                    let evt = unsafe { &*(buf.as_ptr() as *const WindowLifecycleEvent) };
+                    self.handle_lifecycle_event(evt);
+                }
+                OP_RELOAD_CONFIG => self.reload_config(),
+                OP_REFRESH_APPS => self.refresh_apps(),
+                OP_SET_WALLPAPER if size >= core::mem::size_of::<SetWallpaperRequest>() => {
+                    let req = unsafe { &*(buf.as_ptr() as *const SetWallpaperRequest) };
+                    self.handle_set_wallpaper(req);
+                }
+                _ => {}
             }
         }
     }
 
+    /// Relê a configuração e aplica o que mudou.
+    fn reload_config(&mut self) {
+        println!("[Shell] Recarregando {}", SHELL_CONFIG_PATH);
+        let config = ShellConfig::load();
+        self.wallpaper.apply_config(config.wallpaper);
+        self.damage.add_all();
+    }
+
+    /// Relê os apps instalados e atualiza taskbar e menu iniciar.
+    ///
+    /// Janelas abertas e o estado dos painéis são mantidos; tudo que se
+    /// refere a apps usa o id, não a posição na lista.
+    fn refresh_apps(&mut self) {
+        println!("[Shell] Atualizando lista de apps...");
+        let apps = rescan_apps();
+        println!("[Shell] {} apps encontrados", apps.len());
+
+        // Apps removidos, (re)instalados ou com outro ícone perdem as cópias
+        // em disco; os demais são revalidados pelo hash do conteúdo
+        let same_icon = |app: &AppInfo, list: &[AppInfo]| {
+            list.iter()
+                .any(|other| other.id == app.id && other.icon_path == app.icon_path)
+        };
+        let changed: BTreeSet<&str> = self
+            .available_apps
+            .iter()
+            .chain(&apps)
+            .filter(|app| !same_icon(app, &self.available_apps) || !same_icon(app, &apps))
+            .map(|app| app.id.as_str())
+            .collect();
+        for id in changed {
+            self.icons.invalidate(id);
+        }
+        self.icons.clear();
+
+        self.taskbar.set_available_apps(apps.clone());
+        self.start_menu.set_apps(apps.clone());
+        self.available_apps = apps;
+
+        self.damage.add(self.taskbar.center_bounds());
+        if self.start_menu.is_visible() {
+            self.damage.add(self.start_menu.bounds());
+        }
+    }
+
+    /// Troca o wallpaper a pedido de outro processo.
+    fn handle_set_wallpaper(&mut self, req: &SetWallpaperRequest) {
+        let mut config = self.wallpaper.config().clone();
+        config.slideshow = None;
+
+        if req.mode != 0 {
+            match WallpaperMode::from_id(req.mode) {
+                Some(mode) => config.mode = mode,
+                None => {
+                    println!("[Shell] WARN: Modo de wallpaper invalido: {}", req.mode);
+                    return;
+                }
+            }
+        }
+
+        let len = req
+            .path
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(req.path.len());
+        if len > 0 {
+            match core::str::from_utf8(&req.path[..len]) {
+                Ok(path) => config.path = path.into(),
+                Err(_) => {
+                    println!("[Shell] WARN: Caminho de wallpaper nao e UTF-8");
+                    return;
+                }
+            }
+        }
+
+        println!("[Shell] Wallpaper: {} ({:?})", config.path, config.mode);
+        self.wallpaper.apply_config(config);
+        self.damage.add_all();
+    }
+
     /// Trata evento de lifecycle.
     fn handle_lifecycle_event(&mut self, evt: &WindowLifecycleEvent) {
-        let title_len = evt
-            .title
-            .iter()
-            .position(|&c| c == 0)
-            .unwrap_or(evt.title.len());
-        let title = core::str::from_utf8(&evt.title[..title_len])
-            .unwrap_or("")
-            .to_string();
+        let title = font::decode_utf8_lossy(&evt.title);
 
         match evt.event_type {
             x if x == lifecycle_events::CREATED => {
                 if evt.window_id != self.window.id {
+                    // Janela de um spawn pendente: o título é o nome do app
+                    let app = self
+                        .available_apps
+                        .iter()
+                        .find(|app| app.name.eq_ignore_ascii_case(&title));
+                    if let Some(app) = app {
+                        if let Some(instance) = self
+                            .instances
+                            .iter_mut()
+                            .find(|i| i.window_id.is_none() && i.app_id == app.id)
+                        {
+                            instance.window_id = Some(evt.window_id);
+                        }
+                    }
+
                     self.taskbar.add_window(evt.window_id, title);
-                    self.dirty = true;
+                    self.damage.add(self.taskbar.center_bounds());
                 }
             }
             x if x == lifecycle_events::DESTROYED => {
+                self.instances
+                    .retain(|instance| instance.window_id != Some(evt.window_id));
+
+                // Entradas seguintes se deslocam
                 self.taskbar.remove_window(evt.window_id);
-                self.dirty = true;
+                self.damage.add(self.taskbar.center_bounds());
             }
             x if x == lifecycle_events::MINIMIZED => {
                 self.taskbar.set_window_minimized(evt.window_id, true);
-                self.dirty = true;
+                self.damage_window_entry(evt.window_id);
             }
             x if x == lifecycle_events::RESTORED => {
                 self.taskbar.set_window_minimized(evt.window_id, false);
-                self.dirty = true;
+                self.damage_window_entry(evt.window_id);
             }
             _ => {}
         }
@@ -265,27 +462,28 @@ impl Desktop {
     fn handle_click(&mut self, x: i32, y: i32) {
         // Verificar painéis primeiro (ordem de cima para baixo)
         if self.quick_settings.is_visible() && self.quick_settings.handle_click(x, y) {
-            self.dirty = true;
+            self.damage.add(self.quick_settings.bounds());
             return;
         }
 
         if self.start_menu.is_visible() {
             if self.start_menu.handle_click(x, y) {
                 // Verificar se há ação
-                if let StartMenuAction::LaunchApp(path) = self.start_menu.take_action() {
-                    launch_app(&path);
+                match self.start_menu.take_action() {
+                    StartMenuAction::LaunchApp(id) => self.launch(&id),
+                    StartMenuAction::RefreshApps => self.refresh_apps(),
+                    StartMenuAction::None => {}
                 }
-                self.dirty = true;
+                self.damage.add(self.start_menu.bounds());
                 return;
             } else {
                 // Clique fora do menu - fechar
                 self.start_menu.set_visible(false);
-                self.dirty = true;
             }
         }
 
         if self.widget_panel.is_visible() && self.widget_panel.handle_click(x, y) {
-            self.dirty = true;
+            self.damage.add(self.widget_panel.bounds());
             return;
         }
 
@@ -306,28 +504,64 @@ impl Desktop {
             TaskbarAction::ToggleWidgetPanel => {
                 self.close_other_panels(Some(crate::ui::PanelType::Widget));
                 self.widget_panel.toggle();
-                self.dirty = true;
             }
             TaskbarAction::ToggleStartMenu => {
                 self.close_other_panels(Some(crate::ui::PanelType::StartMenu));
                 self.start_menu.toggle();
-                self.dirty = true;
             }
             TaskbarAction::ToggleQuickSettings => {
                 self.close_other_panels(Some(crate::ui::PanelType::QuickSettings));
                 self.quick_settings.toggle();
-                self.dirty = true;
             }
             TaskbarAction::ToggleWindow(id) => {
                 self.toggle_window(id);
-                self.dirty = true;
+                self.damage_window_entry(id);
             }
-            TaskbarAction::LaunchApp(idx) => {
-                if idx < self.available_apps.len() {
-                    launch_app(&self.available_apps[idx].path);
+            TaskbarAction::LaunchApp(id) => self.launch(&id),
+            TaskbarAction::None => {}
+        }
+    }
+
+    /// Lança o app de id `id`.
+    ///
+    /// Apps `single_instance` já lançados pelo shell têm a janela
+    /// restaurada em vez de um novo processo; enquanto a janela não aparece
+    /// (até [`INSTANCE_WINDOW_TIMEOUT_MS`]), novos cliques são ignorados.
+    fn launch(&mut self, id: &str) {
+        let Some(app) = self.available_apps.iter().find(|app| app.id == id) else {
+            println!("[Shell] WARN: App {} nao encontrado", id);
+            return;
+        };
+
+        let now = redpowder::time::clock().unwrap_or(0);
+        if app.single_instance {
+            // Spawn sem janela dentro do prazo: o app falhou ou não abre janela
+            self.instances.retain(|instance| {
+                instance.window_id.is_some()
+                    || now.saturating_sub(instance.launched_at) < INSTANCE_WINDOW_TIMEOUT_MS
+            });
+
+            if let Some(instance) = self.instances.iter().find(|i| i.app_id == app.id) {
+                match instance.window_id {
+                    Some(window_id) => {
+                        println!(
+                            "[Shell] {} ja aberto, restaurando janela {}",
+                            app.id, window_id
+                        );
+                        Self::send_window_op(window_id, opcodes::RESTORE_WINDOW);
+                    }
+                    None => println!("[Shell] {} ja esta iniciando", app.id),
                 }
+                return;
             }
-            TaskbarAction::None => {}
+        }
+
+        if launch_app_info(app) && app.single_instance {
+            self.instances.push(AppInstance {
+                app_id: app.id.clone(),
+                window_id: None,
+                launched_at: now,
+            });
         }
     }
 
@@ -361,7 +595,6 @@ impl Desktop {
         self.widget_panel.set_visible(false);
         self.start_menu.set_visible(false);
         self.quick_settings.set_visible(false);
-        self.dirty = true;
     }
 
     /// Fecha outros painéis exceto o especificado.
@@ -377,32 +610,85 @@ impl Desktop {
         }
     }
 
-    /// Atualiza animações. Retorna true se ainda animando.
-    fn update_animations(&mut self) -> bool {
-        let a1 = self.widget_panel.update_animation();
-        let a2 = self.start_menu.update_animation();
-        let a3 = self.quick_settings.update_animation();
-        a1 || a2 || a3
+    /// Marca a entrada da janela na taskbar como danificada.
+    fn damage_window_entry(&mut self, window_id: u32) {
+        let rect = self
+            .taskbar
+            .entry_bounds(window_id)
+            .unwrap_or(self.taskbar.center_bounds());
+        self.damage.add(rect);
+    }
+
+    /// Atualiza animações.
+    ///
+    /// A posição anterior e a nova de cada painel que se moveu entram no
+    /// damage, com a sombra.
+    fn update_animations(&mut self) {
+        let panels: [&mut dyn Panel; 3] = [
+            &mut self.widget_panel,
+            &mut self.start_menu,
+            &mut self.quick_settings,
+        ];
+
+        for panel in panels {
+            let before = panel.visual_bounds();
+            panel.update_animation();
+            let after = panel.visual_bounds();
+
+            if after.y != before.y {
+                self.damage.add(before);
+                self.damage.add(after);
+            }
+        }
+    }
+
+    /// Amplia o damage para cobrir por inteiro todo vidro que ele toca.
+    ///
+    /// O blur do vidro lê os pixels vizinhos; fora do damage eles ainda
+    /// guardam o frame anterior (já desfocado).
+    fn expand_damage_to_glass(&mut self) {
+        let panels: [&dyn Panel; 3] = [&self.widget_panel, &self.start_menu, &self.quick_settings];
+        for panel in panels {
+            if panel.is_visible() {
+                self.damage.expand_to(panel.animated_bounds());
+            }
+        }
+        for bar in self.taskbar.bar_bounds() {
+            self.damage.expand_to(bar);
+        }
     }
 
-    /// Redesenha tudo.
+    /// Redesenha as regiões danificadas.
     fn redraw(&mut self) {
-        // Obter buffer
-        let buffer = self.window.buffer();
-        let size = self.screen_size;
+        self.expand_damage_to_glass();
+
+        {
+            // Obter buffer
+            let mut canvas = Canvas::new(self.window.buffer(), self.screen_size);
 
-        // 1. Wallpaper
-        self.wallpaper.draw(buffer, size);
+            for &rect in self.damage.rects() {
+                canvas.save();
+                canvas.clip(rect);
 
-        // 2. Painéis (se visíveis)
-        self.widget_panel.draw(buffer, size);
-        self.start_menu.draw(buffer, size);
-        self.quick_settings.draw(buffer, size);
+                // 1. Wallpaper
+                self.wallpaper.draw(&mut canvas);
 
-        // 3. Taskbar (sempre por cima)
-        self.taskbar.draw(buffer, size);
+                // 2. Painéis (se visíveis)
+                self.widget_panel.draw(&mut canvas, &self.fonts);
+                self.start_menu.draw(&mut canvas, &self.fonts);
+                self.quick_settings.draw(&mut canvas, &self.fonts);
+
+                // 3. Taskbar (sempre por cima)
+                self.taskbar.draw(&mut canvas, &self.fonts);
+
+                canvas.restore();
+            }
+        }
 
         // 4. Present
+        // TODO: Repassar `self.damage.rects()` ao compositor quando a API de
+        // janela aceitar regiões; por enquanto o present envia o buffer todo.
         let _ = self.window.present();
+        self.damage.clear();
     }
 }
@@ -4,15 +4,49 @@
 //!
 //! ## Formato do Cache
 //!
-//! O cache fica em `/state/indexes/apps/apps.cache` e usa formato texto simples:
+//! O cache fica em `/state/indexes/apps/apps.cache`, é gerado pelo
+//! [`indexer`](super::indexer) a partir dos manifestos e usa formato texto
+//! simples:
 //!
 //! ```text
 //! # Comentários começam com #
-//! vendor|name|display_name|icon_path|category
-//! system|terminal|Terminal|/apps/system/terminal/assets/terminal.svg|system
+//! #!version 3
+//! #!entries 1
+//! vendor|name|display_name|icon_path|category|exec|args|env|description|keywords|flags
+//! system|terminal|Terminal \| Console|/apps/system/terminal/assets/terminal.svg|system|/apps/system/terminal/terminal.app|--login|TERM=firefly|Console|shell;console|single-instance
+//! #!checksum 1a2b3c4d
 //! ```
 //!
-//! ## Estrutura de Apps (referência para geração do cache)
+//! Campos de lista (`args`, `env`, `keywords`, `flags`) separam itens com
+//! `;`; `env` usa `CHAVE=valor`. Flags conhecidas: `hidden` e
+//! `single-instance`. Campos finais ausentes ficam vazios.
+//!
+//! Caracteres reservados dentro dos valores são escapados com `\`: `\\`,
+//! `\|`, `\;`, `\=`, `\n` (quebra de linha) e `\r`.
+//!
+//! ## Integridade
+//!
+//! `#!entries` declara quantos apps o arquivo contém e a última linha,
+//! `#!checksum`, traz o FNV-1a (32 bits, hexadecimal) de todos os bytes
+//! anteriores a ela. Um cache da versão 3 com linha inválida, contagem
+//! diferente, checksum errado ou ausente (gravação truncada) é rejeitado
+//! por inteiro e regenerado.
+//!
+//! ## Atualização
+//!
+//! Com um cache válido, `/apps` não é percorrido na inicialização. Apps
+//! instalados ou removidos entram na lista quando o instalador envia
+//! `OP_REFRESH_APPS` ou pelo botão "Atualizar" do menu iniciar, que chamam
+//! [`rescan_apps`].
+//!
+//! ## Versões Anteriores
+//!
+//! Sem a linha `#!version`, o cache é da versão 1: apenas os 5 primeiros
+//! campos, com o executável em `/apps/<vendor>/<name>/<name>.app`. As
+//! versões 1 e 2 não têm escape nem verificação: linhas inválidas são
+//! apenas puladas. Campos extras de versões futuras são ignorados.
+//!
+//! ## Estrutura de Apps
 //!
 //! ```text
 //! /apps/<vendor>/<name>/
@@ -29,25 +63,106 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use redpowder::fs::File;
 
+use super::indexer::update_apps_cache;
+use crate::render::image::{self, ImageError};
+use crate::util::hash::{fnv1a, FNV1A_SEED};
+
 // =============================================================================
 // CONSTANTES
 // =============================================================================
 
 /// Caminho do cache de apps.
-const APPS_CACHE_PATH: &str = "/state/indexes/apps/apps.cache";
+pub const APPS_CACHE_PATH: &str = "/state/indexes/apps/apps.cache";
+
+/// Diretório raiz de apps.
+pub const APPS_ROOT: &str = "/apps";
+
+/// Versão do formato do cache gerado.
+pub const CACHE_VERSION: u32 = 3;
+
+/// Primeira versão com escape, contagem e checksum.
+const CHECKED_VERSION: u32 = 3;
 
-/// Diretório raiz de apps (para construir paths).
-const APPS_ROOT: &str = "/apps";
+/// Prefixo da linha que declara a versão do cache.
+pub const VERSION_PREFIX: &str = "#!version ";
 
-/// Tamanho máximo do buffer de leitura do cache.
-const CACHE_BUFFER_SIZE: usize = 2048;
+/// Prefixo da linha que declara o número de apps.
+pub const ENTRIES_PREFIX: &str = "#!entries ";
+
+/// Prefixo da última linha, com o checksum do conteúdo anterior.
+pub const CHECKSUM_PREFIX: &str = "#!checksum ";
+
+/// Separador de campos.
+pub const FIELD_SEPARATOR: char = '|';
+
+/// Separador de itens nos campos de lista.
+pub const LIST_SEPARATOR: char = ';';
+
+/// Separador entre chave e valor no campo `env`.
+pub const ENV_SEPARATOR: char = '=';
+
+/// Prefixo de escape (versão 3+).
+const ESCAPE: char = '\\';
+
+/// Flag: app escondido do menu.
+pub const FLAG_HIDDEN: &str = "hidden";
+
+/// Flag: no máximo uma instância.
+pub const FLAG_SINGLE_INSTANCE: &str = "single-instance";
+
+/// Tamanho de cada bloco lido do cache.
+const READ_CHUNK_SIZE: usize = 512;
+
+/// Comprimento máximo de uma linha do cache (linhas maiores são descartadas).
+const MAX_LINE_LENGTH: usize = 4096;
+
+// =============================================================================
+// ERROS
+// =============================================================================
+
+/// Erro em uma linha do cache.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CacheLineError {
+    /// Bytes não formam UTF-8 válido.
+    NotUtf8,
+    /// Linha maior que [`MAX_LINE_LENGTH`].
+    TooLong,
+    /// Menos campos que o formato exige (quantidade encontrada).
+    MissingFields(usize),
+    /// Linha `#!version` malformada.
+    BadVersion,
+    /// Linha `#!entries` malformada.
+    BadEntries,
+    /// Linha `#!checksum` malformada ou com valor diferente do calculado.
+    BadChecksum,
+    /// Escape desconhecido ou `\` no fim do campo.
+    BadEscape,
+    /// Conteúdo depois da linha `#!checksum`.
+    AfterChecksum,
+}
+
+/// Motivo para rejeitar um cache inteiro.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CacheError {
+    /// Arquivo ausente ou ilegível.
+    Unreadable,
+    /// Linha inválida (número 1-based).
+    Line(usize, CacheLineError),
+    /// `#!entries` ausente ou diferente do número de apps lidos.
+    EntryCount {
+        declared: Option<usize>,
+        found: usize,
+    },
+    /// Falta a linha `#!checksum` (gravação truncada).
+    MissingChecksum,
+}
 
 // =============================================================================
 // APP INFO
 // =============================================================================
 
 /// Informações de um aplicativo.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AppInfo {
     /// ID único (vendor.name).
     pub id: String,
@@ -61,61 +176,129 @@ pub struct AppInfo {
     pub icon_path: Option<String>,
     /// Categoria.
     pub category: String,
+    /// Argumentos padrão do executável.
+    pub args: Vec<String>,
+    /// Variáveis de ambiente passadas ao processo.
+    pub env: Vec<(String, String)>,
+    /// Descrição curta.
+    pub description: String,
+    /// Palavras-chave para busca.
+    pub keywords: Vec<String>,
+    /// Não aparece no menu iniciar.
+    pub hidden: bool,
+    /// No máximo uma instância em execução.
+    pub single_instance: bool,
 }
 
 impl AppInfo {
-    /// Cria AppInfo a partir de uma linha do cache.
+    /// Cria AppInfo a partir de uma linha do cache na versão `version`.
     ///
-    /// Formato: vendor|name|display_name|icon_path|category
-    fn from_cache_line(line: &str) -> Option<Self> {
+    /// Formato: vendor|name|display_name|icon_path|category, seguidos (a
+    /// partir da versão 2) de exec|args|env|description|keywords|flags.
+    ///
+    /// Linhas vazias e comentários resultam em `Ok(None)`.
+    fn from_cache_line(line: &str, version: u32) -> Result<Option<Self>, CacheLineError> {
         let line = line.trim();
 
         // Ignorar linhas vazias e comentários
         if line.is_empty() || line.starts_with('#') {
-            return None;
+            return Ok(None);
         }
 
-        let parts: Vec<&str> = line.split('|').collect();
+        let escaped = version >= CHECKED_VERSION;
+        let mut parts = split(line, FIELD_SEPARATOR, escaped);
         if parts.len() < 5 {
-            redpowder::println!("[Discovery] WARN: Linha invalida no cache: {}", line);
-            return None;
+            return Err(CacheLineError::MissingFields(parts.len()));
+        }
+        if version < 2 {
+            parts.truncate(5);
         }
+        // Campos finais ausentes ficam vazios
+        parts.resize(11, "");
+
+        let [vendor, name, display_name, icon_path, category, exec, args, env, description, keywords, flags] =
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10].map(|i| parts[i]);
+        let text = |field: &str| unescape(field, escaped);
+        let texts = |field: &str| -> Result<Vec<String>, CacheLineError> {
+            list(field, escaped).into_iter().map(text).collect()
+        };
 
-        let vendor = parts[0].trim();
-        let name = parts[1].trim();
-        let display_name = parts[2].trim();
-        let icon_path = parts[3].trim();
-        let category = parts[4].trim();
+        let vendor = text(vendor)?;
+        let name = text(name)?;
 
-        // Construir caminho do executável
-        let exec_path = alloc::format!("{}/{}/{}/{}.app", APPS_ROOT, vendor, name, name);
+        // Caminho do executável (padrão: <app>/<name>.app)
+        let exec_path = if exec.is_empty() {
+            alloc::format!("{}/{}/{}/{}.app", APPS_ROOT, vendor, name, name)
+        } else {
+            text(exec)?
+        };
 
         // Icon path pode ser vazio
         let icon = if icon_path.is_empty() {
             None
         } else {
-            Some(icon_path.to_string())
+            Some(text(icon_path)?)
         };
 
-        Some(Self {
+        let mut env_vars = Vec::new();
+        for pair in list(env, escaped) {
+            if let [key, value] = split(pair, ENV_SEPARATOR, escaped)[..] {
+                env_vars.push((text(key)?, text(value)?));
+            }
+        }
+        let flags = texts(flags)?;
+        let has_flag = |flag: &str| flags.iter().any(|f| f == flag);
+
+        Ok(Some(Self {
             id: alloc::format!("{}.{}", vendor, name),
-            name: display_name.to_string(),
-            vendor: vendor.to_string(),
+            name: text(display_name)?,
             path: exec_path,
             icon_path: icon,
-            category: category.to_string(),
-        })
+            category: text(category)?,
+            args: texts(args)?,
+            env: env_vars,
+            description: text(description)?,
+            keywords: texts(keywords)?,
+            hidden: has_flag(FLAG_HIDDEN),
+            single_instance: has_flag(FLAG_SINGLE_INSTANCE),
+            vendor,
+        }))
     }
 }
 
-/// Ícone de app (placeholder por enquanto).
+/// Itens não vazios de um campo de lista.
+fn list(field: &str, escaped: bool) -> Vec<&str> {
+    split(field, LIST_SEPARATOR, escaped)
+        .into_iter()
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Ícone de app rasterizado.
 #[derive(Debug, Clone)]
 pub struct AppIcon {
     pub width: u32,
     pub height: u32,
+    /// Pixels ARGB pré-multiplicados.
     pub data: Vec<u32>,
 }
 
+impl AppIcon {
+    /// Decodifica o conteúdo de um arquivo de ícone no tamanho
+    /// `size`×`size`.
+    ///
+    /// SVG é rasterizado direto no tamanho pedido; imagens raster são
+    /// reduzidas mantendo a proporção.
+    pub fn decode(data: &[u8], size: u32) -> Result<Self, ImageError> {
+        let image = image::decode_sized(data, size, size)?;
+        Ok(Self {
+            width: image.width,
+            height: image.height,
+            data: image.pixels,
+        })
+    }
+}
+
 impl Default for AppIcon {
     fn default() -> Self {
         Self {
@@ -126,153 +309,621 @@ impl Default for AppIcon {
     }
 }
 
+// =============================================================================
+// ESCAPE
+// =============================================================================
+
+/// Divide `text` em `separator`, sem separar em ocorrências escapadas
+/// (quando `escaped`). As partes voltam aparadas e ainda escapadas.
+fn split(text: &str, separator: char, escaped: bool) -> Vec<&str> {
+    if !escaped {
+        return text.split(separator).map(str::trim).collect();
+    }
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == ESCAPE {
+            chars.next();
+        } else if c == separator {
+            parts.push(text[start..i].trim());
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Desfaz o escape de um campo (ou apenas copia, sem `escaped`).
+fn unescape(field: &str, escaped: bool) -> Result<String, CacheLineError> {
+    if !escaped {
+        return Ok(field.to_string());
+    }
+
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != ESCAPE {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some(c @ ('\\' | '|' | ';' | '=')) => c,
+            _ => return Err(CacheLineError::BadEscape),
+        });
+    }
+    Ok(out)
+}
+
+/// Acrescenta `text` a `out`, escapando os caracteres reservados do
+/// formato (versão 3+).
+pub fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\\' | '|' | ';' | '=' => {
+                out.push(ESCAPE);
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
 // =============================================================================
 // DISCOVERY VIA CACHE
 // =============================================================================
 
 /// Descobre todos os apps instalados lendo do cache.
 ///
-/// O cache está em `/state/indexes/apps/apps.cache` e evita múltiplas
-/// syscalls de listagem de diretório que podem causar instabilidade.
-///
-/// Se o cache não existir ou estiver vazio, retorna vetor vazio.
+/// O cache está em `/state/indexes/apps/apps.cache` e evita listar `/apps`
+/// e ler cada manifesto a cada inicialização: ele só é regenerado
+/// ([`rescan_apps`]) quando está ausente ou inválido. Um cache inválido é
+/// descartado por inteiro.
 pub fn discover_apps() -> Vec<AppInfo> {
     redpowder::println!("[Discovery] Lendo cache de apps...");
 
-    let mut apps = Vec::new();
+    match read_cache() {
+        Ok(apps) => {
+            log_apps(&apps);
+            apps
+        }
+        Err(CacheError::Unreadable) => rescan_apps(),
+        Err(e) => {
+            redpowder::println!("[Discovery] WARN: Cache corrompido ({:?}), regenerando", e);
+            rescan_apps()
+        }
+    }
+}
+
+/// Relê `/apps` e regenera o cache ([`update_apps_cache`]).
+///
+/// Usado quando o cache não serve e nos pedidos explícitos de atualização
+/// (`OP_REFRESH_APPS` e o botão "Atualizar"). O conteúdo gerado é lido
+/// direto da memória, esteja ele gravado ou não (sem a feature `fs-write`
+/// nada é gravado). Se `/apps` não puder ser listado, o cache atual vale.
+pub fn rescan_apps() -> Vec<AppInfo> {
+    let result = match update_apps_cache() {
+        Some(content) => parse_cache(content.as_bytes()),
+        None => read_cache(),
+    };
+
+    match result {
+        Ok(apps) => {
+            log_apps(&apps);
+            apps
+        }
+        Err(CacheError::Unreadable) => Vec::new(),
+        Err(e) => {
+            redpowder::println!("[Discovery] ERRO: Cache invalido ({:?}), apps ignorados", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Registra os apps encontrados.
+fn log_apps(apps: &[AppInfo]) {
+    for app_info in apps {
+        redpowder::println!("[Discovery] App: {} ({})", app_info.name, app_info.id);
+    }
+    redpowder::println!("[Discovery] {} apps encontrados", apps.len());
+}
 
+/// Interpreta um cache inteiro já em memória.
+fn parse_cache(bytes: &[u8]) -> Result<Vec<AppInfo>, CacheError> {
+    let mut reader = CacheReader::new();
+    let mut splitter = LineSplitter::new();
+    splitter.feed(bytes, |number, line| reader.line(number, line));
+    splitter.finish(|number, line| reader.line(number, line));
+    reader.finish()
+}
+
+/// Lê e valida o cache inteiro.
+///
+/// O arquivo é lido em blocos até o fim, sem limite de tamanho.
+fn read_cache() -> Result<Vec<AppInfo>, CacheError> {
     // Tentar abrir o arquivo de cache
     let file = match File::open(APPS_CACHE_PATH) {
         Ok(f) => f,
         Err(e) => {
             redpowder::println!("[Discovery] WARN: Cache nao encontrado: {:?}", e);
-            redpowder::println!("[Discovery] Crie manualmente: {}", APPS_CACHE_PATH);
-            return apps;
+            return Err(CacheError::Unreadable);
         }
     };
 
-    // Ler conteúdo do cache
-    let mut buffer = [0u8; CACHE_BUFFER_SIZE];
-    let bytes_read = match file.read(&mut buffer) {
-        Ok(n) => n,
-        Err(e) => {
-            redpowder::println!("[Discovery] ERRO: Falha ao ler cache: {:?}", e);
-            return apps;
-        }
-    };
+    // Ler em blocos, processando cada linha completa
+    let mut reader = CacheReader::new();
+    let mut splitter = LineSplitter::new();
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+    let mut total = 0;
+    loop {
+        let n = match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) => {
+                redpowder::println!("[Discovery] ERRO: Falha ao ler cache: {:?}", e);
+                return Err(CacheError::Unreadable);
+            }
+        };
+        total += n;
+        splitter.feed(&chunk[..n], |number, line| reader.line(number, line));
+    }
+    splitter.finish(|number, line| reader.line(number, line));
 
-    if bytes_read == 0 {
+    if total == 0 {
         redpowder::println!("[Discovery] Cache vazio");
-        return apps;
     }
+    reader.finish()
+}
+
+/// Interpreta as linhas do cache, acompanhando a versão declarada e o
+/// checksum.
+struct CacheReader {
+    apps: Vec<AppInfo>,
+    /// Versão do formato (1 até aparecer `#!version`).
+    version: u32,
+    /// Número de apps declarado em `#!entries`.
+    entries: Option<usize>,
+    /// Checksum dos bytes lidos até aqui.
+    hash: u32,
+    /// A linha `#!checksum` já foi vista (e conferiu).
+    sealed: bool,
+    /// Primeira linha inválida de um cache verificado.
+    error: Option<CacheError>,
+}
 
-    // Converter para string
-    let content = match core::str::from_utf8(&buffer[..bytes_read]) {
-        Ok(s) => s,
-        Err(_) => {
-            redpowder::println!("[Discovery] ERRO: Cache nao e UTF-8 valido");
-            return apps;
+impl CacheReader {
+    fn new() -> Self {
+        Self {
+            apps: Vec::new(),
+            version: 1,
+            entries: None,
+            hash: FNV1A_SEED,
+            sealed: false,
+            error: None,
         }
-    };
+    }
 
-    // Processar cada linha
-    for line in content.lines() {
-        if let Some(app_info) = AppInfo::from_cache_line(line) {
-            redpowder::println!("[Discovery] App: {} ({})", app_info.name, app_info.id);
-            apps.push(app_info);
+    /// Processa a linha `number` (1-based).
+    fn line(&mut self, number: usize, line: Result<&[u8], CacheLineError>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = line.and_then(|bytes| {
+            if self.sealed {
+                return Err(CacheLineError::AfterChecksum);
+            }
+            let text = core::str::from_utf8(bytes).map_err(|_| CacheLineError::NotUtf8)?;
+            if let Some(value) = text.strip_prefix(CHECKSUM_PREFIX) {
+                self.check(value)?;
+                return Ok(None);
+            }
+
+            self.hash = fnv1a(fnv1a(self.hash, bytes), b"\n");
+            let directive = text.trim();
+            if let Some(version) = directive.strip_prefix(VERSION_PREFIX) {
+                self.set_version(version)?;
+                return Ok(None);
+            }
+            if let Some(entries) = directive.strip_prefix(ENTRIES_PREFIX) {
+                let entries = entries
+                    .trim()
+                    .parse()
+                    .map_err(|_| CacheLineError::BadEntries)?;
+                self.entries = Some(entries);
+                return Ok(None);
+            }
+            AppInfo::from_cache_line(text, self.version)
+        });
+
+        match result {
+            Ok(Some(app_info)) => self.apps.push(app_info),
+            Ok(None) => {}
+            // Versões verificadas: uma linha ruim invalida o arquivo
+            Err(e) if self.version >= CHECKED_VERSION => {
+                self.error = Some(CacheError::Line(number, e));
+            }
+            Err(e) => {
+                redpowder::println!(
+                    "[Discovery] WARN: Linha {} invalida no cache: {:?}",
+                    number,
+                    e
+                );
+            }
         }
     }
 
-    redpowder::println!("[Discovery] {} apps encontrados", apps.len());
-    apps
+    /// Aplica uma linha `#!version`.
+    fn set_version(&mut self, value: &str) -> Result<(), CacheLineError> {
+        let version = value
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| CacheLineError::BadVersion)?;
+        if version > CACHE_VERSION {
+            redpowder::println!(
+                "[Discovery] WARN: Cache versao {} (suportada: {}), campos extras ignorados",
+                version,
+                CACHE_VERSION
+            );
+        }
+        self.version = version;
+        Ok(())
+    }
+
+    /// Confere a linha `#!checksum` com o checksum acumulado.
+    fn check(&mut self, value: &str) -> Result<(), CacheLineError> {
+        let value = value.trim();
+        let expected = match u32::from_str_radix(value, 16) {
+            Ok(expected) if value.bytes().all(|b| b.is_ascii_hexdigit()) => expected,
+            _ => return Err(CacheLineError::BadChecksum),
+        };
+        if expected != self.hash {
+            return Err(CacheLineError::BadChecksum);
+        }
+        self.sealed = true;
+        Ok(())
+    }
+
+    /// Valida o arquivo lido e entrega os apps.
+    fn finish(self) -> Result<Vec<AppInfo>, CacheError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.version >= CHECKED_VERSION {
+            if self.entries != Some(self.apps.len()) {
+                return Err(CacheError::EntryCount {
+                    declared: self.entries,
+                    found: self.apps.len(),
+                });
+            }
+            if !self.sealed {
+                return Err(CacheError::MissingChecksum);
+            }
+        }
+        Ok(self.apps)
+    }
 }
 
 // =============================================================================
-// LEGACY - DESCOBERTA DINÂMICA (DESABILITADA)
+// LINE SPLITTER
 // =============================================================================
 
-/// Descoberta dinâmica de apps (desabilitada por instabilidade).
+/// Divide um fluxo de blocos em linhas.
 ///
-/// Este código é mantido para referência. A descoberta dinâmica causa
-/// kernel panic devido a:
-/// - Múltiplas syscalls de listagem de diretório
-/// - Alocações recursivas de memória
-/// - Stack overflow no processo do shell
-///
-/// Usar `discover_apps()` que lê do cache é a solução estável.
-#[allow(dead_code)]
-mod legacy {
+/// Uma linha cortada entre dois blocos fica em `pending` até o `\n`
+/// chegar, então caracteres multi-byte divididos no limite do bloco são
+/// reconstituídos antes da validação UTF-8.
+struct LineSplitter {
+    /// Início da linha atual, ainda sem `\n`.
+    pending: Vec<u8>,
+    /// Número da linha atual (1-based).
+    number: usize,
+    /// A linha atual passou de [`MAX_LINE_LENGTH`] e está sendo descartada.
+    overflow: bool,
+}
+
+impl LineSplitter {
+    fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            number: 1,
+            overflow: false,
+        }
+    }
+
+    /// Consome um bloco, chamando `on_line` para cada linha completa.
+    fn feed(
+        &mut self,
+        chunk: &[u8],
+        mut on_line: impl FnMut(usize, Result<&[u8], CacheLineError>),
+    ) {
+        let mut rest = chunk;
+        while let Some(end) = rest.iter().position(|&b| b == b'\n') {
+            self.push(&rest[..end]);
+            self.emit(&mut on_line);
+            rest = &rest[end + 1..];
+        }
+        self.push(rest);
+    }
+
+    /// Entrega a última linha, se o arquivo não terminar em `\n`.
+    fn finish(mut self, mut on_line: impl FnMut(usize, Result<&[u8], CacheLineError>)) {
+        if !self.pending.is_empty() || self.overflow {
+            self.emit(&mut on_line);
+        }
+    }
+
+    /// Acumula parte da linha atual, respeitando o limite de tamanho.
+    fn push(&mut self, bytes: &[u8]) {
+        if self.overflow {
+            return;
+        }
+        if self.pending.len() + bytes.len() > MAX_LINE_LENGTH {
+            self.overflow = true;
+            self.pending.clear();
+            return;
+        }
+        self.pending.extend_from_slice(bytes);
+    }
+
+    /// Entrega a linha atual e passa para a próxima.
+    fn emit(&mut self, on_line: &mut impl FnMut(usize, Result<&[u8], CacheLineError>)) {
+        let line = if self.overflow {
+            Err(CacheLineError::TooLong)
+        } else {
+            Ok(self.pending.strip_suffix(b"\r").unwrap_or(&self.pending))
+        };
+        on_line(self.number, line);
+
+        self.pending.clear();
+        self.overflow = false;
+        self.number += 1;
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
     use super::*;
-    use redpowder::fs::list_dir;
-
-    const APPS_ROOT_LEGACY: &str = "/apps";
-    const APP_MANIFEST: &str = "app.toml";
-
-    /// Descobre apps dinamicamente (INSTÁVEL - NÃO USAR).
-    pub fn discover_apps_dynamic() -> Vec<AppInfo> {
-        redpowder::println!("[Discovery] WARN: Descoberta dinamica desabilitada");
-        redpowder::println!("[Discovery] Causa: instabilidade no filesystem/memoria");
-        Vec::new()
-
-        // O código original está comentado abaixo para referência:
-        /*
-        let mut apps = Vec::new();
-
-        redpowder::println!("[Discovery] Buscando apps em {}", APPS_ROOT_LEGACY);
-
-        // Listar vendors
-        if let Ok(vendors) = list_dir(APPS_ROOT_LEGACY) {
-            for vendor_entry in vendors {
-                if !vendor_entry.is_dir() {
-                    continue;
-                }
-
-                let vendor_name = vendor_entry.name();
-
-                // Ignorar . e ..
-                if vendor_name == "." || vendor_name == ".." {
-                    continue;
-                }
-
-                let vendor_path = alloc::format!("{}/{}", APPS_ROOT_LEGACY, vendor_name);
-
-                redpowder::println!("[Discovery] Vendor: {}", vendor_name);
-
-                // Listar apps do vendor
-                if let Ok(app_dirs) = list_dir(&vendor_path) {
-                    for app_entry in app_dirs {
-                        if !app_entry.is_dir() {
-                            continue;
-                        }
-
-                        let app_name = app_entry.name();
-
-                        // Ignorar . e ..
-                        if app_name == "." || app_name == ".." {
-                            continue;
-                        }
-
-                        // Criar AppInfo básico
-                        let base_path = alloc::format!("{}/{}", vendor_path, app_name);
-                        let exec_path = alloc::format!("{}/{}.app", base_path, app_name);
-
-                        apps.push(AppInfo {
-                            id: alloc::format!("{}.{}", vendor_name, app_name),
-                            name: app_name.to_string(),
-                            vendor: vendor_name.to_string(),
-                            path: exec_path,
-                            icon_path: None,
-                            category: "other".to_string(),
-                        });
-                    }
-                }
+    use alloc::format;
+    use alloc::vec;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    /// Apps com todos os caracteres reservados e texto multi-byte.
+    fn sample_apps() -> Vec<AppInfo> {
+        vec![
+            AppInfo {
+                id: "redstone.term".to_string(),
+                name: "Terminal | Console; v=2 \\ ação ✓".to_string(),
+                vendor: "redstone".to_string(),
+                path: "/apps/redstone/term/bin/term.app".to_string(),
+                icon_path: Some("/apps/redstone/term/assets/ícone.svg".to_string()),
+                category: "system".to_string(),
+                args: strings(&["--login", "--title=a;b|c"]),
+                env: vec![
+                    ("TERM".to_string(), "firefly".to_string()),
+                    ("PS1".to_string(), "a=b|c\\".to_string()),
+                ],
+                description: "linha 1\nlinha 2\r".to_string(),
+                keywords: strings(&["shell", "console"]),
+                hidden: false,
+                single_instance: true,
+            },
+            AppInfo {
+                id: "acme.calc".to_string(),
+                name: "Calc".to_string(),
+                vendor: "acme".to_string(),
+                path: "/apps/acme/calc/calc.app".to_string(),
+                icon_path: None,
+                category: "other".to_string(),
+                args: Vec::new(),
+                env: Vec::new(),
+                description: String::new(),
+                keywords: Vec::new(),
+                hidden: true,
+                single_instance: false,
+            },
+        ]
+    }
+
+    /// Escreve o cache como o indexer, sem a linha `#!checksum`.
+    fn render_body(apps: &[AppInfo]) -> String {
+        let mut out = format!(
+            "# teste\n{}{}\n{}{}\n",
+            VERSION_PREFIX,
+            CACHE_VERSION,
+            ENTRIES_PREFIX,
+            apps.len()
+        );
+        for app in apps {
+            let name = &app.id[app.vendor.len() + 1..];
+            let env: Vec<String> = app
+                .env
+                .iter()
+                .map(|(key, value)| {
+                    let mut pair = String::new();
+                    escape_into(&mut pair, key);
+                    pair.push(ENV_SEPARATOR);
+                    escape_into(&mut pair, value);
+                    pair
+                })
+                .collect();
+            let mut flags = Vec::new();
+            if app.hidden {
+                flags.push(FLAG_HIDDEN);
             }
-        } else {
-            redpowder::println!("[Discovery] Falha ao ler {}", APPS_ROOT_LEGACY);
+            if app.single_instance {
+                flags.push(FLAG_SINGLE_INSTANCE);
+            }
+
+            let fields = [
+                escaped(&app.vendor),
+                escaped(name),
+                escaped(&app.name),
+                escaped(app.icon_path.as_deref().unwrap_or("")),
+                escaped(&app.category),
+                escaped(&app.path),
+                escaped_list(&app.args),
+                env.join(";"),
+                escaped(&app.description),
+                escaped_list(&app.keywords),
+                escaped_list(&flags),
+            ];
+            out.push_str(&fields.join("|"));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn escaped(text: &str) -> String {
+        let mut out = String::new();
+        escape_into(&mut out, text);
+        out
+    }
+
+    fn escaped_list(items: &[impl AsRef<str>]) -> String {
+        let items: Vec<String> = items.iter().map(|item| escaped(item.as_ref())).collect();
+        items.join(";")
+    }
+
+    /// Acrescenta a linha `#!checksum` de `body`.
+    fn seal(body: &str) -> String {
+        let hash = fnv1a(FNV1A_SEED, body.as_bytes());
+        format!("{}{}{:08x}\n", body, CHECKSUM_PREFIX, hash)
+    }
+
+    /// Lê `bytes` em blocos de `chunk` bytes, como [`read_cache`].
+    fn parse(bytes: &[u8], chunk: usize) -> Result<Vec<AppInfo>, CacheError> {
+        let mut reader = CacheReader::new();
+        let mut splitter = LineSplitter::new();
+        for part in bytes.chunks(chunk) {
+            splitter.feed(part, |number, line| reader.line(number, line));
+        }
+        splitter.finish(|number, line| reader.line(number, line));
+        reader.finish()
+    }
+
+    #[test]
+    fn escape_round_trip() {
+        for text in ["", "plain", "\\", "a|b;c=d", "\\|\\;", "\n\r", "ação ✓ \\n"] {
+            let escaped = escaped(text);
+            assert_eq!(split(&escaped, FIELD_SEPARATOR, true), [escaped.as_str()]);
+            assert_eq!(split(&escaped, LIST_SEPARATOR, true), [escaped.as_str()]);
+            assert_eq!(unescape(&escaped, true).as_deref(), Ok(text));
         }
 
-        redpowder::println!("[Discovery] {} apps encontrados", apps.len());
-        apps
-        */
+        assert_eq!(unescape("a\\x", true), Err(CacheLineError::BadEscape));
+        assert_eq!(unescape("a\\", true), Err(CacheLineError::BadEscape));
+        assert_eq!(unescape("a\\x", false).as_deref(), Ok("a\\x"));
+    }
+
+    #[test]
+    fn cache_round_trip_in_any_chunk_size() {
+        let apps = sample_apps();
+        let text = seal(&render_body(&apps));
+
+        // Blocos de 1 byte em diante cortam linhas e caracteres multi-byte
+        for chunk in 1..=text.len() {
+            assert_eq!(
+                parse(text.as_bytes(), chunk),
+                Ok(apps.clone()),
+                "chunk {}",
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_crlf_and_missing_final_newline() {
+        let apps = sample_apps();
+        let text = seal(&render_body(&apps));
+
+        let unterminated = text.trim_end_matches('\n');
+        assert_eq!(parse(unterminated.as_bytes(), 7), Ok(apps.clone()));
+
+        // `\r` no fim da linha não entra no checksum
+        let crlf = text.replace('\n', "\r\n");
+        assert_eq!(parse(crlf.as_bytes(), 7), Ok(apps));
+    }
+
+    #[test]
+    fn rejects_corrupted_cache() {
+        let apps = sample_apps();
+        let body = render_body(&apps);
+        let text = seal(&body);
+        let checksum_line = body.lines().count() + 1;
+
+        // Conteúdo alterado depois do checksum calculado
+        let tampered = text.replace("Calc", "Calx");
+        assert_eq!(
+            parse(tampered.as_bytes(), 16),
+            Err(CacheError::Line(checksum_line, CacheLineError::BadChecksum))
+        );
+
+        // Gravação truncada
+        assert_eq!(parse(body.as_bytes(), 16), Err(CacheError::MissingChecksum));
+
+        let count = seal(&body.replace("#!entries 2", "#!entries 3"));
+        assert_eq!(
+            parse(count.as_bytes(), 16),
+            Err(CacheError::EntryCount {
+                declared: Some(3),
+                found: 2
+            })
+        );
+
+        let bad_escape = seal(&body.replace("Calc", "Ca\\xlc"));
+        assert_eq!(
+            parse(bad_escape.as_bytes(), 16),
+            Err(CacheError::Line(5, CacheLineError::BadEscape))
+        );
+
+        let trailing = format!("{}extra\n", text);
+        assert_eq!(
+            parse(trailing.as_bytes(), 16),
+            Err(CacheError::Line(
+                checksum_line + 1,
+                CacheLineError::AfterChecksum
+            ))
+        );
+
+        // Caractere multi-byte sem o byte inicial (no nome do primeiro app)
+        let mut bytes = seal(&body).into_bytes();
+        let pos = bytes.windows(2).position(|w| w == "ç".as_bytes()).unwrap();
+        bytes[pos] = b'?';
+        assert_eq!(
+            parse(&bytes, 16),
+            Err(CacheError::Line(4, CacheLineError::NotUtf8))
+        );
+
+        let long = seal(&format!("{}{}\n", body, "a".repeat(MAX_LINE_LENGTH + 1)));
+        assert_eq!(
+            parse(long.as_bytes(), 512),
+            Err(CacheError::Line(checksum_line, CacheLineError::TooLong))
+        );
+    }
+
+    #[test]
+    fn reads_unversioned_cache() {
+        let text = "redstone|term|Terminal|/apps/redstone/term/assets/t.svg|system|extra\n\
+                    acme|calc|C\\alc||other\n";
+        let apps = parse(text.as_bytes(), 5).unwrap();
+
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[0].id, "redstone.term");
+        assert_eq!(apps[0].path, "/apps/redstone/term/term.app");
+        assert_eq!(apps[1].name, "C\\alc");
+        assert_eq!(apps[1].icon_path, None);
     }
 }
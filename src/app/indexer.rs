@@ -0,0 +1,267 @@
+//! # Indexer
+//!
+//! Gera `apps.cache` a partir dos manifestos em `/apps/<vendor>/<name>/`.
+//!
+//! ## Percurso
+//!
+//! A antiga descoberta dinâmica derrubava o sistema por recursão e
+//! alocações sem limite. Aqui o percurso é iterativo (pilha explícita, dois
+//! níveis fixos: vendor e app), cada diretório é listado uma única vez e
+//! há tetos para vendors, apps e tamanho de manifesto.
+//!
+//! ## Atualização
+//!
+//! O conteúdo gerado é devolvido à descoberta, que o usa direto da memória.
+//! Com a feature `fs-write` ele também é comparado com o cache atual e
+//! gravado quando difere (cache ausente ou desatualizado). A gravação passa
+//! por um arquivo temporário + rename, então um cache pela metade nunca
+//! fica no lugar do anterior; se ainda assim o arquivo for truncado, o
+//! checksum final não confere e a descoberta o regenera. Se `/apps` não
+//! puder ser listado, o cache existente é mantido.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use redpowder::fs::list_dir;
+
+#[cfg(feature = "fs-write")]
+use super::discovery::APPS_CACHE_PATH;
+use super::discovery::{
+    escape_into, APPS_ROOT, CACHE_VERSION, CHECKSUM_PREFIX, ENTRIES_PREFIX, ENV_SEPARATOR,
+    FIELD_SEPARATOR, FLAG_HIDDEN, FLAG_SINGLE_INSTANCE, LIST_SEPARATOR, VERSION_PREFIX,
+};
+use super::manifest::{AppManifest, MANIFEST_NAME};
+use crate::util::fs::read_file;
+#[cfg(feature = "fs-write")]
+use crate::util::fs::{create_dir_all, write_atomic};
+use crate::util::hash::{fnv1a, FNV1A_SEED};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Diretório do cache, criado se ausente.
+#[cfg(feature = "fs-write")]
+const CACHE_DIR: &str = "/state/indexes/apps";
+
+/// Máximo de vendors percorridos.
+const MAX_VENDORS: usize = 64;
+
+/// Máximo de apps indexados.
+const MAX_APPS: usize = 256;
+
+/// Maior manifesto aceito.
+const MAX_MANIFEST_SIZE: usize = 16 * 1024;
+
+/// Maior cache existente lido para comparação.
+#[cfg(feature = "fs-write")]
+const MAX_CACHE_SIZE: usize = 256 * 1024;
+
+/// Comentário no topo do cache gerado.
+const CACHE_COMMENT: &str = "# Gerado pelo shell a partir de /apps/*/*/app.toml (nao editar)";
+
+// =============================================================================
+// INDEXER
+// =============================================================================
+
+/// App encontrado no percurso.
+struct IndexedApp {
+    vendor: String,
+    name: String,
+    manifest: AppManifest,
+    icon_path: Option<String>,
+    exec_path: String,
+}
+
+/// Gera o conteúdo do cache de apps a partir de `/apps`.
+///
+/// Com a feature `fs-write`, o cache em disco é regravado se estiver
+/// ausente ou desatualizado. `None` se `/apps` não puder ser listado.
+pub fn update_apps_cache() -> Option<String> {
+    let Some(apps) = scan_apps() else {
+        redpowder::println!("[Indexer] WARN: {} inacessivel, cache mantido", APPS_ROOT);
+        return None;
+    };
+
+    let content = render_cache(&apps);
+    #[cfg(feature = "fs-write")]
+    store_cache(&content, apps.len());
+    Some(content)
+}
+
+/// Grava `content` no cache em disco, se diferir do atual.
+#[cfg(feature = "fs-write")]
+fn store_cache(content: &str, count: usize) {
+    if read_file(APPS_CACHE_PATH, MAX_CACHE_SIZE).as_deref() == Ok(content.as_bytes()) {
+        return;
+    }
+
+    create_dir_all(CACHE_DIR);
+    if !write_atomic(APPS_CACHE_PATH, content.as_bytes()) {
+        redpowder::println!("[Indexer] ERRO: Falha ao gravar {}", APPS_CACHE_PATH);
+        return;
+    }
+
+    redpowder::println!("[Indexer] Cache regenerado: {} apps", count);
+}
+
+/// Percorre `/apps` e lê o manifesto de cada app.
+///
+/// `None` se a raiz não puder ser listada.
+fn scan_apps() -> Option<Vec<IndexedApp>> {
+    let mut apps = Vec::new();
+
+    // (caminho, vendor): vendor é `None` na raiz
+    let mut stack: Vec<(String, Option<String>)> = alloc::vec![(APPS_ROOT.to_string(), None)];
+
+    'scan: while let Some((dir, vendor)) = stack.pop() {
+        let entries = match list_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if vendor.is_none() => {
+                redpowder::println!("[Indexer] ERRO: Falha ao listar {}: {:?}", dir, e);
+                return None;
+            }
+            Err(e) => {
+                redpowder::println!("[Indexer] WARN: Falha ao listar {}: {:?}", dir, e);
+                continue;
+            }
+        };
+
+        // Em ordem de nome: os limites cortam sempre os mesmos itens
+        let mut names: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.is_dir() && entry.name() != "." && entry.name() != "..")
+            .map(|entry| entry.name().to_string())
+            .collect();
+        names.sort();
+
+        match &vendor {
+            None => {
+                if names.len() > MAX_VENDORS {
+                    redpowder::println!("[Indexer] WARN: Limite de {} vendors", MAX_VENDORS);
+                    names.truncate(MAX_VENDORS);
+                }
+                // Pilha: o primeiro vendor em ordem é o próximo a sair
+                for name in names.into_iter().rev() {
+                    let path = alloc::format!("{}/{}", dir, name);
+                    stack.push((path, Some(name)));
+                }
+            }
+            Some(vendor) => {
+                for name in names {
+                    if apps.len() == MAX_APPS {
+                        redpowder::println!("[Indexer] WARN: Limite de {} apps", MAX_APPS);
+                        break 'scan;
+                    }
+                    let path = alloc::format!("{}/{}", dir, name);
+                    if let Some(app) = read_app(vendor, &name, &path) {
+                        apps.push(app);
+                    }
+                }
+            }
+        }
+    }
+
+    // Ordem estável, independente da ordem de listagem
+    apps.sort_by(|a, b| (&a.vendor, &a.name).cmp(&(&b.vendor, &b.name)));
+    Some(apps)
+}
+
+/// Lê o manifesto do app em `app_dir`.
+fn read_app(vendor: &str, name: &str, app_dir: &str) -> Option<IndexedApp> {
+    let path = alloc::format!("{}/{}", app_dir, MANIFEST_NAME);
+    let Ok(data) = read_file(&path, MAX_MANIFEST_SIZE) else {
+        redpowder::println!("[Indexer] WARN: {} ausente ou grande demais", path);
+        return None;
+    };
+    let Ok(text) = core::str::from_utf8(&data) else {
+        redpowder::println!("[Indexer] WARN: {} nao e UTF-8 valido", path);
+        return None;
+    };
+
+    match AppManifest::parse(text) {
+        Ok(manifest) => Some(IndexedApp {
+            vendor: vendor.to_string(),
+            name: name.to_string(),
+            icon_path: manifest.icon_path(app_dir),
+            exec_path: manifest.exec_path(app_dir, name),
+            manifest,
+        }),
+        Err(e) => {
+            redpowder::println!("[Indexer] WARN: {} invalido: {:?}", path, e);
+            None
+        }
+    }
+}
+
+/// Conteúdo do cache no formato [`CACHE_VERSION`] (ver `discovery`).
+fn render_cache(apps: &[IndexedApp]) -> String {
+    let mut out = alloc::format!(
+        "{}\n{}{}\n{}{}\n",
+        CACHE_COMMENT,
+        VERSION_PREFIX,
+        CACHE_VERSION,
+        ENTRIES_PREFIX,
+        apps.len()
+    );
+    for app in apps {
+        let manifest = &app.manifest;
+        let mut flags = Vec::new();
+        if manifest.hidden {
+            flags.push(FLAG_HIDDEN);
+        }
+        if manifest.single_instance {
+            flags.push(FLAG_SINGLE_INSTANCE);
+        }
+
+        push_field(&mut out, &app.vendor);
+        push_field(&mut out, &app.name);
+        push_field(&mut out, &manifest.name);
+        push_field(&mut out, app.icon_path.as_deref().unwrap_or(""));
+        push_field(&mut out, &manifest.category);
+        push_field(&mut out, &app.exec_path);
+        push_list(&mut out, &manifest.args);
+        push_env(&mut out, &manifest.env);
+        push_field(&mut out, &manifest.description);
+        push_list(&mut out, &manifest.keywords);
+        push_list(&mut out, &flags);
+
+        // Troca o último separador pelo fim de linha
+        out.pop();
+        out.push('\n');
+    }
+
+    // Checksum de tudo que veio antes, por último: gravação truncada não confere
+    let hash = fnv1a(FNV1A_SEED, out.as_bytes());
+    out.push_str(&alloc::format!("{}{:08x}\n", CHECKSUM_PREFIX, hash));
+    out
+}
+
+/// Acrescenta um campo escapado seguido de [`FIELD_SEPARATOR`].
+fn push_field(out: &mut String, field: &str) {
+    escape_into(out, field);
+    out.push(FIELD_SEPARATOR);
+}
+
+/// Acrescenta um campo de lista (itens separados por [`LIST_SEPARATOR`]).
+fn push_list(out: &mut String, items: &[impl AsRef<str>]) {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push(LIST_SEPARATOR);
+        }
+        escape_into(out, item.as_ref());
+    }
+    out.push(FIELD_SEPARATOR);
+}
+
+/// Acrescenta o campo `env` (itens `CHAVE=valor`).
+fn push_env(out: &mut String, env: &[(String, String)]) {
+    for (i, (key, value)) in env.iter().enumerate() {
+        if i > 0 {
+            out.push(LIST_SEPARATOR);
+        }
+        escape_into(out, key);
+        out.push(ENV_SEPARATOR);
+        escape_into(out, value);
+    }
+    out.push(FIELD_SEPARATOR);
+}
@@ -2,16 +2,53 @@
 //!
 //! Lançamento de aplicativos.
 
+use alloc::vec::Vec;
 use redpowder::process;
 
 /// Lança um aplicativo pelo caminho.
+// TODO: Revisar no futuro
+#[allow(unused)]
 pub fn launch_app(path: &str) -> bool {
+    spawn(path, &[], &[])
+}
+
+/// Lança app por AppInfo, com os argumentos e o ambiente do manifesto.
+pub fn launch_app_info(app: &super::AppInfo) -> bool {
+    let args: Vec<&str> = app.args.iter().map(|a| a.as_str()).collect();
+    let env: Vec<(&str, &str)> = app
+        .env
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    spawn(&app.path, &args, &env)
+}
+
+/// Cria o processo (com ambiente próprio só quando há variáveis).
+///
+/// `spawn_with_env` ainda não existe no SDK publicado e só é usado com a
+/// feature `spawn-env`; sem ela o app é lançado sem as variáveis.
+fn spawn(path: &str, args: &[&str], env: &[(&str, &str)]) -> bool {
     redpowder::println!("[Launcher] Iniciando: {}", path);
 
-    // spawn requer path e args
-    let args: &[&str] = &[];
+    #[cfg(feature = "spawn-env")]
+    let result = if env.is_empty() {
+        process::spawn(path, args)
+    } else {
+        process::spawn_with_env(path, args, env)
+    };
 
-    match process::spawn(path, args) {
+    #[cfg(not(feature = "spawn-env"))]
+    let result = {
+        if !env.is_empty() {
+            redpowder::println!(
+                "[Launcher] WARN: {} variaveis de ambiente ignoradas (SDK sem spawn_with_env)",
+                env.len()
+            );
+        }
+        process::spawn(path, args)
+    };
+
+    match result {
         Ok(pid) => {
             redpowder::println!("[Launcher] App iniciado (PID {})", pid);
             true
@@ -22,10 +59,3 @@ pub fn launch_app(path: &str) -> bool {
         }
     }
 }
-
-// TODO: Revisar no futuro
-#[allow(unused)]
-/// Lança app por AppInfo.
-pub fn launch_app_info(app: &super::AppInfo) -> bool {
-    launch_app(&app.path)
-}
@@ -0,0 +1,285 @@
+//! # Manifest
+//!
+//! Manifesto de app (`/apps/<vendor>/<name>/app.toml`).
+//!
+//! ## Formato
+//!
+//! ```toml
+//! [app]
+//! name = "Terminal"        # obrigatório
+//! icon = "icon.svg"        # opcional, relativo a assets/
+//! category = "system"      # opcional, padrão "other"
+//! exec = "bin/term.app"    # opcional, relativo ao app; padrão "<name>.app"
+//! args = ["--login"]       # argumentos padrão
+//! description = "Console do sistema"
+//! keywords = ["shell", "console"]
+//! hidden = false           # true (ou no_display) esconde do menu
+//! single_instance = true   # reabre a janela existente em vez de lançar
+//!
+//! [app.env]                # variáveis de ambiente
+//! TERM = "firefly"
+//! ```
+//!
+//! Chaves desconhecidas são ignoradas, para que manifestos mais novos
+//! continuem legíveis.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::toml::{Document, Table, TomlError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Nome do arquivo de manifesto dentro do diretório do app.
+pub const MANIFEST_NAME: &str = "app.toml";
+
+/// Categoria usada quando o manifesto não define uma.
+const DEFAULT_CATEGORY: &str = "other";
+
+// =============================================================================
+// ERROS
+// =============================================================================
+
+/// Erro ao interpretar um manifesto.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManifestError {
+    /// TOML inválido.
+    Toml(TomlError),
+    /// Falta a tabela `[app]`.
+    MissingTable,
+    /// Falta um campo obrigatório.
+    MissingField(&'static str),
+    /// Campo com tipo errado.
+    WrongType(&'static str),
+}
+
+impl From<TomlError> for ManifestError {
+    fn from(e: TomlError) -> Self {
+        ManifestError::Toml(e)
+    }
+}
+
+// =============================================================================
+// MANIFEST
+// =============================================================================
+
+/// Metadados de um app, lidos de `app.toml`.
+#[derive(Debug, Clone)]
+pub struct AppManifest {
+    /// Nome de exibição.
+    pub name: String,
+    /// Ícone, como escrito no manifesto.
+    pub icon: Option<String>,
+    /// Categoria.
+    pub category: String,
+    /// Executável, como escrito no manifesto.
+    pub exec: Option<String>,
+    /// Argumentos padrão.
+    pub args: Vec<String>,
+    /// Variáveis de ambiente (`[app.env]`).
+    pub env: Vec<(String, String)>,
+    /// Descrição curta.
+    pub description: String,
+    /// Palavras-chave para busca.
+    pub keywords: Vec<String>,
+    /// Não aparece no menu iniciar.
+    pub hidden: bool,
+    /// No máximo uma instância em execução.
+    pub single_instance: bool,
+}
+
+impl AppManifest {
+    /// Interpreta o conteúdo de um `app.toml`.
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        let document = Document::parse(text)?;
+        let app = document.table("app").ok_or(ManifestError::MissingTable)?;
+
+        let name = string(app, "name")?.ok_or(ManifestError::MissingField("name"))?;
+        let icon = string(app, "icon")?.filter(|icon| !icon.is_empty());
+        let category = string(app, "category")?.unwrap_or(DEFAULT_CATEGORY);
+        let exec = string(app, "exec")?.filter(|exec| !exec.is_empty());
+        let description = string(app, "description")?.unwrap_or("");
+        let hidden = boolean(app, "hidden")? || boolean(app, "no_display")?;
+
+        // Variáveis de ambiente: todos os valores precisam ser strings
+        let mut env = Vec::new();
+        if let Some(table) = document.table("app.env") {
+            for (key, value) in &table.entries {
+                let value = value.as_str().ok_or(ManifestError::WrongType("env"))?;
+                env.push((key.clone(), value.to_string()));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            icon: icon.map(ToString::to_string),
+            category: category.to_string(),
+            exec: exec.map(ToString::to_string),
+            args: string_list(app, "args")?,
+            env,
+            description: description.to_string(),
+            keywords: string_list(app, "keywords")?,
+            hidden,
+            single_instance: boolean(app, "single_instance")?,
+        })
+    }
+
+    /// Caminho absoluto do executável do app `name` instalado em `app_dir`.
+    ///
+    /// Caminhos absolutos são mantidos; os demais são relativos a `app_dir`.
+    pub fn exec_path(&self, app_dir: &str, name: &str) -> String {
+        match self.exec.as_deref() {
+            Some(exec) if exec.starts_with('/') => exec.to_string(),
+            Some(exec) => alloc::format!("{}/{}", app_dir, exec),
+            None => alloc::format!("{}/{}.app", app_dir, name),
+        }
+    }
+
+    /// Caminho absoluto do ícone de um app instalado em `app_dir`.
+    ///
+    /// Caminhos absolutos são mantidos; os demais são relativos a `assets/`.
+    pub fn icon_path(&self, app_dir: &str) -> Option<String> {
+        let icon = self.icon.as_deref()?;
+        if icon.starts_with('/') {
+            Some(icon.to_string())
+        } else {
+            Some(alloc::format!("{}/assets/{}", app_dir, icon))
+        }
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Campo string opcional.
+fn string<'a>(table: &'a Table, key: &'static str) -> Result<Option<&'a str>, ManifestError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .as_str()
+            .map(Some)
+            .ok_or(ManifestError::WrongType(key)),
+    }
+}
+
+/// Campo booleano opcional (ausente = false).
+fn boolean(table: &Table, key: &'static str) -> Result<bool, ManifestError> {
+    match table.get(key) {
+        None => Ok(false),
+        Some(value) => value.as_bool().ok_or(ManifestError::WrongType(key)),
+    }
+}
+
+/// Campo opcional com array de strings (ausente = vazio).
+fn string_list(table: &Table, key: &'static str) -> Result<Vec<String>, ManifestError> {
+    let Some(value) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .ok_or(ManifestError::WrongType(key))?
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(ToString::to_string)
+                .ok_or(ManifestError::WrongType(key))
+        })
+        .collect()
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::toml::TomlErrorKind;
+
+    #[test]
+    fn parses_full_manifest() {
+        let text = r#"
+[app]
+name = "Terminal"
+icon = "icon.svg"
+category = "system"
+exec = "bin/term.app"
+args = ["--login"]
+description = "Console do sistema"
+keywords = ["shell", "console"]
+hidden = false
+single_instance = true
+
+[app.env]
+TERM = "firefly"
+"#;
+        let manifest = AppManifest::parse(text).unwrap();
+
+        assert_eq!(manifest.name, "Terminal");
+        assert_eq!(manifest.category, "system");
+        assert_eq!(manifest.args, ["--login"]);
+        assert_eq!(manifest.keywords, ["shell", "console"]);
+        assert_eq!(manifest.env, [("TERM".to_string(), "firefly".to_string())]);
+        assert!(!manifest.hidden);
+        assert!(manifest.single_instance);
+
+        let dir = "/apps/redstone/term";
+        assert_eq!(
+            manifest.exec_path(dir, "term"),
+            "/apps/redstone/term/bin/term.app"
+        );
+        assert_eq!(
+            manifest.icon_path(dir).as_deref(),
+            Some("/apps/redstone/term/assets/icon.svg")
+        );
+    }
+
+    #[test]
+    fn applies_defaults() {
+        let manifest = AppManifest::parse("[app]\nname = \"Calc\"\nno_display = true\n").unwrap();
+
+        assert_eq!(manifest.category, DEFAULT_CATEGORY);
+        assert!(manifest.args.is_empty() && manifest.env.is_empty());
+        assert!(manifest.hidden);
+        assert!(!manifest.single_instance);
+        assert_eq!(
+            manifest.exec_path("/apps/x/calc", "calc"),
+            "/apps/x/calc/calc.app"
+        );
+        assert_eq!(manifest.icon_path("/apps/x/calc"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert_eq!(
+            AppManifest::parse("name = \"Calc\"").unwrap_err(),
+            ManifestError::MissingTable
+        );
+        assert_eq!(
+            AppManifest::parse("[app]\nicon = \"x.svg\"").unwrap_err(),
+            ManifestError::MissingField("name")
+        );
+        assert_eq!(
+            AppManifest::parse("[app]\nname = 1").unwrap_err(),
+            ManifestError::WrongType("name")
+        );
+        assert_eq!(
+            AppManifest::parse("[app]\nname = \"a\"\nargs = [\"x\", 1]").unwrap_err(),
+            ManifestError::WrongType("args")
+        );
+        assert_eq!(
+            AppManifest::parse("[app]\nname = \"a\"\n[app.env]\nN = 1").unwrap_err(),
+            ManifestError::WrongType("env")
+        );
+        assert!(matches!(
+            AppManifest::parse("[app]\nname = \"a\"\nname = \"b\""),
+            Err(ManifestError::Toml(TomlError {
+                line: 3,
+                kind: TomlErrorKind::DuplicateKey
+            }))
+        ));
+    }
+}
@@ -4,16 +4,25 @@
 //!
 //! ## Componentes
 //!
+//! - **config**: Configuração do shell (`shell.conf`)
 //! - **desktop**: Desktop Environment principal
-//! - **discovery**: Descoberta de apps com app.toml
+//! - **discovery**: Descoberta de apps (leitura do cache)
+//! - **indexer**: Geração do cache a partir de /apps
 //! - **launcher**: Lançamento de apps
+//! - **manifest**: Manifesto `app.toml` de cada app
+//! - **toml**: Parser de um subconjunto de TOML
 
+mod config;
 mod desktop;
 mod discovery;
+mod indexer;
 mod launcher;
+mod manifest;
+mod toml;
 
+pub use config::{ShellConfig, SHELL_CONFIG_PATH};
 pub use desktop::Desktop;
 // TODO: Revisar no futuro
 #[allow(unused)]
-pub use discovery::{discover_apps, AppIcon, AppInfo};
-pub use launcher::launch_app;
+pub use discovery::{discover_apps, rescan_apps, AppIcon, AppInfo};
+pub use launcher::launch_app_info;
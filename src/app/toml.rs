@@ -0,0 +1,604 @@
+//! # TOML
+//!
+//! Parser de um subconjunto de TOML, suficiente para manifestos de apps.
+//!
+//! ## Suportado
+//!
+//! - Tabelas `[nome]` (nomes com ponto são tratados como texto simples)
+//! - Chaves simples ou entre aspas: `key = valor`
+//! - Strings básicas (`"..."`, com escapes `\" \\ \n \t \r \uXXXX`) e
+//!   literais (`'...'`)
+//! - Inteiros decimais com sinal e `_` separador
+//! - Booleanos
+//! - Arrays desses valores, inclusive em várias linhas
+//! - Comentários `#` até o fim da linha
+//!
+//! Fora disso (datas, floats, tabelas inline, strings multilinha, arrays de
+//! tabelas) é erro de sintaxe, sempre com o número da linha.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Profundidade máxima de arrays aninhados.
+const MAX_ARRAY_DEPTH: usize = 8;
+
+// =============================================================================
+// ERROS
+// =============================================================================
+
+/// Tipo de erro de parse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TomlErrorKind {
+    /// Construção fora do subconjunto suportado.
+    Syntax,
+    /// String sem aspas de fechamento.
+    UnterminatedString,
+    /// Escape desconhecido ou `\u` inválido.
+    BadEscape,
+    /// Inteiro malformado ou fora do intervalo de `i64`.
+    BadNumber,
+    /// Chave repetida na mesma tabela.
+    DuplicateKey,
+    /// Tabela declarada duas vezes.
+    DuplicateTable,
+    /// Arrays aninhados demais.
+    TooDeep,
+}
+
+/// Erro de parse, com a linha (1-based) onde ocorreu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TomlError {
+    pub line: usize,
+    pub kind: TomlErrorKind,
+}
+
+// =============================================================================
+// VALORES
+// =============================================================================
+
+/// Valor TOML.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Conteúdo, se for string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Conteúdo, se for inteiro.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Conteúdo, se for booleano.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Itens, se for array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Tabela: pares chave/valor na ordem do arquivo.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    /// Nome (vazio para a tabela raiz).
+    pub name: String,
+    pub entries: Vec<(String, Value)>,
+}
+
+impl Table {
+    /// Valor de `key`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Documento: tabela raiz seguida das tabelas declaradas.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub tables: Vec<Table>,
+}
+
+impl Document {
+    /// Faz o parse de `text`.
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        Parser::new(text).document()
+    }
+
+    /// Tabela `name` (`""` para a raiz).
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+}
+
+// =============================================================================
+// PARSER
+// =============================================================================
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            text,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn error(&self, kind: TomlErrorKind) -> TomlError {
+        TomlError {
+            line: self.line,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+        }
+        Some(b)
+    }
+
+    /// Pula espaços e tabs (não quebras de linha).
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Pula espaços, quebras de linha e comentários.
+    fn skip_blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => {
+                    self.bump();
+                }
+                Some(b'#') => self.skip_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while !matches!(self.peek(), None | Some(b'\n')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Exige fim de linha (com comentário opcional) após uma declaração.
+    fn end_of_line(&mut self) -> Result<(), TomlError> {
+        self.skip_spaces();
+        if self.peek() == Some(b'#') {
+            self.skip_comment();
+        }
+        match self.peek() {
+            None | Some(b'\n') => Ok(()),
+            Some(b'\r') if self.bytes.get(self.pos + 1) == Some(&b'\n') => Ok(()),
+            Some(_) => Err(self.error(TomlErrorKind::Syntax)),
+        }
+    }
+
+    fn document(mut self) -> Result<Document, TomlError> {
+        let mut tables = Vec::new();
+        // Pares antes do primeiro cabeçalho vão para a raiz
+        let mut current = Table::default();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => {
+                    tables.push(current);
+                    return Ok(Document { tables });
+                }
+                Some(b'[') => {
+                    self.bump();
+                    if self.peek() == Some(b'[') {
+                        // Arrays de tabelas ficam de fora
+                        return Err(self.error(TomlErrorKind::Syntax));
+                    }
+                    self.skip_spaces();
+                    let name = self.key()?;
+                    self.skip_spaces();
+                    if self.bump() != Some(b']') {
+                        return Err(self.error(TomlErrorKind::Syntax));
+                    }
+                    self.end_of_line()?;
+                    if name == current.name || tables.iter().any(|t: &Table| t.name == name) {
+                        return Err(self.error(TomlErrorKind::DuplicateTable));
+                    }
+                    tables.push(core::mem::replace(
+                        &mut current,
+                        Table {
+                            name,
+                            entries: Vec::new(),
+                        },
+                    ));
+                }
+                Some(_) => {
+                    let key = self.key()?;
+                    self.skip_spaces();
+                    if self.bump() != Some(b'=') {
+                        return Err(self.error(TomlErrorKind::Syntax));
+                    }
+                    self.skip_spaces();
+                    let value = self.value(0)?;
+                    self.end_of_line()?;
+
+                    if current.get(&key).is_some() {
+                        return Err(self.error(TomlErrorKind::DuplicateKey));
+                    }
+                    current.entries.push((key, value));
+                }
+            }
+        }
+    }
+
+    /// Chave simples (`[A-Za-z0-9_.-]+`) ou entre aspas.
+    fn key(&mut self) -> Result<String, TomlError> {
+        match self.peek() {
+            Some(b'"') => self.basic_string(),
+            Some(b'\'') => self.literal_string(),
+            _ => {
+                let start = self.pos;
+                while matches!(
+                    self.peek(),
+                    Some(b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.')
+                ) {
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(self.error(TomlErrorKind::Syntax));
+                }
+                Ok(self.text[start..self.pos].to_string())
+            }
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, TomlError> {
+        match self.peek() {
+            Some(b'"') => self.basic_string().map(Value::String),
+            Some(b'\'') => self.literal_string().map(Value::String),
+            Some(b'[') => self.array(depth),
+            Some(b't') if self.text[self.pos..].starts_with("true") => {
+                self.pos += 4;
+                Ok(Value::Boolean(true))
+            }
+            Some(b'f') if self.text[self.pos..].starts_with("false") => {
+                self.pos += 5;
+                Ok(Value::Boolean(false))
+            }
+            Some(b'+' | b'-' | b'0'..=b'9') => self.integer(),
+            _ => Err(self.error(TomlErrorKind::Syntax)),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, TomlError> {
+        if depth >= MAX_ARRAY_DEPTH {
+            return Err(self.error(TomlErrorKind::TooDeep));
+        }
+        self.bump(); // '['
+
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(b']') {
+                self.bump();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value(depth + 1)?);
+            self.skip_blank();
+            match self.bump() {
+                Some(b',') => {}
+                Some(b']') => return Ok(Value::Array(items)),
+                _ => return Err(self.error(TomlErrorKind::Syntax)),
+            }
+        }
+    }
+
+    fn integer(&mut self) -> Result<Value, TomlError> {
+        let negative = match self.peek() {
+            Some(b'-') => {
+                self.pos += 1;
+                true
+            }
+            Some(b'+') => {
+                self.pos += 1;
+                false
+            }
+            _ => false,
+        };
+
+        let mut value: i64 = 0;
+        let mut digits = 0;
+        let mut last_underscore = false;
+        loop {
+            match self.peek() {
+                Some(d @ b'0'..=b'9') => {
+                    let digit = (d - b'0') as i64;
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| {
+                            if negative {
+                                v.checked_sub(digit)
+                            } else {
+                                v.checked_add(digit)
+                            }
+                        })
+                        .ok_or(self.error(TomlErrorKind::BadNumber))?;
+                    digits += 1;
+                    last_underscore = false;
+                }
+                Some(b'_') if digits > 0 && !last_underscore => last_underscore = true,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+
+        // Floats e datas começam como inteiros; ficam de fora
+        let trailing = matches!(
+            self.peek(),
+            Some(b'.' | b'e' | b'E' | b':' | b'-' | b'_' | b'A'..=b'Z' | b'a'..=b'z')
+        );
+        if digits == 0 || last_underscore || trailing {
+            return Err(self.error(TomlErrorKind::BadNumber));
+        }
+        Ok(Value::Integer(value))
+    }
+
+    fn literal_string(&mut self) -> Result<String, TomlError> {
+        self.bump(); // '\''
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                None | Some(b'\n') => return Err(self.error(TomlErrorKind::UnterminatedString)),
+                Some(b'\'') => break,
+                Some(_) => self.pos += 1,
+            }
+        }
+        let s = self.text[start..self.pos].to_string();
+        self.pos += 1;
+        Ok(s)
+    }
+
+    fn basic_string(&mut self) -> Result<String, TomlError> {
+        self.bump(); // '"'
+        let mut out = String::new();
+        loop {
+            // Copia o trecho sem escapes de uma vez
+            let start = self.pos;
+            while !matches!(self.peek(), None | Some(b'"' | b'\\' | b'\n')) {
+                self.pos += 1;
+            }
+            out.push_str(&self.text[start..self.pos]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    out.push(self.escape()?);
+                }
+                _ => return Err(self.error(TomlErrorKind::UnterminatedString)),
+            }
+        }
+    }
+
+    /// Escape após a barra.
+    fn escape(&mut self) -> Result<char, TomlError> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'n') => '\n',
+            Some(b't') => '\t',
+            Some(b'r') => '\r',
+            Some(b'u') => {
+                let hex = self
+                    .text
+                    .get(self.pos + 1..self.pos + 5)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .ok_or(self.error(TomlErrorKind::BadEscape))?;
+                let code = u32::from_str_radix(hex, 16)
+                    .map_err(|_| self.error(TomlErrorKind::BadEscape))?;
+                self.pos += 4;
+                char::from_u32(code).ok_or(self.error(TomlErrorKind::BadEscape))?
+            }
+            _ => return Err(self.error(TomlErrorKind::BadEscape)),
+        };
+        self.pos += 1;
+        Ok(c)
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::vec;
+
+    /// Escreve `value` em TOML, para conferir o caminho de volta.
+    fn render(value: &Value) -> String {
+        match value {
+            Value::String(s) => {
+                let mut out = String::from("\"");
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\t' => out.push_str("\\t"),
+                        '\r' => out.push_str("\\r"),
+                        c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+                out
+            }
+            Value::Integer(n) => n.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(render).collect();
+                format!("[{}]", items.join(", "))
+            }
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+
+    fn error(text: &str) -> (usize, TomlErrorKind) {
+        let e = Document::parse(text).unwrap_err();
+        (e.line, e.kind)
+    }
+
+    #[test]
+    fn parses_supported_subset() {
+        let text = r#"
+# comentário
+title = "Firefly"   # fim de linha
+count = -1_000
+big = +42
+on = true
+off = false
+'quoted key' = 'C:\path'
+"escaped" = "a\"b\\c\n\u00e9"
+
+[app]
+list = [
+  "a", # comentário
+  'b',
+]
+nested = [[1, 2], []]
+
+[app.env]
+TERM = "firefly"
+"#;
+        let doc = Document::parse(text).unwrap();
+        let root = doc.table("").unwrap();
+
+        assert_eq!(root.get("title"), Some(&string("Firefly")));
+        assert_eq!(root.get("count").and_then(Value::as_integer), Some(-1000));
+        assert_eq!(root.get("big").and_then(Value::as_integer), Some(42));
+        assert_eq!(root.get("on").and_then(Value::as_bool), Some(true));
+        assert_eq!(root.get("off").and_then(Value::as_bool), Some(false));
+        assert_eq!(root.get("quoted key"), Some(&string("C:\\path")));
+        assert_eq!(root.get("escaped"), Some(&string("a\"b\\c\né")));
+
+        let app = doc.table("app").unwrap();
+        assert_eq!(
+            app.get("list"),
+            Some(&Value::Array(vec![string("a"), string("b")]))
+        );
+        assert_eq!(
+            app.get("nested"),
+            Some(&Value::Array(vec![
+                Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+                Value::Array(Vec::new()),
+            ]))
+        );
+
+        // Nome com ponto é só texto; a ordem das tabelas é a do arquivo
+        let names: Vec<&str> = doc.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["", "app", "app.env"]);
+        assert_eq!(
+            doc.table("app.env").unwrap().get("TERM"),
+            Some(&string("firefly"))
+        );
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let values = [
+            string(""),
+            string("Terminal"),
+            string("aspas \" barra \\ fim"),
+            string("linha\nnova\ttab\rcr"),
+            string("controle \u{1} \u{7f} e unicode: ação ✓"),
+            Value::Integer(0),
+            Value::Integer(i64::MAX),
+            Value::Integer(i64::MIN),
+            Value::Boolean(true),
+            Value::Array(Vec::new()),
+            Value::Array(vec![
+                string("--login"),
+                Value::Array(vec![Value::Integer(-1), Value::Boolean(false)]),
+            ]),
+        ];
+
+        for value in values {
+            let text = format!("key = {}\n", render(&value));
+            let doc = Document::parse(&text).unwrap();
+            assert_eq!(doc.table("").unwrap().get("key"), Some(&value), "{}", text);
+        }
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        use TomlErrorKind::*;
+
+        assert_eq!(error("a = 1.5"), (1, BadNumber));
+        assert_eq!(error("a = 1\nb = 2024-01-01"), (2, BadNumber));
+        assert_eq!(error("a = 1_"), (1, BadNumber));
+        assert_eq!(error("a = 9223372036854775808"), (1, BadNumber));
+        assert_eq!(error("a = _1"), (1, Syntax));
+        assert_eq!(error("a = \"abc"), (1, UnterminatedString));
+        assert_eq!(error("a = 'abc\n'"), (1, UnterminatedString));
+        assert_eq!(error("a = \"\\x\""), (1, BadEscape));
+        assert_eq!(error("a = \"\\u12\"\n"), (1, BadEscape));
+        assert_eq!(error("a = 1\n\na = 2"), (3, DuplicateKey));
+        assert_eq!(error("[x]\n[x]"), (2, DuplicateTable));
+        assert_eq!(error("[[x]]"), (1, Syntax));
+        assert_eq!(error("a = {b = 1}"), (1, Syntax));
+        assert_eq!(error("a = 1 b"), (1, Syntax));
+        assert_eq!(error("a = [1,\n2\n"), (3, Syntax));
+        assert_eq!(error("a = [[[[[[[[[1]]]]]]]]]"), (1, TooDeep));
+    }
+}
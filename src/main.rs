@@ -8,7 +8,8 @@
 //! - **Painel de Widgets**: Slide-up da esquerda
 //! - **Quick Settings**: Configurações rápidas da direita
 //! - **Menu Iniciar**: Lista apps com ícones do app.toml
-//! - **Wallpaper**: Suporte a webp com fallback gradiente
+//! - **Wallpaper**: PNG, QOI, BMP e WebP, modos de escala, slideshow e
+//!   fallback gradiente, configurável em `shell.conf`
 //!
 //! ## Arquitetura
 //!
@@ -16,12 +17,17 @@
 //! shell/src/
 //! ├── main.rs           # Entry point
 //! ├── app/              # Gerenciamento de apps
+//! │   ├── config.rs     # Configuração (shell.conf)
 //! │   ├── desktop.rs    # Desktop Environment
-//! │   ├── discovery.rs  # Descoberta de apps (app.toml)
-//! │   └── launcher.rs   # Lançamento de apps
+//! │   ├── discovery.rs  # Descoberta de apps (apps.cache)
+//! │   ├── indexer.rs    # Geração do apps.cache a partir de /apps
+//! │   ├── launcher.rs   # Lançamento de apps
+//! │   ├── manifest.rs   # Manifesto app.toml
+//! │   └── toml.rs       # Parser de um subconjunto de TOML
 //! ├── ui/               # Componentes visuais
 //! │   ├── wallpaper.rs  # Papel de parede
 //! │   ├── taskbar.rs    # Barras flutuantes
+//! │   ├── icons.rs      # Cache de ícones de apps
 //! │   └── panels/       # Painéis popup
 //! │       ├── widget_panel.rs
 //! │       ├── start_menu.rs
@@ -30,12 +36,30 @@
 //! │   ├── colors.rs     # Paleta de cores
 //! │   ├── glass.rs      # Efeitos de vidro
 //! │   └── metrics.rs    # Métricas de layout
-//! └── render/           # Renderização
-//!     └── font.rs       # Fontes
+//! ├── render/           # Renderização
+//! │   ├── blur.rs       # Blur e saturação (vidro fosco)
+//! │   ├── canvas.rs     # Superfície de desenho (clip, origem, primitivas)
+//! │   ├── damage.rs     # Regiões danificadas (redesenho parcial)
+//! │   ├── font/         # Fontes (TrueType, PSF2, BDF, 8x8)
+//! │   ├── glyph_cache.rs # Cache de glifos rasterizados
+//! │   ├── image/        # Imagens (PNG, QOI, BMP, WebP e SVG)
+//! │   ├── layout.rs     # Medição, reticências e quebra de linhas
+//! │   ├── paint.rs      # Tintas: cor, gradientes e imagem
+//! │   ├── pixel.rs      # Blend pré-multiplicado sem divisão
+//! │   ├── raster.rs     # Rasterizador com anti-aliasing
+//! │   ├── shadow.rs     # Sombras projetadas (canto pré-desfocado)
+//! │   ├── shape.rs      # Retângulos arredondados com anti-aliasing
+//! │   ├── surface.rs    # Buffers offscreen (camadas)
+//! │   └── text.rs       # Desenho de texto
+//! └── util/             # Utilitários compartilhados
+//!     ├── fs.rs         # Leitura e gravação de arquivos inteiros
+//!     └── hash.rs       # Hash FNV-1a estável
 //! ```
 
-#![no_std]
-#![no_main]
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(test), no_main)]
+// Nos testes de host o ponto de entrada não existe; quase tudo fica sem uso
+#![cfg_attr(test, allow(dead_code, unused_imports))]
 
 extern crate alloc;
 
@@ -43,6 +67,7 @@ mod app;
 mod render;
 mod theme;
 mod ui;
+mod util;
 
 use redpowder::println;
 
@@ -51,10 +76,12 @@ use redpowder::println;
 // =============================================================================
 
 /// Allocator global.
+#[cfg(not(test))]
 #[global_allocator]
 static ALLOCATOR: redpowder::mem::heap::SyscallAllocator = redpowder::mem::heap::SyscallAllocator;
 
 /// Panic handler.
+#[cfg(not(test))]
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
     println!("[Shell] PANIC: {:?}", info);
@@ -65,11 +92,13 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
 // ENTRY POINT
 // =============================================================================
 
+#[cfg(not(test))]
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
     main()
 }
 
+#[cfg(not(test))]
 fn main() -> ! {
     println!("[Shell] ========================================");
     println!("[Shell] Firefly Shell v0.3.0");
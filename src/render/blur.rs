@@ -0,0 +1,293 @@
+//! # Blur
+//!
+//! Filtros de fundo para o efeito de vidro fosco.
+//!
+//! O blur é um box blur separável (horizontal e depois vertical) com soma
+//! deslizante: o custo por pixel não depende do raio. Três passadas
+//! aproximam um blur gaussiano. Tudo em inteiros; a divisão pela largura
+//! da janela vira multiplicação por um recíproco em ponto fixo, e dois
+//! canais são somados por operação.
+//!
+//! Raios grandes usam o caminho rápido: a região é reduzida à metade,
+//! desfocada com metade do raio e ampliada de volta (bilinear). O resultado
+//! é visualmente igual para vidro fosco e custa cerca de 1/4.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Passadas de box blur (3 ≈ gaussiano).
+pub const BOX_BLUR_PASSES: u32 = 3;
+
+/// Raio a partir do qual o blur é feito em meia resolução.
+pub const HALF_RES_MIN_RADIUS: u32 = 4;
+
+/// Bits de fração do recíproco usado na média.
+const RECIPROCAL_SHIFT: u32 = 16;
+
+// =============================================================================
+// BLUR
+// =============================================================================
+
+/// Aplica box blur de raio `radius` em `pixels` (`width * height`, contínuo).
+///
+/// As bordas são estendidas (clamp), então a região não escurece nos cantos.
+pub fn box_blur(pixels: &mut [u32], width: usize, height: usize, radius: u32) {
+    if radius == 0 || width == 0 || height == 0 || pixels.len() < width * height {
+        return;
+    }
+
+    let pixels = &mut pixels[..width * height];
+    if radius >= HALF_RES_MIN_RADIUS && width >= 2 && height >= 2 {
+        let (mut small, small_width, small_height) = downsample(pixels, width, height);
+        blur_full(&mut small, small_width, small_height, radius / 2);
+        upsample(&small, small_width, small_height, pixels, width, height);
+    } else {
+        blur_full(pixels, width, height, radius);
+    }
+}
+
+/// Box blur em resolução cheia.
+fn blur_full(pixels: &mut [u32], width: usize, height: usize, radius: u32) {
+    let mut temp = vec![0u32; width * height];
+    let mut sums = vec![Lanes(0, 0); width];
+    for _ in 0..BOX_BLUR_PASSES {
+        blur_horizontal(pixels, &mut temp, width, radius);
+        blur_vertical(&temp, pixels, width, height, radius, &mut sums);
+    }
+}
+
+/// Box blur de cada linha de `src` para `dst`.
+fn blur_horizontal(src: &[u32], dst: &mut [u32], width: usize, radius: u32) {
+    let r = radius as usize;
+    let reciprocal = reciprocal(radius);
+
+    for (row, out) in src.chunks_exact(width).zip(dst.chunks_exact_mut(width)) {
+        let first = spread(row[0]);
+        let last = spread(row[width - 1]);
+        let at = |i: usize| if i < width { spread(row[i]) } else { last };
+
+        // Soma inicial da janela centrada no primeiro pixel
+        let mut sum = Lanes(0, 0);
+        for _ in 0..r {
+            sum.add(first);
+        }
+        for i in 0..=r {
+            sum.add(at(i));
+        }
+
+        for (x, px) in out.iter_mut().enumerate() {
+            *px = sum.average(reciprocal);
+            sum.sub(if x >= r { spread(row[x - r]) } else { first });
+            sum.add(at(x + r + 1));
+        }
+    }
+}
+
+/// Box blur de cada coluna de `src` para `dst`.
+///
+/// Percorre linha a linha mantendo uma soma por coluna em `sums`, para ler
+/// a memória em ordem.
+fn blur_vertical(
+    src: &[u32],
+    dst: &mut [u32],
+    width: usize,
+    height: usize,
+    radius: u32,
+    sums: &mut [Lanes],
+) {
+    let r = radius as usize;
+    let reciprocal = reciprocal(radius);
+    let row = |y: usize| &src[y.min(height - 1) * width..][..width];
+
+    // Soma inicial da janela centrada na primeira linha
+    for (x, sum) in sums.iter_mut().enumerate() {
+        let first = spread(src[x]);
+        *sum = Lanes(0, 0);
+        for _ in 0..r {
+            sum.add(first);
+        }
+        for y in 0..=r {
+            sum.add(spread(row(y)[x]));
+        }
+    }
+
+    for (y, out) in dst.chunks_exact_mut(width).enumerate() {
+        let leaving = row(y.saturating_sub(r));
+        let entering = row(y + r + 1);
+        let columns = sums.iter_mut().zip(leaving).zip(entering);
+        for (px, ((sum, &old), &new)) in out.iter_mut().zip(columns) {
+            *px = sum.average(reciprocal);
+            sum.sub(spread(old));
+            sum.add(spread(new));
+        }
+    }
+}
+
+// =============================================================================
+// MEIA RESOLUÇÃO
+// =============================================================================
+
+/// Reduz à metade (média de blocos 2x2). Retorna pixels e dimensões.
+fn downsample(src: &[u32], width: usize, height: usize) -> (Vec<u32>, usize, usize) {
+    let small_width = width.div_ceil(2);
+    let small_height = height.div_ceil(2);
+    let mut small = Vec::with_capacity(small_width * small_height);
+
+    for sy in 0..small_height {
+        let row0 = &src[sy * 2 * width..][..width];
+        let row1 = &src[(sy * 2 + 1).min(height - 1) * width..][..width];
+        for sx in 0..small_width {
+            let x0 = sx * 2;
+            let x1 = (x0 + 1).min(width - 1);
+            let mut sum = spread(row0[x0]);
+            sum.add(spread(row0[x1]));
+            sum.add(spread(row1[x0]));
+            sum.add(spread(row1[x1]));
+            small.push(sum.shift(2));
+        }
+    }
+
+    (small, small_width, small_height)
+}
+
+/// Amplia `small` para `dst` (`width * height`) com interpolação bilinear.
+///
+/// O centro do pixel `x` de `dst` cai em `x/2 - 1/4` de `small`; os pesos
+/// são quartos de pixel.
+fn upsample(
+    small: &[u32],
+    small_width: usize,
+    small_height: usize,
+    dst: &mut [u32],
+    width: usize,
+    height: usize,
+) {
+    // Índices de origem e peso (0-3) do segundo, por coordenada de destino
+    let sample = |i: usize, len: usize| {
+        let pos = (2 * i as isize - 1).max(0) as usize;
+        let a = (pos / 4).min(len - 1);
+        let b = (a + 1).min(len - 1);
+        (a, b, (pos % 4) as u64)
+    };
+
+    let columns: Vec<_> = (0..width).map(|x| sample(x, small_width)).collect();
+
+    for (y, out) in dst.chunks_exact_mut(width).take(height).enumerate() {
+        let (y0, y1, fy) = sample(y, small_height);
+        let row0 = &small[y0 * small_width..][..small_width];
+        let row1 = &small[y1 * small_width..][..small_width];
+
+        for (px, &(x0, x1, fx)) in out.iter_mut().zip(&columns) {
+            let mut sum = spread(row0[x0]).scale((4 - fx) * (4 - fy));
+            sum.add(spread(row0[x1]).scale(fx * (4 - fy)));
+            sum.add(spread(row1[x0]).scale((4 - fx) * fy));
+            sum.add(spread(row1[x1]).scale(fx * fy));
+            *px = sum.shift(4);
+        }
+    }
+}
+
+/// Recíproco em ponto fixo de uma janela de raio `radius`.
+#[inline]
+fn reciprocal(radius: u32) -> u64 {
+    ((1 << RECIPROCAL_SHIFT) / (2 * radius + 1)) as u64
+}
+
+// =============================================================================
+// CANAIS
+// =============================================================================
+
+/// Máscara dos dois canais de uma lane (bits 0-7 e 32-39).
+const LANE_MASK: u64 = 0x0000_00FF_0000_00FF;
+
+/// Canais ARGB em duas lanes de 64 bits: `(A, G)` e `(R, B)`, cada canal em
+/// 32 bits, para somar dois canais por operação sem transbordar.
+#[derive(Clone, Copy)]
+struct Lanes(u64, u64);
+
+/// Separa `pixel` em lanes.
+#[inline]
+fn spread(pixel: u32) -> Lanes {
+    let p = pixel as u64;
+    Lanes(
+        ((p << 8) | (p >> 8)) & LANE_MASK,
+        ((p << 16) | p) & LANE_MASK,
+    )
+}
+
+impl Lanes {
+    #[inline]
+    fn add(&mut self, other: Lanes) {
+        self.0 += other.0;
+        self.1 += other.1;
+    }
+
+    #[inline]
+    fn sub(&mut self, other: Lanes) {
+        self.0 -= other.0;
+        self.1 -= other.1;
+    }
+
+    /// Multiplica todos os canais por `factor`.
+    #[inline]
+    fn scale(self, factor: u64) -> Lanes {
+        Lanes(self.0 * factor, self.1 * factor)
+    }
+
+    /// Divide todos os canais por `2^bits` e junta em um pixel.
+    #[inline]
+    fn shift(self, bits: u32) -> u32 {
+        join((self.0 >> bits) & LANE_MASK, (self.1 >> bits) & LANE_MASK)
+    }
+
+    /// Média da janela usando o recíproco em ponto fixo.
+    ///
+    /// Cada canal soma no máximo `255 * janela`; vezes o recíproco fica
+    /// abaixo de 2^24, sem invadir a lane vizinha.
+    #[inline]
+    fn average(self, reciprocal: u64) -> u32 {
+        let half = (1u64 << (RECIPROCAL_SHIFT - 1)) * 0x0000_0001_0000_0001;
+        let ag = ((self.0 * reciprocal + half) >> RECIPROCAL_SHIFT) & LANE_MASK;
+        let rb = ((self.1 * reciprocal + half) >> RECIPROCAL_SHIFT) & LANE_MASK;
+        join(ag, rb)
+    }
+}
+
+/// Junta lanes `(A, G)` e `(R, B)` já reduzidas a 8 bits por canal.
+#[inline]
+fn join(ag: u64, rb: u64) -> u32 {
+    ((ag >> 8) | (ag << 8) | (rb >> 16) | rb) as u32
+}
+
+// =============================================================================
+// SATURAÇÃO
+// =============================================================================
+
+/// Ajusta a saturação em `percent` (100 = inalterado, 0 = cinza).
+///
+/// Os pixels são pré-multiplicados: nenhum canal passa do alpha.
+pub fn saturate(pixels: &mut [u32], percent: u16) {
+    if percent == 100 {
+        return;
+    }
+
+    // Fator em ponto fixo 8.8
+    let factor = percent as i32 * 256 / 100;
+    for px in pixels {
+        let a = *px & 0xFF000000;
+        let max = (*px >> 24) as i32;
+        let r = ((*px >> 16) & 0xFF) as i32;
+        let g = ((*px >> 8) & 0xFF) as i32;
+        let b = (*px & 0xFF) as i32;
+
+        // Luminância aproximada (Rec. 601)
+        let gray = (r * 77 + g * 150 + b * 29) >> 8;
+        let adjust = |c: i32| (gray + (((c - gray) * factor) >> 8)).clamp(0, max) as u32;
+
+        *px = a | (adjust(r) << 16) | (adjust(g) << 8) | adjust(b);
+    }
+}
@@ -0,0 +1,616 @@
+//! # Canvas
+//!
+//! Superfície de desenho sobre um buffer ARGB.
+//!
+//! ## Estado
+//!
+//! Todo desenho passa por um [`Canvas`], que mantém:
+//!
+//! - **clip**: retângulo fora do qual nenhum pixel é tocado, opcionalmente
+//!   com cantos arredondados ([`Canvas::clip_rounded`])
+//! - **origem**: deslocamento somado a todas as coordenadas
+//!
+//! O estado é empilhado com [`Canvas::save`] e restaurado com
+//! [`Canvas::restore`]:
+//!
+//! ```text
+//! canvas.save();
+//! canvas.translate(panel.x, panel.y);
+//! canvas.clip(Rect::new(0, 0, panel.width, panel.height));
+//! canvas.fill_rect(Rect::new(8, 8, 32, 32), colors::ACCENT); // relativo ao painel
+//! canvas.restore();
+//! ```
+//!
+//! Coordenadas passadas às primitivas são sempre locais (antes da origem).
+//!
+//! ## Pixels
+//!
+//! O buffer guarda ARGB pré-multiplicado (ver [`super::pixel`]). Cores
+//! recebidas como [`Color`] são ARGB direto e convertidas uma vez por
+//! primitiva; buffers de origem (`blit`, surfaces) já vêm pré-multiplicados.
+
+use alloc::vec::Vec;
+use gfx_types::color::Color;
+use gfx_types::geometry::{Point, Rect, Size};
+
+use super::layout::{TextAlign, TextLayout};
+use super::paint::Paint;
+use super::pixel::{self, over, premultiply};
+use super::shadow::ShadowKernel;
+use super::shape::{self, CornerRadii};
+use super::surface::Surface;
+use super::text::{TextRenderer, TextStyle};
+
+// =============================================================================
+// BLEND
+// =============================================================================
+
+/// Alpha blend (source-over) de `src` (ARGB direto) sobre `dst`
+/// (pré-multiplicado).
+#[inline]
+pub fn blend(dst: u32, src: u32) -> u32 {
+    over(dst, premultiply(src))
+}
+
+/// Multiplica o alpha de `color` (ARGB direto) por `opacity` (0-255).
+#[inline]
+pub fn with_opacity(color: u32, opacity: u8) -> u32 {
+    let a = pixel::div255(((color >> 24) & 0xFF) * opacity as u32);
+    (color & 0x00FFFFFF) | (a << 24)
+}
+
+// =============================================================================
+// CANVAS
+// =============================================================================
+
+/// Clip e origem salvos por [`Canvas::save`].
+#[derive(Debug, Clone, Copy)]
+struct CanvasState {
+    /// Clip em coordenadas do buffer.
+    clip: Rect,
+    /// Retângulo arredondado do clip (coordenadas do buffer) e raios.
+    rounded: Option<(Rect, CornerRadii)>,
+    /// Origem em coordenadas do buffer.
+    origin: Point,
+}
+
+/// Superfície de desenho com clip e translação.
+pub struct Canvas<'a> {
+    /// Pixels ARGB.
+    buffer: &'a mut [u32],
+    /// Pixels por linha.
+    stride: usize,
+    /// Estado atual.
+    state: CanvasState,
+    /// Estados salvos.
+    stack: Vec<CanvasState>,
+}
+
+impl<'a> Canvas<'a> {
+    /// Cria canvas sobre um buffer contínuo (`stride == size.width`).
+    pub fn new(buffer: &'a mut [u32], size: Size) -> Self {
+        Self::with_stride(buffer, size, size.width as usize)
+    }
+
+    /// Cria canvas com `stride` pixels por linha.
+    pub fn with_stride(buffer: &'a mut [u32], size: Size, stride: usize) -> Self {
+        // Nunca tocar além do buffer, mesmo com tamanho inconsistente
+        let rows = buffer.len().checked_div(stride).unwrap_or(0);
+        let width = size.width.min(stride as u32);
+        let height = size.height.min(rows as u32);
+
+        Self {
+            buffer,
+            stride,
+            state: CanvasState {
+                clip: Rect::new(0, 0, width, height),
+                rounded: None,
+                origin: Point::new(0, 0),
+            },
+            stack: Vec::new(),
+        }
+    }
+
+    // =========================================================================
+    // ESTADO
+    // =========================================================================
+
+    /// Salva clip e origem.
+    pub fn save(&mut self) {
+        self.stack.push(self.state);
+    }
+
+    /// Restaura o último estado salvo.
+    pub fn restore(&mut self) {
+        if let Some(state) = self.stack.pop() {
+            self.state = state;
+        }
+    }
+
+    /// Desloca a origem em `(dx, dy)`.
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        self.state.origin.x += dx;
+        self.state.origin.y += dy;
+    }
+
+    /// Restringe o clip à intersecção com `rect` (coordenadas locais).
+    pub fn clip(&mut self, rect: Rect) {
+        let rect = self.to_device(rect);
+        self.state.clip = intersect(self.state.clip, rect);
+    }
+
+    /// Restringe o clip a `rect` com cantos `radii` (coordenadas locais).
+    ///
+    /// Pixels com menos de 50% de cobertura ficam de fora, o mesmo limite
+    /// usado por [`Canvas::fill_rounded_rect`].
+    ///
+    /// Só um arredondamento fica ativo: um novo `clip_rounded` substitui os
+    /// cantos do anterior (o retângulo continua sendo intersectado).
+    pub fn clip_rounded(&mut self, rect: Rect, radii: impl Into<CornerRadii>) {
+        self.clip(rect);
+        let radii = radii.into().clamped(rect.width, rect.height);
+        self.state.rounded = if radii.is_zero() {
+            None
+        } else {
+            Some((self.to_device(rect), radii))
+        };
+    }
+
+    /// Clip atual em coordenadas locais.
+    pub fn clip_rect(&self) -> Rect {
+        let clip = self.state.clip;
+        Rect::new(
+            clip.x - self.state.origin.x,
+            clip.y - self.state.origin.y,
+            clip.width,
+            clip.height,
+        )
+    }
+
+    /// Retorna se alguma parte de `rect` (local) está dentro do clip.
+    pub fn is_visible(&self, rect: Rect) -> bool {
+        let area = intersect(self.to_device(rect), self.state.clip);
+        area.width > 0 && area.height > 0
+    }
+
+    // =========================================================================
+    // PIXELS
+    // =========================================================================
+
+    /// Índice do pixel local `(x, y)`, se dentro do clip.
+    #[inline]
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let x = x + self.state.origin.x;
+        let y = y + self.state.origin.y;
+        let clip = self.state.clip;
+        if x < clip.x || y < clip.y || x >= clip.right() || y >= clip.bottom() {
+            return None;
+        }
+        let (left, right) = self.row_limits(y);
+        if x < left || x >= right {
+            return None;
+        }
+        Some(y as usize * self.stride + x as usize)
+    }
+
+    /// Lê o pixel `(x, y)` (pré-multiplicado).
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<u32> {
+        self.index(x, y).map(|idx| self.buffer[idx])
+    }
+
+    /// Escreve o pixel `(x, y)` sem blend.
+    #[inline]
+    pub fn put_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(idx) = self.index(x, y) {
+            self.buffer[idx] = premultiply(color.as_u32());
+        }
+    }
+
+    /// Combina `color` sobre o pixel `(x, y)`.
+    #[inline]
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(idx) = self.index(x, y) {
+            self.buffer[idx] = blend(self.buffer[idx], color.as_u32());
+        }
+    }
+
+    // =========================================================================
+    // RETÂNGULOS
+    // =========================================================================
+
+    /// Preenche retângulo substituindo os pixels.
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let color = premultiply(color.as_u32());
+        self.for_each_span(rect, |span| span.fill(color));
+    }
+
+    /// Preenche retângulo com alpha blend.
+    pub fn blend_rect(&mut self, rect: Rect, color: Color) {
+        match color.alpha() {
+            0 => {}
+            255 => self.fill_rect(rect, color),
+            _ => {
+                let color = premultiply(color.as_u32());
+                self.for_each_span(rect, |span| pixel::blend_span(span, color));
+            }
+        }
+    }
+
+    /// Preenche retângulo com cantos arredondados e anti-aliasing.
+    pub fn fill_rounded_rect(&mut self, rect: Rect, radii: impl Into<CornerRadii>, color: Color) {
+        let radii = radii.into().clamped(rect.width, rect.height);
+        let (w, h) = (rect.width, rect.height);
+        let premultiplied = premultiply(color.as_u32());
+
+        for y in 0..h {
+            // Pixels parcialmente cobertos só existem nos cantos
+            let (left, right) = radii.row(y, h);
+            let py = rect.y + y as i32;
+
+            let middle = w - left - right;
+            if middle > 0 {
+                self.blend_rect(Rect::new(rect.x + left as i32, py, middle, 1), color);
+            }
+            for x in (0..left).chain(w - right..w) {
+                let coverage = shape::coverage(w, h, &radii, x as i32, y as i32);
+                self.blend_coverage(rect.x + x as i32, py, premultiplied, coverage);
+            }
+        }
+    }
+
+    /// Preenche retângulo arredondado com `paint` (gradiente, imagem...).
+    ///
+    /// A tinta é avaliada relativa a `rect`: o gradiente ocupa o retângulo
+    /// inteiro, e o padrão começa no seu canto superior esquerdo.
+    pub fn fill_rounded_paint(&mut self, rect: Rect, radii: impl Into<CornerRadii>, paint: &Paint) {
+        let shader = paint.shader(rect);
+        if let Paint::Solid(color) = paint {
+            self.fill_rounded_rect(rect, radii, *color);
+            return;
+        }
+
+        let radii = radii.into().clamped(rect.width, rect.height);
+        let (w, h) = (rect.width, rect.height);
+        let device = self.to_device(rect);
+
+        for y in 0..h {
+            let (left, right) = radii.row(y, h);
+            let py = rect.y + y as i32;
+
+            let middle = w - left - right;
+            if middle > 0 {
+                let span_rect = Rect::new(rect.x + left as i32, py, middle, 1);
+                self.for_each_span_at(span_rect, |span, x, _| {
+                    let x = x - device.x;
+                    for (i, px) in span.iter_mut().enumerate() {
+                        *px = over(*px, shader.color_at(x + i as i32, y as i32));
+                    }
+                });
+            }
+            for x in (0..left).chain(w - right..w) {
+                let coverage = shape::coverage(w, h, &radii, x as i32, y as i32);
+                let color = shader.color_at(x as i32, y as i32);
+                self.blend_coverage(rect.x + x as i32, py, color, coverage);
+            }
+        }
+    }
+
+    /// Contorna retângulo arredondado com espessura `thickness` e anti-aliasing.
+    ///
+    /// O contorno fica inteiro dentro de `rect` e acompanha exatamente a
+    /// borda de [`Canvas::fill_rounded_rect`] com os mesmos raios.
+    pub fn stroke_rounded_rect(
+        &mut self,
+        rect: Rect,
+        radii: impl Into<CornerRadii>,
+        thickness: u32,
+        color: Color,
+    ) {
+        let (w, h) = (rect.width, rect.height);
+        let t = thickness.min(w / 2).min(h / 2);
+        if t == 0 {
+            return;
+        }
+
+        let color = premultiply(color.as_u32());
+        let outer = radii.into().clamped(w, h);
+        let (inner_w, inner_h) = (w - 2 * t, h - 2 * t);
+        let inner = outer.inset(t).clamped(inner_w, inner_h);
+
+        // Cobertura do anel = externa - interna
+        let ring = |x: i32, y: i32| {
+            let outside = shape::coverage(w, h, &outer, x, y);
+            let inside = shape::coverage(inner_w, inner_h, &inner, x - t as i32, y - t as i32);
+            (outside - inside).max(0.0)
+        };
+
+        for y in 0..h {
+            let py = rect.y + y as i32;
+
+            // Faixas de cima e de baixo: a linha inteira é contorno
+            if y < t || y >= h - t {
+                for x in 0..w {
+                    self.blend_coverage(rect.x + x as i32, py, color, ring(x as i32, y as i32));
+                }
+                continue;
+            }
+
+            // Demais linhas: só as laterais (e o arco interno, se houver)
+            let (left, right) = outer.row(y, h);
+            let (left, right) = (left.max(t), right.max(t));
+            for x in (0..left).chain(w - right..w) {
+                self.blend_coverage(rect.x + x as i32, py, color, ring(x as i32, y as i32));
+            }
+        }
+    }
+
+    /// Combina `color` (pré-multiplicado) atenuado pela cobertura
+    /// `coverage` (0.0 - 1.0).
+    #[inline]
+    fn blend_coverage(&mut self, x: i32, y: i32, color: u32, coverage: f32) {
+        if coverage <= 0.0 {
+            return;
+        }
+        let opacity = (coverage * 255.0 + 0.5) as u32;
+        if let Some(idx) = self.index(x, y) {
+            self.buffer[idx] = over(self.buffer[idx], pixel::scale(color, opacity));
+        }
+    }
+
+    // =========================================================================
+    // IMAGENS
+    // =========================================================================
+
+    /// Copia `src` (`src_size`, contínuo, pré-multiplicado) para `dst`, sem
+    /// blend.
+    pub fn blit(&mut self, src: &[u32], src_size: Size, dst: Point) {
+        let src_width = src_size.width as usize;
+        let rect = Rect::new(dst.x, dst.y, src_size.width, src_size.height);
+        let device = self.to_device(rect);
+
+        self.for_each_span_at(rect, |span, x, y| {
+            let sx = (x - device.x) as usize;
+            let sy = (y - device.y) as usize;
+            let start = sy * src_width + sx;
+            if let Some(row) = src.get(start..start + span.len()) {
+                span.copy_from_slice(row);
+            }
+        });
+    }
+
+    /// Combina `src` (`src_size`, contínuo, pré-multiplicado) sobre `dst`
+    /// usando o alpha de cada pixel.
+    pub fn blit_blend(&mut self, src: &[u32], src_size: Size, dst: Point) {
+        let src_width = src_size.width as usize;
+        let rect = Rect::new(dst.x, dst.y, src_size.width, src_size.height);
+        let device = self.to_device(rect);
+
+        self.for_each_span_at(rect, |span, x, y| {
+            let sx = (x - device.x) as usize;
+            let sy = (y - device.y) as usize;
+            let start = sy * src_width + sx;
+            if let Some(row) = src.get(start..start + span.len()) {
+                pixel::blend_span_from(span, row);
+            }
+        });
+    }
+
+    /// Compõe `surface` em `dst` com opacidade `opacity` (0-255).
+    pub fn draw_surface(&mut self, surface: &Surface, dst: Point, opacity: u8) {
+        match opacity {
+            0 => {}
+            255 => self.blit_blend(surface.pixels(), surface.size(), dst),
+            _ => {
+                let src = surface.pixels();
+                let src_width = surface.size().width as usize;
+                let rect = Rect::new(dst.x, dst.y, surface.size().width, surface.size().height);
+                let device = self.to_device(rect);
+
+                self.for_each_span_at(rect, |span, x, y| {
+                    let start = (y - device.y) as usize * src_width + (x - device.x) as usize;
+                    if let Some(row) = src.get(start..start + span.len()) {
+                        pixel::blend_span_from_scaled(span, row, opacity);
+                    }
+                });
+            }
+        }
+    }
+
+    // =========================================================================
+    // SOMBRAS
+    // =========================================================================
+
+    /// Desenha a sombra da silhueta `rect` usando `kernel`, na cor `color`.
+    ///
+    /// A sombra ocupa `rect` expandido por `kernel.blur()`. Pixels
+    /// totalmente cobertos por `exclude` (quem projeta a sombra) são pulados,
+    /// para a sombra não escurecer vidro translúcido por baixo.
+    pub fn draw_shadow(
+        &mut self,
+        rect: Rect,
+        kernel: &ShadowKernel,
+        color: Color,
+        exclude: Option<(Rect, CornerRadii)>,
+    ) {
+        if color.alpha() == 0 {
+            return;
+        }
+
+        let blur = kernel.blur();
+        let area = Rect::new(
+            rect.x - blur as i32,
+            rect.y - blur as i32,
+            rect.width + 2 * blur,
+            rect.height + 2 * blur,
+        );
+        let device = self.to_device(area);
+        let exclude =
+            exclude.map(|(r, radii)| (self.to_device(r), radii.clamped(r.width, r.height)));
+        let color = premultiply(color.as_u32());
+
+        self.for_each_span_at(area, |span, x, y| {
+            // Trecho da linha totalmente coberto por quem projeta a sombra
+            let (skip_from, skip_to) = match exclude {
+                Some((r, radii)) if y >= r.y && y < r.bottom() => {
+                    let (left, right) = radii.row((y - r.y) as u32, r.height);
+                    (r.x + left as i32, r.right() - right as i32)
+                }
+                _ => (0, 0),
+            };
+
+            let ly = (y - device.y) as u32;
+            for (i, px) in span.iter_mut().enumerate() {
+                let px_x = x + i as i32;
+                if px_x >= skip_from && px_x < skip_to {
+                    continue;
+                }
+                let alpha = kernel.alpha((px_x - device.x) as u32, ly, rect.width, rect.height);
+                if alpha > 0 {
+                    *px = over(*px, pixel::scale(color, alpha as u32));
+                }
+            }
+        });
+    }
+
+    // =========================================================================
+    // FILTROS
+    // =========================================================================
+
+    /// Aplica `filter` aos pixels já desenhados sob `rect`.
+    ///
+    /// `filter` recebe uma cópia contínua da parte visível de `rect`
+    /// (`pixels, largura, altura`); o resultado só é escrito de volta
+    /// dentro do clip, inclusive dos cantos arredondados.
+    pub fn filter_rect(&mut self, rect: Rect, filter: impl FnOnce(&mut [u32], usize, usize)) {
+        let area = intersect(self.to_device(rect), self.state.clip);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let width = area.width as usize;
+        let height = area.height as usize;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in area.y..area.bottom() {
+            let row = y as usize * self.stride + area.x as usize;
+            pixels.extend_from_slice(&self.buffer[row..row + width]);
+        }
+
+        filter(&mut pixels, width, height);
+
+        self.for_each_span_at(rect, |span, x, y| {
+            let start = (y - area.y) as usize * width + (x - area.x) as usize;
+            span.copy_from_slice(&pixels[start..start + span.len()]);
+        });
+    }
+
+    // =========================================================================
+    // TEXTO
+    // =========================================================================
+
+    /// Desenha `text` em `origin`. Retorna o X logo após o último caractere.
+    pub fn draw_text(&mut self, text: &str, origin: Point, style: &TextStyle) -> i32 {
+        TextRenderer::draw(self, text, origin, style)
+    }
+
+    /// Desenha texto centralizado horizontalmente em `cx`.
+    pub fn draw_text_centered(&mut self, text: &str, cx: i32, y: i32, style: &TextStyle) -> i32 {
+        TextRenderer::draw_centered(self, text, cx, y, style)
+    }
+
+    /// Desenha um [`TextLayout`] alinhado dentro de `bounds`.
+    pub fn draw_layout(
+        &mut self,
+        layout: &TextLayout,
+        bounds: Rect,
+        align: TextAlign,
+        style: &TextStyle,
+    ) {
+        TextRenderer::draw_layout(self, layout, bounds, align, style);
+    }
+
+    // =========================================================================
+    // HELPERS
+    // =========================================================================
+
+    /// Converte retângulo local para coordenadas do buffer.
+    #[inline]
+    fn to_device(&self, rect: Rect) -> Rect {
+        Rect::new(
+            rect.x + self.state.origin.x,
+            rect.y + self.state.origin.y,
+            rect.width,
+            rect.height,
+        )
+    }
+
+    /// Chama `f` para cada linha visível de `rect`.
+    #[inline]
+    fn for_each_span(&mut self, rect: Rect, mut f: impl FnMut(&mut [u32])) {
+        self.for_each_span_at(rect, |span, _, _| f(span));
+    }
+
+    /// Chama `f(span, x, y)` para cada linha visível de `rect`, com a
+    /// posição do início do trecho em coordenadas do buffer.
+    fn for_each_span_at(&mut self, rect: Rect, mut f: impl FnMut(&mut [u32], i32, i32)) {
+        let area = intersect(self.to_device(rect), self.state.clip);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        for y in area.y..area.bottom() {
+            let (left, right) = self.row_limits(y);
+            let x0 = area.x.max(left);
+            let x1 = area.right().min(right);
+            if x1 <= x0 {
+                continue;
+            }
+            let row = y as usize * self.stride;
+            f(
+                &mut self.buffer[row + x0 as usize..row + x1 as usize],
+                x0,
+                y,
+            );
+        }
+    }
+
+    /// Limites X (buffer) da linha `y` impostos pelos cantos do clip.
+    #[inline]
+    fn row_limits(&self, y: i32) -> (i32, i32) {
+        let Some((rect, radii)) = self.state.rounded else {
+            return (i32::MIN, i32::MAX);
+        };
+        if y < rect.y || y >= rect.bottom() {
+            return (rect.x, rect.right());
+        }
+
+        let (left, right) = radii.row_insets((y - rect.y) as u32, rect.height);
+        (rect.x + left as i32, rect.right() - right as i32)
+    }
+}
+
+// =============================================================================
+// GEOMETRIA
+// =============================================================================
+
+/// Intersecção de dois retângulos (vazia se disjuntos).
+pub fn intersect(a: Rect, b: Rect) -> Rect {
+    let x0 = a.x.max(b.x);
+    let y0 = a.y.max(b.y);
+    let x1 = a.right().min(b.right());
+    let y1 = a.bottom().min(b.bottom());
+
+    if x1 <= x0 || y1 <= y0 {
+        Rect::new(x0, y0, 0, 0)
+    } else {
+        Rect::new(x0, y0, (x1 - x0) as u32, (y1 - y0) as u32)
+    }
+}
+
+/// Menor retângulo que contém `a` e `b`.
+pub fn union(a: Rect, b: Rect) -> Rect {
+    let x0 = a.x.min(b.x);
+    let y0 = a.y.min(b.y);
+    let x1 = a.right().max(b.right());
+    let y1 = a.bottom().max(b.bottom());
+    Rect::new(x0, y0, (x1 - x0) as u32, (y1 - y0) as u32)
+}
@@ -0,0 +1,130 @@
+//! # Damage
+//!
+//! Regiões da tela que precisam ser redesenhadas.
+//!
+//! Cada componente informa o retângulo que mudou (dígitos do uptime, um
+//! painel animando, uma entrada da taskbar) e o desktop recompõe apenas
+//! essas áreas, em vez da tela inteira.
+//!
+//! ```text
+//! damage.add(taskbar.status_bounds());
+//! for rect in damage.rects() {
+//!     canvas.save();
+//!     canvas.clip(*rect);
+//!     // ... desenhar camadas ...
+//!     canvas.restore();
+//! }
+//! damage.clear();
+//! ```
+
+use alloc::vec::Vec;
+use gfx_types::geometry::Rect;
+
+use super::canvas::{intersect, union};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Máximo de retângulos antes de colapsar tudo na caixa envolvente.
+const MAX_DAMAGE_RECTS: usize = 16;
+
+// =============================================================================
+// DAMAGE REGION
+// =============================================================================
+
+/// Conjunto de retângulos danificados dentro de uma área.
+pub struct DamageRegion {
+    /// Área total (tela).
+    bounds: Rect,
+    /// Retângulos disjuntos e não adjacentes.
+    rects: Vec<Rect>,
+}
+
+impl DamageRegion {
+    /// Cria região vazia limitada a `bounds`.
+    pub fn new(bounds: Rect) -> Self {
+        Self {
+            bounds,
+            rects: Vec::new(),
+        }
+    }
+
+    /// Marca `rect` como danificado.
+    ///
+    /// Retângulos que se tocam são fundidos; com muitos retângulos a região
+    /// vira a caixa envolvente de todos.
+    pub fn add(&mut self, rect: Rect) {
+        let mut rect = intersect(rect, self.bounds);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+
+        // Fundir com todos que tocam (a união pode tocar outros)
+        let mut i = 0;
+        while i < self.rects.len() {
+            if touches(self.rects[i], rect) {
+                rect = union(self.rects.swap_remove(i), rect);
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        self.rects.push(rect);
+
+        if self.rects.len() > MAX_DAMAGE_RECTS {
+            let all = self.bounding_box();
+            self.rects.clear();
+            self.rects.push(all);
+        }
+    }
+
+    /// Danifica `rect` inteiro se alguma parte dele já estiver danificada.
+    ///
+    /// Usado por efeitos que leem a vizinhança (blur): redesenhar só um
+    /// pedaço deles amostraria pixels antigos.
+    pub fn expand_to(&mut self, rect: Rect) {
+        let hit = self.rects.iter().any(|r| {
+            let area = intersect(*r, rect);
+            area.width > 0 && area.height > 0
+        });
+        if hit {
+            self.add(rect);
+        }
+    }
+
+    /// Marca a área inteira como danificada.
+    pub fn add_all(&mut self) {
+        self.rects.clear();
+        self.rects.push(self.bounds);
+    }
+
+    /// Retângulos danificados.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Retorna se não há nada a redesenhar.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Caixa envolvente de todos os retângulos.
+    pub fn bounding_box(&self) -> Rect {
+        self.rects
+            .iter()
+            .copied()
+            .reduce(union)
+            .unwrap_or(Rect::new(self.bounds.x, self.bounds.y, 0, 0))
+    }
+
+    /// Esvazia a região.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+}
+
+/// Retorna se `a` e `b` se sobrepõem ou são adjacentes.
+fn touches(a: Rect, b: Rect) -> bool {
+    a.x <= b.right() && b.x <= a.right() && a.y <= b.bottom() && b.y <= a.bottom()
+}
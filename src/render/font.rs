@@ -1,184 +0,0 @@
-//! # Fonte Bitmap 8x8
-//!
-//! Fonte rasterizada para renderização de texto no modo gráfico.
-//!
-//! ## Formato
-//!
-//! Cada caractere é representado por 8 bytes, onde cada byte
-//! corresponde a uma linha horizontal de 8 pixels.
-//! Bit 7 = pixel mais à esquerda, Bit 0 = pixel mais à direita.
-//!
-//! ## Cobertura
-//!
-//! Caracteres ASCII 32 (espaço) até 126 (~), totalizando 95 glifos.
-
-#![allow(dead_code)]
-
-// ============================================================================
-// CONSTANTES
-// ============================================================================
-
-/// Largura de cada caractere em pixels
-pub const CHAR_WIDTH: u32 = 8;
-
-/// Altura de cada caractere em pixels
-pub const CHAR_HEIGHT: u32 = 8;
-
-// ============================================================================
-// FONTE BITMAP
-// ============================================================================
-
-/// Tabela de glifos bitmap 8x8 para ASCII 32-126.
-pub const FONT_8X8: [[u8; 8]; 95] = [
-    // Space (32)
-    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
-    // ! (33)
-    [0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x18, 0x00],
-    // " (34)
-    [0x6C, 0x6C, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00],
-    // # (35)
-    [0x6C, 0x6C, 0xFE, 0x6C, 0xFE, 0x6C, 0x6C, 0x00],
-    // $ (36)
-    [0x18, 0x7E, 0xC0, 0x7C, 0x06, 0xFC, 0x18, 0x00],
-    // % (37)
-    [0x00, 0xC6, 0xCC, 0x18, 0x30, 0x66, 0xC6, 0x00],
-    // & (38)
-    [0x38, 0x6C, 0x38, 0x76, 0xDC, 0xCC, 0x76, 0x00],
-    // ' (39)
-    [0x18, 0x18, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00],
-    // ( (40)
-    [0x0C, 0x18, 0x30, 0x30, 0x30, 0x18, 0x0C, 0x00],
-    // ) (41)
-    [0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x18, 0x30, 0x00],
-    // * (42)
-    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00],
-    // + (43)
-    [0x00, 0x18, 0x18, 0x7E, 0x18, 0x18, 0x00, 0x00],
-    // , (44)
-    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x30],
-    // - (45)
-    [0x00, 0x00, 0x00, 0x7E, 0x00, 0x00, 0x00, 0x00],
-    // . (46)
-    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x00],
-    // / (47)
-    [0x06, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0x80, 0x00],
-    // 0-9 (48-57)
-    [0x7C, 0xC6, 0xCE, 0xD6, 0xE6, 0xC6, 0x7C, 0x00],
-    [0x18, 0x38, 0x18, 0x18, 0x18, 0x18, 0x7E, 0x00],
-    [0x7C, 0xC6, 0x06, 0x1C, 0x30, 0x66, 0xFE, 0x00],
-    [0x7C, 0xC6, 0x06, 0x3C, 0x06, 0xC6, 0x7C, 0x00],
-    [0x1C, 0x3C, 0x6C, 0xCC, 0xFE, 0x0C, 0x1E, 0x00],
-    [0xFE, 0xC0, 0xFC, 0x06, 0x06, 0xC6, 0x7C, 0x00],
-    [0x38, 0x60, 0xC0, 0xFC, 0xC6, 0xC6, 0x7C, 0x00],
-    [0xFE, 0xC6, 0x0C, 0x18, 0x30, 0x30, 0x30, 0x00],
-    [0x7C, 0xC6, 0xC6, 0x7C, 0xC6, 0xC6, 0x7C, 0x00],
-    [0x7C, 0xC6, 0xC6, 0x7E, 0x06, 0x0C, 0x78, 0x00],
-    // : ; < = > ? @ (58-64)
-    [0x00, 0x18, 0x18, 0x00, 0x00, 0x18, 0x18, 0x00],
-    [0x00, 0x18, 0x18, 0x00, 0x00, 0x18, 0x18, 0x30],
-    [0x0C, 0x18, 0x30, 0x60, 0x30, 0x18, 0x0C, 0x00],
-    [0x00, 0x00, 0x7E, 0x00, 0x7E, 0x00, 0x00, 0x00],
-    [0x30, 0x18, 0x0C, 0x06, 0x0C, 0x18, 0x30, 0x00],
-    [0x7C, 0xC6, 0x0C, 0x18, 0x18, 0x00, 0x18, 0x00],
-    [0x7C, 0xC6, 0xDE, 0xDE, 0xDC, 0xC0, 0x7C, 0x00],
-    // A-Z (65-90)
-    [0x38, 0x6C, 0xC6, 0xC6, 0xFE, 0xC6, 0xC6, 0x00],
-    [0xFC, 0x66, 0x66, 0x7C, 0x66, 0x66, 0xFC, 0x00],
-    [0x3C, 0x66, 0xC0, 0xC0, 0xC0, 0x66, 0x3C, 0x00],
-    [0xF8, 0x6C, 0x66, 0x66, 0x66, 0x6C, 0xF8, 0x00],
-    [0xFE, 0x62, 0x68, 0x78, 0x68, 0x62, 0xFE, 0x00],
-    [0xFE, 0x62, 0x68, 0x78, 0x68, 0x60, 0xF0, 0x00],
-    [0x3C, 0x66, 0xC0, 0xC0, 0xCE, 0x66, 0x3A, 0x00],
-    [0xC6, 0xC6, 0xC6, 0xFE, 0xC6, 0xC6, 0xC6, 0x00],
-    [0x3C, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
-    [0x1E, 0x0C, 0x0C, 0x0C, 0xCC, 0xCC, 0x78, 0x00],
-    [0xE6, 0x66, 0x6C, 0x78, 0x6C, 0x66, 0xE6, 0x00],
-    [0xF0, 0x60, 0x60, 0x60, 0x62, 0x66, 0xFE, 0x00],
-    [0xC6, 0xEE, 0xFE, 0xFE, 0xD6, 0xC6, 0xC6, 0x00],
-    [0xC6, 0xE6, 0xF6, 0xDE, 0xCE, 0xC6, 0xC6, 0x00],
-    [0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
-    [0xFC, 0x66, 0x66, 0x7C, 0x60, 0x60, 0xF0, 0x00],
-    [0x7C, 0xC6, 0xC6, 0xC6, 0xD6, 0x7C, 0x0E, 0x00],
-    [0xFC, 0x66, 0x66, 0x7C, 0x6C, 0x66, 0xE6, 0x00],
-    [0x7C, 0xC6, 0x60, 0x38, 0x0C, 0xC6, 0x7C, 0x00],
-    [0x7E, 0x5A, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
-    [0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
-    [0xC6, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x10, 0x00],
-    [0xC6, 0xC6, 0xD6, 0xFE, 0xFE, 0xEE, 0xC6, 0x00],
-    [0xC6, 0x6C, 0x38, 0x38, 0x38, 0x6C, 0xC6, 0x00],
-    [0x66, 0x66, 0x66, 0x3C, 0x18, 0x18, 0x3C, 0x00],
-    [0xFE, 0xC6, 0x8C, 0x18, 0x32, 0x66, 0xFE, 0x00],
-    // [ \ ] ^ _ ` (91-96)
-    [0x3C, 0x30, 0x30, 0x30, 0x30, 0x30, 0x3C, 0x00],
-    [0xC0, 0x60, 0x30, 0x18, 0x0C, 0x06, 0x02, 0x00],
-    [0x3C, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x3C, 0x00],
-    [0x10, 0x38, 0x6C, 0xC6, 0x00, 0x00, 0x00, 0x00],
-    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF],
-    [0x30, 0x18, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00],
-    // a-z (97-122)
-    [0x00, 0x00, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
-    [0xE0, 0x60, 0x7C, 0x66, 0x66, 0x66, 0xDC, 0x00],
-    [0x00, 0x00, 0x7C, 0xC6, 0xC0, 0xC6, 0x7C, 0x00],
-    [0x1C, 0x0C, 0x7C, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
-    [0x00, 0x00, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
-    [0x3C, 0x66, 0x60, 0xF8, 0x60, 0x60, 0xF0, 0x00],
-    [0x00, 0x00, 0x76, 0xCC, 0xCC, 0x7C, 0x0C, 0x78],
-    [0xE0, 0x60, 0x6C, 0x76, 0x66, 0x66, 0xE6, 0x00],
-    [0x18, 0x00, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
-    [0x06, 0x00, 0x0E, 0x06, 0x06, 0x66, 0x66, 0x3C],
-    [0xE0, 0x60, 0x66, 0x6C, 0x78, 0x6C, 0xE6, 0x00],
-    [0x38, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
-    [0x00, 0x00, 0xEC, 0xFE, 0xD6, 0xC6, 0xC6, 0x00],
-    [0x00, 0x00, 0xDC, 0x66, 0x66, 0x66, 0x66, 0x00],
-    [0x00, 0x00, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
-    [0x00, 0x00, 0xDC, 0x66, 0x66, 0x7C, 0x60, 0xF0],
-    [0x00, 0x00, 0x76, 0xCC, 0xCC, 0x7C, 0x0C, 0x1E],
-    [0x00, 0x00, 0xDC, 0x76, 0x60, 0x60, 0xF0, 0x00],
-    [0x00, 0x00, 0x7C, 0xC0, 0x7C, 0x06, 0xFC, 0x00],
-    [0x30, 0x30, 0xFC, 0x30, 0x30, 0x36, 0x1C, 0x00],
-    [0x00, 0x00, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
-    [0x00, 0x00, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x00],
-    [0x00, 0x00, 0xC6, 0xC6, 0xD6, 0xFE, 0x6C, 0x00],
-    [0x00, 0x00, 0xC6, 0x6C, 0x38, 0x6C, 0xC6, 0x00],
-    [0x00, 0x00, 0xC6, 0xC6, 0xCE, 0x76, 0x06, 0x7C],
-    [0x00, 0x00, 0xFC, 0x98, 0x30, 0x64, 0xFC, 0x00],
-    // { | } ~ (123-126)
-    [0x0E, 0x18, 0x18, 0x70, 0x18, 0x18, 0x0E, 0x00],
-    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00],
-    [0x70, 0x18, 0x18, 0x0E, 0x18, 0x18, 0x70, 0x00],
-    [0x76, 0xDC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
-];
-
-// ============================================================================
-// FUNÇÕES
-// ============================================================================
-
-/// Obtém o bitmap de um caractere ASCII.
-pub fn get_char_bitmap(c: char) -> Option<&'static [u8; 8]> {
-    let code = c as u8;
-    if code >= 32 && code <= 126 {
-        Some(&FONT_8X8[(code - 32) as usize])
-    } else {
-        None
-    }
-}
-
-/// Calcula a largura em pixels de uma string.
-pub fn measure_text_width(text: &str) -> u32 {
-    text.chars().count() as u32 * CHAR_WIDTH
-}
-
-/// Wrapper para a fonte.
-pub struct Font;
-
-impl Font {
-    /// Obtém bitmap de caractere.
-    pub fn get_char(c: char) -> Option<&'static [u8; 8]> {
-        get_char_bitmap(c)
-    }
-
-    /// Mede texto.
-    pub fn measure(text: &str) -> u32 {
-        measure_text_width(text)
-    }
-}
@@ -0,0 +1,386 @@
+//! # BDF
+//!
+//! Parser de fontes Glyph Bitmap Distribution Format (X11).
+//!
+//! ## Estrutura Relevante
+//!
+//! ```text
+//! STARTFONT 2.1
+//! FONTBOUNDINGBOX 8 16 0 -4
+//! STARTPROPERTIES 2
+//! FONT_ASCENT 12
+//! FONT_DESCENT 4
+//! ENDPROPERTIES
+//! STARTCHAR A
+//! ENCODING 65
+//! DWIDTH 8 0
+//! BBX 8 16 0 -4
+//! BITMAP
+//! 00
+//! 18
+//! ...
+//! ENDCHAR
+//! ENDFONT
+//! ```
+//!
+//! Cada glifo é reposicionado dentro da célula da fonte usando seu `BBX`
+//! relativo à linha de base. Glifos com `ENCODING -1` são ignorados.
+
+use alloc::vec::Vec;
+
+use super::{Font, FontBuilder, FontError};
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Limite de sanidade para a célula.
+const MAX_GLYPH_DIMENSION: i32 = 64;
+
+// ============================================================================
+// PARSER
+// ============================================================================
+
+/// Retorna se os bytes começam com `STARTFONT`.
+pub fn is_bdf(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"STARTFONT")
+}
+
+/// Caixa de um glifo (largura, altura, offset x, offset y).
+#[derive(Debug, Clone, Copy, Default)]
+struct BoundingBox {
+    width: i32,
+    height: i32,
+    x_offset: i32,
+    y_offset: i32,
+}
+
+/// Glifo em construção.
+struct PendingGlyph {
+    encoding: i32,
+    advance: Option<i32>,
+    bbx: Option<BoundingBox>,
+    rows: Vec<u64>,
+}
+
+/// Interpreta uma fonte BDF.
+pub fn parse(bytes: &[u8]) -> Result<Font, FontError> {
+    if !is_bdf(bytes) {
+        return Err(FontError::BadMagic);
+    }
+
+    let text = core::str::from_utf8(bytes).map_err(|e| {
+        let line = bytes[..e.valid_up_to()]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        FontError::Syntax(line as u32 + 1)
+    })?;
+
+    let mut font_bbx: Option<BoundingBox> = None;
+    let mut ascent: Option<i32> = None;
+    let mut descent: Option<i32> = None;
+    let mut builder: Option<FontBuilder> = None;
+    let mut glyph: Option<PendingGlyph> = None;
+    let mut in_bitmap = false;
+
+    for (line_no, raw_line) in text.lines().enumerate() {
+        let line_no = line_no as u32 + 1;
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // Linhas hexadecimais do bitmap
+        if in_bitmap {
+            if line == "ENDCHAR" {
+                in_bitmap = false;
+            } else {
+                let row = u64::from_str_radix(line, 16).map_err(|_| FontError::Syntax(line_no))?;
+                if let Some(g) = glyph.as_mut() {
+                    g.rows.push(row);
+                }
+                continue;
+            }
+        }
+
+        let mut fields = line.split_ascii_whitespace();
+        let keyword = fields.next().unwrap_or("");
+
+        match keyword {
+            "FONTBOUNDINGBOX" => {
+                font_bbx = Some(parse_bbx(&mut fields, line_no)?);
+            }
+            "FONT_ASCENT" => ascent = Some(parse_int(fields.next(), line_no)?),
+            "FONT_DESCENT" => descent = Some(parse_int(fields.next(), line_no)?),
+            "STARTCHAR" => {
+                glyph = Some(PendingGlyph {
+                    encoding: -1,
+                    advance: None,
+                    bbx: None,
+                    rows: Vec::new(),
+                });
+            }
+            "ENCODING" => {
+                if let Some(g) = glyph.as_mut() {
+                    g.encoding = parse_int(fields.next(), line_no)?;
+                }
+            }
+            "DWIDTH" => {
+                if let Some(g) = glyph.as_mut() {
+                    g.advance = Some(parse_int(fields.next(), line_no)?);
+                }
+            }
+            "BBX" => {
+                if let Some(g) = glyph.as_mut() {
+                    g.bbx = Some(parse_bbx(&mut fields, line_no)?);
+                }
+            }
+            "BITMAP" => {
+                if glyph.is_none() {
+                    return Err(FontError::Syntax(line_no));
+                }
+                in_bitmap = true;
+            }
+            "ENDCHAR" => {
+                let g = glyph.take().ok_or(FontError::Syntax(line_no))?;
+
+                // A célula só é conhecida após FONTBOUNDINGBOX/propriedades
+                if builder.is_none() {
+                    builder = Some(new_builder(font_bbx, ascent, descent, line_no)?);
+                }
+                if let (Some(b), Some(fbb)) = (builder.as_mut(), font_bbx) {
+                    place_glyph(b, &g, &fbb, ascent.unwrap_or(fbb.height + fbb.y_offset));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let builder = builder.ok_or(FontError::Empty)?;
+    if builder.glyph_count() == 0 {
+        return Err(FontError::Empty);
+    }
+
+    Ok(builder.finish(&[]))
+}
+
+/// Cria o builder com a célula derivada do cabeçalho.
+fn new_builder(
+    font_bbx: Option<BoundingBox>,
+    ascent: Option<i32>,
+    descent: Option<i32>,
+    line_no: u32,
+) -> Result<FontBuilder, FontError> {
+    let fbb = font_bbx.ok_or(FontError::Syntax(line_no))?;
+
+    let ascent = ascent.unwrap_or(fbb.height + fbb.y_offset);
+    let descent = descent.unwrap_or(-fbb.y_offset);
+    let height = (ascent + descent).max(fbb.height);
+
+    if fbb.width <= 0
+        || height <= 0
+        || ascent < 0
+        || fbb.width > MAX_GLYPH_DIMENSION
+        || height > MAX_GLYPH_DIMENSION
+    {
+        return Err(FontError::InvalidHeader);
+    }
+
+    Ok(FontBuilder::new(fbb.width as u32, height as u32))
+}
+
+/// Copia o bitmap do glifo para a posição correta dentro da célula.
+fn place_glyph(builder: &mut FontBuilder, glyph: &PendingGlyph, fbb: &BoundingBox, ascent: i32) {
+    let Some(c) = u32::try_from(glyph.encoding).ok().and_then(char::from_u32) else {
+        return;
+    };
+
+    let bbx = glyph.bbx.unwrap_or(*fbb);
+    let cell_width = builder.cell_width() as i32;
+    let cell_height = builder.cell_height() as i32;
+    let bytes_per_row = builder.bytes_per_row();
+
+    // Cada linha hex tem ceil(bbx.width / 8) bytes, alinhada à esquerda
+    let row_bits = (bbx.width.max(0) as u32).div_ceil(8) * 8;
+    let mut bitmap = alloc::vec![0u8; builder.glyph_size()];
+
+    for (r, &row) in glyph
+        .rows
+        .iter()
+        .take(bbx.height.max(0) as usize)
+        .enumerate()
+    {
+        let cell_y = ascent - bbx.y_offset - bbx.height + r as i32;
+        if cell_y < 0 || cell_y >= cell_height {
+            continue;
+        }
+
+        for col in 0..bbx.width {
+            let bit = row_bits as i32 - 1 - col;
+            if !(0..64).contains(&bit) || row & (1u64 << bit) == 0 {
+                continue;
+            }
+
+            let cell_x = bbx.x_offset - fbb.x_offset + col;
+            if cell_x < 0 || cell_x >= cell_width {
+                continue;
+            }
+
+            let idx = cell_y as usize * bytes_per_row + (cell_x / 8) as usize;
+            bitmap[idx] |= 0x80 >> (cell_x % 8);
+        }
+    }
+
+    let advance = glyph.advance.unwrap_or(cell_width).max(0) as u32;
+    builder.push(c, &bitmap, advance);
+}
+
+fn parse_bbx<'a>(
+    fields: &mut impl Iterator<Item = &'a str>,
+    line_no: u32,
+) -> Result<BoundingBox, FontError> {
+    Ok(BoundingBox {
+        width: parse_int(fields.next(), line_no)?,
+        height: parse_int(fields.next(), line_no)?,
+        x_offset: parse_int(fields.next(), line_no)?,
+        y_offset: parse_int(fields.next(), line_no)?,
+    })
+}
+
+fn parse_int(field: Option<&str>, line_no: u32) -> Result<i32, FontError> {
+    field
+        .and_then(|f| f.parse::<i32>().ok())
+        .ok_or(FontError::Syntax(line_no))
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::font::TextFont;
+
+    const SAMPLE: &str = "STARTFONT 2.1
+FONT -test-fixed-medium-r-normal--8-80-75-75-c-60-iso10646-1
+FONTBOUNDINGBOX 6 8 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 6
+FONT_DESCENT 2
+ENDPROPERTIES
+CHARS 4
+STARTCHAR A
+ENCODING 65
+DWIDTH 6 0
+BBX 4 3 1 0
+BITMAP
+F0
+90
+F0
+ENDCHAR
+STARTCHAR g
+ENCODING 103
+DWIDTH 4 0
+BBX 3 2 0 -2
+BITMAP
+E0
+20
+ENDCHAR
+STARTCHAR unmapped
+ENCODING -1
+BBX 6 8 0 -2
+BITMAP
+FC
+ENDCHAR
+STARTCHAR bar
+ENCODING 124
+BBX 1 8 0 -2
+BITMAP
+80
+80
+80
+80
+80
+80
+80
+80
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn parses_cell_from_header() {
+        let font = parse(SAMPLE.as_bytes()).unwrap();
+
+        assert_eq!((font.width(), font.height()), (6, 8));
+        assert!(font.has_glyph('A'));
+        assert!(font.has_glyph('g'));
+        assert!(font.has_glyph('|'));
+    }
+
+    #[test]
+    fn places_glyphs_relative_to_baseline() {
+        let font = parse(SAMPLE.as_bytes()).unwrap();
+
+        // 'A': caixa 4x3 em x=1, apoiada na linha de base (linhas 3..=5)
+        let a = font.glyph('A');
+        assert!(!a.pixel(1, 2));
+        assert!(a.pixel(1, 3) && a.pixel(4, 3));
+        assert!(a.pixel(1, 4) && !a.pixel(2, 4) && a.pixel(4, 4));
+        assert!(a.pixel(1, 5));
+        assert!(!a.pixel(0, 3) && !a.pixel(5, 3));
+
+        // 'g': descendente abaixo da linha de base (linhas 6..=7)
+        let g = font.glyph('g');
+        assert!(g.pixel(0, 6) && g.pixel(2, 6));
+        assert!(!g.pixel(0, 7) && g.pixel(2, 7));
+        assert!(!g.pixel(0, 5));
+
+        let bar = font.glyph('|');
+        assert!((0..8).all(|y| bar.pixel(0, y)));
+    }
+
+    #[test]
+    fn advance_defaults_to_cell_width() {
+        let font = parse(SAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(font.advance('A'), 6);
+        assert_eq!(font.advance('g'), 4);
+        assert_eq!(font.advance('|'), 6);
+        assert_eq!(font.measure("Ag|"), 16);
+    }
+
+    #[test]
+    fn skips_unencoded_glyphs() {
+        let font = parse(SAMPLE.as_bytes()).unwrap();
+
+        // Sem glifo próprio: cai no glifo de ausente gerado (caixa)
+        assert!(!font.has_glyph('x'));
+        let missing = font.glyph('x');
+        assert!(missing.pixel(1, 1));
+        assert!(!missing.pixel(0, 0));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert_eq!(parse(b"STARTFOO").err(), Some(FontError::BadMagic));
+        assert_eq!(parse(b"PSF2").err(), Some(FontError::BadMagic));
+
+        let text = SAMPLE.replace("90\n", "9Z\n");
+        assert_eq!(parse(text.as_bytes()).err(), Some(FontError::Syntax(15)));
+
+        let text = SAMPLE.replace("BBX 3 2 0 -2", "BBX 3 2 0");
+        assert_eq!(parse(text.as_bytes()).err(), Some(FontError::Syntax(21)));
+
+        // ENDCHAR antes de FONTBOUNDINGBOX
+        let text = SAMPLE.replace("FONTBOUNDINGBOX 6 8 0 -2\n", "");
+        assert_eq!(parse(text.as_bytes()).err(), Some(FontError::Syntax(16)));
+
+        assert_eq!(
+            parse(b"STARTFONT 2.1\nFONTBOUNDINGBOX 6 8 0 -2\nENDFONT\n").err(),
+            Some(FontError::Empty)
+        );
+    }
+}
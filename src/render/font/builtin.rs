@@ -0,0 +1,385 @@
+//! # Fonte Bitmap 8x8
+//!
+//! Fonte rasterizada para renderização de texto no modo gráfico.
+//!
+//! ## Formato
+//!
+//! Cada caractere é representado por 8 bytes, onde cada byte
+//! corresponde a uma linha horizontal de 8 pixels.
+//! Bit 7 = pixel mais à esquerda, Bit 0 = pixel mais à direita.
+//!
+//! ## Cobertura
+//!
+//! - ASCII 32 (espaço) até 126 (~), totalizando 95 glifos
+//! - Latin-1 Supplement U+00A0 até U+00FF (acentos do português,
+//!   símbolos e pontuação), totalizando 96 glifos
+//!
+//! Qualquer outro caractere imprimível é desenhado como uma caixa
+//! ([`MISSING_GLYPH`]), nunca como outro glifo por truncamento.
+//!
+//! Esta é a fonte de fallback quando nenhuma fonte de
+//! `/system/resources/fonts/` pode ser carregada.
+
+use super::{Font, FontBuilder};
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Largura de cada caractere em pixels
+pub const CHAR_WIDTH: u32 = 8;
+
+/// Altura de cada caractere em pixels
+pub const CHAR_HEIGHT: u32 = 8;
+
+// ============================================================================
+// FONTE BITMAP
+// ============================================================================
+
+/// Tabela de glifos bitmap 8x8 para ASCII 32-126.
+pub const FONT_8X8: [[u8; 8]; 95] = [
+    // Space (32)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // ! (33)
+    [0x18, 0x18, 0x18, 0x18, 0x18, 0x00, 0x18, 0x00],
+    // " (34)
+    [0x6C, 0x6C, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // # (35)
+    [0x6C, 0x6C, 0xFE, 0x6C, 0xFE, 0x6C, 0x6C, 0x00],
+    // $ (36)
+    [0x18, 0x7E, 0xC0, 0x7C, 0x06, 0xFC, 0x18, 0x00],
+    // % (37)
+    [0x00, 0xC6, 0xCC, 0x18, 0x30, 0x66, 0xC6, 0x00],
+    // & (38)
+    [0x38, 0x6C, 0x38, 0x76, 0xDC, 0xCC, 0x76, 0x00],
+    // ' (39)
+    [0x18, 0x18, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // ( (40)
+    [0x0C, 0x18, 0x30, 0x30, 0x30, 0x18, 0x0C, 0x00],
+    // ) (41)
+    [0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x18, 0x30, 0x00],
+    // * (42)
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00],
+    // + (43)
+    [0x00, 0x18, 0x18, 0x7E, 0x18, 0x18, 0x00, 0x00],
+    // , (44)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x30],
+    // - (45)
+    [0x00, 0x00, 0x00, 0x7E, 0x00, 0x00, 0x00, 0x00],
+    // . (46)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x18, 0x00],
+    // / (47)
+    [0x06, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0x80, 0x00],
+    // 0-9 (48-57)
+    [0x7C, 0xC6, 0xCE, 0xD6, 0xE6, 0xC6, 0x7C, 0x00],
+    [0x18, 0x38, 0x18, 0x18, 0x18, 0x18, 0x7E, 0x00],
+    [0x7C, 0xC6, 0x06, 0x1C, 0x30, 0x66, 0xFE, 0x00],
+    [0x7C, 0xC6, 0x06, 0x3C, 0x06, 0xC6, 0x7C, 0x00],
+    [0x1C, 0x3C, 0x6C, 0xCC, 0xFE, 0x0C, 0x1E, 0x00],
+    [0xFE, 0xC0, 0xFC, 0x06, 0x06, 0xC6, 0x7C, 0x00],
+    [0x38, 0x60, 0xC0, 0xFC, 0xC6, 0xC6, 0x7C, 0x00],
+    [0xFE, 0xC6, 0x0C, 0x18, 0x30, 0x30, 0x30, 0x00],
+    [0x7C, 0xC6, 0xC6, 0x7C, 0xC6, 0xC6, 0x7C, 0x00],
+    [0x7C, 0xC6, 0xC6, 0x7E, 0x06, 0x0C, 0x78, 0x00],
+    // : ; < = > ? @ (58-64)
+    [0x00, 0x18, 0x18, 0x00, 0x00, 0x18, 0x18, 0x00],
+    [0x00, 0x18, 0x18, 0x00, 0x00, 0x18, 0x18, 0x30],
+    [0x0C, 0x18, 0x30, 0x60, 0x30, 0x18, 0x0C, 0x00],
+    [0x00, 0x00, 0x7E, 0x00, 0x7E, 0x00, 0x00, 0x00],
+    [0x30, 0x18, 0x0C, 0x06, 0x0C, 0x18, 0x30, 0x00],
+    [0x7C, 0xC6, 0x0C, 0x18, 0x18, 0x00, 0x18, 0x00],
+    [0x7C, 0xC6, 0xDE, 0xDE, 0xDC, 0xC0, 0x7C, 0x00],
+    // A-Z (65-90)
+    [0x38, 0x6C, 0xC6, 0xC6, 0xFE, 0xC6, 0xC6, 0x00],
+    [0xFC, 0x66, 0x66, 0x7C, 0x66, 0x66, 0xFC, 0x00],
+    [0x3C, 0x66, 0xC0, 0xC0, 0xC0, 0x66, 0x3C, 0x00],
+    [0xF8, 0x6C, 0x66, 0x66, 0x66, 0x6C, 0xF8, 0x00],
+    [0xFE, 0x62, 0x68, 0x78, 0x68, 0x62, 0xFE, 0x00],
+    [0xFE, 0x62, 0x68, 0x78, 0x68, 0x60, 0xF0, 0x00],
+    [0x3C, 0x66, 0xC0, 0xC0, 0xCE, 0x66, 0x3A, 0x00],
+    [0xC6, 0xC6, 0xC6, 0xFE, 0xC6, 0xC6, 0xC6, 0x00],
+    [0x3C, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    [0x1E, 0x0C, 0x0C, 0x0C, 0xCC, 0xCC, 0x78, 0x00],
+    [0xE6, 0x66, 0x6C, 0x78, 0x6C, 0x66, 0xE6, 0x00],
+    [0xF0, 0x60, 0x60, 0x60, 0x62, 0x66, 0xFE, 0x00],
+    [0xC6, 0xEE, 0xFE, 0xFE, 0xD6, 0xC6, 0xC6, 0x00],
+    [0xC6, 0xE6, 0xF6, 0xDE, 0xCE, 0xC6, 0xC6, 0x00],
+    [0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    [0xFC, 0x66, 0x66, 0x7C, 0x60, 0x60, 0xF0, 0x00],
+    [0x7C, 0xC6, 0xC6, 0xC6, 0xD6, 0x7C, 0x0E, 0x00],
+    [0xFC, 0x66, 0x66, 0x7C, 0x6C, 0x66, 0xE6, 0x00],
+    [0x7C, 0xC6, 0x60, 0x38, 0x0C, 0xC6, 0x7C, 0x00],
+    [0x7E, 0x5A, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    [0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    [0xC6, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x10, 0x00],
+    [0xC6, 0xC6, 0xD6, 0xFE, 0xFE, 0xEE, 0xC6, 0x00],
+    [0xC6, 0x6C, 0x38, 0x38, 0x38, 0x6C, 0xC6, 0x00],
+    [0x66, 0x66, 0x66, 0x3C, 0x18, 0x18, 0x3C, 0x00],
+    [0xFE, 0xC6, 0x8C, 0x18, 0x32, 0x66, 0xFE, 0x00],
+    // [ \ ] ^ _ ` (91-96)
+    [0x3C, 0x30, 0x30, 0x30, 0x30, 0x30, 0x3C, 0x00],
+    [0xC0, 0x60, 0x30, 0x18, 0x0C, 0x06, 0x02, 0x00],
+    [0x3C, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x3C, 0x00],
+    [0x10, 0x38, 0x6C, 0xC6, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF],
+    [0x30, 0x18, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // a-z (97-122)
+    [0x00, 0x00, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    [0xE0, 0x60, 0x7C, 0x66, 0x66, 0x66, 0xDC, 0x00],
+    [0x00, 0x00, 0x7C, 0xC6, 0xC0, 0xC6, 0x7C, 0x00],
+    [0x1C, 0x0C, 0x7C, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    [0x00, 0x00, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
+    [0x3C, 0x66, 0x60, 0xF8, 0x60, 0x60, 0xF0, 0x00],
+    [0x00, 0x00, 0x76, 0xCC, 0xCC, 0x7C, 0x0C, 0x78],
+    [0xE0, 0x60, 0x6C, 0x76, 0x66, 0x66, 0xE6, 0x00],
+    [0x18, 0x00, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    [0x06, 0x00, 0x0E, 0x06, 0x06, 0x66, 0x66, 0x3C],
+    [0xE0, 0x60, 0x66, 0x6C, 0x78, 0x6C, 0xE6, 0x00],
+    [0x38, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    [0x00, 0x00, 0xEC, 0xFE, 0xD6, 0xC6, 0xC6, 0x00],
+    [0x00, 0x00, 0xDC, 0x66, 0x66, 0x66, 0x66, 0x00],
+    [0x00, 0x00, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    [0x00, 0x00, 0xDC, 0x66, 0x66, 0x7C, 0x60, 0xF0],
+    [0x00, 0x00, 0x76, 0xCC, 0xCC, 0x7C, 0x0C, 0x1E],
+    [0x00, 0x00, 0xDC, 0x76, 0x60, 0x60, 0xF0, 0x00],
+    [0x00, 0x00, 0x7C, 0xC0, 0x7C, 0x06, 0xFC, 0x00],
+    [0x30, 0x30, 0xFC, 0x30, 0x30, 0x36, 0x1C, 0x00],
+    [0x00, 0x00, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    [0x00, 0x00, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x00],
+    [0x00, 0x00, 0xC6, 0xC6, 0xD6, 0xFE, 0x6C, 0x00],
+    [0x00, 0x00, 0xC6, 0x6C, 0x38, 0x6C, 0xC6, 0x00],
+    [0x00, 0x00, 0xC6, 0xC6, 0xCE, 0x76, 0x06, 0x7C],
+    [0x00, 0x00, 0xFC, 0x98, 0x30, 0x64, 0xFC, 0x00],
+    // { | } ~ (123-126)
+    [0x0E, 0x18, 0x18, 0x70, 0x18, 0x18, 0x0E, 0x00],
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00],
+    [0x70, 0x18, 0x18, 0x0E, 0x18, 0x18, 0x70, 0x00],
+    [0x76, 0xDC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+];
+
+/// Tabela de glifos bitmap 8x8 para Latin-1 Supplement (U+00A0-U+00FF).
+pub const FONT_8X8_LATIN1: [[u8; 8]; 96] = [
+    // NBSP (U+00A0)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // ¡ (U+00A1)
+    [0x18, 0x00, 0x18, 0x18, 0x18, 0x18, 0x18, 0x00],
+    // ¢ (U+00A2)
+    [0x18, 0x3E, 0x60, 0x60, 0x60, 0x3E, 0x18, 0x00],
+    // £ (U+00A3)
+    [0x38, 0x6C, 0x60, 0xF0, 0x60, 0x66, 0xFC, 0x00],
+    // ¤ (U+00A4)
+    [0x00, 0xC6, 0x7C, 0x6C, 0x7C, 0xC6, 0x00, 0x00],
+    // ¥ (U+00A5)
+    [0x66, 0x66, 0x3C, 0x7E, 0x18, 0x7E, 0x18, 0x00],
+    // ¦ (U+00A6)
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00],
+    // § (U+00A7)
+    [0x3C, 0x60, 0x38, 0x6C, 0x38, 0x0C, 0x78, 0x00],
+    // ¨ (U+00A8)
+    [0x6C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // © (U+00A9)
+    [0x7E, 0x81, 0x9D, 0xA1, 0xA1, 0x9D, 0x81, 0x7E],
+    // ª (U+00AA)
+    [0x3C, 0x06, 0x3E, 0x66, 0x3F, 0x00, 0x7E, 0x00],
+    // « (U+00AB)
+    [0x00, 0x33, 0x66, 0xCC, 0x66, 0x33, 0x00, 0x00],
+    // ¬ (U+00AC)
+    [0x00, 0x00, 0x7E, 0x02, 0x02, 0x00, 0x00, 0x00],
+    // SHY (U+00AD)
+    [0x00, 0x00, 0x00, 0x3C, 0x00, 0x00, 0x00, 0x00],
+    // ® (U+00AE)
+    [0x7E, 0x81, 0xB9, 0xA5, 0xB9, 0xA5, 0x81, 0x7E],
+    // ¯ (U+00AF)
+    [0x7E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // ° (U+00B0)
+    [0x38, 0x6C, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // ± (U+00B1)
+    [0x18, 0x18, 0x7E, 0x18, 0x18, 0x00, 0x7E, 0x00],
+    // ² (U+00B2)
+    [0x70, 0x18, 0x30, 0x78, 0x00, 0x00, 0x00, 0x00],
+    // ³ (U+00B3)
+    [0x70, 0x18, 0x30, 0x18, 0x70, 0x00, 0x00, 0x00],
+    // ´ (U+00B4)
+    [0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // µ (U+00B5)
+    [0x00, 0x00, 0x66, 0x66, 0x66, 0x7C, 0x60, 0xC0],
+    // ¶ (U+00B6)
+    [0x3F, 0x7A, 0x7A, 0x3A, 0x0A, 0x0A, 0x0A, 0x00],
+    // · (U+00B7)
+    [0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x00],
+    // ¸ (U+00B8)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x38],
+    // ¹ (U+00B9)
+    [0x30, 0x70, 0x30, 0x30, 0x78, 0x00, 0x00, 0x00],
+    // º (U+00BA)
+    [0x38, 0x6C, 0x6C, 0x38, 0x00, 0x7C, 0x00, 0x00],
+    // » (U+00BB)
+    [0x00, 0xCC, 0x66, 0x33, 0x66, 0xCC, 0x00, 0x00],
+    // ¼ (U+00BC)
+    [0x42, 0xC4, 0x48, 0x54, 0x2C, 0x5E, 0x84, 0x00],
+    // ½ (U+00BD)
+    [0x42, 0xC4, 0x48, 0x5E, 0x22, 0x44, 0x8E, 0x00],
+    // ¾ (U+00BE)
+    [0xC2, 0x44, 0xC8, 0x64, 0xEC, 0x5E, 0x84, 0x00],
+    // ¿ (U+00BF)
+    [0x18, 0x00, 0x18, 0x30, 0x60, 0x66, 0x3C, 0x00],
+    // À (U+00C0)
+    [0x30, 0x18, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Á (U+00C1)
+    [0x0C, 0x18, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Â (U+00C2)
+    [0x38, 0x6C, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Ã (U+00C3)
+    [0x76, 0xDC, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Ä (U+00C4)
+    [0x6C, 0x00, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Å (U+00C5)
+    [0x38, 0x28, 0x38, 0x6C, 0xC6, 0xFE, 0xC6, 0xC6],
+    // Æ (U+00C6)
+    [0x3F, 0x6C, 0xCC, 0xFE, 0xCC, 0xCC, 0xCF, 0x00],
+    // Ç (U+00C7)
+    [0x3C, 0x66, 0xC0, 0xC0, 0x66, 0x3C, 0x0C, 0x38],
+    // È (U+00C8)
+    [0x30, 0x18, 0xFE, 0x68, 0x78, 0x68, 0x62, 0xFE],
+    // É (U+00C9)
+    [0x0C, 0x18, 0xFE, 0x68, 0x78, 0x68, 0x62, 0xFE],
+    // Ê (U+00CA)
+    [0x38, 0x6C, 0xFE, 0x68, 0x78, 0x68, 0x62, 0xFE],
+    // Ë (U+00CB)
+    [0x6C, 0x00, 0xFE, 0x68, 0x78, 0x68, 0x62, 0xFE],
+    // Ì (U+00CC)
+    [0x30, 0x18, 0x3C, 0x18, 0x18, 0x18, 0x18, 0x3C],
+    // Í (U+00CD)
+    [0x0C, 0x18, 0x3C, 0x18, 0x18, 0x18, 0x18, 0x3C],
+    // Î (U+00CE)
+    [0x38, 0x6C, 0x3C, 0x18, 0x18, 0x18, 0x18, 0x3C],
+    // Ï (U+00CF)
+    [0x6C, 0x00, 0x3C, 0x18, 0x18, 0x18, 0x18, 0x3C],
+    // Ð (U+00D0)
+    [0xF8, 0x6C, 0x66, 0xF6, 0x66, 0x6C, 0xF8, 0x00],
+    // Ñ (U+00D1)
+    [0x76, 0xDC, 0xC6, 0xE6, 0xF6, 0xDE, 0xCE, 0xC6],
+    // Ò (U+00D2)
+    [0x30, 0x18, 0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ó (U+00D3)
+    [0x0C, 0x18, 0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ô (U+00D4)
+    [0x38, 0x6C, 0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Õ (U+00D5)
+    [0x76, 0xDC, 0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ö (U+00D6)
+    [0x6C, 0x00, 0x7C, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // × (U+00D7)
+    [0x00, 0x66, 0x3C, 0x18, 0x3C, 0x66, 0x00, 0x00],
+    // Ø (U+00D8)
+    [0x7A, 0xCC, 0xDC, 0xEC, 0xCC, 0xCC, 0xBC, 0x00],
+    // Ù (U+00D9)
+    [0x30, 0x18, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ú (U+00DA)
+    [0x0C, 0x18, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Û (U+00DB)
+    [0x38, 0x6C, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ü (U+00DC)
+    [0x6C, 0x00, 0xC6, 0xC6, 0xC6, 0xC6, 0xC6, 0x7C],
+    // Ý (U+00DD)
+    [0x0C, 0x18, 0x66, 0x66, 0x3C, 0x18, 0x18, 0x3C],
+    // Þ (U+00DE)
+    [0xF0, 0x60, 0x7C, 0x66, 0x7C, 0x60, 0xF0, 0x00],
+    // ß (U+00DF)
+    [0x3C, 0x66, 0x6C, 0x66, 0x66, 0x6C, 0x60, 0x00],
+    // à (U+00E0)
+    [0x30, 0x18, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // á (U+00E1)
+    [0x0C, 0x18, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // â (U+00E2)
+    [0x38, 0x6C, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // ã (U+00E3)
+    [0x76, 0xDC, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // ä (U+00E4)
+    [0x6C, 0x00, 0x78, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // å (U+00E5)
+    [0x30, 0x48, 0x30, 0x0C, 0x7C, 0xCC, 0x76, 0x00],
+    // æ (U+00E6)
+    [0x00, 0x00, 0x6C, 0x1A, 0x7E, 0xD8, 0x6E, 0x00],
+    // ç (U+00E7)
+    [0x00, 0x7C, 0xC6, 0xC0, 0xC6, 0x7C, 0x0C, 0x38],
+    // è (U+00E8)
+    [0x30, 0x18, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
+    // é (U+00E9)
+    [0x0C, 0x18, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
+    // ê (U+00EA)
+    [0x38, 0x6C, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
+    // ë (U+00EB)
+    [0x6C, 0x00, 0x7C, 0xC6, 0xFE, 0xC0, 0x7C, 0x00],
+    // ì (U+00EC)
+    [0x30, 0x18, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    // í (U+00ED)
+    [0x0C, 0x18, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    // î (U+00EE)
+    [0x38, 0x6C, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    // ï (U+00EF)
+    [0x6C, 0x00, 0x38, 0x18, 0x18, 0x18, 0x3C, 0x00],
+    // ð (U+00F0)
+    [0x6C, 0x38, 0x0C, 0x7C, 0xCC, 0xCC, 0x78, 0x00],
+    // ñ (U+00F1)
+    [0x76, 0xDC, 0xDC, 0x66, 0x66, 0x66, 0x66, 0x00],
+    // ò (U+00F2)
+    [0x30, 0x18, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    // ó (U+00F3)
+    [0x0C, 0x18, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    // ô (U+00F4)
+    [0x38, 0x6C, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    // õ (U+00F5)
+    [0x76, 0xDC, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    // ö (U+00F6)
+    [0x6C, 0x00, 0x7C, 0xC6, 0xC6, 0xC6, 0x7C, 0x00],
+    // ÷ (U+00F7)
+    [0x00, 0x18, 0x00, 0x7E, 0x00, 0x18, 0x00, 0x00],
+    // ø (U+00F8)
+    [0x00, 0x02, 0x7C, 0xCE, 0xD6, 0xE6, 0x7C, 0x80],
+    // ù (U+00F9)
+    [0x30, 0x18, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    // ú (U+00FA)
+    [0x0C, 0x18, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    // û (U+00FB)
+    [0x38, 0x6C, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    // ü (U+00FC)
+    [0x6C, 0x00, 0xCC, 0xCC, 0xCC, 0xCC, 0x76, 0x00],
+    // ý (U+00FD)
+    [0x0C, 0x18, 0xC6, 0xC6, 0xCE, 0x76, 0x06, 0x7C],
+    // þ (U+00FE)
+    [0x00, 0xE0, 0x60, 0x7C, 0x66, 0x7C, 0x60, 0xF0],
+    // ÿ (U+00FF)
+    [0x6C, 0x00, 0xC6, 0xC6, 0xCE, 0x76, 0x06, 0x7C],
+];
+
+/// Glifo de caractere ausente (caixa vazada).
+pub const MISSING_GLYPH: [u8; 8] = [0x00, 0x7E, 0x42, 0x42, 0x42, 0x42, 0x7E, 0x00];
+
+// ============================================================================
+// FUNÇÕES
+// ============================================================================
+
+/// Obtém o bitmap de um caractere, se a fonte o cobrir.
+pub fn get_char_bitmap(c: char) -> Option<&'static [u8; 8]> {
+    match c as u32 {
+        code @ 0x20..=0x7E => Some(&FONT_8X8[(code - 0x20) as usize]),
+        code @ 0xA0..=0xFF => Some(&FONT_8X8_LATIN1[(code - 0xA0) as usize]),
+        _ => None,
+    }
+}
+
+/// Constrói a fonte embutida como [`Font`].
+pub fn font() -> Font {
+    let mut builder = FontBuilder::new(CHAR_WIDTH, CHAR_HEIGHT);
+
+    for code in (0x20..=0x7Eu32).chain(0xA0..=0xFF) {
+        if let Some(c) = char::from_u32(code) {
+            if let Some(bitmap) = get_char_bitmap(c) {
+                builder.push(c, bitmap, CHAR_WIDTH);
+            }
+        }
+    }
+
+    builder.finish(&MISSING_GLYPH)
+}
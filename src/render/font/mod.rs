@@ -0,0 +1,616 @@
+//! # Fontes
+//!
+//! Fontes carregadas de `/system/resources/fonts/`, com fallback para a
+//! fonte 8x8 embutida.
+//!
+//! ## Formatos
+//!
+//! - **TrueType**: contornos `glyf` rasterizados com anti-aliasing (`.ttf`)
+//! - **PSF2**: formato binário do console Linux (`.psf`)
+//! - **BDF**: formato texto do X11 (`.bdf`)
+//!
+//! Os parsers operam apenas sobre `&[u8]`, sem syscalls, e podem ser
+//! exercitados no host com arquivos de exemplo.
+//!
+//! ## Armazenamento
+//!
+//! Todos os glifos de uma [`Font`] ocupam uma célula fixa
+//! `width x height`, com `(width + 7) / 8` bytes por linha (bit 7 = pixel
+//! mais à esquerda). O avanço horizontal é guardado por glifo.
+//!
+//! Fontes TrueType ([`ScalableFont`]) produzem máscaras de cobertura
+//! (0-255) guardadas no [`GlyphCache`]. Ambos os tipos implementam
+//! [`TextFont`], usado pelo renderizador de texto.
+
+pub mod bdf;
+pub mod builtin;
+pub mod psf2;
+pub mod scalable;
+pub mod ttf;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use scalable::ScalableFont;
+pub use ttf::TrueTypeFont;
+
+use super::glyph_cache::{GlyphCache, RasterGlyph};
+use crate::util::fs::{self, ReadError};
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Diretório de fontes do sistema.
+const FONTS_DIR: &str = "/system/resources/fonts";
+
+/// Fonte TrueType da UI.
+const FONT_SCALABLE_NAME: &str = "ui.ttf";
+
+/// Tamanhos (pixels por em) da fonte TrueType: pequena, padrão e títulos.
+const FONT_SCALABLE_SIZES: [u16; 3] = [11, 14, 22];
+
+/// Fonte 8x16 (texto da UI).
+const FONT_REGULAR_NAME: &str = "ui-8x16";
+
+/// Fonte 12x24 (títulos).
+const FONT_LARGE_NAME: &str = "ui-12x24";
+
+/// Extensões tentadas, em ordem.
+const FONT_EXTENSIONS: [&str; 2] = ["psf", "bdf"];
+
+/// Tamanho máximo aceito para um arquivo de fonte.
+const MAX_FONT_FILE_SIZE: usize = 4 * 1024 * 1024;
+
+// ============================================================================
+// ERROS
+// ============================================================================
+
+/// Erro ao interpretar um arquivo de fonte.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FontError {
+    /// Assinatura do arquivo não reconhecida.
+    BadMagic,
+    /// Arquivo menor que o indicado pelo cabeçalho.
+    Truncated,
+    /// Cabeçalho com valores inválidos ou não suportados.
+    InvalidHeader,
+    /// Erro de sintaxe (BDF) na linha indicada.
+    Syntax(u32),
+    /// Fonte sem nenhum glifo utilizável.
+    Empty,
+}
+
+// ============================================================================
+// GLYPH
+// ============================================================================
+
+/// Glifo emprestado de uma [`Font`].
+#[derive(Debug, Clone, Copy)]
+pub struct Glyph<'a> {
+    /// Linhas do bitmap (`bytes_per_row` bytes cada).
+    pub bitmap: &'a [u8],
+    /// Bytes por linha.
+    pub bytes_per_row: usize,
+    /// Largura da célula.
+    pub width: u32,
+    /// Altura da célula.
+    pub height: u32,
+    /// Avanço horizontal em pixels.
+    pub advance: u32,
+}
+
+impl Glyph<'_> {
+    /// Retorna se o pixel `(x, y)` da célula está aceso.
+    #[cfg(test)]
+    pub fn pixel(&self, x: u32, y: u32) -> bool {
+        let byte = self.bitmap[y as usize * self.bytes_per_row + (x / 8) as usize];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Retorna a linha `y` do bitmap.
+    #[cfg(test)]
+    pub fn row(&self, y: u32) -> &[u8] {
+        let start = y as usize * self.bytes_per_row;
+        &self.bitmap[start..start + self.bytes_per_row]
+    }
+}
+
+// ============================================================================
+// TEXT FONT
+// ============================================================================
+
+/// Máscara de um [`GlyphImage`].
+#[derive(Debug, Clone)]
+pub enum GlyphMask<'a> {
+    /// Bitmap de 1 bit por pixel (fontes bitmap).
+    Bits {
+        data: &'a [u8],
+        bytes_per_row: usize,
+    },
+    /// Cobertura de 8 bits por pixel (fontes TrueType).
+    Coverage(Rc<RasterGlyph>),
+}
+
+/// Glifo pronto para desenho, independente do tipo de fonte.
+#[derive(Debug, Clone)]
+pub struct GlyphImage<'a> {
+    /// Máscara do glifo.
+    pub mask: GlyphMask<'a>,
+    /// Largura da máscara.
+    pub width: u32,
+    /// Altura da máscara.
+    pub height: u32,
+    /// Deslocamento X da máscara em relação à caneta.
+    pub left: i32,
+    /// Deslocamento Y da máscara em relação ao topo da linha.
+    pub top: i32,
+    /// Avanço horizontal em pixels.
+    pub advance: u32,
+}
+
+impl GlyphImage<'_> {
+    /// Cobertura (0-255) do pixel `(x, y)` da máscara.
+    #[inline]
+    pub fn coverage(&self, x: u32, y: u32) -> u8 {
+        match &self.mask {
+            GlyphMask::Bits {
+                data,
+                bytes_per_row,
+            } => {
+                let byte = data[y as usize * bytes_per_row + (x / 8) as usize];
+                if byte & (0x80 >> (x % 8)) != 0 {
+                    255
+                } else {
+                    0
+                }
+            }
+            GlyphMask::Coverage(glyph) => glyph.coverage[(y * self.width + x) as usize],
+        }
+    }
+}
+
+/// Fonte utilizável pelo renderizador de texto.
+pub trait TextFont {
+    /// Altura da linha de glifos (ascendente + descendente).
+    fn height(&self) -> u32;
+
+    /// Altura de linha recomendada.
+    fn line_height(&self) -> u32 {
+        self.height() + self.height() / 4
+    }
+
+    /// Retorna se a fonte tem glifo próprio para `c`.
+    fn has_glyph(&self, c: char) -> bool;
+
+    /// Avanço horizontal de `c`.
+    fn advance(&self, c: char) -> u32;
+
+    /// Mede a largura de uma string.
+    fn measure(&self, text: &str) -> u32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+
+    /// Obtém o glifo de `c` pronto para desenho.
+    fn glyph_image(&self, c: char) -> GlyphImage<'_>;
+}
+
+// ============================================================================
+// FONT
+// ============================================================================
+
+/// Fonte bitmap com células de tamanho fixo.
+pub struct Font {
+    /// Largura da célula.
+    width: u32,
+    /// Altura da célula.
+    height: u32,
+    /// Bytes por linha de glifo.
+    bytes_per_row: usize,
+    /// Bitmaps de todos os glifos, em sequência.
+    data: Vec<u8>,
+    /// Avanço horizontal de cada glifo.
+    advances: Vec<u16>,
+    /// Mapa codepoint -> índice do glifo, ordenado por codepoint.
+    map: Vec<(u32, u32)>,
+    /// Índice do glifo de caractere ausente.
+    missing: u32,
+    /// Índice do glifo em branco (caracteres de controle).
+    blank: u32,
+}
+
+impl Font {
+    /// Fonte 8x8 embutida.
+    pub fn builtin() -> Self {
+        builtin::font()
+    }
+
+    /// Interpreta uma fonte PSF2.
+    pub fn from_psf2(bytes: &[u8]) -> Result<Self, FontError> {
+        psf2::parse(bytes)
+    }
+
+    /// Interpreta uma fonte BDF.
+    pub fn from_bdf(bytes: &[u8]) -> Result<Self, FontError> {
+        bdf::parse(bytes)
+    }
+
+    /// Interpreta uma fonte detectando o formato pela assinatura.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FontError> {
+        if psf2::is_psf2(bytes) {
+            Self::from_psf2(bytes)
+        } else if bdf::is_bdf(bytes) {
+            Self::from_bdf(bytes)
+        } else {
+            Err(FontError::BadMagic)
+        }
+    }
+
+    /// Carrega uma fonte do disco.
+    pub fn load(path: &str) -> Option<Self> {
+        let bytes = read_file(path)?;
+
+        match Self::from_bytes(&bytes) {
+            Ok(font) => {
+                redpowder::println!(
+                    "[Font] {} carregada ({}x{}, {} glifos)",
+                    path,
+                    font.width,
+                    font.height,
+                    font.map.len()
+                );
+                Some(font)
+            }
+            Err(e) => {
+                redpowder::println!("[Font] ERRO: {} invalida: {:?}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Largura da célula.
+    #[cfg(test)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Altura da célula.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Altura de linha recomendada.
+    pub fn line_height(&self) -> u32 {
+        self.height + self.height / 4
+    }
+
+    /// Retorna se a fonte tem glifo próprio para `c`.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.index_of(c).is_some()
+    }
+
+    /// Obtém o glifo de `c`, com fallback para o glifo de ausente.
+    pub fn glyph(&self, c: char) -> Glyph<'_> {
+        let index = match self.index_of(c) {
+            Some(index) => index,
+            None if c.is_control() => self.blank,
+            None => self.missing,
+        };
+        self.glyph_at(index)
+    }
+
+    /// Avanço horizontal de `c`.
+    pub fn advance(&self, c: char) -> u32 {
+        self.glyph(c).advance
+    }
+
+    fn index_of(&self, c: char) -> Option<u32> {
+        let code = c as u32;
+        self.map
+            .binary_search_by_key(&code, |&(cp, _)| cp)
+            .ok()
+            .map(|pos| self.map[pos].1)
+    }
+
+    fn glyph_at(&self, index: u32) -> Glyph<'_> {
+        let glyph_size = self.bytes_per_row * self.height as usize;
+        let start = index as usize * glyph_size;
+        Glyph {
+            bitmap: &self.data[start..start + glyph_size],
+            bytes_per_row: self.bytes_per_row,
+            width: self.width,
+            height: self.height,
+            advance: self.advances[index as usize] as u32,
+        }
+    }
+}
+
+impl TextFont for Font {
+    fn height(&self) -> u32 {
+        Font::height(self)
+    }
+
+    fn line_height(&self) -> u32 {
+        Font::line_height(self)
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        Font::has_glyph(self, c)
+    }
+
+    fn advance(&self, c: char) -> u32 {
+        Font::advance(self, c)
+    }
+
+    fn glyph_image(&self, c: char) -> GlyphImage<'_> {
+        let glyph = self.glyph(c);
+        GlyphImage {
+            mask: GlyphMask::Bits {
+                data: glyph.bitmap,
+                bytes_per_row: glyph.bytes_per_row,
+            },
+            width: glyph.width,
+            height: glyph.height,
+            left: 0,
+            top: 0,
+            advance: glyph.advance,
+        }
+    }
+}
+
+// ============================================================================
+// FONT BUILDER
+// ============================================================================
+
+/// Acumula glifos durante o parsing e monta a [`Font`].
+pub(crate) struct FontBuilder {
+    width: u32,
+    height: u32,
+    bytes_per_row: usize,
+    data: Vec<u8>,
+    advances: Vec<u16>,
+    map: Vec<(u32, u32)>,
+}
+
+impl FontBuilder {
+    /// Cria builder para células `width x height`.
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            bytes_per_row: width.div_ceil(8) as usize,
+            data: Vec::new(),
+            advances: Vec::new(),
+            map: Vec::new(),
+        }
+    }
+
+    /// Largura da célula.
+    pub(crate) fn cell_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Altura da célula.
+    pub(crate) fn cell_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Bytes por linha de glifo.
+    pub(crate) fn bytes_per_row(&self) -> usize {
+        self.bytes_per_row
+    }
+
+    /// Bytes por glifo.
+    pub(crate) fn glyph_size(&self) -> usize {
+        self.bytes_per_row * self.height as usize
+    }
+
+    /// Adiciona um glifo. `bitmap` deve ter [`Self::glyph_size`] bytes.
+    pub(crate) fn push(&mut self, c: char, bitmap: &[u8], advance: u32) {
+        let index = self.push_bitmap(bitmap, advance);
+        self.map.push((c as u32, index));
+    }
+
+    /// Associa mais um codepoint a um glifo já adicionado.
+    pub(crate) fn alias(&mut self, c: char, index: u32) {
+        self.map.push((c as u32, index));
+    }
+
+    /// Adiciona um bitmap sem codepoint, retornando seu índice.
+    pub(crate) fn push_bitmap(&mut self, bitmap: &[u8], advance: u32) -> u32 {
+        let size = self.glyph_size();
+        let index = self.advances.len() as u32;
+        let len = bitmap.len().min(size);
+        self.data.extend_from_slice(&bitmap[..len]);
+        self.data.resize(self.data.len() + (size - len), 0);
+        self.advances.push(advance.min(u16::MAX as u32) as u16);
+        index
+    }
+
+    /// Número de glifos adicionados.
+    pub(crate) fn glyph_count(&self) -> usize {
+        self.advances.len()
+    }
+
+    /// Finaliza a fonte.
+    ///
+    /// `missing` é usado como glifo de ausente se a fonte não tiver
+    /// U+FFFD; se vazio, uma caixa do tamanho da célula é gerada.
+    pub(crate) fn finish(mut self, missing: &[u8]) -> Font {
+        self.map.sort_unstable_by_key(|&(cp, _)| cp);
+        self.map.dedup_by_key(|&mut (cp, _)| cp);
+
+        let replacement = self
+            .map
+            .binary_search_by_key(&(char::REPLACEMENT_CHARACTER as u32), |&(cp, _)| cp)
+            .ok()
+            .map(|pos| self.map[pos].1);
+
+        let missing = match replacement {
+            Some(index) => index,
+            None if !missing.is_empty() => self.push_bitmap(missing, self.width),
+            None => {
+                let boxed = self.box_bitmap();
+                self.push_bitmap(&boxed, self.width)
+            }
+        };
+
+        let blank_bitmap = alloc::vec![0u8; self.glyph_size()];
+        let blank = self.push_bitmap(&blank_bitmap, self.width);
+
+        Font {
+            width: self.width,
+            height: self.height,
+            bytes_per_row: self.bytes_per_row,
+            data: self.data,
+            advances: self.advances,
+            map: self.map,
+            missing,
+            blank,
+        }
+    }
+
+    /// Gera uma caixa vazada ocupando a célula (com 1px de margem).
+    fn box_bitmap(&self) -> Vec<u8> {
+        let mut bitmap = alloc::vec![0u8; self.glyph_size()];
+        if self.width < 4 || self.height < 4 {
+            return bitmap;
+        }
+
+        let (x0, x1) = (1, self.width - 2);
+        let (y0, y1) = (1, self.height - 2);
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if y == y0 || y == y1 || x == x0 || x == x1 {
+                    let idx = y as usize * self.bytes_per_row + (x / 8) as usize;
+                    bitmap[idx] |= 0x80 >> (x % 8);
+                }
+            }
+        }
+
+        bitmap
+    }
+}
+
+// ============================================================================
+// FONT SET
+// ============================================================================
+
+/// Conjunto de fontes usado pela UI.
+pub struct FontSet {
+    /// Fonte pequena.
+    small: Box<dyn TextFont>,
+    /// Fonte de texto padrão.
+    regular: Box<dyn TextFont>,
+    /// Fonte de títulos.
+    large: Box<dyn TextFont>,
+}
+
+impl FontSet {
+    /// Carrega as fontes do sistema.
+    ///
+    /// Prefere a fonte TrueType; sem ela, usa as fontes bitmap e, em
+    /// último caso, a fonte embutida.
+    pub fn load() -> Self {
+        let path = alloc::format!("{}/{}", FONTS_DIR, FONT_SCALABLE_NAME);
+
+        if let Some(face) = load_face(&path) {
+            // Cache compartilhado pelos três tamanhos
+            let cache = Rc::new(GlyphCache::default());
+            let [small, regular, large] = FONT_SCALABLE_SIZES.map(|size| {
+                Box::new(ScalableFont::new(face.clone(), size, cache.clone())) as Box<dyn TextFont>
+            });
+            return Self {
+                small,
+                regular,
+                large,
+            };
+        }
+
+        Self {
+            small: Box::new(Font::builtin()),
+            regular: Box::new(Self::load_named(FONT_REGULAR_NAME)),
+            large: Box::new(Self::load_named(FONT_LARGE_NAME)),
+        }
+    }
+
+    /// Fonte pequena.
+    pub fn small(&self) -> &dyn TextFont {
+        self.small.as_ref()
+    }
+
+    /// Fonte de texto padrão.
+    pub fn regular(&self) -> &dyn TextFont {
+        self.regular.as_ref()
+    }
+
+    /// Fonte de títulos.
+    pub fn large(&self) -> &dyn TextFont {
+        self.large.as_ref()
+    }
+
+    /// Tenta `<nome>.psf` e `<nome>.bdf` no diretório de fontes.
+    fn load_named(name: &str) -> Font {
+        for ext in FONT_EXTENSIONS {
+            let path = alloc::format!("{}/{}.{}", FONTS_DIR, name, ext);
+            if let Some(font) = Font::load(&path) {
+                return font;
+            }
+        }
+
+        redpowder::println!("[Font] {} indisponivel, usando fonte embutida", name);
+        Font::builtin()
+    }
+}
+
+/// Carrega uma fonte TrueType do disco.
+pub fn load_face(path: &str) -> Option<Rc<TrueTypeFont>> {
+    let bytes = read_file(path)?;
+
+    match TrueTypeFont::parse(bytes) {
+        Ok(face) => {
+            redpowder::println!("[Font] {} carregada (TrueType)", path);
+            Some(Rc::new(face))
+        }
+        Err(e) => {
+            redpowder::println!("[Font] ERRO: {} invalida: {:?}", path, e);
+            None
+        }
+    }
+}
+
+// ============================================================================
+// UTF-8
+// ============================================================================
+
+/// Decodifica bytes UTF-8 terminados em NUL (ex.: títulos de janela IPC).
+///
+/// Sequências inválidas ou truncadas no fim do buffer viram U+FFFD em vez
+/// de descartar a string inteira.
+pub fn decode_utf8_lossy(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+// ============================================================================
+// I/O
+// ============================================================================
+
+/// Lê um arquivo inteiro.
+fn read_file(path: &str) -> Option<Vec<u8>> {
+    match fs::read_file(path, MAX_FONT_FILE_SIZE) {
+        Ok(data) => Some(data),
+        Err(ReadError::Open) => None,
+        Err(ReadError::TooLarge) => {
+            redpowder::println!("[Font] ERRO: {} excede o tamanho maximo", path);
+            None
+        }
+        Err(ReadError::Read) => {
+            redpowder::println!("[Font] ERRO: Falha ao ler {}", path);
+            None
+        }
+    }
+}
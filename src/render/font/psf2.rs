@@ -0,0 +1,281 @@
+//! # PSF2
+//!
+//! Parser de fontes PC Screen Font versão 2.
+//!
+//! ## Layout
+//!
+//! ```text
+//! +------------------------+
+//! | header (32 bytes, LE)  |  magic, version, headersize, flags,
+//! |                        |  length, charsize, height, width
+//! +------------------------+
+//! | glyphs                 |  length * charsize bytes
+//! +------------------------+
+//! | unicode table          |  (se flags & 1) por glifo: UTF-8...,
+//! |                        |  0xFE inicia sequência, 0xFF termina
+//! +------------------------+
+//! ```
+//!
+//! Sem tabela unicode, o glifo `i` corresponde ao codepoint `i`.
+
+use super::{Font, FontBuilder, FontError};
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Assinatura PSF2 (little-endian 0x864AB572).
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xB5, 0x4A, 0x86];
+
+/// Tamanho mínimo do cabeçalho.
+const PSF2_HEADER_SIZE: usize = 32;
+
+/// Flag: arquivo possui tabela unicode.
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+
+/// Separador de sequência na tabela unicode.
+const PSF2_STARTSEQ: u8 = 0xFE;
+
+/// Terminador de entrada na tabela unicode.
+const PSF2_SEPARATOR: u8 = 0xFF;
+
+/// Limites de sanidade do cabeçalho.
+const MAX_GLYPH_DIMENSION: u32 = 64;
+const MAX_GLYPHS: u32 = 65536;
+
+// ============================================================================
+// PARSER
+// ============================================================================
+
+/// Retorna se os bytes começam com a assinatura PSF2.
+pub fn is_psf2(bytes: &[u8]) -> bool {
+    bytes.len() >= 4 && bytes[..4] == PSF2_MAGIC
+}
+
+/// Interpreta uma fonte PSF2.
+pub fn parse(bytes: &[u8]) -> Result<Font, FontError> {
+    if !is_psf2(bytes) {
+        return Err(FontError::BadMagic);
+    }
+    if bytes.len() < PSF2_HEADER_SIZE {
+        return Err(FontError::Truncated);
+    }
+
+    let header_size = read_u32(bytes, 8) as usize;
+    let flags = read_u32(bytes, 12);
+    let length = read_u32(bytes, 16);
+    let char_size = read_u32(bytes, 20) as usize;
+    let height = read_u32(bytes, 24);
+    let width = read_u32(bytes, 28);
+
+    if header_size < PSF2_HEADER_SIZE
+        || width == 0
+        || height == 0
+        || width > MAX_GLYPH_DIMENSION
+        || height > MAX_GLYPH_DIMENSION
+        || length == 0
+        || length > MAX_GLYPHS
+        || char_size != width.div_ceil(8) as usize * height as usize
+    {
+        return Err(FontError::InvalidHeader);
+    }
+
+    let glyphs_end = header_size + length as usize * char_size;
+    if bytes.len() < glyphs_end {
+        return Err(FontError::Truncated);
+    }
+
+    let mut builder = FontBuilder::new(width, height);
+
+    for i in 0..length as usize {
+        let start = header_size + i * char_size;
+        builder.push_bitmap(&bytes[start..start + char_size], width);
+    }
+
+    if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+        parse_unicode_table(&bytes[glyphs_end..], length, &mut builder);
+    } else {
+        for i in 0..length {
+            if let Some(c) = char::from_u32(i) {
+                builder.alias(c, i);
+            }
+        }
+    }
+
+    Ok(builder.finish(&[]))
+}
+
+/// Associa codepoints aos glifos segundo a tabela unicode.
+///
+/// Sequências (após 0xFE) descrevem combinações de vários codepoints e
+/// são ignoradas; apenas codepoints isolados são mapeados.
+fn parse_unicode_table(table: &[u8], length: u32, builder: &mut FontBuilder) {
+    let mut pos = 0;
+
+    for index in 0..length {
+        let mut in_sequence = false;
+
+        while pos < table.len() {
+            let byte = table[pos];
+
+            if byte == PSF2_SEPARATOR {
+                pos += 1;
+                break;
+            }
+            if byte == PSF2_STARTSEQ {
+                in_sequence = true;
+                pos += 1;
+                continue;
+            }
+
+            let len = utf8_len(byte);
+            let end = (pos + len).min(table.len());
+            let decoded = core::str::from_utf8(&table[pos..end])
+                .ok()
+                .and_then(|s| s.chars().next());
+            pos = end.max(pos + 1);
+
+            if let (Some(c), false) = (decoded, in_sequence) {
+                builder.alias(c, index);
+            }
+        }
+    }
+}
+
+/// Comprimento de um caractere UTF-8 a partir do primeiro byte.
+fn utf8_len(first: u8) -> usize {
+    match first {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    }
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Monta um arquivo PSF2 com glifos `width x height`.
+    fn sample(flags: u32, width: u32, height: u32, glyphs: &[&[u8]], table: &[u8]) -> Vec<u8> {
+        let char_size = width.div_ceil(8) * height;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&PSF2_MAGIC);
+        for field in [
+            0,
+            PSF2_HEADER_SIZE as u32,
+            flags,
+            glyphs.len() as u32,
+            char_size,
+            height,
+            width,
+        ] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        for glyph in glyphs {
+            bytes.extend_from_slice(glyph);
+        }
+        bytes.extend_from_slice(table);
+        bytes
+    }
+
+    #[test]
+    fn without_table_maps_index_to_codepoint() {
+        let bytes = sample(0, 8, 4, &[&[0; 4], &[0x80, 0, 0, 0x01], &[0xFF; 4]], &[]);
+        let font = parse(&bytes).unwrap();
+
+        assert_eq!((font.width(), font.height()), (8, 4));
+        assert!(font.has_glyph('\u{1}'));
+        assert!(!font.has_glyph('\u{3}'));
+
+        let glyph = font.glyph('\u{1}');
+        assert!(glyph.pixel(0, 0));
+        assert!(!glyph.pixel(1, 0));
+        assert!(glyph.pixel(7, 3));
+        assert_eq!(glyph.advance, 8);
+        assert_eq!(font.glyph('\u{2}').row(2), &[0xFF]);
+    }
+
+    #[test]
+    fn unicode_table_maps_codepoints_and_skips_sequences() {
+        let table = [
+            b'A',
+            0xC3,
+            0x81,
+            PSF2_SEPARATOR, // glifo 0: 'A' e 'Á'
+            b'B',
+            PSF2_STARTSEQ,
+            b'e',
+            0xCC,
+            0x81,
+            PSF2_SEPARATOR, // glifo 1: 'B'
+            0xEF,
+            0xBF,
+            0xBD,
+            PSF2_SEPARATOR, // glifo 2: U+FFFD
+        ];
+        let bytes = sample(
+            PSF2_HAS_UNICODE_TABLE,
+            8,
+            4,
+            &[&[0x01; 4], &[0x02; 4], &[0x3C; 4]],
+            &table,
+        );
+        let font = parse(&bytes).unwrap();
+
+        assert_eq!(font.glyph('A').row(0), &[0x01]);
+        assert_eq!(font.glyph('Á').row(0), &[0x01]);
+        assert_eq!(font.glyph('B').row(0), &[0x02]);
+        assert!(!font.has_glyph('e'));
+        assert!(!font.has_glyph('\u{0}'));
+
+        // U+FFFD da fonte vira o glifo de ausente
+        assert_eq!(font.glyph('Z').row(0), &[0x3C]);
+    }
+
+    #[test]
+    fn wide_glyphs_use_two_bytes_per_row() {
+        let bytes = sample(0, 12, 2, &[&[0x80, 0x10, 0x00, 0x00]], &[]);
+        let font = parse(&bytes).unwrap();
+        let glyph = font.glyph('\u{0}');
+
+        assert_eq!(glyph.bytes_per_row, 2);
+        assert!(glyph.pixel(0, 0));
+        assert!(glyph.pixel(11, 0));
+        assert!(!glyph.pixel(11, 1));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert_eq!(parse(b"PSF1").err(), Some(FontError::BadMagic));
+        assert_eq!(parse(&PSF2_MAGIC).err(), Some(FontError::Truncated));
+
+        let mut bytes = sample(0, 8, 4, &[&[0; 4], &[0; 4]], &[]);
+        bytes.truncate(bytes.len() - 1);
+        assert_eq!(parse(&bytes).err(), Some(FontError::Truncated));
+
+        // charsize não bate com largura x altura
+        let mut bytes = sample(0, 8, 4, &[&[0; 4]], &[]);
+        bytes[20] = 5;
+        assert_eq!(parse(&bytes).err(), Some(FontError::InvalidHeader));
+
+        let bytes = sample(0, 8, 4, &[], &[]);
+        assert_eq!(parse(&bytes).err(), Some(FontError::InvalidHeader));
+    }
+}
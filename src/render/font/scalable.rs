@@ -0,0 +1,167 @@
+//! # Scalable Font
+//!
+//! Fonte TrueType rasterizada em um tamanho fixo, com anti-aliasing.
+//!
+//! Os glifos são rasterizados sob demanda e guardados no [`GlyphCache`]
+//! compartilhado, de modo que cada glifo é rasterizado uma única vez por
+//! tamanho.
+
+use alloc::rc::Rc;
+
+use super::ttf::{Segment, TrueTypeFont};
+use super::{GlyphImage, GlyphMask, TextFont};
+use crate::render::glyph_cache::{GlyphCache, GlyphKey, RasterGlyph};
+use crate::render::raster::{ceilf, floorf, PointF, Rasterizer};
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Limite de sanidade para a máscara de um glifo.
+const MAX_GLYPH_DIMENSION: i32 = 256;
+
+// ============================================================================
+// SCALABLE FONT
+// ============================================================================
+
+/// Fonte TrueType em um tamanho específico.
+pub struct ScalableFont {
+    /// Fonte carregada (compartilhada entre tamanhos).
+    face: Rc<TrueTypeFont>,
+    /// Tamanho em pixels (em).
+    size: u16,
+    /// Pixels por unidade da fonte.
+    scale: f32,
+    /// Ascendente em pixels.
+    ascent: u32,
+    /// Descendente em pixels.
+    descent: u32,
+    /// Espaço extra entre linhas em pixels.
+    line_gap: u32,
+    /// Cache de glifos rasterizados.
+    cache: Rc<GlyphCache>,
+}
+
+impl ScalableFont {
+    /// Cria a fonte `face` no tamanho `size` (pixels por em).
+    pub fn new(face: Rc<TrueTypeFont>, size: u16, cache: Rc<GlyphCache>) -> Self {
+        let scale = size.max(1) as f32 / face.units_per_em() as f32;
+        let ascent = ceilf(face.ascender().max(0) as f32 * scale) as u32;
+        let descent = ceilf(-(face.descender().min(0) as f32) * scale) as u32;
+        let line_gap = round(face.line_gap().max(0) as f32 * scale);
+
+        Self {
+            face,
+            size,
+            scale,
+            ascent,
+            descent,
+            line_gap,
+            cache,
+        }
+    }
+
+    /// Índice do glifo usado para `c` (controle vira espaço).
+    fn glyph_index(&self, c: char) -> u16 {
+        if c.is_control() {
+            self.face.glyph_index(' ')
+        } else {
+            self.face.glyph_index(c)
+        }
+    }
+
+    /// Avanço em pixels do glifo `glyph`.
+    fn glyph_advance(&self, glyph: u16) -> u32 {
+        round(self.face.advance_width(glyph) as f32 * self.scale)
+    }
+
+    /// Rasteriza o glifo de `c`.
+    fn rasterize(&self, c: char) -> RasterGlyph {
+        let glyph = self.glyph_index(c);
+        let advance = self.glyph_advance(glyph);
+
+        let Some(outline) = self.face.outline(glyph) else {
+            return RasterGlyph {
+                advance,
+                ..RasterGlyph::default()
+            };
+        };
+
+        // Caixa em pixels (y para baixo, relativa à linha de base)
+        let (x_min, y_min, x_max, y_max) = outline.bounds;
+        let left = floorf(x_min as f32 * self.scale) as i32;
+        let right = ceilf(x_max as f32 * self.scale) as i32;
+        let top = floorf(-(y_max as f32) * self.scale) as i32;
+        let bottom = ceilf(-(y_min as f32) * self.scale) as i32;
+
+        let width = (right - left).clamp(0, MAX_GLYPH_DIMENSION);
+        let height = (bottom - top).clamp(0, MAX_GLYPH_DIMENSION);
+        if width == 0 || height == 0 {
+            return RasterGlyph {
+                advance,
+                ..RasterGlyph::default()
+            };
+        }
+
+        let scale = self.scale;
+        let to_px = |x: f32, y: f32| PointF::new(x * scale - left as f32, -y * scale - top as f32);
+
+        let mut raster = Rasterizer::new(width as u32, height as u32);
+        outline.walk(|segment| match segment {
+            Segment::Move(x, y) => raster.move_to(to_px(x, y)),
+            Segment::Line(x, y) => raster.line_to(to_px(x, y)),
+            Segment::Quad(cx, cy, x, y) => raster.quad_to(to_px(cx, cy), to_px(x, y)),
+        });
+
+        RasterGlyph {
+            width: width as u32,
+            height: height as u32,
+            left,
+            top,
+            advance,
+            coverage: raster.into_coverage(),
+        }
+    }
+}
+
+impl TextFont for ScalableFont {
+    fn height(&self) -> u32 {
+        self.ascent + self.descent
+    }
+
+    fn line_height(&self) -> u32 {
+        self.height() + self.line_gap.max(self.height() / 8)
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        self.face.glyph_index(c) != 0
+    }
+
+    fn advance(&self, c: char) -> u32 {
+        self.glyph_advance(self.glyph_index(c))
+    }
+
+    fn glyph_image(&self, c: char) -> GlyphImage<'_> {
+        let key = GlyphKey {
+            font_id: self.face.id(),
+            size: self.size,
+            codepoint: c as u32,
+        };
+        let glyph = self.cache.get_or_insert_with(key, || self.rasterize(c));
+
+        GlyphImage {
+            width: glyph.width,
+            height: glyph.height,
+            left: glyph.left,
+            top: self.ascent as i32 + glyph.top,
+            advance: glyph.advance,
+            mask: GlyphMask::Coverage(glyph),
+        }
+    }
+}
+
+/// Arredonda valores não-negativos.
+#[inline]
+fn round(x: f32) -> u32 {
+    (x + 0.5) as u32
+}
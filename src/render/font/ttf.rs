@@ -0,0 +1,670 @@
+//! # TrueType
+//!
+//! Parser de fontes TrueType/OpenType com contornos `glyf`.
+//!
+//! ## Tabelas Usadas
+//!
+//! | Tabela | Uso |
+//! |--------|-----|
+//! | `head` | unitsPerEm, formato da `loca` |
+//! | `maxp` | Número de glifos |
+//! | `hhea` | Ascender, descender, lineGap |
+//! | `hmtx` | Avanço horizontal |
+//! | `cmap` | Codepoint -> glifo (formatos 4 e 12) |
+//! | `loca` | Offset de cada glifo |
+//! | `glyf` | Contornos quadráticos (simples e compostos) |
+//!
+//! Fontes OpenType com contornos CFF (`OTTO`) não são suportadas.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use super::FontError;
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+
+/// Versões sfnt aceitas.
+const SFNT_VERSION_TRUETYPE: u32 = 0x0001_0000;
+const SFNT_VERSION_APPLE: u32 = 0x7472_7565; // 'true'
+const SFNT_VERSION_CFF: u32 = 0x4F54_544F; // 'OTTO'
+const TTC_TAG: u32 = 0x7474_6366; // 'ttcf'
+
+/// Flags de pontos de glifos simples.
+const FLAG_ON_CURVE: u8 = 0x01;
+const FLAG_X_SHORT: u8 = 0x02;
+const FLAG_Y_SHORT: u8 = 0x04;
+const FLAG_REPEAT: u8 = 0x08;
+const FLAG_X_SAME_OR_POSITIVE: u8 = 0x10;
+const FLAG_Y_SAME_OR_POSITIVE: u8 = 0x20;
+
+/// Flags de componentes de glifos compostos.
+const COMPONENT_ARGS_ARE_WORDS: u16 = 0x0001;
+const COMPONENT_ARGS_ARE_XY: u16 = 0x0002;
+const COMPONENT_HAS_SCALE: u16 = 0x0008;
+const COMPONENT_MORE: u16 = 0x0020;
+const COMPONENT_HAS_XY_SCALE: u16 = 0x0040;
+const COMPONENT_HAS_2X2: u16 = 0x0080;
+
+/// Profundidade máxima de glifos compostos aninhados.
+const MAX_COMPONENT_DEPTH: u32 = 4;
+
+/// Próximo ID de fonte (chave do cache de glifos).
+static NEXT_FONT_ID: AtomicU32 = AtomicU32::new(1);
+
+// ============================================================================
+// OUTLINE
+// ============================================================================
+
+/// Ponto de contorno em unidades da fonte (y para cima).
+#[derive(Debug, Clone, Copy)]
+pub struct OutlinePoint {
+    pub x: f32,
+    pub y: f32,
+    pub on_curve: bool,
+}
+
+/// Segmento emitido ao percorrer um [`Outline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Inicia um contorno em `(x, y)`.
+    Move(f32, f32),
+    /// Reta até `(x, y)`.
+    Line(f32, f32),
+    /// Bézier quadrática com controle `(cx, cy)` até `(x, y)`.
+    Quad(f32, f32, f32, f32),
+}
+
+/// Contornos de um glifo.
+#[derive(Debug, Clone, Default)]
+pub struct Outline {
+    /// Pontos de todos os contornos.
+    pub points: Vec<OutlinePoint>,
+    /// Índice final (exclusivo) de cada contorno em `points`.
+    pub contour_ends: Vec<usize>,
+    /// Bounding box (x_min, y_min, x_max, y_max) em unidades da fonte.
+    pub bounds: (i16, i16, i16, i16),
+}
+
+impl Outline {
+    /// Percorre os contornos emitindo segmentos em unidades da fonte.
+    ///
+    /// Pontos on-curve implícitos entre dois off-curve são reconstruídos.
+    pub fn walk(&self, mut emit: impl FnMut(Segment)) {
+        let mut start = 0;
+
+        for &end in &self.contour_ends {
+            let contour = &self.points[start..end.min(self.points.len())];
+            start = end;
+
+            if contour.len() < 2 {
+                continue;
+            }
+
+            // Ponto inicial on-curve (real ou implícito)
+            let first = contour[0];
+            let last = contour[contour.len() - 1];
+            let (sx, sy, skip) = if first.on_curve {
+                (first.x, first.y, 1)
+            } else if last.on_curve {
+                (last.x, last.y, 0)
+            } else {
+                ((first.x + last.x) / 2.0, (first.y + last.y) / 2.0, 0)
+            };
+
+            emit(Segment::Move(sx, sy));
+
+            let mut control: Option<(f32, f32)> = None;
+            let count = contour.len();
+
+            // Com `skip == 1` o primeiro ponto é revisitado no fim (fechamento)
+            for i in skip..count + skip {
+                let p = contour[i % count];
+                match (p.on_curve, control) {
+                    (true, None) => emit(Segment::Line(p.x, p.y)),
+                    (true, Some((cx, cy))) => {
+                        emit(Segment::Quad(cx, cy, p.x, p.y));
+                        control = None;
+                    }
+                    (false, None) => control = Some((p.x, p.y)),
+                    (false, Some((cx, cy))) => {
+                        let mx = (cx + p.x) / 2.0;
+                        let my = (cy + p.y) / 2.0;
+                        emit(Segment::Quad(cx, cy, mx, my));
+                        control = Some((p.x, p.y));
+                    }
+                }
+            }
+
+            // Fechar no ponto inicial implícito
+            if let Some((cx, cy)) = control {
+                emit(Segment::Quad(cx, cy, sx, sy));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// TRUETYPE FONT
+// ============================================================================
+
+/// Fonte TrueType carregada em memória.
+pub struct TrueTypeFont {
+    /// Identificador único (chave do cache).
+    id: u32,
+    /// Arquivo inteiro.
+    data: Vec<u8>,
+    /// Unidades por em.
+    units_per_em: u16,
+    /// Número de glifos.
+    num_glyphs: u16,
+    /// `loca` usa offsets de 32 bits.
+    long_loca: bool,
+    /// Métricas verticais (unidades da fonte).
+    ascender: i16,
+    descender: i16,
+    line_gap: i16,
+    /// Número de entradas completas em `hmtx`.
+    num_h_metrics: u16,
+    /// Offsets das tabelas.
+    loca: usize,
+    glyf: usize,
+    glyf_len: usize,
+    hmtx: usize,
+    /// Subtabela cmap escolhida (offset absoluto e formato).
+    cmap: usize,
+    cmap_format: u16,
+}
+
+impl TrueTypeFont {
+    /// Interpreta um arquivo TrueType (ou o primeiro de uma coleção).
+    pub fn parse(data: Vec<u8>) -> Result<Self, FontError> {
+        let base = match read_u32(&data, 0) {
+            Some(TTC_TAG) => read_u32(&data, 12).ok_or(FontError::Truncated)? as usize,
+            Some(_) => 0,
+            None => return Err(FontError::Truncated),
+        };
+
+        match read_u32(&data, base) {
+            Some(SFNT_VERSION_TRUETYPE) | Some(SFNT_VERSION_APPLE) => {}
+            Some(SFNT_VERSION_CFF) => return Err(FontError::InvalidHeader),
+            Some(_) => return Err(FontError::BadMagic),
+            None => return Err(FontError::Truncated),
+        }
+
+        let num_tables = read_u16(&data, base + 4).ok_or(FontError::Truncated)? as usize;
+        let find = |tag: &[u8; 4]| -> Option<(usize, usize)> {
+            (0..num_tables).find_map(|i| {
+                let rec = base + 12 + i * 16;
+                let t = data.get(rec..rec + 4)?;
+                if t != tag {
+                    return None;
+                }
+                let offset = read_u32(&data, rec + 8)? as usize;
+                let len = read_u32(&data, rec + 12)? as usize;
+                (offset.checked_add(len)? <= data.len()).then_some((offset, len))
+            })
+        };
+
+        let (head, _) = find(b"head").ok_or(FontError::InvalidHeader)?;
+        let (maxp, _) = find(b"maxp").ok_or(FontError::InvalidHeader)?;
+        let (hhea, _) = find(b"hhea").ok_or(FontError::InvalidHeader)?;
+        let (hmtx, _) = find(b"hmtx").ok_or(FontError::InvalidHeader)?;
+        let (cmap, _) = find(b"cmap").ok_or(FontError::InvalidHeader)?;
+        let (loca, _) = find(b"loca").ok_or(FontError::InvalidHeader)?;
+        let (glyf, glyf_len) = find(b"glyf").ok_or(FontError::InvalidHeader)?;
+
+        let units_per_em = read_u16(&data, head + 18).ok_or(FontError::Truncated)?;
+        let long_loca = read_i16(&data, head + 50).ok_or(FontError::Truncated)? != 0;
+        let num_glyphs = read_u16(&data, maxp + 4).ok_or(FontError::Truncated)?;
+        let ascender = read_i16(&data, hhea + 4).ok_or(FontError::Truncated)?;
+        let descender = read_i16(&data, hhea + 6).ok_or(FontError::Truncated)?;
+        let line_gap = read_i16(&data, hhea + 8).ok_or(FontError::Truncated)?;
+        let num_h_metrics = read_u16(&data, hhea + 34).ok_or(FontError::Truncated)?;
+
+        if units_per_em == 0 || num_glyphs == 0 || num_h_metrics == 0 {
+            return Err(FontError::InvalidHeader);
+        }
+
+        let (cmap, cmap_format) = Self::select_cmap(&data, cmap).ok_or(FontError::InvalidHeader)?;
+
+        Ok(Self {
+            id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+            data,
+            units_per_em,
+            num_glyphs,
+            long_loca,
+            ascender,
+            descender,
+            line_gap,
+            num_h_metrics,
+            loca,
+            glyf,
+            glyf_len,
+            hmtx,
+            cmap,
+            cmap_format,
+        })
+    }
+
+    /// Escolhe a melhor subtabela cmap Unicode (formato 12, depois 4).
+    fn select_cmap(data: &[u8], cmap: usize) -> Option<(usize, u16)> {
+        let count = read_u16(data, cmap + 2)? as usize;
+        let mut best: Option<(usize, u16)> = None;
+
+        for i in 0..count {
+            let rec = cmap + 4 + i * 8;
+            let platform = read_u16(data, rec)?;
+            let encoding = read_u16(data, rec + 2)?;
+            let offset = cmap + read_u32(data, rec + 4)? as usize;
+            let format = read_u16(data, offset)?;
+
+            let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+            if !unicode {
+                continue;
+            }
+
+            match (format, best) {
+                (12, _) => return Some((offset, 12)),
+                (4, None) => best = Some((offset, 4)),
+                _ => {}
+            }
+        }
+
+        best
+    }
+
+    /// Identificador único desta fonte.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Unidades por em.
+    pub fn units_per_em(&self) -> u16 {
+        self.units_per_em
+    }
+
+    /// Ascender (unidades da fonte, positivo).
+    pub fn ascender(&self) -> i16 {
+        self.ascender
+    }
+
+    /// Descender (unidades da fonte, negativo).
+    pub fn descender(&self) -> i16 {
+        self.descender
+    }
+
+    /// Espaço extra entre linhas (unidades da fonte).
+    pub fn line_gap(&self) -> i16 {
+        self.line_gap
+    }
+
+    /// Índice do glifo de `c` (0 = `.notdef`).
+    pub fn glyph_index(&self, c: char) -> u16 {
+        let code = c as u32;
+        let data = &self.data;
+        let t = self.cmap;
+
+        let found = match self.cmap_format {
+            4 => Self::lookup_format4(data, t, code),
+            12 => Self::lookup_format12(data, t, code),
+            _ => None,
+        };
+
+        found.filter(|&g| g < self.num_glyphs).unwrap_or(0)
+    }
+
+    fn lookup_format4(data: &[u8], t: usize, code: u32) -> Option<u16> {
+        if code > 0xFFFF {
+            return None;
+        }
+        let code = code as u16;
+        let seg_count = read_u16(data, t + 6)? as usize / 2;
+        let ends = t + 14;
+        let starts = ends + seg_count * 2 + 2;
+        let deltas = starts + seg_count * 2;
+        let range_offsets = deltas + seg_count * 2;
+
+        // Busca binária pelo primeiro segmento com end >= code
+        let (mut lo, mut hi) = (0, seg_count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if read_u16(data, ends + mid * 2)? < code {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        if lo >= seg_count {
+            return None;
+        }
+
+        let seg = lo;
+        let start = read_u16(data, starts + seg * 2)?;
+        if code < start {
+            return None;
+        }
+
+        let delta = read_u16(data, deltas + seg * 2)?;
+        let range_offset = read_u16(data, range_offsets + seg * 2)? as usize;
+
+        if range_offset == 0 {
+            return Some(code.wrapping_add(delta));
+        }
+
+        let addr = range_offsets + seg * 2 + range_offset + (code - start) as usize * 2;
+        let glyph = read_u16(data, addr)?;
+        if glyph == 0 {
+            None
+        } else {
+            Some(glyph.wrapping_add(delta))
+        }
+    }
+
+    fn lookup_format12(data: &[u8], t: usize, code: u32) -> Option<u16> {
+        let groups = read_u32(data, t + 12)? as usize;
+        let (mut lo, mut hi) = (0, groups);
+
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let g = t + 16 + mid * 12;
+            let start = read_u32(data, g)?;
+            let end = read_u32(data, g + 4)?;
+
+            if code < start {
+                hi = mid;
+            } else if code > end {
+                lo = mid + 1;
+            } else {
+                let glyph = read_u32(data, g + 8)? + (code - start);
+                return u16::try_from(glyph).ok();
+            }
+        }
+
+        None
+    }
+
+    /// Avanço horizontal do glifo (unidades da fonte).
+    pub fn advance_width(&self, glyph: u16) -> u16 {
+        let index = glyph.min(self.num_h_metrics - 1) as usize;
+        read_u16(&self.data, self.hmtx + index * 4).unwrap_or(0)
+    }
+
+    /// Intervalo `glyf` do glifo (vazio para glifos sem contorno).
+    fn glyph_range(&self, glyph: u16) -> Option<(usize, usize)> {
+        if glyph >= self.num_glyphs {
+            return None;
+        }
+        let i = glyph as usize;
+        let (start, end) = if self.long_loca {
+            (
+                read_u32(&self.data, self.loca + i * 4)? as usize,
+                read_u32(&self.data, self.loca + i * 4 + 4)? as usize,
+            )
+        } else {
+            (
+                read_u16(&self.data, self.loca + i * 2)? as usize * 2,
+                read_u16(&self.data, self.loca + i * 2 + 2)? as usize * 2,
+            )
+        };
+
+        if start > end || end > self.glyf_len {
+            return None;
+        }
+        Some((self.glyf + start, self.glyf + end))
+    }
+
+    /// Extrai o contorno do glifo (None para glifos vazios, ex.: espaço).
+    pub fn outline(&self, glyph: u16) -> Option<Outline> {
+        let (start, end) = self.glyph_range(glyph)?;
+        if start == end {
+            return None;
+        }
+
+        let mut outline = Outline {
+            bounds: (
+                read_i16(&self.data, start + 2)?,
+                read_i16(&self.data, start + 4)?,
+                read_i16(&self.data, start + 6)?,
+                read_i16(&self.data, start + 8)?,
+            ),
+            ..Outline::default()
+        };
+
+        self.append_glyph(glyph, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0], 0, &mut outline)?;
+
+        if outline.points.is_empty() {
+            None
+        } else {
+            Some(outline)
+        }
+    }
+
+    /// Adiciona os contornos de `glyph`, transformados, a `outline`.
+    fn append_glyph(
+        &self,
+        glyph: u16,
+        transform: [f32; 6],
+        depth: u32,
+        outline: &mut Outline,
+    ) -> Option<()> {
+        if depth > MAX_COMPONENT_DEPTH {
+            return None;
+        }
+
+        let (start, end) = self.glyph_range(glyph)?;
+        if start == end {
+            return Some(());
+        }
+
+        let contours = read_i16(&self.data, start)?;
+        if contours >= 0 {
+            self.append_simple(start, end, contours as usize, transform, outline)
+        } else {
+            self.append_composite(start + 10, transform, depth, outline)
+        }
+    }
+
+    fn append_simple(
+        &self,
+        start: usize,
+        end: usize,
+        contours: usize,
+        transform: [f32; 6],
+        outline: &mut Outline,
+    ) -> Option<()> {
+        let data = &self.data[..end];
+        let mut pos = start + 10;
+
+        let mut ends = Vec::with_capacity(contours);
+        for _ in 0..contours {
+            ends.push(read_u16(data, pos)? as usize);
+            pos += 2;
+        }
+
+        let num_points = match ends.last() {
+            Some(&last) => last + 1,
+            None => return Some(()),
+        };
+
+        let instructions = read_u16(data, pos)? as usize;
+        pos += 2 + instructions;
+
+        // Flags (com repetição)
+        let mut flags = Vec::with_capacity(num_points);
+        while flags.len() < num_points {
+            let flag = *data.get(pos)?;
+            pos += 1;
+            flags.push(flag);
+
+            if flag & FLAG_REPEAT != 0 {
+                let count = *data.get(pos)?;
+                pos += 1;
+                for _ in 0..count {
+                    flags.push(flag);
+                }
+            }
+        }
+        flags.truncate(num_points);
+
+        // Coordenadas X e Y (deltas)
+        let xs = Self::read_coords(
+            data,
+            &mut pos,
+            &flags,
+            FLAG_X_SHORT,
+            FLAG_X_SAME_OR_POSITIVE,
+        )?;
+        let ys = Self::read_coords(
+            data,
+            &mut pos,
+            &flags,
+            FLAG_Y_SHORT,
+            FLAG_Y_SAME_OR_POSITIVE,
+        )?;
+
+        let base = outline.points.len();
+        for i in 0..num_points {
+            let (x, y) = (xs[i] as f32, ys[i] as f32);
+            outline.points.push(OutlinePoint {
+                x: transform[0] * x + transform[2] * y + transform[4],
+                y: transform[1] * x + transform[3] * y + transform[5],
+                on_curve: flags[i] & FLAG_ON_CURVE != 0,
+            });
+        }
+
+        let mut prev_end = 0;
+        for &e in &ends {
+            // Índices devem ser crescentes e dentro do glifo
+            if e < prev_end || e >= num_points {
+                return None;
+            }
+            prev_end = e;
+            outline.contour_ends.push(base + e + 1);
+        }
+
+        Some(())
+    }
+
+    fn read_coords(
+        data: &[u8],
+        pos: &mut usize,
+        flags: &[u8],
+        short_flag: u8,
+        same_flag: u8,
+    ) -> Option<Vec<i32>> {
+        let mut coords = Vec::with_capacity(flags.len());
+        let mut value = 0i32;
+
+        for &flag in flags {
+            if flag & short_flag != 0 {
+                let delta = *data.get(*pos)? as i32;
+                *pos += 1;
+                value += if flag & same_flag != 0 { delta } else { -delta };
+            } else if flag & same_flag == 0 {
+                value += read_i16(data, *pos)? as i32;
+                *pos += 2;
+            }
+            coords.push(value);
+        }
+
+        Some(coords)
+    }
+
+    fn append_composite(
+        &self,
+        mut pos: usize,
+        transform: [f32; 6],
+        depth: u32,
+        outline: &mut Outline,
+    ) -> Option<()> {
+        loop {
+            let flags = read_u16(&self.data, pos)?;
+            let component = read_u16(&self.data, pos + 2)?;
+            pos += 4;
+
+            let (dx, dy) = if flags & COMPONENT_ARGS_ARE_WORDS != 0 {
+                let v = (read_i16(&self.data, pos)?, read_i16(&self.data, pos + 2)?);
+                pos += 4;
+                (v.0 as f32, v.1 as f32)
+            } else {
+                let v = (*self.data.get(pos)? as i8, *self.data.get(pos + 1)? as i8);
+                pos += 2;
+                (v.0 as f32, v.1 as f32)
+            };
+
+            // Pontos âncora (args não-XY) não são suportados: sem offset
+            let (dx, dy) = if flags & COMPONENT_ARGS_ARE_XY != 0 {
+                (dx, dy)
+            } else {
+                (0.0, 0.0)
+            };
+
+            let (a, b, c, d) = if flags & COMPONENT_HAS_SCALE != 0 {
+                let s = read_f2dot14(&self.data, pos)?;
+                pos += 2;
+                (s, 0.0, 0.0, s)
+            } else if flags & COMPONENT_HAS_XY_SCALE != 0 {
+                let sx = read_f2dot14(&self.data, pos)?;
+                let sy = read_f2dot14(&self.data, pos + 2)?;
+                pos += 4;
+                (sx, 0.0, 0.0, sy)
+            } else if flags & COMPONENT_HAS_2X2 != 0 {
+                let m = (
+                    read_f2dot14(&self.data, pos)?,
+                    read_f2dot14(&self.data, pos + 2)?,
+                    read_f2dot14(&self.data, pos + 4)?,
+                    read_f2dot14(&self.data, pos + 6)?,
+                );
+                pos += 8;
+                m
+            } else {
+                (1.0, 0.0, 0.0, 1.0)
+            };
+
+            // Compor: transform * (componente)
+            let t = transform;
+            let combined = [
+                t[0] * a + t[2] * b,
+                t[1] * a + t[3] * b,
+                t[0] * c + t[2] * d,
+                t[1] * c + t[3] * d,
+                t[0] * dx + t[2] * dy + t[4],
+                t[1] * dx + t[3] * dy + t[5],
+            ];
+
+            self.append_glyph(component, combined, depth + 1, outline)?;
+
+            if flags & COMPONENT_MORE == 0 {
+                return Some(());
+            }
+        }
+    }
+}
+
+// ============================================================================
+// LEITURA BIG-ENDIAN
+// ============================================================================
+
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let b = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+#[inline]
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|v| v as i16)
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let b = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[inline]
+fn read_f2dot14(data: &[u8], offset: usize) -> Option<f32> {
+    read_i16(data, offset).map(|v| v as f32 / 16384.0)
+}
@@ -0,0 +1,99 @@
+//! # Glyph Cache
+//!
+//! Cache de glifos rasterizados, indexado por `(fonte, tamanho, codepoint)`.
+//!
+//! Rasterizar um contorno custa muito mais que copiar sua cobertura; com o
+//! cache, cada glifo é rasterizado uma única vez por tamanho e os redraws
+//! seguintes apenas fazem blend da máscara já pronta.
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Número padrão de glifos mantidos em cache.
+pub const DEFAULT_GLYPH_CACHE_CAPACITY: usize = 1024;
+
+// =============================================================================
+// TIPOS
+// =============================================================================
+
+/// Chave de um glifo no cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GlyphKey {
+    /// Identificador da fonte.
+    pub font_id: u32,
+    /// Tamanho em pixels.
+    pub size: u16,
+    /// Codepoint Unicode.
+    pub codepoint: u32,
+}
+
+/// Glifo rasterizado (máscara de cobertura 0-255).
+#[derive(Debug, Clone, Default)]
+pub struct RasterGlyph {
+    /// Largura da máscara.
+    pub width: u32,
+    /// Altura da máscara.
+    pub height: u32,
+    /// Deslocamento X da máscara em relação à caneta.
+    pub left: i32,
+    /// Deslocamento Y da máscara em relação à linha de base (para baixo).
+    pub top: i32,
+    /// Avanço horizontal em pixels.
+    pub advance: u32,
+    /// Cobertura por pixel, `width * height` bytes.
+    pub coverage: Vec<u8>,
+}
+
+// =============================================================================
+// GLYPH CACHE
+// =============================================================================
+
+/// Cache compartilhado de glifos rasterizados.
+pub struct GlyphCache {
+    entries: RefCell<BTreeMap<GlyphKey, Rc<RasterGlyph>>>,
+    capacity: usize,
+}
+
+impl GlyphCache {
+    /// Cria cache com capacidade para `capacity` glifos.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: RefCell::new(BTreeMap::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Obtém o glifo de `key`, rasterizando com `rasterize` se ausente.
+    ///
+    /// Quando o cache enche ele é esvaziado por inteiro: o conjunto de
+    /// glifos da UI é pequeno e volta a ser populado em um frame.
+    pub fn get_or_insert_with(
+        &self,
+        key: GlyphKey,
+        rasterize: impl FnOnce() -> RasterGlyph,
+    ) -> Rc<RasterGlyph> {
+        if let Some(glyph) = self.entries.borrow().get(&key) {
+            return glyph.clone();
+        }
+
+        let glyph = Rc::new(rasterize());
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() >= self.capacity {
+            entries.clear();
+        }
+        entries.insert(key, glyph.clone());
+        glyph
+    }
+}
+
+impl Default for GlyphCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_GLYPH_CACHE_CAPACITY)
+    }
+}
@@ -0,0 +1,95 @@
+//! # Bits
+//!
+//! Leitor de bits do VP8L e do deflate: LSB primeiro.
+//!
+//! Ler além do fim devolve zeros e marca [`BitReader::overflowed`]; o
+//! decoder confere a flag ao terminar em vez de checar cada leitura.
+
+// =============================================================================
+// BIT READER
+// =============================================================================
+
+/// Leitor de bits LSB-first sobre um slice.
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    /// Próximo byte a carregar em `buffer`.
+    pos: usize,
+    /// Bits ainda não consumidos (o próximo é o bit 0).
+    buffer: u64,
+    /// Quantidade de bits válidos em `buffer`.
+    count: u32,
+    /// Alguma leitura passou do fim dos dados.
+    overflowed: bool,
+}
+
+impl<'a> BitReader<'a> {
+    /// Cria leitor no início de `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            buffer: 0,
+            count: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Garante pelo menos 32 bits em `buffer` (zeros após o fim).
+    #[inline]
+    fn refill(&mut self) {
+        while self.count <= 56 {
+            let byte = match self.data.get(self.pos) {
+                Some(&byte) => byte,
+                None if self.count >= 32 => return,
+                None => 0,
+            };
+            self.buffer |= (byte as u64) << self.count;
+            self.pos += 1;
+            self.count += 8;
+        }
+    }
+
+    /// Próximos `n` bits (até 32) sem consumir.
+    #[inline]
+    pub fn peek(&mut self, n: u32) -> u32 {
+        if self.count < n {
+            self.refill();
+        }
+        (self.buffer & ((1u64 << n) - 1)) as u32
+    }
+
+    /// Consome `n` bits já vistos com [`peek`](Self::peek).
+    #[inline]
+    pub fn consume(&mut self, n: u32) {
+        self.buffer >>= n;
+        self.count -= n;
+        if self.pos * 8 - self.count as usize > self.data.len() * 8 {
+            self.overflowed = true;
+        }
+    }
+
+    /// Lê `n` bits (até 32).
+    #[inline]
+    pub fn read(&mut self, n: u32) -> u32 {
+        let value = self.peek(n);
+        self.consume(n);
+        value
+    }
+
+    /// Lê um bit como booleano.
+    #[inline]
+    pub fn read_bit(&mut self) -> bool {
+        self.read(1) != 0
+    }
+
+    /// Descarta os bits até o próximo limite de byte.
+    #[inline]
+    pub fn align_to_byte(&mut self) {
+        self.consume(self.count % 8);
+    }
+
+    /// Retorna se alguma leitura passou do fim dos dados.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
@@ -0,0 +1,408 @@
+//! # BMP
+//!
+//! Decoder BMP sem compressão: 1, 4 e 8 bits com paleta, 16, 24 e 32 bits
+//! diretos, e máscaras de cor (`BI_BITFIELDS`).
+//!
+//! ```text
+//! "BM" | tamanho (4) | reservado (4) | offset dos pixels (4)
+//! cabeçalho DIB (12, 40, 52, 56, 108 ou 124 bytes) | máscaras? | paleta?
+//! pixels: linhas de baixo para cima (altura positiva), alinhadas a 4 bytes
+//! ```
+//!
+//! Compressões RLE, JPEG e PNG não são suportadas.
+
+use alloc::vec::Vec;
+
+use super::{alloc_pixels, Image, ImageError};
+use crate::render::pixel::premultiply;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Assinatura do arquivo.
+const MAGIC: &[u8; 2] = b"BM";
+
+/// Tamanho do cabeçalho de arquivo.
+const FILE_HEADER_SIZE: usize = 14;
+
+/// Cabeçalho `BITMAPCOREHEADER` (OS/2).
+const CORE_HEADER_SIZE: usize = 12;
+
+/// Cabeçalho `BITMAPINFOHEADER`.
+const INFO_HEADER_SIZE: usize = 40;
+
+/// Cabeçalhos a partir deste tamanho trazem a máscara de alpha.
+const ALPHA_MASK_HEADER_SIZE: usize = 56;
+
+/// Compressões.
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+// =============================================================================
+// MÁSCARAS
+// =============================================================================
+
+/// Um canal descrito por máscara de bits.
+#[derive(Clone, Copy)]
+struct Channel {
+    mask: u32,
+    shift: u32,
+    /// Maior valor do canal (0 = canal ausente).
+    max: u32,
+}
+
+impl Channel {
+    fn new(mask: u32) -> Self {
+        let shift = if mask == 0 { 0 } else { mask.trailing_zeros() };
+        let bits = (mask >> shift).trailing_ones();
+        Self {
+            mask,
+            shift,
+            max: ((1u64 << bits) - 1) as u32,
+        }
+    }
+
+    /// Valor do canal em 0-255 (`default` se ausente).
+    #[inline]
+    fn extract(&self, pixel: u32, default: u32) -> u32 {
+        if self.max == 0 {
+            return default;
+        }
+        let value = (pixel & self.mask) >> self.shift;
+        (value.min(self.max) as u64 * 255 / self.max as u64) as u32
+    }
+}
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Retorna se `data` tem a assinatura de um BMP.
+pub fn is_bmp(data: &[u8]) -> bool {
+    // "BM" sozinho é curto demais; exige um tamanho de cabeçalho conhecido
+    data.starts_with(MAGIC)
+        && matches!(
+            read_u32(data, FILE_HEADER_SIZE),
+            Some(12 | 40 | 52 | 56 | 64 | 108 | 124)
+        )
+}
+
+/// Decodifica um arquivo BMP completo.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if !is_bmp(data) {
+        return Err(ImageError::BadMagic);
+    }
+    let truncated = ImageError::Truncated;
+    let pixel_offset = read_u32(data, 10).ok_or(truncated)? as usize;
+    let header_size = read_u32(data, FILE_HEADER_SIZE).ok_or(truncated)? as usize;
+    let h = FILE_HEADER_SIZE;
+
+    let (width, height, bpp, compression, colors_used) = if header_size == CORE_HEADER_SIZE {
+        let width = read_u16(data, h + 4).ok_or(truncated)? as i32;
+        let height = read_u16(data, h + 6).ok_or(truncated)? as i32;
+        let bpp = read_u16(data, h + 10).ok_or(truncated)?;
+        (width, height, bpp, BI_RGB, 0)
+    } else {
+        let width = read_u32(data, h + 4).ok_or(truncated)? as i32;
+        let height = read_u32(data, h + 8).ok_or(truncated)? as i32;
+        let bpp = read_u16(data, h + 14).ok_or(truncated)?;
+        let compression = read_u32(data, h + 16).ok_or(truncated)?;
+        let colors_used = read_u32(data, h + 32).ok_or(truncated)? as usize;
+        (width, height, bpp, compression, colors_used)
+    };
+
+    if width <= 0 || height == 0 || height == i32::MIN {
+        return Err(ImageError::InvalidHeader);
+    }
+    let top_down = height < 0;
+    let (width, height) = (width as u32, height.unsigned_abs());
+    let mut pixels = alloc_pixels(width, height)?;
+
+    // Máscaras: dentro do cabeçalho (V2+) ou logo depois (INFO)
+    let mut tables = h + header_size;
+    let masks = match compression {
+        BI_RGB => match bpp {
+            16 => [0x7C00, 0x03E0, 0x001F, 0],
+            24 | 32 => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0],
+            _ => [0; 4],
+        },
+        BI_BITFIELDS | BI_ALPHABITFIELDS if matches!(bpp, 16 | 32) => {
+            let base = if header_size == INFO_HEADER_SIZE {
+                tables
+            } else {
+                h + INFO_HEADER_SIZE
+            };
+            let count = if compression == BI_ALPHABITFIELDS || header_size >= ALPHA_MASK_HEADER_SIZE
+            {
+                4
+            } else {
+                3
+            };
+            let mut masks = [0u32; 4];
+            for (i, mask) in masks.iter_mut().take(count).enumerate() {
+                *mask = read_u32(data, base + i * 4).ok_or(truncated)?;
+            }
+            if header_size == INFO_HEADER_SIZE {
+                tables += count * 4;
+            }
+            masks
+        }
+        BI_BITFIELDS | BI_ALPHABITFIELDS => return Err(ImageError::InvalidHeader),
+        _ => return Err(ImageError::Unsupported),
+    };
+    let channels = masks.map(Channel::new);
+
+    // Paleta (BGR0, ou BGR no cabeçalho OS/2)
+    let palette: Vec<u32> = if matches!(bpp, 1 | 4 | 8) {
+        let entry_size = if header_size == CORE_HEADER_SIZE {
+            3
+        } else {
+            4
+        };
+        let max_colors = 1usize << bpp;
+        let count = if colors_used == 0 {
+            max_colors
+        } else {
+            colors_used.min(max_colors)
+        };
+        let table = data
+            .get(tables..tables + count * entry_size)
+            .ok_or(truncated)?;
+        table
+            .chunks_exact(entry_size)
+            .map(|c| 0xFF00_0000 | (c[2] as u32) << 16 | (c[1] as u32) << 8 | c[0] as u32)
+            .collect()
+    } else if matches!(bpp, 16 | 24 | 32) {
+        Vec::new()
+    } else {
+        return Err(ImageError::InvalidHeader);
+    };
+
+    let stride = (width as usize * bpp as usize).div_ceil(32) * 4;
+    let size = stride * height as usize;
+    let rows = data
+        .get(pixel_offset..pixel_offset.saturating_add(size))
+        .ok_or(truncated)?;
+
+    for (i, row) in rows.chunks_exact(stride).enumerate() {
+        let y = if top_down { i } else { height as usize - 1 - i };
+        let out = &mut pixels[y * width as usize..][..width as usize];
+        for (x, px) in out.iter_mut().enumerate() {
+            let argb = match bpp {
+                1 | 4 | 8 => {
+                    let bits = bpp as usize;
+                    let bit = x * bits;
+                    let shift = 8 - bits - bit % 8;
+                    let index = (row[bit / 8] >> shift) as usize & ((1 << bits) - 1);
+                    // Índices fora da paleta viram preto opaco
+                    palette.get(index).copied().unwrap_or(0xFF00_0000)
+                }
+                24 => {
+                    let p = &row[x * 3..x * 3 + 3];
+                    0xFF00_0000 | (p[2] as u32) << 16 | (p[1] as u32) << 8 | p[0] as u32
+                }
+                _ => {
+                    let raw = if bpp == 16 {
+                        u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]) as u32
+                    } else {
+                        let p = &row[x * 4..x * 4 + 4];
+                        u32::from_le_bytes([p[0], p[1], p[2], p[3]])
+                    };
+                    let [r, g, b, a] = channels;
+                    a.extract(raw, 255) << 24
+                        | r.extract(raw, 0) << 16
+                        | g.extract(raw, 0) << 8
+                        | b.extract(raw, 0)
+                }
+            };
+            *px = premultiply(argb);
+        }
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Lê u32 little-endian.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Lê u16 little-endian.
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monta um BMP com cabeçalho `BITMAPINFOHEADER`, seguido de `tables`
+    /// (máscaras e/ou paleta) e das linhas `rows` (já alinhadas).
+    fn sample(
+        width: i32,
+        height: i32,
+        bpp: u16,
+        compression: u32,
+        tables: &[u8],
+        rows: &[u8],
+    ) -> Vec<u8> {
+        let offset = (FILE_HEADER_SIZE + INFO_HEADER_SIZE + tables.len()) as u32;
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(offset + rows.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&offset.to_le_bytes());
+
+        bytes.extend_from_slice(&(INFO_HEADER_SIZE as u32).to_le_bytes());
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&bpp.to_le_bytes());
+        bytes.extend_from_slice(&compression.to_le_bytes());
+        // Tamanho da imagem, resolução e cores usadas/importantes
+        bytes.extend_from_slice(&[0; 20]);
+
+        bytes.extend_from_slice(tables);
+        bytes.extend_from_slice(rows);
+        bytes
+    }
+
+    /// Paleta BGR0.
+    fn palette(colors: &[u32]) -> Vec<u8> {
+        colors.iter().flat_map(|c| c.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn decodes_24_bit_bottom_up() {
+        // Linhas de 6 bytes + 2 de alinhamento; a primeira é a de baixo
+        let rows = [
+            0x00, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0, 0, //
+            0xFF, 0x00, 0x00, 0x10, 0x20, 0x30, 0, 0,
+        ];
+        let image = decode(&sample(2, 2, 24, BI_RGB, &[], &rows)).unwrap();
+
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(
+            image.pixels,
+            [0xFF00_00FF, 0xFF30_2010, 0xFFFF_0000, 0xFF00_FF00]
+        );
+    }
+
+    #[test]
+    fn decodes_32_and_16_bit_top_down() {
+        // BI_RGB em 32 bits ignora o quarto byte
+        let rows = [0x01, 0x02, 0x03, 0x00, 0x04, 0x05, 0x06, 0x80];
+        let image = decode(&sample(1, -2, 32, BI_RGB, &[], &rows)).unwrap();
+        assert_eq!(image.pixels, [0xFF03_0201, 0xFF06_0504]);
+
+        // 5-5-5: vermelho cheio, depois azul cheio
+        let rows = [0x00, 0x7C, 0x1F, 0x00];
+        let image = decode(&sample(2, -1, 16, BI_RGB, &[], &rows)).unwrap();
+        assert_eq!(image.pixels, [0xFFFF_0000, 0xFF00_00FF]);
+    }
+
+    #[test]
+    fn decodes_bitfields() {
+        // 5-6-5
+        let masks = palette(&[0xF800, 0x07E0, 0x001F]);
+        let rows = [0xE0, 0x07, 0x00, 0x00];
+        let image = decode(&sample(1, 1, 16, BI_BITFIELDS, &masks, &rows)).unwrap();
+        assert_eq!(image.pixels, [0xFF00_FF00]);
+
+        // ABGR com alpha
+        let masks = palette(&[0x0000_00FF, 0x0000_FF00, 0x00FF_0000, 0xFF00_0000]);
+        let rows = 0x8030_2010u32.to_le_bytes();
+        let image = decode(&sample(1, 1, 32, BI_ALPHABITFIELDS, &masks, &rows)).unwrap();
+        assert_eq!(image.pixels, [premultiply(0x8010_2030)]);
+    }
+
+    #[test]
+    fn decodes_palettes() {
+        let colors = palette(&[0x00FF_0000, 0x0000_FF00, 0x0000_00FF]);
+        let (red, green, blue) = (0xFFFF_0000, 0xFF00_FF00, 0xFF00_00FF);
+
+        let rows = [0b0100_0000, 0, 0, 0];
+        let image = decode(&sample(3, 1, 1, BI_RGB, &colors[..8], &rows)).unwrap();
+        assert_eq!(image.pixels, [red, green, red]);
+
+        let mut table = palette(&[0; 16]);
+        table[..12].copy_from_slice(&colors);
+        let image = decode(&sample(3, 1, 4, BI_RGB, &table, &[0x21, 0x00, 0, 0])).unwrap();
+        assert_eq!(image.pixels, [blue, green, red]);
+
+        let mut table = palette(&[0; 256]);
+        table[..12].copy_from_slice(&colors);
+        let image = decode(&sample(2, 1, 8, BI_RGB, &table, &[2, 1, 0, 0])).unwrap();
+        assert_eq!(image.pixels, [blue, green]);
+    }
+
+    #[test]
+    fn decodes_core_header() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&((FILE_HEADER_SIZE + CORE_HEADER_SIZE) as u32).to_le_bytes());
+        bytes.extend_from_slice(&(CORE_HEADER_SIZE as u32).to_le_bytes());
+        for field in [1u16, 1, 1, 24] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0x30, 0x20, 0x10, 0]);
+
+        let image = decode(&bytes).unwrap();
+        assert_eq!(image.pixels, [0xFF10_2030]);
+    }
+
+    #[test]
+    fn truncated_files_fail() {
+        let bytes = sample(2, 2, 24, BI_RGB, &[], &[0x7F; 16]);
+        assert!(decode(&bytes).is_ok());
+        for len in 0..bytes.len() {
+            assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+        }
+
+        // Paleta cortada
+        let bytes = sample(1, 1, 8, BI_RGB, &palette(&[0; 4]), &[]);
+        assert_eq!(decode(&bytes).err(), Some(ImageError::Truncated));
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let error = |width, height, bpp, compression| {
+            decode(&sample(width, height, bpp, compression, &[], &[0; 64])).err()
+        };
+
+        assert_eq!(error(0, 1, 24, BI_RGB), Some(ImageError::InvalidHeader));
+        assert_eq!(error(1, 0, 24, BI_RGB), Some(ImageError::InvalidHeader));
+        assert_eq!(
+            error(1, i32::MIN, 24, BI_RGB),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(error(1, 1, 2, BI_RGB), Some(ImageError::InvalidHeader));
+        assert_eq!(
+            error(1, 1, 24, BI_BITFIELDS),
+            Some(ImageError::InvalidHeader)
+        );
+        // RLE8
+        assert_eq!(error(1, 1, 8, 1), Some(ImageError::Unsupported));
+
+        assert!(!is_bmp(b"BM"));
+        assert_eq!(
+            decode(b"BMxxxxxxxxxxxx\x07\0\0\0").err(),
+            Some(ImageError::BadMagic)
+        );
+    }
+}
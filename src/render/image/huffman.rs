@@ -0,0 +1,174 @@
+//! # Huffman
+//!
+//! Códigos de prefixo canônicos do VP8L e do deflate.
+//!
+//! Os códigos são atribuídos em ordem de comprimento e
+//! símbolo e gravados a partir do bit mais significativo. Códigos de até
+//! [`FAST_BITS`] bits saem de uma tabela indexada pelos próximos bits; os
+//! mais longos são decodificados bit a bit.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::bits::BitReader;
+use super::ImageError;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Maior comprimento de código.
+pub const MAX_CODE_LENGTH: usize = 15;
+
+/// Bits resolvidos pela tabela rápida.
+const FAST_BITS: u32 = 8;
+
+// =============================================================================
+// PREFIX CODE
+// =============================================================================
+
+/// Código de prefixo pronto para decodificar.
+pub struct PrefixCode {
+    /// `símbolo << 4 | comprimento` por combinação dos próximos
+    /// `FAST_BITS` bits; comprimento 0 = código mais longo.
+    fast: Vec<u16>,
+    /// Quantidade de códigos por comprimento.
+    counts: [u16; MAX_CODE_LENGTH + 1],
+    /// Símbolos ordenados por (comprimento, símbolo).
+    symbols: Vec<u16>,
+    /// Único símbolo do alfabeto.
+    single: Option<u16>,
+    /// Bits consumidos pelo símbolo único (0 no VP8L, 1 no deflate).
+    single_bits: u32,
+}
+
+impl PrefixCode {
+    /// Monta o código a partir do comprimento de cada símbolo.
+    ///
+    /// Códigos incompletos ou com excesso de códigos são rejeitados, exceto
+    /// o caso de um único símbolo.
+    pub fn new(lengths: &[u8]) -> Result<Self, ImageError> {
+        let mut counts = [0u16; MAX_CODE_LENGTH + 1];
+        for &len in lengths {
+            if len as usize > MAX_CODE_LENGTH {
+                return Err(ImageError::Corrupt);
+            }
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let used = counts.iter().map(|&c| c as usize).sum::<usize>();
+        if used == 0 {
+            return Err(ImageError::Corrupt);
+        }
+        if used == 1 {
+            let symbol = lengths.iter().position(|&len| len != 0).unwrap_or(0);
+            return Ok(Self {
+                fast: Vec::new(),
+                counts,
+                symbols: Vec::new(),
+                single: Some(symbol as u16),
+                single_bits: 0,
+            });
+        }
+
+        // Desigualdade de Kraft: o código tem que ser completo
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - count as i32;
+            if left < 0 {
+                return Err(ImageError::Corrupt);
+            }
+        }
+        if left != 0 {
+            return Err(ImageError::Corrupt);
+        }
+
+        let mut offsets = [0u16; MAX_CODE_LENGTH + 2];
+        for len in 1..=MAX_CODE_LENGTH {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; used];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+
+        // Códigos canônicos curtos, bits invertidos (o leitor é LSB-first)
+        let mut fast = vec![0u16; 1 << FAST_BITS];
+        let mut code = 0u32;
+        let mut index = 0;
+        for len in 1..=FAST_BITS {
+            for _ in 0..counts[len as usize] {
+                let entry = symbols[index] << 4 | len as u16;
+                let mut slot = code.reverse_bits() >> (32 - len);
+                while slot < 1 << FAST_BITS {
+                    fast[slot as usize] = entry;
+                    slot += 1 << len;
+                }
+                code += 1;
+                index += 1;
+            }
+            code <<= 1;
+        }
+
+        Ok(Self {
+            fast,
+            counts,
+            symbols,
+            single: None,
+            single_bits: 0,
+        })
+    }
+
+    /// Monta um código do deflate.
+    ///
+    /// Um único símbolo só é aceito com comprimento 1, e ocupa esse bit no
+    /// fluxo (o outro código de 1 bit fica inválido).
+    pub fn deflate(lengths: &[u8]) -> Result<Self, ImageError> {
+        let mut code = Self::new(lengths)?;
+        if let Some(symbol) = code.single {
+            if lengths[symbol as usize] != 1 {
+                return Err(ImageError::Corrupt);
+            }
+            code.single_bits = 1;
+        }
+        Ok(code)
+    }
+
+    /// Lê um símbolo.
+    #[inline]
+    pub fn read(&self, br: &mut BitReader) -> u16 {
+        if let Some(symbol) = self.single {
+            br.read(self.single_bits);
+            return symbol;
+        }
+
+        let bits = br.peek(MAX_CODE_LENGTH as u32);
+        let entry = self.fast[(bits & ((1 << FAST_BITS) - 1)) as usize];
+        if entry & 0xF != 0 {
+            br.consume((entry & 0xF) as u32);
+            return entry >> 4;
+        }
+
+        // Decodificação canônica bit a bit
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..=MAX_CODE_LENGTH {
+            code |= ((bits >> (len - 1)) & 1) as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                br.consume(len as u32);
+                return self.symbols[(index + code - first) as usize];
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        // Inalcançável com código completo
+        br.consume(MAX_CODE_LENGTH as u32);
+        0
+    }
+}
@@ -0,0 +1,363 @@
+//! # Inflate
+//!
+//! Descompressor zlib/deflate (RFC 1950 e 1951), usado pelo PNG.
+//!
+//! ```text
+//! CMF FLG | bloco* | adler32
+//! bloco: BFINAL (1) | BTYPE (2) | armazenado, códigos fixos ou dinâmicos
+//! ```
+//!
+//! A saída tem um limite conhecido por quem chama (o tamanho exato das
+//! linhas do PNG); passar dele é erro, e não realocação sem fim.
+
+use alloc::vec::Vec;
+
+use super::bits::BitReader;
+use super::huffman::PrefixCode;
+use super::ImageError;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Símbolos do alfabeto literal/comprimento (os 2 últimos não ocorrem).
+const NUM_LITERAL_CODES: usize = 288;
+
+/// Símbolos do alfabeto de distâncias (os 2 últimos não ocorrem).
+const NUM_DISTANCE_CODES: usize = 32;
+
+/// Fim de bloco.
+const END_OF_BLOCK: u16 = 256;
+
+/// Ordem dos comprimentos do código de comprimentos.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Comprimento base dos símbolos 257..285.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+
+/// Bits extras dos símbolos de comprimento.
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Distância base dos símbolos 0..29.
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+/// Bits extras dos símbolos de distância.
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// =============================================================================
+// ZLIB
+// =============================================================================
+
+/// Descomprime um fluxo zlib com no máximo `max_output` bytes de saída.
+///
+/// O checksum adler32 não é conferido: as linhas do PNG já são validadas
+/// pelo tamanho e pelos bytes de filtro.
+pub fn zlib_decompress(data: &[u8], max_output: usize) -> Result<Vec<u8>, ImageError> {
+    if data.len() < 2 {
+        return Err(ImageError::Truncated);
+    }
+    let (cmf, flg) = (data[0], data[1]);
+    let method = cmf & 0x0F;
+    let window_bits = (cmf >> 4) + 8;
+    let preset_dictionary = flg & 0x20 != 0;
+    if method != 8 || window_bits > 15 || !(cmf as u16 * 256 + flg as u16).is_multiple_of(31) {
+        return Err(ImageError::InvalidHeader);
+    }
+    if preset_dictionary {
+        return Err(ImageError::Unsupported);
+    }
+
+    inflate(&data[2..], max_output)
+}
+
+/// Descomprime um fluxo deflate cru.
+pub fn inflate(data: &[u8], max_output: usize) -> Result<Vec<u8>, ImageError> {
+    let mut br = BitReader::new(data);
+    // Reserva pelo tamanho comprimido, não pelo limite: um cabeçalho
+    // mentiroso não deve alocar a imagem inteira de uma vez
+    let mut out = Vec::with_capacity(max_output.min(data.len().saturating_mul(4)));
+
+    loop {
+        let last = br.read_bit();
+        match br.read(2) {
+            0 => stored_block(&mut br, &mut out, max_output)?,
+            1 => {
+                let (literals, distances) = fixed_codes()?;
+                compressed_block(&mut br, &mut out, max_output, &literals, Some(&distances))?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut br)?;
+                compressed_block(&mut br, &mut out, max_output, &literals, distances.as_ref())?;
+            }
+            _ => return Err(ImageError::Corrupt),
+        }
+
+        if br.overflowed() {
+            return Err(ImageError::Truncated);
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+// =============================================================================
+// BLOCOS
+// =============================================================================
+
+/// Bloco armazenado: `LEN`, `NLEN` e os bytes, alinhados a byte.
+fn stored_block(br: &mut BitReader, out: &mut Vec<u8>, max: usize) -> Result<(), ImageError> {
+    br.align_to_byte();
+    let len = br.read(16) as usize;
+    let nlen = br.read(16) as usize;
+    if len != !nlen & 0xFFFF {
+        return Err(ImageError::Corrupt);
+    }
+    if out.len() + len > max {
+        return Err(ImageError::Corrupt);
+    }
+    for _ in 0..len {
+        out.push(br.read(8) as u8);
+    }
+    Ok(())
+}
+
+/// Bloco comprimido: literais e pares (comprimento, distância).
+fn compressed_block(
+    br: &mut BitReader,
+    out: &mut Vec<u8>,
+    max: usize,
+    literals: &PrefixCode,
+    distances: Option<&PrefixCode>,
+) -> Result<(), ImageError> {
+    loop {
+        let symbol = literals.read(br);
+        if br.overflowed() {
+            return Err(ImageError::Truncated);
+        }
+
+        match symbol {
+            0..=255 => {
+                if out.len() == max {
+                    return Err(ImageError::Corrupt);
+                }
+                out.push(symbol as u8);
+            }
+            END_OF_BLOCK => return Ok(()),
+            _ => {
+                let index = (symbol - 257) as usize;
+                if index >= LENGTH_BASE.len() {
+                    return Err(ImageError::Corrupt);
+                }
+                let length =
+                    LENGTH_BASE[index] as usize + br.read(LENGTH_EXTRA[index] as u32) as usize;
+
+                let distances = distances.ok_or(ImageError::Corrupt)?;
+                let code = distances.read(br) as usize;
+                if code >= DISTANCE_BASE.len() {
+                    return Err(ImageError::Corrupt);
+                }
+                let distance =
+                    DISTANCE_BASE[code] as usize + br.read(DISTANCE_EXTRA[code] as u32) as usize;
+
+                if distance > out.len() || out.len() + length > max {
+                    return Err(ImageError::Corrupt);
+                }
+                // Cópia byte a byte: a origem pode sobrepor o destino
+                let start = out.len() - distance;
+                for i in 0..length {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// CÓDIGOS
+// =============================================================================
+
+/// Códigos fixos (BTYPE = 1).
+fn fixed_codes() -> Result<(PrefixCode, PrefixCode), ImageError> {
+    let mut lengths = [0u8; NUM_LITERAL_CODES];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    let literals = PrefixCode::deflate(&lengths)?;
+    let distances = PrefixCode::deflate(&[5; NUM_DISTANCE_CODES])?;
+    Ok((literals, distances))
+}
+
+/// Códigos dinâmicos (BTYPE = 2), descritos no início do bloco.
+///
+/// Sem nenhum código de distância, o bloco só pode ter literais.
+fn dynamic_codes(br: &mut BitReader) -> Result<(PrefixCode, Option<PrefixCode>), ImageError> {
+    let num_literals = br.read(5) as usize + 257;
+    let num_distances = br.read(5) as usize + 1;
+    let num_code_lengths = br.read(4) as usize + 4;
+    if num_literals > 286 || num_distances > 30 {
+        return Err(ImageError::Corrupt);
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..num_code_lengths] {
+        code_lengths[index] = br.read(3) as u8;
+    }
+    let code_length_code = PrefixCode::deflate(&code_lengths)?;
+
+    // Literais e distâncias formam uma sequência só (repetições podem
+    // atravessar de um alfabeto para o outro)
+    let total = num_literals + num_distances;
+    let mut lengths = [0u8; NUM_LITERAL_CODES + NUM_DISTANCE_CODES];
+    let mut i = 0;
+    while i < total {
+        let symbol = code_length_code.read(br);
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths[..i].last().ok_or(ImageError::Corrupt)?;
+                (previous, 3 + br.read(2) as usize)
+            }
+            17 => (0, 3 + br.read(3) as usize),
+            _ => (0, 11 + br.read(7) as usize),
+        };
+        if i + repeat > total || br.overflowed() {
+            return Err(ImageError::Corrupt);
+        }
+        lengths[i..i + repeat].fill(value);
+        i += repeat;
+    }
+
+    let (literal_lengths, distance_lengths) = lengths[..total].split_at(num_literals);
+    if literal_lengths[END_OF_BLOCK as usize] == 0 {
+        return Err(ImageError::Corrupt);
+    }
+    let literals = PrefixCode::deflate(literal_lengths)?;
+    let distances = if distance_lengths.iter().all(|&len| len == 0) {
+        None
+    } else {
+        Some(PrefixCode::deflate(distance_lengths)?)
+    };
+    Ok((literals, distances))
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "abcabcabcabc" comprimido pelo zlib (bloco com códigos fixos e uma
+    /// cópia de distância 3).
+    const FIXED: [u8; 13] = [
+        0x78, 0xDA, 0x4B, 0x4C, 0x4A, 0x4E, 0x84, 0x21, 0x00, 0x1D, 0xE0, 0x04, 0x99,
+    ];
+
+    /// Texto de `DYNAMIC_TEXT` repetido duas vezes, comprimido pelo zlib
+    /// (bloco com códigos dinâmicos).
+    const DYNAMIC: [u8; 105] = [
+        0x78, 0xDA, 0xBD, 0xCD, 0xC1, 0x0D, 0xC2, 0x30, 0x0C, 0x05, 0xD0, 0x55, 0xFE, 0x00, 0x51,
+        0x07, 0x60, 0x09, 0xA4, 0x76, 0x02, 0x43, 0xDC, 0xD4, 0xAA, 0x71, 0x43, 0xBE, 0xAB, 0x8A,
+        0xED, 0x11, 0x4B, 0x70, 0x7B, 0xB7, 0x37, 0x6B, 0x65, 0x1E, 0xA1, 0xB8, 0x2F, 0x37, 0xAC,
+        0x36, 0x74, 0xF5, 0x0F, 0xB8, 0xA9, 0x7B, 0x41, 0x0A, 0xF7, 0x87, 0x8C, 0x02, 0xA6, 0x8C,
+        0xC4, 0x4B, 0xE3, 0x2C, 0x78, 0x9F, 0xF6, 0xDC, 0x41, 0xCD, 0xB4, 0x68, 0x2C, 0xB8, 0xAC,
+        0x36, 0xCD, 0x1F, 0xC4, 0xBD, 0x4B, 0xD7, 0x01, 0xBA, 0x55, 0xE5, 0x76, 0x5C, 0x90, 0xA8,
+        0x68, 0x2E, 0x24, 0xBA, 0x84, 0x3A, 0x27, 0xCC, 0x7F, 0xFE, 0xBE, 0xD2, 0x65, 0x51, 0x9B,
+    ];
+
+    const DYNAMIC_TEXT: &str = "Redstone OS: firefly shell, taskbar, start menu, quick settings, \
+                                widgets, wallpaper slideshow and glass panels. ";
+
+    /// Fluxo zlib com um único bloco armazenado (adler32 zerado).
+    fn stored(data: &[u8]) -> Vec<u8> {
+        let len = data.len() as u16;
+        let mut bytes = alloc::vec![0x78, 0x01, 0x01];
+        bytes.extend_from_slice(&len.to_le_bytes());
+        bytes.extend_from_slice(&(!len).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(&[0; 4]);
+        bytes
+    }
+
+    #[test]
+    fn decodes_every_block_type() {
+        assert_eq!(zlib_decompress(&stored(b"abc"), 3).unwrap(), b"abc");
+        assert_eq!(zlib_decompress(&FIXED, 12).unwrap(), b"abcabcabcabc");
+
+        let expected = DYNAMIC_TEXT.repeat(2);
+        let out = zlib_decompress(&DYNAMIC, expected.len()).unwrap();
+        assert_eq!(out, expected.as_bytes());
+    }
+
+    #[test]
+    fn output_limit_is_an_error() {
+        assert_eq!(
+            zlib_decompress(&stored(b"abc"), 2),
+            Err(ImageError::Corrupt)
+        );
+        assert_eq!(zlib_decompress(&FIXED, 11), Err(ImageError::Corrupt));
+    }
+
+    #[test]
+    fn truncated_streams_fail() {
+        // O adler32 final não é conferido
+        for stream in [&FIXED[..], &DYNAMIC[..], &stored(b"abcdef")[..]] {
+            for len in 0..stream.len() - 4 {
+                assert!(
+                    zlib_decompress(&stream[..len], 1024).is_err(),
+                    "{} bytes",
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_corrupt_streams() {
+        // Checksum do cabeçalho e método
+        assert_eq!(
+            zlib_decompress(&[0x78, 0x00, 0x03, 0x00], 16),
+            Err(ImageError::InvalidHeader)
+        );
+        assert_eq!(
+            zlib_decompress(&[0x79, 0xD9, 0x03, 0x00], 16),
+            Err(ImageError::InvalidHeader)
+        );
+        // Dicionário predefinido
+        assert_eq!(
+            zlib_decompress(&[0x78, 0xBB, 0x03, 0x00], 16),
+            Err(ImageError::Unsupported)
+        );
+
+        // BTYPE = 3
+        assert_eq!(inflate(&[0x07], 16), Err(ImageError::Corrupt));
+
+        // LEN e NLEN que não se complementam
+        let mut bytes = stored(b"abc");
+        bytes[5] ^= 0x01;
+        assert_eq!(zlib_decompress(&bytes, 16), Err(ImageError::Corrupt));
+
+        // Códigos fixos: literal 'a' seguido de cópia de distância 2
+        // (maior que a saída)
+        assert_eq!(
+            inflate(&[0x4B, 0x04, 0x42, 0x00], 16),
+            Err(ImageError::Corrupt)
+        );
+    }
+}
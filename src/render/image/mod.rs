@@ -0,0 +1,186 @@
+//! # Imagens
+//!
+//! Decoders de imagem sem `std`, com um tipo de saída comum.
+//!
+//! ## Formatos
+//!
+//! - **PNG**: todos os tipos de cor e profundidades, Adam7 (`.png`)
+//! - **QOI**: Quite OK Image (`.qoi`)
+//! - **BMP**: sem compressão, com paleta ou bitfields (`.bmp`)
+//! - **WebP**: sem perdas, com perdas e alpha (`.webp`)
+//! - **SVG**: subconjunto vetorial para ícones (`.svg`)
+//!
+//! O formato é detectado pela assinatura, não pela extensão. Os decoders
+//! operam apenas sobre `&[u8]` e nunca entram em pânico com dados
+//! inválidos: qualquer inconsistência vira um [`ImageError`], e quem chama
+//! decide o fallback.
+//!
+//! ## Saída
+//!
+//! [`Image::pixels`] sai em ARGB pré-multiplicado, o formato do
+//! [`Canvas`](super::Canvas), pronto para `blit`. [`scale`] redimensiona
+//! nesse mesmo formato.
+//!
+//! Para ícones, [`decode_sized`] entrega a imagem já no tamanho de exibição:
+//! SVG é rasterizado direto nele, e imagens raster são escaladas.
+
+pub mod bmp;
+pub mod png;
+pub mod qoi;
+pub mod scale;
+pub mod svg;
+pub mod webp;
+
+mod bits;
+mod huffman;
+mod inflate;
+
+use alloc::vec::Vec;
+
+use crate::util::fs::{self, ReadError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Maior imagem aceita (pixels), para limitar a alocação.
+pub const MAX_IMAGE_PIXELS: usize = 4096 * 4096;
+
+/// Maior arquivo de imagem aceito por [`load`].
+pub const MAX_IMAGE_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+// =============================================================================
+// ERROS
+// =============================================================================
+
+/// Erro ao carregar ou decodificar uma imagem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageError {
+    /// Falha ao abrir ou ler o arquivo.
+    Io,
+    /// Assinatura não reconhecida.
+    BadMagic,
+    /// Dados terminam antes do esperado.
+    Truncated,
+    /// Cabeçalho com valores inválidos.
+    InvalidHeader,
+    /// Recurso válido, mas não suportado (animação, compressão de BMP...).
+    Unsupported,
+    /// Arquivo acima de [`MAX_IMAGE_FILE_SIZE`] ou imagem acima de
+    /// [`MAX_IMAGE_PIXELS`].
+    TooLarge,
+    /// Fluxo comprimido inconsistente.
+    Corrupt,
+}
+
+// =============================================================================
+// IMAGEM
+// =============================================================================
+
+/// Formato de arquivo de imagem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Qoi,
+    Bmp,
+    Webp,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Detecta o formato pela assinatura.
+    pub fn sniff(data: &[u8]) -> Option<Self> {
+        if png::is_png(data) {
+            Some(Self::Png)
+        } else if qoi::is_qoi(data) {
+            Some(Self::Qoi)
+        } else if bmp::is_bmp(data) {
+            Some(Self::Bmp)
+        } else if webp::is_webp(data) {
+            Some(Self::Webp)
+        } else if svg::is_svg(data) {
+            Some(Self::Svg)
+        } else {
+            None
+        }
+    }
+}
+
+/// Imagem decodificada.
+pub struct Image {
+    /// Largura em pixels.
+    pub width: u32,
+    /// Altura em pixels.
+    pub height: u32,
+    /// Pixels ARGB pré-multiplicados, linha a linha.
+    pub pixels: Vec<u32>,
+}
+
+/// Decodifica uma imagem em qualquer formato suportado.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    match ImageFormat::sniff(data) {
+        Some(ImageFormat::Png) => png::decode(data),
+        Some(ImageFormat::Qoi) => qoi::decode(data),
+        Some(ImageFormat::Bmp) => bmp::decode(data),
+        Some(ImageFormat::Webp) => webp::decode(data),
+        Some(ImageFormat::Svg) => svg::decode(data),
+        None => Err(ImageError::BadMagic),
+    }
+}
+
+/// Decodifica uma imagem para caber em `width` x `height`.
+///
+/// SVG sai exatamente nesse tamanho (conteúdo centralizado); imagens
+/// raster são escaladas mantendo a proporção, se já não couberem exatas.
+pub fn decode_sized(data: &[u8], width: u32, height: u32) -> Result<Image, ImageError> {
+    if ImageFormat::sniff(data) == Some(ImageFormat::Svg) {
+        return svg::render(data, width, height);
+    }
+
+    let image = decode(data)?;
+    let (iw, ih) = (image.width as u64, image.height as u64);
+    let (w, h) = (width as u64, height as u64);
+    let (fit_w, fit_h) = if iw * h > ih * w {
+        (w, (ih * w / iw).max(1))
+    } else {
+        ((iw * h / ih).max(1), h)
+    };
+    if (fit_w, fit_h) == (iw, ih) {
+        return Ok(image);
+    }
+    scale::resize(&image, fit_w as u32, fit_h as u32)
+}
+
+/// Lê e decodifica um arquivo de imagem.
+pub fn load(path: &str) -> Result<Image, ImageError> {
+    decode(&read_file(path)?)
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Valida dimensões e retorna a quantidade de pixels.
+fn pixel_count(width: u32, height: u32) -> Result<usize, ImageError> {
+    if width == 0 || height == 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+    let count = width as usize * height as usize;
+    if count > MAX_IMAGE_PIXELS {
+        return Err(ImageError::TooLarge);
+    }
+    Ok(count)
+}
+
+/// Valida dimensões e aloca o buffer de pixels.
+fn alloc_pixels(width: u32, height: u32) -> Result<Vec<u32>, ImageError> {
+    Ok(alloc::vec![0u32; pixel_count(width, height)?])
+}
+
+/// Lê um arquivo inteiro.
+fn read_file(path: &str) -> Result<Vec<u8>, ImageError> {
+    fs::read_file(path, MAX_IMAGE_FILE_SIZE).map_err(|e| match e {
+        ReadError::TooLarge => ImageError::TooLarge,
+        ReadError::Open | ReadError::Read => ImageError::Io,
+    })
+}
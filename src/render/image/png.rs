@@ -0,0 +1,766 @@
+//! # PNG
+//!
+//! Decoder PNG: todos os tipos de cor e profundidades, transparência
+//! (`tRNS`) e entrelaçamento Adam7.
+//!
+//! ```text
+//! 89 50 4E 47 0D 0A 1A 0A
+//! IHDR | PLTE? | tRNS? | IDAT+ | IEND   (outros chunks ignorados)
+//! ```
+//!
+//! Os `IDAT` formam um único fluxo zlib com as linhas filtradas, cada uma
+//! precedida do tipo de filtro. Amostras de 16 bits são reduzidas ao byte
+//! alto. CRCs não são conferidos.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::inflate::zlib_decompress;
+use super::{alloc_pixels, pixel_count, Image, ImageError};
+use crate::render::pixel::premultiply;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Assinatura do arquivo.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Tipos de cor.
+const COLOR_GRAY: u8 = 0;
+const COLOR_RGB: u8 = 2;
+const COLOR_INDEXED: u8 = 3;
+const COLOR_GRAY_ALPHA: u8 = 4;
+const COLOR_RGBA: u8 = 6;
+
+/// Passes do Adam7: (x inicial, y inicial, passo x, passo y).
+const ADAM7: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+// =============================================================================
+// CABEÇALHO
+// =============================================================================
+
+/// Campos do `IHDR` e tabelas auxiliares.
+struct Header {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: u8,
+    interlaced: bool,
+    /// Paleta (ARGB), com o alpha do `tRNS`.
+    palette: Vec<u32>,
+    /// Cor transparente (tons de cinza ou RGB, na profundidade original).
+    transparent: Option<[u16; 3]>,
+}
+
+impl Header {
+    /// Amostras por pixel.
+    fn channels(&self) -> usize {
+        match self.color_type {
+            COLOR_RGB => 3,
+            COLOR_GRAY_ALPHA => 2,
+            COLOR_RGBA => 4,
+            _ => 1,
+        }
+    }
+
+    /// Bits por pixel.
+    fn bits_per_pixel(&self) -> usize {
+        self.channels() * self.bit_depth as usize
+    }
+
+    /// Bytes de uma linha filtrada de `width` pixels (sem o byte de filtro).
+    fn row_bytes(&self, width: usize) -> usize {
+        (width * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+/// Retorna se `data` tem a assinatura de um PNG.
+pub fn is_png(data: &[u8]) -> bool {
+    data.starts_with(&SIGNATURE)
+}
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Decodifica um arquivo PNG completo.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if !is_png(data) {
+        return Err(ImageError::BadMagic);
+    }
+
+    let mut header: Option<Header> = None;
+    let mut compressed = Vec::new();
+    let mut pos = SIGNATURE.len();
+    loop {
+        let length = read_u32(data, pos).ok_or(ImageError::Truncated)? as usize;
+        let kind = data.get(pos + 4..pos + 8).ok_or(ImageError::Truncated)?;
+        let body = data
+            .get(pos + 8..(pos + 8).saturating_add(length))
+            .ok_or(ImageError::Truncated)?;
+        // Corpo + CRC
+        pos += 12 + length;
+
+        match kind {
+            b"IHDR" => header = Some(parse_header(body)?),
+            b"PLTE" => {
+                let header = header.as_mut().ok_or(ImageError::InvalidHeader)?;
+                if body.len() % 3 != 0 || body.len() > 256 * 3 {
+                    return Err(ImageError::InvalidHeader);
+                }
+                header.palette = body
+                    .chunks_exact(3)
+                    .map(|c| 0xFF00_0000 | (c[0] as u32) << 16 | (c[1] as u32) << 8 | c[2] as u32)
+                    .collect();
+            }
+            b"tRNS" => {
+                let header = header.as_mut().ok_or(ImageError::InvalidHeader)?;
+                parse_transparency(header, body)?;
+            }
+            b"IDAT" => {
+                if header.is_none() {
+                    return Err(ImageError::InvalidHeader);
+                }
+                compressed.extend_from_slice(body);
+            }
+            b"IEND" => break,
+            // Chunks críticos desconhecidos (primeira letra maiúscula)
+            // mudam a interpretação da imagem
+            _ if kind[0].is_ascii_uppercase() => return Err(ImageError::Unsupported),
+            _ => {}
+        }
+    }
+
+    let header = header.ok_or(ImageError::InvalidHeader)?;
+    if header.color_type == COLOR_INDEXED && header.palette.is_empty() {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let (width, height) = (header.width as usize, header.height as usize);
+    let raw_size = if header.interlaced {
+        ADAM7
+            .iter()
+            .map(|&pass| {
+                let (pw, ph) = pass_size(pass, width, height);
+                if pw == 0 {
+                    0
+                } else {
+                    ph * (1 + header.row_bytes(pw))
+                }
+            })
+            .sum()
+    } else {
+        height * (1 + header.row_bytes(width))
+    };
+    let mut raw = zlib_decompress(&compressed, raw_size)?;
+    if raw.len() != raw_size {
+        return Err(ImageError::Truncated);
+    }
+
+    let mut pixels = alloc_pixels(header.width, header.height)?;
+    if header.interlaced {
+        let mut offset = 0;
+        for pass in ADAM7 {
+            let (pw, ph) = pass_size(pass, width, height);
+            if pw == 0 || ph == 0 {
+                continue;
+            }
+            let size = ph * (1 + header.row_bytes(pw));
+            let (x0, y0, dx, dy) = pass;
+            decode_rows(
+                &header,
+                &mut raw[offset..offset + size],
+                pw,
+                |x, y, argb| {
+                    pixels[(y0 + y * dy) * width + x0 + x * dx] = argb;
+                },
+            )?;
+            offset += size;
+        }
+    } else {
+        decode_rows(&header, &mut raw, width, |x, y, argb| {
+            pixels[y * width + x] = argb;
+        })?;
+    }
+
+    for px in pixels.iter_mut() {
+        *px = premultiply(*px);
+    }
+    Ok(Image {
+        width: header.width,
+        height: header.height,
+        pixels,
+    })
+}
+
+/// Lê e valida o `IHDR`.
+fn parse_header(body: &[u8]) -> Result<Header, ImageError> {
+    if body.len() < 13 {
+        return Err(ImageError::Truncated);
+    }
+    let width = read_u32(body, 0).ok_or(ImageError::Truncated)?;
+    let height = read_u32(body, 4).ok_or(ImageError::Truncated)?;
+    let (bit_depth, color_type) = (body[8], body[9]);
+    let (compression, filter, interlace) = (body[10], body[11], body[12]);
+
+    let depth_ok = match color_type {
+        COLOR_GRAY => matches!(bit_depth, 1 | 2 | 4 | 8 | 16),
+        COLOR_INDEXED => matches!(bit_depth, 1 | 2 | 4 | 8),
+        COLOR_RGB | COLOR_GRAY_ALPHA | COLOR_RGBA => matches!(bit_depth, 8 | 16),
+        _ => false,
+    };
+    if !depth_ok || compression != 0 || filter != 0 || interlace > 1 {
+        return Err(ImageError::InvalidHeader);
+    }
+    pixel_count(width, height)?;
+
+    Ok(Header {
+        width,
+        height,
+        bit_depth,
+        color_type,
+        interlaced: interlace == 1,
+        palette: Vec::new(),
+        transparent: None,
+    })
+}
+
+/// Aplica o `tRNS`: alpha da paleta ou cor transparente.
+fn parse_transparency(header: &mut Header, body: &[u8]) -> Result<(), ImageError> {
+    match header.color_type {
+        COLOR_INDEXED => {
+            if body.len() > header.palette.len() {
+                return Err(ImageError::InvalidHeader);
+            }
+            for (color, &alpha) in header.palette.iter_mut().zip(body) {
+                *color = (*color & 0x00FF_FFFF) | (alpha as u32) << 24;
+            }
+        }
+        COLOR_GRAY => {
+            let gray = read_u16(body, 0).ok_or(ImageError::Truncated)?;
+            header.transparent = Some([gray, gray, gray]);
+        }
+        COLOR_RGB => {
+            let r = read_u16(body, 0).ok_or(ImageError::Truncated)?;
+            let g = read_u16(body, 2).ok_or(ImageError::Truncated)?;
+            let b = read_u16(body, 4).ok_or(ImageError::Truncated)?;
+            header.transparent = Some([r, g, b]);
+        }
+        // Tipos com alpha não podem ter tRNS
+        _ => return Err(ImageError::InvalidHeader),
+    }
+    Ok(())
+}
+
+/// Tamanho de um passe do Adam7.
+fn pass_size(pass: (usize, usize, usize, usize), width: usize, height: usize) -> (usize, usize) {
+    let (x0, y0, dx, dy) = pass;
+    let w = if width > x0 {
+        (width - x0).div_ceil(dx)
+    } else {
+        0
+    };
+    let h = if height > y0 {
+        (height - y0).div_ceil(dy)
+    } else {
+        0
+    };
+    (w, h)
+}
+
+// =============================================================================
+// LINHAS
+// =============================================================================
+
+/// Desfaz os filtros de uma sequência de linhas de `width` pixels e
+/// entrega cada pixel (ARGB não pré-multiplicado) a `put`.
+fn decode_rows(
+    header: &Header,
+    raw: &mut [u8],
+    width: usize,
+    mut put: impl FnMut(usize, usize, u32),
+) -> Result<(), ImageError> {
+    let stride = header.row_bytes(width);
+    // Distância até o byte correspondente do pixel anterior
+    let bpp = header.bits_per_pixel().div_ceil(8);
+    let mut previous = vec![0u8; stride];
+
+    for (y, line) in raw.chunks_exact_mut(stride + 1).enumerate() {
+        let (filter, row) = line.split_first_mut().ok_or(ImageError::Truncated)?;
+        unfilter(*filter, row, &previous, bpp)?;
+        for x in 0..width {
+            put(x, y, read_pixel(header, row, x));
+        }
+        previous.copy_from_slice(row);
+    }
+    Ok(())
+}
+
+/// Desfaz o filtro de uma linha, no lugar.
+fn unfilter(filter: u8, row: &mut [u8], previous: &[u8], bpp: usize) -> Result<(), ImageError> {
+    match filter {
+        0 => {}
+        // Sub
+        1 => {
+            for i in bpp..row.len() {
+                row[i] = row[i].wrapping_add(row[i - bpp]);
+            }
+        }
+        // Up
+        2 => {
+            for (value, &up) in row.iter_mut().zip(previous) {
+                *value = value.wrapping_add(up);
+            }
+        }
+        // Average
+        3 => {
+            for i in 0..row.len() {
+                let left = if i >= bpp { row[i - bpp] as u16 } else { 0 };
+                row[i] = row[i].wrapping_add(((left + previous[i] as u16) / 2) as u8);
+            }
+        }
+        // Paeth
+        4 => {
+            for i in 0..row.len() {
+                let (left, up_left) = if i >= bpp {
+                    (row[i - bpp], previous[i - bpp])
+                } else {
+                    (0, 0)
+                };
+                row[i] = row[i].wrapping_add(paeth(left, previous[i], up_left));
+            }
+        }
+        _ => return Err(ImageError::Corrupt),
+    }
+    Ok(())
+}
+
+/// Preditor de Paeth: o vizinho mais próximo de `a + b - c`.
+#[inline]
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Lê o pixel `x` de uma linha já sem filtro.
+fn read_pixel(header: &Header, row: &[u8], x: usize) -> u32 {
+    let depth = header.bit_depth as usize;
+    // Amostra `i` do pixel, na profundidade original
+    let sample = |i: usize| -> u16 {
+        let index = x * header.channels() + i;
+        match depth {
+            16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+            8 => row[index] as u16,
+            _ => {
+                let bit = index * depth;
+                let shift = 8 - depth - bit % 8;
+                ((row[bit / 8] >> shift) & ((1 << depth) - 1)) as u16
+            }
+        }
+    };
+    // Reduz uma amostra a 8 bits
+    let to_u8 = |value: u16| -> u32 {
+        match depth {
+            16 => (value >> 8) as u32,
+            8 => value as u32,
+            _ => value as u32 * 255 / ((1 << depth) - 1),
+        }
+    };
+    let argb = |a: u32, r: u32, g: u32, b: u32| a << 24 | r << 16 | g << 8 | b;
+
+    match header.color_type {
+        COLOR_GRAY => {
+            let gray = sample(0);
+            let alpha = if header.transparent == Some([gray; 3]) {
+                0
+            } else {
+                255
+            };
+            let v = to_u8(gray);
+            argb(alpha, v, v, v)
+        }
+        COLOR_RGB => {
+            let rgb = [sample(0), sample(1), sample(2)];
+            let alpha = if header.transparent == Some(rgb) {
+                0
+            } else {
+                255
+            };
+            argb(alpha, to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]))
+        }
+        COLOR_INDEXED => {
+            // Índices fora da paleta viram preto transparente
+            header.palette.get(sample(0) as usize).copied().unwrap_or(0)
+        }
+        COLOR_GRAY_ALPHA => {
+            let v = to_u8(sample(0));
+            argb(to_u8(sample(1)), v, v, v)
+        }
+        _ => argb(
+            to_u8(sample(3)),
+            to_u8(sample(0)),
+            to_u8(sample(1)),
+            to_u8(sample(2)),
+        ),
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Lê u32 big-endian.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Lê u16 big-endian.
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fluxo zlib com blocos armazenados (adler32 zerado).
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x78, 0x01];
+        let mut chunks = data.chunks(0xFFFF).peekable();
+        if chunks.peek().is_none() {
+            bytes.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+        }
+        while let Some(chunk) = chunks.next() {
+            let len = chunk.len() as u16;
+            bytes.push(chunks.peek().is_none() as u8);
+            bytes.extend_from_slice(&len.to_le_bytes());
+            bytes.extend_from_slice(&(!len).to_le_bytes());
+            bytes.extend_from_slice(chunk);
+        }
+        bytes.extend_from_slice(&[0; 4]);
+        bytes
+    }
+
+    /// Acrescenta um chunk (CRC zerado: não é conferido).
+    fn chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+        bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(kind);
+        bytes.extend_from_slice(body);
+        bytes.extend_from_slice(&[0; 4]);
+    }
+
+    /// Corpo do `IHDR`.
+    fn ihdr(width: u32, height: u32, depth: u8, color_type: u8, interlace: u8) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&width.to_be_bytes());
+        body.extend_from_slice(&height.to_be_bytes());
+        body.extend_from_slice(&[depth, color_type, 0, 0, interlace]);
+        body
+    }
+
+    /// Monta um PNG com `chunks` entre o `IHDR` e o `IDAT` de `raw`
+    /// (linhas já com o byte de filtro).
+    fn sample(header: &[u8], chunks: &[(&[u8; 4], &[u8])], raw: &[u8]) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        chunk(&mut bytes, b"IHDR", header);
+        for (kind, body) in chunks {
+            chunk(&mut bytes, kind, body);
+        }
+        chunk(&mut bytes, b"IDAT", &zlib_stored(raw));
+        chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// Linhas sem filtro.
+    fn unfiltered(rows: &[&[u8]]) -> Vec<u8> {
+        rows.iter()
+            .flat_map(|row| [&[0][..], row].concat())
+            .collect()
+    }
+
+    /// Decodifica um PNG sem entrelaçamento com linhas sem filtro.
+    fn decode_rows(
+        width: u32,
+        height: u32,
+        depth: u8,
+        color_type: u8,
+        chunks: &[(&[u8; 4], &[u8])],
+        rows: &[&[u8]],
+    ) -> Vec<u32> {
+        let header = ihdr(width, height, depth, color_type, 0);
+        let image = decode(&sample(&header, chunks, &unfiltered(rows))).unwrap();
+        assert_eq!((image.width, image.height), (width, height));
+        image.pixels
+    }
+
+    /// Cinza opaco `v`.
+    fn gray(v: u32) -> u32 {
+        0xFF00_0000 | v << 16 | v << 8 | v
+    }
+
+    #[test]
+    fn decodes_gray_at_every_depth() {
+        let pixels = decode_rows(3, 2, 1, COLOR_GRAY, &[], &[&[0b1010_0000], &[0b0100_0000]]);
+        assert_eq!(
+            pixels,
+            [gray(255), gray(0), gray(255), gray(0), gray(255), gray(0)]
+        );
+
+        let pixels = decode_rows(3, 1, 2, COLOR_GRAY, &[], &[&[0b0001_1000]]);
+        assert_eq!(pixels, [gray(0), gray(85), gray(170)]);
+
+        let pixels = decode_rows(3, 1, 4, COLOR_GRAY, &[], &[&[0x0F, 0x80]]);
+        assert_eq!(pixels, [gray(0), gray(255), gray(136)]);
+
+        let pixels = decode_rows(2, 1, 8, COLOR_GRAY, &[], &[&[0x00, 0x7F]]);
+        assert_eq!(pixels, [gray(0), gray(0x7F)]);
+
+        let pixels = decode_rows(2, 1, 16, COLOR_GRAY, &[], &[&[0x12, 0x34, 0xFF, 0x00]]);
+        assert_eq!(pixels, [gray(0x12), gray(0xFF)]);
+    }
+
+    #[test]
+    fn applies_transparent_color() {
+        let trns: [(&[u8; 4], &[u8]); 1] = [(b"tRNS", &[0x00, 0x7F])];
+        let pixels = decode_rows(2, 1, 8, COLOR_GRAY, &trns, &[&[0x7F, 0x80]]);
+        assert_eq!(pixels, [0, gray(0x80)]);
+
+        // Em 16 bits a comparação usa a amostra inteira
+        let trns: [(&[u8; 4], &[u8]); 1] = [(b"tRNS", &[0x12, 0x34, 0x00, 0x00, 0xAB, 0xCD])];
+        let row = [
+            0x12, 0x34, 0x00, 0x00, 0xAB, 0xCD, 0x12, 0x35, 0x00, 0x00, 0xAB, 0xCD,
+        ];
+        let pixels = decode_rows(2, 1, 16, COLOR_RGB, &trns, &[&row]);
+        assert_eq!(pixels, [0, 0xFF12_00AB]);
+    }
+
+    #[test]
+    fn decodes_rgb_and_alpha_types() {
+        let pixels = decode_rows(2, 1, 8, COLOR_RGB, &[], &[&[1, 2, 3, 0xFF, 0x80, 0x00]]);
+        assert_eq!(pixels, [0xFF01_0203, 0xFFFF_8000]);
+
+        let row = [0x10, 0xFF, 0x20, 0x00, 0x30, 0x11];
+        let pixels = decode_rows(1, 1, 16, COLOR_RGB, &[], &[&row]);
+        assert_eq!(pixels, [0xFF10_2030]);
+
+        let pixels = decode_rows(2, 1, 8, COLOR_GRAY_ALPHA, &[], &[&[0xC8, 0xFF, 0xC8, 0x80]]);
+        assert_eq!(pixels, [gray(0xC8), premultiply(0x80C8_C8C8)]);
+
+        let row = [0xC8, 0x01, 0x00, 0x02];
+        let pixels = decode_rows(1, 1, 16, COLOR_GRAY_ALPHA, &[], &[&row]);
+        assert_eq!(pixels, [0]);
+
+        let row = [0xFF, 0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x80];
+        let pixels = decode_rows(2, 1, 8, COLOR_RGBA, &[], &[&row]);
+        assert_eq!(pixels, [0xFFFF_0000, premultiply(0x80FF_0000)]);
+
+        let row = [0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x40, 0x00];
+        let pixels = decode_rows(1, 1, 16, COLOR_RGBA, &[], &[&row]);
+        assert_eq!(pixels, [premultiply(0x4000_FF00)]);
+    }
+
+    #[test]
+    fn decodes_indexed_at_every_depth() {
+        let plte = [0xFF, 0, 0, 0, 0xFF, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+        let chunks: [(&[u8; 4], &[u8]); 2] = [(b"PLTE", &plte), (b"tRNS", &[0xFF, 0x80])];
+        let (red, green, blue, white) = (
+            0xFFFF_0000,
+            premultiply(0x8000_FF00),
+            0xFF00_00FF,
+            0xFFFF_FFFF,
+        );
+
+        let pixels = decode_rows(3, 1, 1, COLOR_INDEXED, &chunks, &[&[0b0100_0000]]);
+        assert_eq!(pixels, [red, green, red]);
+
+        let pixels = decode_rows(4, 1, 2, COLOR_INDEXED, &chunks, &[&[0b0001_1011]]);
+        assert_eq!(pixels, [red, green, blue, white]);
+
+        let pixels = decode_rows(3, 1, 4, COLOR_INDEXED, &chunks, &[&[0x32, 0x10]]);
+        assert_eq!(pixels, [white, blue, green]);
+
+        // Índice fora da paleta vira preto transparente
+        let pixels = decode_rows(2, 1, 8, COLOR_INDEXED, &chunks, &[&[2, 9]]);
+        assert_eq!(pixels, [blue, 0]);
+    }
+
+    /// Aplica o filtro `kind` a `row`, como um encoder faria.
+    fn filter(kind: u8, row: &[u8], previous: &[u8], bpp: usize) -> Vec<u8> {
+        let mut out = vec![kind];
+        for i in 0..row.len() {
+            let left = if i >= bpp { row[i - bpp] } else { 0 };
+            let up_left = if i >= bpp { previous[i - bpp] } else { 0 };
+            let up = previous[i];
+            let predicted = match kind {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                _ => paeth(left, up, up_left),
+            };
+            out.push(row[i].wrapping_sub(predicted));
+        }
+        out
+    }
+
+    #[test]
+    fn undoes_every_filter() {
+        let rows: [[u8; 9]; 5] = [
+            [10, 20, 30, 200, 100, 50, 0, 255, 128],
+            [11, 22, 33, 190, 110, 40, 5, 250, 130],
+            [255, 0, 128, 1, 254, 127, 64, 64, 64],
+            [3, 200, 17, 99, 0, 250, 180, 33, 71],
+            [90, 90, 90, 91, 89, 92, 0, 0, 1],
+        ];
+        let mut raw = Vec::new();
+        let mut previous = [0u8; 9];
+        for (kind, row) in rows.iter().enumerate() {
+            raw.extend(filter(kind as u8, row, &previous, 3));
+            previous = *row;
+        }
+
+        let image = decode(&sample(&ihdr(3, 5, 8, COLOR_RGB, 0), &[], &raw)).unwrap();
+        let expected: Vec<u32> = rows
+            .iter()
+            .flat_map(|row| row.chunks(3))
+            .map(|p| 0xFF00_0000 | (p[0] as u32) << 16 | (p[1] as u32) << 8 | p[2] as u32)
+            .collect();
+        assert_eq!(image.pixels, expected);
+    }
+
+    /// Linhas Adam7 (sem filtro) de uma imagem em tons de cinza de 8 bits.
+    fn interlace(pixels: &[u8], width: usize, height: usize) -> Vec<u8> {
+        let mut raw = Vec::new();
+        for pass in ADAM7 {
+            let (pw, ph) = pass_size(pass, width, height);
+            if pw == 0 {
+                continue;
+            }
+            let (x0, y0, dx, dy) = pass;
+            for y in 0..ph {
+                raw.push(0);
+                for x in 0..pw {
+                    raw.push(pixels[(y0 + y * dy) * width + x0 + x * dx]);
+                }
+            }
+        }
+        raw
+    }
+
+    #[test]
+    fn decodes_adam7() {
+        // 3x2 deixa vários passes vazios
+        for (width, height) in [(9, 9), (3, 2), (1, 1)] {
+            let pixels: Vec<u8> = (0..width * height).map(|i| (i * 3) as u8).collect();
+            let header = ihdr(width as u32, height as u32, 8, COLOR_GRAY, 1);
+            let image = decode(&sample(&header, &[], &interlace(&pixels, width, height))).unwrap();
+
+            let expected: Vec<u32> = pixels.iter().map(|&v| gray(v as u32)).collect();
+            assert_eq!(image.pixels, expected, "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn truncated_files_fail() {
+        let bytes = sample(
+            &ihdr(2, 2, 8, COLOR_RGB, 0),
+            &[],
+            &unfiltered(&[&[7; 6], &[9; 6]]),
+        );
+        assert!(decode(&bytes).is_ok());
+
+        // O CRC do IEND não é lido
+        for len in 0..bytes.len() - 4 {
+            assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+        }
+
+        // Linhas faltando no fluxo
+        let bytes = sample(&ihdr(2, 2, 8, COLOR_RGB, 0), &[], &unfiltered(&[&[7; 6]]));
+        assert_eq!(decode(&bytes).err(), Some(ImageError::Truncated));
+    }
+
+    #[test]
+    fn rejects_corrupt_files() {
+        let header = ihdr(1, 1, 8, COLOR_GRAY, 0);
+        let error = |bytes: &[u8]| decode(bytes).err();
+
+        assert_eq!(error(b"\x89PNG\r\n\x1A\x00"), Some(ImageError::BadMagic));
+        // Filtro desconhecido
+        assert_eq!(
+            error(&sample(&header, &[], &[5, 0])),
+            Some(ImageError::Corrupt)
+        );
+        // Profundidade inválida para o tipo, e tipo de cor inexistente
+        let bad = ihdr(1, 1, 4, COLOR_RGB, 0);
+        assert_eq!(
+            error(&sample(&bad, &[], &[0, 0])),
+            Some(ImageError::InvalidHeader)
+        );
+        let bad = ihdr(1, 1, 8, 5, 0);
+        assert_eq!(
+            error(&sample(&bad, &[], &[0, 0])),
+            Some(ImageError::InvalidHeader)
+        );
+        let bad = ihdr(0, 1, 8, COLOR_GRAY, 0);
+        assert_eq!(
+            error(&sample(&bad, &[], &[0])),
+            Some(ImageError::InvalidHeader)
+        );
+        // Paleta ausente
+        let indexed = ihdr(1, 1, 8, COLOR_INDEXED, 0);
+        assert_eq!(
+            error(&sample(&indexed, &[], &[0, 0])),
+            Some(ImageError::InvalidHeader)
+        );
+        // tRNS com alpha próprio
+        let rgba = ihdr(1, 1, 8, COLOR_RGBA, 0);
+        let trns: [(&[u8; 4], &[u8]); 1] = [(b"tRNS", &[0; 6])];
+        assert_eq!(
+            error(&sample(&rgba, &trns, &[0; 5])),
+            Some(ImageError::InvalidHeader)
+        );
+        // Chunk crítico desconhecido
+        let unknown: [(&[u8; 4], &[u8]); 1] = [(b"XYZW", &[])];
+        assert_eq!(
+            error(&sample(&header, &unknown, &[0, 0])),
+            Some(ImageError::Unsupported)
+        );
+
+        // IDAT antes do IHDR
+        let mut bytes = SIGNATURE.to_vec();
+        chunk(&mut bytes, b"IDAT", &zlib_stored(&[0, 0]));
+        chunk(&mut bytes, b"IHDR", &header);
+        chunk(&mut bytes, b"IEND", &[]);
+        assert_eq!(error(&bytes), Some(ImageError::InvalidHeader));
+
+        // Fluxo zlib corrompido
+        let mut bytes = sample(&header, &[], &[0, 0]);
+        let idat = bytes.windows(4).position(|w| w == b"IDAT").unwrap();
+        bytes[idat + 4 + 3] ^= 0xFF;
+        assert_eq!(error(&bytes), Some(ImageError::Corrupt));
+    }
+}
@@ -0,0 +1,208 @@
+//! # QOI
+//!
+//! Decoder "Quite OK Image".
+//!
+//! ```text
+//! "qoif" | largura (4, BE) | altura (4, BE) | canais (1) | espaço de cor (1)
+//! operações* | 00 00 00 00 00 00 00 01
+//! ```
+//!
+//! Cada operação gera um ou mais pixels a partir do anterior: cor
+//! completa, índice numa tabela de 64 cores vistas, pequenas diferenças
+//! ou repetição.
+
+use super::{alloc_pixels, Image, ImageError};
+use crate::render::pixel::premultiply;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Assinatura do arquivo.
+const MAGIC: &[u8; 4] = b"qoif";
+
+/// Tamanho do cabeçalho.
+const HEADER_SIZE: usize = 14;
+
+/// Operações com tag de 8 bits.
+const OP_RGB: u8 = 0xFE;
+const OP_RGBA: u8 = 0xFF;
+
+/// Operações com tag de 2 bits (a restante, 0xC0, é OP_RUN).
+const OP_INDEX: u8 = 0x00;
+const OP_DIFF: u8 = 0x40;
+const OP_LUMA: u8 = 0x80;
+
+/// Máscara da tag de 2 bits.
+const MASK_2: u8 = 0xC0;
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Retorna se `data` tem a assinatura de um QOI.
+pub fn is_qoi(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Decodifica um arquivo QOI completo.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if !is_qoi(data) {
+        return Err(ImageError::BadMagic);
+    }
+    if data.len() < HEADER_SIZE {
+        return Err(ImageError::Truncated);
+    }
+    let width = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+    let height = u32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+    let (channels, colorspace) = (data[12], data[13]);
+    if !matches!(channels, 3 | 4) || colorspace > 1 {
+        return Err(ImageError::InvalidHeader);
+    }
+    let mut pixels = alloc_pixels(width, height)?;
+
+    // Pixels em [r, g, b, a]
+    let mut index = [[0u8; 4]; 64];
+    let mut px = [0u8, 0, 0, 255];
+    let mut pos = HEADER_SIZE;
+    let mut run = 0u32;
+    let byte = |pos: usize| data.get(pos).copied().ok_or(ImageError::Truncated);
+
+    for out in pixels.iter_mut() {
+        if run > 0 {
+            run -= 1;
+        } else {
+            let tag = byte(pos)?;
+            pos += 1;
+            match tag {
+                OP_RGB => {
+                    px[..3].copy_from_slice(data.get(pos..pos + 3).ok_or(ImageError::Truncated)?);
+                    pos += 3;
+                }
+                OP_RGBA => {
+                    px.copy_from_slice(data.get(pos..pos + 4).ok_or(ImageError::Truncated)?);
+                    pos += 4;
+                }
+                _ => match tag & MASK_2 {
+                    OP_INDEX => px = index[tag as usize],
+                    OP_DIFF => {
+                        px[0] = px[0].wrapping_add((tag >> 4) & 0x03).wrapping_sub(2);
+                        px[1] = px[1].wrapping_add((tag >> 2) & 0x03).wrapping_sub(2);
+                        px[2] = px[2].wrapping_add(tag & 0x03).wrapping_sub(2);
+                    }
+                    OP_LUMA => {
+                        let next = byte(pos)?;
+                        pos += 1;
+                        let dg = (tag & 0x3F).wrapping_sub(32);
+                        px[0] = px[0].wrapping_add(dg.wrapping_sub(8).wrapping_add(next >> 4));
+                        px[1] = px[1].wrapping_add(dg);
+                        px[2] = px[2].wrapping_add(dg.wrapping_sub(8).wrapping_add(next & 0x0F));
+                    }
+                    // OP_RUN: repete o pixel anterior mais `run` vezes
+                    _ => run = (tag & 0x3F) as u32,
+                },
+            }
+            let [r, g, b, a] = px;
+            let hash = (r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64;
+            index[hash] = px;
+        }
+
+        // O campo de canais é só informativo: o alpha vem do fluxo
+        let [r, g, b, a] = px;
+        *out = premultiply((a as u32) << 24 | (r as u32) << 16 | (g as u32) << 8 | b as u32);
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Fim do fluxo.
+    const END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+    /// Monta um QOI `width x height` com as operações `ops`.
+    fn sample(width: u32, height: u32, channels: u8, ops: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[channels, 0]);
+        bytes.extend_from_slice(ops);
+        bytes.extend_from_slice(&END);
+        bytes
+    }
+
+    /// Uma operação de cada tipo.
+    const OPS: [u8; 14] = [
+        OP_RGBA, 10, 20, 30, 128, // (10, 20, 30, 128)
+        OP_RGB, 100, 150, 200,  // (100, 150, 200, 128)
+        0x76, // DIFF +1 -1 +0: (101, 149, 200, 128)
+        0xA5, 0x5A, // LUMA dg +5, dr-dg -3, db-dg +2: (103, 154, 207, 128)
+        20,   // INDEX do primeiro pixel
+        0xC2, // RUN de 3
+    ];
+
+    #[test]
+    fn decodes_every_operation() {
+        let bytes = sample(4, 2, 4, &OPS);
+        let image = decode(&bytes).unwrap();
+
+        let first = premultiply(0x800A_141E);
+        let expected = [
+            first,
+            premultiply(0x8064_96C8),
+            premultiply(0x8065_95C8),
+            premultiply(0x8067_9ACF),
+            first,
+            first,
+            first,
+            first,
+        ];
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.pixels, expected);
+    }
+
+    #[test]
+    fn starts_from_opaque_black() {
+        // DIFF +0 +0 +0 sobre o pixel inicial, depois uma repetição longa
+        let image = decode(&sample(3, 21, 3, &[0x6A, 0xFD])).unwrap();
+        assert_eq!(image.pixels.len(), 63);
+        assert!(image.pixels.iter().all(|&px| px == 0xFF00_0000));
+    }
+
+    #[test]
+    fn truncated_files_fail() {
+        let bytes = sample(4, 2, 4, &OPS);
+        // O marcador de fim não é exigido
+        for len in 0..bytes.len() - END.len() {
+            assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        assert_eq!(decode(b"qoiF").err(), Some(ImageError::BadMagic));
+        assert_eq!(
+            decode(&sample(1, 1, 5, &[0xC0])).err(),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(
+            decode(&sample(0, 1, 4, &[])).err(),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(
+            decode(&sample(1 << 16, 1 << 16, 4, &[])).err(),
+            Some(ImageError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! # Escala
+//!
+//! Redimensionamento de imagens em ARGB pré-multiplicado.
+//!
+//! O filtro é separável e escolhido por eixo: média de área ao reduzir
+//! (cada pixel de saída cobre uma faixa da origem, com peso proporcional
+//! à cobertura) e bilinear ao ampliar. Pesos em ponto fixo de 14 bits,
+//! sem `f32`.
+//!
+//! Operar sobre pixels pré-multiplicados evita halos escuros nas bordas
+//! de regiões transparentes.
+
+use alloc::vec::Vec;
+
+use gfx_types::geometry::Rect;
+
+use super::{alloc_pixels, Image, ImageError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Bits de fração dos pesos.
+const WEIGHT_BITS: u32 = 14;
+
+/// Peso total de um pixel de saída.
+const WEIGHT_ONE: u32 = 1 << WEIGHT_BITS;
+
+/// Bits de fração das coordenadas.
+const COORD_BITS: u32 = 16;
+
+// =============================================================================
+// API
+// =============================================================================
+
+/// Redimensiona a imagem inteira para `width` x `height`.
+pub fn resize(src: &Image, width: u32, height: u32) -> Result<Image, ImageError> {
+    resize_region(src, Rect::new(0, 0, src.width, src.height), width, height)
+}
+
+/// Redimensiona a região `region` de `src` para `width` x `height`.
+///
+/// A região é limitada aos bounds da imagem; vazia é erro.
+pub fn resize_region(
+    src: &Image,
+    region: Rect,
+    width: u32,
+    height: u32,
+) -> Result<Image, ImageError> {
+    let x0 = region.x.clamp(0, src.width as i32) as u32;
+    let y0 = region.y.clamp(0, src.height as i32) as u32;
+    let x1 = (region.x as i64 + region.width as i64).clamp(x0 as i64, src.width as i64) as u32;
+    let y1 = (region.y as i64 + region.height as i64).clamp(y0 as i64, src.height as i64) as u32;
+    let (region_w, region_h) = (x1 - x0, y1 - y0);
+    if region_w == 0 || region_h == 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+    let mut pixels = alloc_pixels(width, height)?;
+
+    let columns = kernel(region_w, width);
+    let rows = kernel(region_h, height);
+    let src_stride = src.width as usize;
+
+    // Passo horizontal: linhas da região -> `width` colunas
+    let mut temp = alloc::vec![0u32; width as usize * region_h as usize];
+    for y in 0..region_h as usize {
+        let row = &src.pixels[(y0 as usize + y) * src_stride + x0 as usize..][..region_w as usize];
+        let out = &mut temp[y * width as usize..][..width as usize];
+        for (px, tap) in out.iter_mut().zip(&columns) {
+            *px = sample(row, tap.start, 1, &tap.weights);
+        }
+    }
+
+    // Passo vertical: `region_h` linhas -> `height` linhas
+    let stride = width as usize;
+    for (y, tap) in rows.iter().enumerate() {
+        let out = &mut pixels[y * stride..][..stride];
+        for (x, px) in out.iter_mut().enumerate() {
+            *px = sample(&temp[x..], tap.start * stride, stride, &tap.weights);
+        }
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// =============================================================================
+// KERNEL
+// =============================================================================
+
+/// Pixels de origem que compõem um pixel de saída.
+struct Tap {
+    /// Primeiro pixel de origem.
+    start: usize,
+    /// Pesos dos pixels consecutivos a partir de `start` (somam
+    /// [`WEIGHT_ONE`]).
+    weights: Vec<u32>,
+}
+
+/// Calcula os taps de um eixo de `src_len` para `dst_len` pixels.
+fn kernel(src_len: u32, dst_len: u32) -> Vec<Tap> {
+    if dst_len >= src_len {
+        (0..dst_len)
+            .map(|i| bilinear_tap(i, src_len, dst_len))
+            .collect()
+    } else {
+        (0..dst_len)
+            .map(|i| area_tap(i, src_len, dst_len))
+            .collect()
+    }
+}
+
+/// Tap bilinear: os dois vizinhos do centro do pixel de saída.
+fn bilinear_tap(i: u32, src_len: u32, dst_len: u32) -> Tap {
+    // Centro na origem: (i + 0.5) * src / dst - 0.5
+    let half = 1i64 << (COORD_BITS - 1);
+    let center = ((2 * i as i64 + 1) * src_len as i64 * 2 * half) / (2 * dst_len as i64) - half;
+    let base = center >> COORD_BITS;
+    let frac = (center & ((1 << COORD_BITS) - 1)) as u32 >> (COORD_BITS - WEIGHT_BITS);
+
+    let last = src_len as i64 - 1;
+    let (first, second) = (base.clamp(0, last), (base + 1).clamp(0, last));
+    if first == second {
+        return Tap {
+            start: first as usize,
+            weights: alloc::vec![WEIGHT_ONE],
+        };
+    }
+    Tap {
+        start: first as usize,
+        weights: alloc::vec![WEIGHT_ONE - frac, frac],
+    }
+}
+
+/// Tap de área: todos os pixels cobertos pela faixa do pixel de saída.
+fn area_tap(i: u32, src_len: u32, dst_len: u32) -> Tap {
+    let one = 1u64 << COORD_BITS;
+    let start = i as u64 * src_len as u64 * one / dst_len as u64;
+    let end = (i as u64 + 1) * src_len as u64 * one / dst_len as u64;
+    let span = end - start;
+    let first = start >> COORD_BITS;
+    let last = (end - 1) >> COORD_BITS;
+
+    let mut weights: Vec<u32> = (first..=last)
+        .map(|j| {
+            let lo = start.max(j << COORD_BITS);
+            let hi = end.min((j + 1) << COORD_BITS);
+            ((hi - lo) * WEIGHT_ONE as u64 / span) as u32
+        })
+        .collect();
+
+    // O arredondamento fica com o maior peso, para a soma fechar
+    let sum: u32 = weights.iter().sum();
+    if let Some(max) = weights.iter_mut().max() {
+        *max += WEIGHT_ONE - sum;
+    }
+
+    Tap {
+        start: first as usize,
+        weights,
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Média ponderada dos pixels `src[start + k * stride]`.
+#[inline]
+fn sample(src: &[u32], start: usize, stride: usize, weights: &[u32]) -> u32 {
+    let mut acc = [0u32; 4];
+    for (k, &weight) in weights.iter().enumerate() {
+        let pixel = src[start + k * stride];
+        for (c, sum) in acc.iter_mut().enumerate() {
+            *sum += (pixel >> (24 - c * 8) & 0xFF) * weight;
+        }
+    }
+
+    let round = |sum: u32| (sum + WEIGHT_ONE / 2) >> WEIGHT_BITS;
+    round(acc[0]) << 24 | round(acc[1]) << 16 | round(acc[2]) << 8 | round(acc[3])
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Imagem `width x height` de uma cor só.
+    fn solid(width: u32, height: u32, color: u32) -> Image {
+        Image {
+            width,
+            height,
+            pixels: alloc::vec![color; (width * height) as usize],
+        }
+    }
+
+    /// Linha com os pixels `pixels`.
+    fn row(pixels: &[u32]) -> Image {
+        Image {
+            width: pixels.len() as u32,
+            height: 1,
+            pixels: pixels.to_vec(),
+        }
+    }
+
+    #[test]
+    fn solid_images_stay_solid() {
+        // Translúcido e pré-multiplicado: qualquer desvio de peso aparece
+        let color = 0x8040_2010;
+        let src = solid(7, 5, color);
+        let sizes = [
+            (1, 1),
+            (3, 2),
+            (7, 5),
+            (6, 4),
+            (13, 11),
+            (40, 3),
+            (2, 29),
+            (100, 77),
+        ];
+        for (width, height) in sizes {
+            let image = resize(&src, width, height).unwrap();
+            assert_eq!((image.width, image.height), (width, height));
+            assert!(
+                image.pixels.iter().all(|&px| px == color),
+                "{}x{}",
+                width,
+                height
+            );
+        }
+
+        let image = resize(&solid(1, 1, color), 9, 4).unwrap();
+        assert!(image.pixels.iter().all(|&px| px == color));
+    }
+
+    #[test]
+    fn weights_always_sum_to_one() {
+        for src_len in 1..40 {
+            for dst_len in 1..40 {
+                for tap in kernel(src_len, dst_len) {
+                    assert_eq!(tap.weights.iter().sum::<u32>(), WEIGHT_ONE);
+                    assert!(tap.start + tap.weights.len() <= src_len as usize);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn downscale_averages_area() {
+        let image = resize(&row(&[0xFF00_0000, 0xFFFF_FFFF]), 1, 1).unwrap();
+        assert_eq!(image.pixels, [0xFF80_8080]);
+
+        // 3 -> 2: cada saída cobre um pixel e meio
+        let image = resize(&row(&[0xFF00_0000, 0xFF00_00FF, 0xFF00_0000]), 2, 1).unwrap();
+        assert_eq!(image.pixels, [0xFF00_0055, 0xFF00_0055]);
+    }
+
+    #[test]
+    fn upscale_interpolates_between_centers() {
+        // Centros em -0.25 (borda), 0.25, 0.75 e 1.25 (borda)
+        let image = resize(&row(&[0xFF00_0000, 0xFF00_00FF]), 4, 1).unwrap();
+        assert_eq!(
+            image.pixels,
+            [0xFF00_0000, 0xFF00_0040, 0xFF00_00BF, 0xFF00_00FF]
+        );
+    }
+
+    #[test]
+    fn region_is_clamped_to_the_image() {
+        let src = row(&[0xFF00_0000, 0xFFFF_FFFF, 0xFF00_0000]);
+        let image = resize_region(&src, Rect::new(1, 0, 1, 1), 4, 3).unwrap();
+        assert!(image.pixels.iter().all(|&px| px == 0xFFFF_FFFF));
+
+        // Só a parte dentro da imagem conta
+        let image = resize_region(&src, Rect::new(-5, -5, 6, 10), 1, 1).unwrap();
+        assert_eq!(image.pixels, [0xFF00_0000]);
+
+        assert_eq!(
+            resize_region(&src, Rect::new(3, 0, 2, 1), 1, 1).err(),
+            Some(ImageError::InvalidHeader)
+        );
+    }
+}
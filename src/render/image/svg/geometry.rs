@@ -0,0 +1,313 @@
+//! # Geometria
+//!
+//! Transformações afins, trigonometria sem libm, achatamento de curvas e
+//! contorno (stroke) de polilinhas.
+
+use alloc::vec::Vec;
+
+use crate::render::raster::{ceilf, floorf, PointF, Rasterizer};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+pub const PI: f32 = core::f32::consts::PI;
+
+/// Tolerância de achatamento (maior = mais segmentos).
+const FLATTEN_TOLERANCE: f32 = 3.0;
+
+/// Limite de segmentos por curva.
+const MAX_CURVE_SEGMENTS: usize = 64;
+
+/// Limite de segmentos de um círculo de junção.
+const MAX_CIRCLE_SEGMENTS: usize = 48;
+
+// =============================================================================
+// TRIGONOMETRIA
+// =============================================================================
+
+/// Seno e cosseno (série de Taylor após redução a [-π/2, π/2]).
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+    // Redução a [-π, π]
+    let turns = floorf(angle / (2.0 * PI) + 0.5);
+    let x = angle - turns * 2.0 * PI;
+
+    // sin(π - x) = sin(x), cos(π - x) = -cos(x)
+    let (x, cos_sign) = if x > PI / 2.0 {
+        (PI - x, -1.0)
+    } else if x < -PI / 2.0 {
+        (-PI - x, -1.0)
+    } else {
+        (x, 1.0)
+    };
+
+    let x2 = x * x;
+    let sin = x * (1.0 - x2 / 6.0 * (1.0 - x2 / 20.0 * (1.0 - x2 / 42.0 * (1.0 - x2 / 72.0))));
+    let cos = 1.0 - x2 / 2.0 * (1.0 - x2 / 12.0 * (1.0 - x2 / 30.0 * (1.0 - x2 / 56.0)));
+    (sin, cos * cos_sign)
+}
+
+/// `atan2` (polinômio mínimax em [-1, 1], erro ~1e-5).
+pub fn atan2(y: f32, x: f32) -> f32 {
+    if x == 0.0 && y == 0.0 {
+        return 0.0;
+    }
+    let (ax, ay) = (x.abs(), y.abs());
+    let z = if ax >= ay { ay / ax } else { ax / ay };
+    let z2 = z * z;
+    let mut angle = z
+        * (0.999_866
+            + z2 * (-0.330_299_5 + z2 * (0.180_141 + z2 * (-0.085_133 + z2 * 0.020_835_1))));
+
+    if ay > ax {
+        angle = PI / 2.0 - angle;
+    }
+    if x < 0.0 {
+        angle = PI - angle;
+    }
+    if y < 0.0 {
+        -angle
+    } else {
+        angle
+    }
+}
+
+// =============================================================================
+// TRANSFORMAÇÃO
+// =============================================================================
+
+/// Matriz afim `[a c e; b d f]` (mesma ordem do atributo `matrix()`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub const fn translate(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    pub const fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Rotação em graus.
+    pub fn rotate(degrees: f32) -> Self {
+        let (sin, cos) = sin_cos(degrees * PI / 180.0);
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// `self * other`: aplica `other` primeiro.
+    pub fn then(self, other: Self) -> Self {
+        Self::new(
+            self.a * other.a + self.c * other.b,
+            self.b * other.a + self.d * other.b,
+            self.a * other.c + self.c * other.d,
+            self.b * other.c + self.d * other.d,
+            self.a * other.e + self.c * other.f + self.e,
+            self.b * other.e + self.d * other.f + self.f,
+        )
+    }
+
+    /// Aplica a um ponto.
+    #[inline]
+    pub fn apply(&self, p: PointF) -> PointF {
+        PointF::new(
+            self.a * p.x + self.c * p.y + self.e,
+            self.b * p.x + self.d * p.y + self.f,
+        )
+    }
+
+    /// Fator de escala médio (para larguras de traço).
+    pub fn mean_scale(&self) -> f32 {
+        rdsmath::sqrtf((self.a * self.d - self.b * self.c).abs())
+    }
+}
+
+// =============================================================================
+// POLILINHAS
+// =============================================================================
+
+/// Subcaminho achatado, em pixels.
+pub struct Polyline {
+    pub points: Vec<PointF>,
+    pub closed: bool,
+}
+
+/// Número de segmentos para uma curva com desvio `dev` (em pixels²).
+pub fn curve_segments(dev: f32) -> usize {
+    let estimate = rdsmath::sqrtf(rdsmath::sqrtf(FLATTEN_TOLERANCE * dev));
+    (1 + ceilf(estimate) as usize).min(MAX_CURVE_SEGMENTS)
+}
+
+#[inline]
+fn lerp(a: PointF, b: PointF, t: f32) -> PointF {
+    PointF::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+/// Acrescenta uma quadrática achatada (sem o ponto inicial).
+pub fn flatten_quad(out: &mut Vec<PointF>, p0: PointF, c: PointF, p: PointF) {
+    let ddx = p0.x - 2.0 * c.x + p.x;
+    let ddy = p0.y - 2.0 * c.y + p.y;
+    let n = curve_segments(ddx * ddx + ddy * ddy);
+    for i in 1..n {
+        let t = i as f32 / n as f32;
+        out.push(lerp(lerp(p0, c, t), lerp(c, p, t), t));
+    }
+    out.push(p);
+}
+
+/// Acrescenta uma cúbica achatada (sem o ponto inicial).
+pub fn flatten_cubic(out: &mut Vec<PointF>, p0: PointF, c1: PointF, c2: PointF, p: PointF) {
+    let dd0x = p0.x - 2.0 * c1.x + c2.x;
+    let dd0y = p0.y - 2.0 * c1.y + c2.y;
+    let dd1x = c1.x - 2.0 * c2.x + p.x;
+    let dd1y = c1.y - 2.0 * c2.y + p.y;
+    let dev = (dd0x * dd0x + dd0y * dd0y).max(dd1x * dd1x + dd1y * dd1y);
+    let n = curve_segments(dev * 2.25);
+    for i in 1..n {
+        let t = i as f32 / n as f32;
+        let a = lerp(p0, c1, t);
+        let b = lerp(c1, c2, t);
+        let c = lerp(c2, p, t);
+        out.push(lerp(lerp(a, b, t), lerp(b, c, t), t));
+    }
+    out.push(p);
+}
+
+/// Deposita as polilinhas como contornos fechados (preenchimento).
+pub fn fill(raster: &mut Rasterizer, lines: &[Polyline]) {
+    for line in lines {
+        let Some((&first, rest)) = line.points.split_first() else {
+            continue;
+        };
+        raster.move_to(first);
+        for &p in rest {
+            raster.line_to(p);
+        }
+    }
+    raster.close();
+}
+
+// =============================================================================
+// STROKE
+// =============================================================================
+
+/// Terminação de traços abertos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+/// Deposita o contorno das polilinhas com meia largura `half`.
+///
+/// Cada segmento vira um quadrilátero e cada vértice interno um círculo
+/// (junções sempre arredondadas). Todos os contornos têm a mesma
+/// orientação, então a regra nonzero os une sem buracos.
+pub fn stroke(raster: &mut Rasterizer, lines: &[Polyline], half: f32, cap: LineCap) {
+    for line in lines {
+        let points = &line.points;
+        let Some(&first) = points.first() else {
+            continue;
+        };
+        let last = points[points.len() - 1];
+        let segments = points.len() - 1;
+
+        if segments == 0 || (segments == 1 && first == last) {
+            // Subcaminho de um ponto: só caps arredondados/quadrados aparecem
+            match cap {
+                LineCap::Round => circle(raster, first, half),
+                LineCap::Square => square(raster, first, half),
+                LineCap::Butt => {}
+            }
+            continue;
+        }
+
+        for (i, pair) in points.windows(2).enumerate() {
+            let (mut a, mut b) = (pair[0], pair[1]);
+            if cap == LineCap::Square && !line.closed {
+                if i == 0 {
+                    a = extend(a, b, half);
+                }
+                if i == segments - 1 {
+                    b = extend(b, a, half);
+                }
+            }
+            segment(raster, a, b, half);
+        }
+        if line.closed {
+            segment(raster, last, first, half);
+        }
+
+        // Junções (e caps arredondados nas pontas)
+        let joins = if line.closed || cap == LineCap::Round {
+            &points[..]
+        } else {
+            &points[1..points.len() - 1]
+        };
+        for &p in joins {
+            circle(raster, p, half);
+        }
+    }
+}
+
+/// Quadrilátero de um segmento.
+fn segment(raster: &mut Rasterizer, a: PointF, b: PointF, half: f32) {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len = rdsmath::sqrtf(dx * dx + dy * dy);
+    if len < 1e-6 {
+        return;
+    }
+    let (nx, ny) = (-dy / len * half, dx / len * half);
+    raster.move_to(PointF::new(a.x + nx, a.y + ny));
+    raster.line_to(PointF::new(b.x + nx, b.y + ny));
+    raster.line_to(PointF::new(b.x - nx, b.y - ny));
+    raster.line_to(PointF::new(a.x - nx, a.y - ny));
+    raster.close();
+}
+
+/// Prolonga `p` por `half` na direção oposta a `toward`.
+fn extend(p: PointF, toward: PointF, half: f32) -> PointF {
+    let (dx, dy) = (p.x - toward.x, p.y - toward.y);
+    let len = rdsmath::sqrtf(dx * dx + dy * dy);
+    if len < 1e-6 {
+        return p;
+    }
+    PointF::new(p.x + dx / len * half, p.y + dy / len * half)
+}
+
+/// Círculo com a mesma orientação dos quadriláteros.
+fn circle(raster: &mut Rasterizer, center: PointF, radius: f32) {
+    let n = (8 + (radius * 2.0) as usize).min(MAX_CIRCLE_SEGMENTS);
+    raster.move_to(PointF::new(center.x + radius, center.y));
+    for i in 1..n {
+        let (sin, cos) = sin_cos(-(i as f32) * 2.0 * PI / n as f32);
+        raster.line_to(PointF::new(
+            center.x + cos * radius,
+            center.y + sin * radius,
+        ));
+    }
+    raster.close();
+}
+
+/// Quadrado de um cap em ponto isolado.
+fn square(raster: &mut Rasterizer, center: PointF, half: f32) {
+    raster.move_to(PointF::new(center.x - half, center.y + half));
+    raster.line_to(PointF::new(center.x + half, center.y + half));
+    raster.line_to(PointF::new(center.x + half, center.y - half));
+    raster.line_to(PointF::new(center.x - half, center.y - half));
+    raster.close();
+}
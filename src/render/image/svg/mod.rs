@@ -0,0 +1,726 @@
+//! # SVG
+//!
+//! Rasterizador de um subconjunto de SVG, suficiente para ícones.
+//!
+//! ## Suportado
+//!
+//! - **Elementos**: `svg` (`viewBox`, `width`, `height`), `g`, `a`, `path`,
+//!   `rect`, `circle`, `ellipse`, `line`, `polyline` e `polygon`
+//! - **Pintura**: `fill`, `stroke`, `stroke-width`, `stroke-linecap`,
+//!   `fill-rule`, `opacity`, `fill-opacity` e `stroke-opacity`, como
+//!   atributo ou no `style`
+//! - **Cores**: `#rgb`, `#rrggbb`, `rgb(r, g, b)`, nomes básicos e
+//!   `currentColor`
+//! - **Transformações**: `matrix`, `translate`, `scale`, `rotate`, `skewX`
+//!   e `skewY`, acumuladas pelos grupos
+//!
+//! ## Limitações
+//!
+//! Gradientes, `use`, texto, máscaras, `clipPath` e folhas `<style>` não
+//! são suportados; elementos desconhecidos são pulados com os filhos. As
+//! junções de traço são sempre arredondadas, e a `opacity` de um grupo é
+//! aplicada a cada filho separadamente.
+//!
+//! O `viewBox` é encaixado no tamanho pedido mantendo a proporção
+//! (`xMidYMid meet`).
+
+mod geometry;
+mod path;
+mod xml;
+
+use alloc::vec::Vec;
+
+use self::geometry::{sin_cos, LineCap, Transform, PI};
+use self::path::{numbers, Path, Scanner};
+use self::xml::{Token, Tokenizer};
+use super::{alloc_pixels, Image, ImageError};
+use crate::render::pixel::{div255, over, premultiply};
+use crate::render::raster::{ceilf, PointF, Rasterizer};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Bytes iniciais examinados por [`is_svg`].
+const SNIFF_LEN: usize = 1024;
+
+/// Maior profundidade de elementos aninhados.
+const MAX_DEPTH: usize = 32;
+
+/// Cor de `currentColor` quando `color` não é definido.
+const DEFAULT_COLOR: u32 = 0xFF00_0000;
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Retorna se `data` parece um documento SVG.
+pub fn is_svg(data: &[u8]) -> bool {
+    let head = &data[..data.len().min(SNIFF_LEN)];
+    let text = match core::str::from_utf8(head) {
+        Ok(text) => text,
+        // O corte pode ter partido um caractere no meio
+        Err(e) => core::str::from_utf8(&head[..e.valid_up_to()]).unwrap_or(""),
+    };
+    let text = text.trim_start_matches('\u{FEFF}').trim_start();
+    text.starts_with('<') && text.contains("<svg")
+}
+
+/// Rasteriza no tamanho intrínseco (`width`/`height`, ou o `viewBox`).
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    rasterize(data, None)
+}
+
+/// Rasteriza em `width` x `height`.
+pub fn render(data: &[u8], width: u32, height: u32) -> Result<Image, ImageError> {
+    rasterize(data, Some((width, height)))
+}
+
+/// Lê o documento e pinta cada forma sobre um buffer transparente.
+fn rasterize(data: &[u8], size: Option<(u32, u32)>) -> Result<Image, ImageError> {
+    let text = core::str::from_utf8(data).map_err(|_| ImageError::Corrupt)?;
+    let mut tokens = Tokenizer::new(text);
+
+    let (root, self_closing) = match tokens.next_token()? {
+        Some(Token::Start {
+            name,
+            attrs,
+            self_closing,
+        }) if local_name(name) == "svg" => (attrs, self_closing),
+        _ => return Err(ImageError::BadMagic),
+    };
+
+    // Viewport: viewBox, ou a origem até width x height
+    let view_box = attr(&root, "viewBox")
+        .map(numbers)
+        .filter(|v| v.len() == 4 && v[2] > 0.0 && v[3] > 0.0);
+    let intrinsic_width = attr(&root, "width").and_then(length);
+    let intrinsic_height = attr(&root, "height").and_then(length);
+    let (view_x, view_y, view_w, view_h) = match (&view_box, intrinsic_width, intrinsic_height) {
+        (Some(v), _, _) => (v[0], v[1], v[2], v[3]),
+        (None, Some(w), Some(h)) if w > 0.0 && h > 0.0 => (0.0, 0.0, w, h),
+        _ => return Err(ImageError::InvalidHeader),
+    };
+
+    let (width, height) = match size {
+        Some(size) => size,
+        None => (
+            ceilf(intrinsic_width.unwrap_or(view_w)).max(1.0) as u32,
+            ceilf(intrinsic_height.unwrap_or(view_h)).max(1.0) as u32,
+        ),
+    };
+    let mut pixels = alloc_pixels(width, height)?;
+
+    let scale = (width as f32 / view_w).min(height as f32 / view_h);
+    let base = Transform::translate(
+        (width as f32 - view_w * scale) / 2.0 - view_x * scale,
+        (height as f32 - view_h * scale) / 2.0 - view_y * scale,
+    )
+    .then(Transform::scale(scale, scale));
+
+    let mut canvas = Target {
+        pixels: &mut pixels,
+        width,
+        height,
+    };
+    let mut stack = Vec::with_capacity(MAX_DEPTH);
+    if !self_closing {
+        stack.push(Style::root(base).child(&root));
+    }
+
+    // Profundidade dentro de um elemento ignorado
+    let mut skip = 0usize;
+
+    while !stack.is_empty() {
+        let Some(token) = tokens.next_token()? else {
+            break;
+        };
+        match token {
+            Token::End if skip > 0 => skip -= 1,
+            Token::End => {
+                stack.pop();
+            }
+            Token::Start { self_closing, .. } if skip > 0 => {
+                if !self_closing {
+                    skip += 1;
+                }
+            }
+            Token::Start {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let name = local_name(name);
+                if !is_supported(name) {
+                    if !self_closing {
+                        skip = 1;
+                    }
+                    continue;
+                }
+
+                let Some(parent) = stack.last() else {
+                    break;
+                };
+                let style = parent.child(&attrs);
+                if let Some(path) = shape(name, &attrs) {
+                    canvas.paint(&path, &style);
+                }
+
+                if !self_closing {
+                    if stack.len() >= MAX_DEPTH {
+                        return Err(ImageError::Unsupported);
+                    }
+                    stack.push(style);
+                }
+            }
+        }
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// =============================================================================
+// ESTILO
+// =============================================================================
+
+/// Tinta de preenchimento ou traço.
+#[derive(Clone, Copy)]
+enum Ink {
+    None,
+    /// ARGB direto.
+    Color(u32),
+    /// Valor de `color`.
+    Current,
+}
+
+/// Estado de pintura herdado pelos elementos.
+#[derive(Clone)]
+struct Style {
+    transform: Transform,
+    fill: Ink,
+    stroke: Ink,
+    color: u32,
+    stroke_width: f32,
+    cap: LineCap,
+    even_odd: bool,
+    /// Opacidade acumulada dos grupos.
+    opacity: f32,
+    fill_opacity: f32,
+    stroke_opacity: f32,
+}
+
+impl Style {
+    /// Valores iniciais da especificação.
+    fn root(transform: Transform) -> Self {
+        Self {
+            transform,
+            fill: Ink::Color(DEFAULT_COLOR),
+            stroke: Ink::None,
+            color: DEFAULT_COLOR,
+            stroke_width: 1.0,
+            cap: LineCap::Butt,
+            even_odd: false,
+            opacity: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+        }
+    }
+
+    /// Estilo de um filho: herda e aplica atributos e `style`.
+    ///
+    /// Declarações em `style` têm precedência sobre atributos.
+    fn child(&self, attrs: &[(&str, &str)]) -> Self {
+        let mut style = self.clone();
+        let mut opacity = 1.0;
+
+        for &(key, value) in attrs {
+            match key {
+                "transform" => {
+                    if let Some(transform) = parse_transform(value) {
+                        style.transform = style.transform.then(transform);
+                    }
+                }
+                "opacity" => opacity = parse_opacity(value).unwrap_or(opacity),
+                _ => style.set(key, value),
+            }
+        }
+
+        if let Some(declarations) = attr(attrs, "style") {
+            for declaration in declarations.split(';') {
+                if let Some((key, value)) = declaration.split_once(':') {
+                    match key.trim() {
+                        "opacity" => opacity = parse_opacity(value.trim()).unwrap_or(opacity),
+                        key => style.set(key, value.trim()),
+                    }
+                }
+            }
+        }
+
+        style.opacity *= opacity;
+        style
+    }
+
+    /// Aplica uma propriedade de apresentação (valores inválidos são
+    /// ignorados, mantendo o herdado).
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "fill" => self.fill = parse_ink(value).unwrap_or(self.fill),
+            "stroke" => self.stroke = parse_ink(value).unwrap_or(self.stroke),
+            "color" => self.color = parse_color(value).unwrap_or(self.color),
+            "stroke-width" => {
+                self.stroke_width = length(value)
+                    .filter(|&w| w >= 0.0)
+                    .unwrap_or(self.stroke_width)
+            }
+            "stroke-linecap" => {
+                self.cap = match value {
+                    "butt" => LineCap::Butt,
+                    "round" => LineCap::Round,
+                    "square" => LineCap::Square,
+                    _ => self.cap,
+                }
+            }
+            "fill-rule" => {
+                self.even_odd = match value {
+                    "evenodd" => true,
+                    "nonzero" => false,
+                    _ => self.even_odd,
+                }
+            }
+            "fill-opacity" => self.fill_opacity = parse_opacity(value).unwrap_or(self.fill_opacity),
+            "stroke-opacity" => {
+                self.stroke_opacity = parse_opacity(value).unwrap_or(self.stroke_opacity)
+            }
+            _ => {}
+        }
+    }
+
+    /// Cor ARGB direta de uma tinta.
+    fn resolve(&self, ink: Ink) -> Option<u32> {
+        match ink {
+            Ink::None => None,
+            Ink::Color(color) => Some(color),
+            Ink::Current => Some(self.color),
+        }
+    }
+}
+
+// =============================================================================
+// PINTURA
+// =============================================================================
+
+/// Buffer de saída.
+struct Target<'a> {
+    pixels: &'a mut [u32],
+    width: u32,
+    height: u32,
+}
+
+impl Target<'_> {
+    /// Preenche e contorna `path`.
+    fn paint(&mut self, path: &Path, style: &Style) {
+        if path.is_empty() {
+            return;
+        }
+        let lines = path.flatten(&style.transform);
+
+        if let Some(color) = style.resolve(style.fill) {
+            let mut raster = Rasterizer::new(self.width, self.height);
+            geometry::fill(&mut raster, &lines);
+            let coverage = if style.even_odd {
+                raster.into_coverage_even_odd()
+            } else {
+                raster.into_coverage()
+            };
+            self.composite(&coverage, color, style.opacity * style.fill_opacity);
+        }
+
+        if let Some(color) = style.resolve(style.stroke) {
+            let half = style.stroke_width * style.transform.mean_scale() / 2.0;
+            if half > 0.0 {
+                let mut raster = Rasterizer::new(self.width, self.height);
+                geometry::stroke(&mut raster, &lines, half, style.cap);
+                let coverage = raster.into_coverage();
+                self.composite(&coverage, color, style.opacity * style.stroke_opacity);
+            }
+        }
+    }
+
+    /// Compõe `color` (ARGB direto) com opacidade e cobertura por pixel.
+    fn composite(&mut self, coverage: &[u8], color: u32, opacity: f32) {
+        let alpha = ((color >> 24) as f32 * opacity.clamp(0.0, 1.0) + 0.5) as u32;
+        if alpha == 0 {
+            return;
+        }
+        let rgb = color & 0x00FF_FFFF;
+
+        for (pixel, &c) in self.pixels.iter_mut().zip(coverage) {
+            if c != 0 {
+                let a = div255(alpha * c as u32);
+                *pixel = over(*pixel, premultiply(a << 24 | rgb));
+            }
+        }
+    }
+}
+
+/// Caminho de um elemento de forma (`None` para contêineres).
+fn shape(name: &str, attrs: &[(&str, &str)]) -> Option<Path> {
+    let num = |key: &str| attr(attrs, key).and_then(length).unwrap_or(0.0);
+
+    match name {
+        "path" => attr(attrs, "d").map(path::parse_path_data),
+        "rect" => {
+            // rx/ry ausente copia o outro
+            let rx = attr(attrs, "rx").and_then(length);
+            let ry = attr(attrs, "ry").and_then(length);
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0),
+            };
+            Some(path::rect(
+                num("x"),
+                num("y"),
+                num("width"),
+                num("height"),
+                rx,
+                ry,
+            ))
+        }
+        "circle" => {
+            let r = num("r");
+            Some(path::ellipse(num("cx"), num("cy"), r, r))
+        }
+        "ellipse" => Some(path::ellipse(num("cx"), num("cy"), num("rx"), num("ry"))),
+        "line" => {
+            let mut line = Path::new();
+            line.move_to(PointF::new(num("x1"), num("y1")));
+            line.line_to(PointF::new(num("x2"), num("y2")));
+            Some(line)
+        }
+        "polyline" => attr(attrs, "points").map(|p| path::polyline(p, false)),
+        "polygon" => attr(attrs, "points").map(|p| path::polyline(p, true)),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// ATRIBUTOS
+// =============================================================================
+
+/// Elementos desenhados ou que agrupam elementos desenhados.
+fn is_supported(name: &str) -> bool {
+    matches!(
+        name,
+        "svg"
+            | "g"
+            | "a"
+            | "path"
+            | "rect"
+            | "circle"
+            | "ellipse"
+            | "line"
+            | "polyline"
+            | "polygon"
+    )
+}
+
+/// Nome sem prefixo de namespace (`svg:path` -> `path`).
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Valor de um atributo.
+fn attr<'a>(attrs: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    attrs.iter().find(|(k, _)| *k == key).map(|&(_, v)| v)
+}
+
+/// Comprimento em unidades do usuário (`12`, `12px`); outras unidades
+/// (`%`, `em`...) não são suportadas.
+fn length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value);
+    let mut scanner = Scanner::new(number);
+    let result = scanner.number()?;
+    scanner.at_end().then_some(result)
+}
+
+/// Opacidade em 0-1 (`0.5` ou `50%`).
+fn parse_opacity(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let opacity = match value.strip_suffix('%') {
+        Some(percent) => length(percent)? / 100.0,
+        None => length(value)?,
+    };
+    Some(opacity.clamp(0.0, 1.0))
+}
+
+/// Tinta: cor, `none` ou `currentColor`.
+fn parse_ink(value: &str) -> Option<Ink> {
+    match value.trim() {
+        "none" => Some(Ink::None),
+        "currentColor" => Some(Ink::Current),
+        value => parse_color(value).map(Ink::Color),
+    }
+}
+
+/// Cor em ARGB direto.
+fn parse_color(value: &str) -> Option<u32> {
+    let value = value.trim();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let v = u32::from_str_radix(hex, 16).ok()?;
+        return match hex.len() {
+            // #rgb: cada dígito repetido
+            3 => {
+                let (r, g, b) = ((v >> 8) & 0xF, (v >> 4) & 0xF, v & 0xF);
+                Some(0xFF00_0000 | (r * 17) << 16 | (g * 17) << 8 | (b * 17))
+            }
+            6 => Some(0xFF00_0000 | v),
+            _ => None,
+        };
+    }
+
+    if let Some(args) = value.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+        let mut channels = [0u32; 3];
+        let mut parts = args.split(',');
+        for channel in channels.iter_mut() {
+            let part = parts.next()?.trim();
+            let v = match part.strip_suffix('%') {
+                Some(percent) => length(percent)? * 2.55,
+                None => length(part)?,
+            };
+            *channel = (v.clamp(0.0, 255.0) + 0.5) as u32;
+        }
+        let [r, g, b] = channels;
+        return Some(0xFF00_0000 | r << 16 | g << 8 | b);
+    }
+
+    let rgb = match value {
+        "transparent" => return Some(0),
+        "black" => 0x000000,
+        "white" => 0xFFFFFF,
+        "red" => 0xFF0000,
+        "lime" => 0x00FF00,
+        "green" => 0x008000,
+        "blue" => 0x0000FF,
+        "yellow" => 0xFFFF00,
+        "orange" => 0xFFA500,
+        "purple" => 0x800080,
+        "gray" | "grey" => 0x808080,
+        "silver" => 0xC0C0C0,
+        _ => return None,
+    };
+    Some(0xFF00_0000 | rgb)
+}
+
+/// Lista de transformações (`translate(10) rotate(45 12 12)`).
+///
+/// Qualquer erro invalida o atributo inteiro.
+fn parse_transform(value: &str) -> Option<Transform> {
+    let mut transform = Transform::IDENTITY;
+    let mut rest = value;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            return Some(transform);
+        }
+        let open = rest.find('(')?;
+        let close = rest.find(')')?;
+        if close < open {
+            return None;
+        }
+        let name = rest[..open].trim();
+        let args = numbers(&rest[open + 1..close]);
+        rest = &rest[close + 1..];
+
+        let tan = |degrees: f32| {
+            let (sin, cos) = sin_cos(degrees * PI / 180.0);
+            sin / cos
+        };
+        let next = match (name, args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Transform::new(a, b, c, d, e, f),
+            ("translate", &[x]) => Transform::translate(x, 0.0),
+            ("translate", &[x, y]) => Transform::translate(x, y),
+            ("scale", &[s]) => Transform::scale(s, s),
+            ("scale", &[x, y]) => Transform::scale(x, y),
+            ("rotate", &[a]) => Transform::rotate(a),
+            ("rotate", &[a, cx, cy]) => Transform::translate(cx, cy)
+                .then(Transform::rotate(a))
+                .then(Transform::translate(-cx, -cy)),
+            ("skewX", &[a]) => Transform::new(1.0, 0.0, tan(a), 1.0, 0.0, 0.0),
+            ("skewY", &[a]) => Transform::new(1.0, tan(a), 0.0, 1.0, 0.0, 0.0),
+            _ => return None,
+        };
+        transform = transform.then(next);
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Preto opaco (pixels pré-multiplicados).
+    const BLACK: u32 = 0xFF00_0000;
+
+    /// Documento com `body` num `viewBox` de 10x10.
+    fn document(body: &str) -> Vec<u8> {
+        let text = alloc::format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 10 10\">{}</svg>",
+            body
+        );
+        text.into_bytes()
+    }
+
+    /// Pixel `(x, y)` de uma imagem.
+    fn pixel(image: &Image, x: u32, y: u32) -> u32 {
+        image.pixels[(y * image.width + x) as usize]
+    }
+
+    /// Colunas (ou linhas, se `vertical`) totalmente opacas.
+    fn opaque_span(image: &Image, vertical: bool) -> (u32, u32) {
+        let (length, across) = if vertical {
+            (image.height, image.width)
+        } else {
+            (image.width, image.height)
+        };
+        let opaque: Vec<u32> = (0..length)
+            .filter(|&i| {
+                (0..across).all(|j| {
+                    let (x, y) = if vertical { (j, i) } else { (i, j) };
+                    pixel(image, x, y) == BLACK
+                })
+            })
+            .collect();
+        (opaque[0], opaque[opaque.len() - 1] + 1)
+    }
+
+    /// Aplica uma transformação a `(x, y)`.
+    fn map(transform: &str, x: f32, y: f32) -> (f32, f32) {
+        let p = parse_transform(transform).unwrap().apply(PointF::new(x, y));
+        (p.x, p.y)
+    }
+
+    /// Confere dois pontos a menos de 0.001.
+    fn assert_near(a: (f32, f32), b: (f32, f32)) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn fill_rules_differ_on_overlaps() {
+        // Dois quadrados com o mesmo sentido: nonzero cobre o miolo, evenodd fura
+        let squares = "M0 0 H10 V10 H0 Z M3 3 H7 V7 H3 Z";
+        let nonzero = decode(&document(&alloc::format!("<path d=\"{}\"/>", squares))).unwrap();
+        let evenodd = decode(&document(&alloc::format!(
+            "<path fill-rule=\"evenodd\" d=\"{}\"/>",
+            squares
+        )))
+        .unwrap();
+        assert_eq!(
+            (pixel(&nonzero, 1, 1), pixel(&nonzero, 5, 5)),
+            (BLACK, BLACK)
+        );
+        assert_eq!((pixel(&evenodd, 1, 1), pixel(&evenodd, 5, 5)), (BLACK, 0));
+
+        // Sentido oposto fura nas duas regras
+        let opposite =
+            "<path style=\"fill-rule: nonzero\" d=\"M0 0 H10 V10 H0 Z M3 3 V7 H7 V3 Z\"/>";
+        let image = decode(&document(opposite)).unwrap();
+        assert_eq!((pixel(&image, 1, 1), pixel(&image, 5, 5)), (BLACK, 0));
+    }
+
+    #[test]
+    fn fill_rule_is_inherited() {
+        let body = "<g fill-rule=\"evenodd\"><path d=\"M0 0 H10 V10 H0 Z M3 3 H7 V7 H3 Z\"/></g>";
+        let image = decode(&document(body)).unwrap();
+        assert_eq!(pixel(&image, 5, 5), 0);
+    }
+
+    #[test]
+    fn view_box_meets_requested_size() {
+        let square = document("<rect width=\"10\" height=\"10\"/>");
+
+        // Mesma proporção: ocupa tudo
+        let image = render(&square, 20, 20).unwrap();
+        assert!(image.pixels.iter().all(|&px| px == BLACK));
+
+        // Mais larga: escala 1, centralizado com faixas laterais vazias
+        let image = render(&square, 20, 10).unwrap();
+        assert_eq!(opaque_span(&image, false), (5, 15));
+        assert_eq!((pixel(&image, 4, 5), pixel(&image, 15, 5)), (0, 0));
+
+        // Mais alta: escala 3, faixas em cima e embaixo
+        let image = render(&square, 30, 40).unwrap();
+        assert_eq!(opaque_span(&image, true), (5, 35));
+        assert_eq!((pixel(&image, 15, 4), pixel(&image, 15, 35)), (0, 0));
+    }
+
+    #[test]
+    fn view_box_origin_is_translated() {
+        let svg = b"<svg viewBox=\"10 20 10 10\"><rect x=\"10\" y=\"20\" width=\"5\" height=\"10\"/></svg>";
+        let image = render(svg, 20, 20).unwrap();
+        assert_eq!(opaque_span(&image, false), (0, 10));
+        assert_eq!(pixel(&image, 10, 10), 0);
+    }
+
+    #[test]
+    fn intrinsic_size_comes_from_the_root() {
+        let svg = b"<svg width=\"12px\" height=\"6\" viewBox=\"0 0 4 2\"/>";
+        let image = decode(svg).unwrap();
+        assert_eq!((image.width, image.height), (12, 6));
+
+        let image = decode(&document("")).unwrap();
+        assert_eq!((image.width, image.height), (10, 10));
+
+        assert_eq!(
+            decode(b"<svg width=\"10\"/>").err(),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(decode(b"<html/>").err(), Some(ImageError::BadMagic));
+    }
+
+    #[test]
+    fn parses_transform_lists() {
+        assert_near(map("translate(10, 20) scale(2)", 1.0, 1.0), (12.0, 22.0));
+        assert_near(map("scale(2) translate(10 20)", 1.0, 1.0), (22.0, 42.0));
+        assert_near(map("translate(3)", 1.0, 1.0), (4.0, 1.0));
+        assert_near(map("scale(2, 3)", 1.0, 1.0), (2.0, 3.0));
+        assert_near(map("matrix(1 0 0 1 3 4)", 0.0, 0.0), (3.0, 4.0));
+        assert_near(map("rotate(90)", 1.0, 0.0), (0.0, 1.0));
+        assert_near(map("rotate(90 5 5)", 10.0, 5.0), (5.0, 10.0));
+        assert_near(map("skewX(45)", 0.0, 10.0), (10.0, 10.0));
+        assert_near(map("skewY(45)", 10.0, 0.0), (10.0, 10.0));
+        assert_eq!(parse_transform(""), Some(Transform::IDENTITY));
+    }
+
+    #[test]
+    fn rejects_invalid_transforms() {
+        assert_eq!(parse_transform("rotate(1 2)"), None);
+        assert_eq!(parse_transform("scale(2"), None);
+        assert_eq!(parse_transform("translate(1) spin(3)"), None);
+        assert_eq!(parse_transform(")translate(1 2("), None);
+    }
+
+    #[test]
+    fn group_transforms_accumulate() {
+        // Largura 5 ampliada para 10, deslocada para 10..20 e reduzida pelo
+        // grupo para 5..10
+        let body = "<g transform=\"scale(0.5)\"><rect transform=\"translate(10 0) scale(2)\" width=\"5\" height=\"10\"/></g>";
+        let image = decode(&document(body)).unwrap();
+        assert_eq!(opaque_span(&image, false), (5, 10));
+    }
+}
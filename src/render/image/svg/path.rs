@@ -0,0 +1,683 @@
+//! # Caminhos
+//!
+//! Dados de caminho (`d`) e formas básicas convertidos em segmentos de
+//! reta e Bézier no espaço do usuário.
+//!
+//! ```text
+//! M m L l H h V v C c S s Q q T t A a Z z
+//! ```
+//!
+//! Como manda a especificação, um erro nos dados interrompe o caminho,
+//! mas o trecho já lido é desenhado.
+
+use alloc::vec::Vec;
+
+use super::geometry::{atan2, flatten_cubic, flatten_quad, sin_cos, Polyline, Transform, PI};
+use crate::render::raster::{ceilf, PointF};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Distância dos controles da cúbica que aproxima um quarto de círculo.
+const KAPPA: f32 = 0.552_284_8;
+
+// =============================================================================
+// SCANNER
+// =============================================================================
+
+/// Leitor de números separados por espaços e vírgulas.
+pub struct Scanner<'a> {
+    data: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Pula espaços e vírgulas.
+    pub fn skip_separators(&mut self) {
+        let bytes = self.data.as_bytes();
+        while self.pos < bytes.len()
+            && (bytes[self.pos].is_ascii_whitespace() || bytes[self.pos] == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Chegou ao fim (após separadores).
+    pub fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.data.len()
+    }
+
+    /// Próximo byte sem consumir (após separadores).
+    pub fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.data.as_bytes().get(self.pos).copied()
+    }
+
+    /// Consome um byte.
+    pub fn bump(&mut self) {
+        self.pos += 1;
+    }
+
+    /// Lê um número (`-1.5e3`, `.5`, `+2`...).
+    pub fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let bytes = self.data.as_bytes();
+        let start = self.pos;
+        let mut pos = start;
+        let digits = |pos: &mut usize| {
+            let from = *pos;
+            while *pos < bytes.len() && bytes[*pos].is_ascii_digit() {
+                *pos += 1;
+            }
+            *pos - from
+        };
+
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+            pos += 1;
+        }
+        let mut count = digits(&mut pos);
+        if bytes.get(pos) == Some(&b'.') {
+            pos += 1;
+            count += digits(&mut pos);
+        }
+        if count == 0 {
+            return None;
+        }
+        // Expoente só se houver dígitos depois (evita consumir "em")
+        if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+            let mut exp = pos + 1;
+            if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+                exp += 1;
+            }
+            if digits(&mut exp) > 0 {
+                pos = exp;
+            }
+        }
+
+        let value: f32 = self.data[start..pos].parse().ok()?;
+        self.pos = pos;
+        value.is_finite().then_some(value)
+    }
+
+    /// Lê uma flag de arco (`0` ou `1`, que podem vir grudadas).
+    pub fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.bump();
+        Some(flag)
+    }
+
+    /// Lê um ponto.
+    fn point(&mut self) -> Option<PointF> {
+        Some(PointF::new(self.number()?, self.number()?))
+    }
+}
+
+/// Lê todos os números de uma lista (`viewBox`, `points`).
+pub fn numbers(data: &str) -> Vec<f32> {
+    let mut scanner = Scanner::new(data);
+    let mut values = Vec::new();
+    while let Some(value) = scanner.number() {
+        values.push(value);
+    }
+    values
+}
+
+// =============================================================================
+// CAMINHO
+// =============================================================================
+
+/// Segmento de caminho no espaço do usuário.
+#[derive(Clone, Copy)]
+enum Segment {
+    MoveTo(PointF),
+    LineTo(PointF),
+    QuadTo(PointF, PointF),
+    CubicTo(PointF, PointF, PointF),
+    Close,
+}
+
+/// Caminho: sequência de subcaminhos.
+#[derive(Default)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn move_to(&mut self, p: PointF) {
+        self.segments.push(Segment::MoveTo(p));
+    }
+
+    pub fn line_to(&mut self, p: PointF) {
+        self.segments.push(Segment::LineTo(p));
+    }
+
+    pub fn quad_to(&mut self, c: PointF, p: PointF) {
+        self.segments.push(Segment::QuadTo(c, p));
+    }
+
+    pub fn cubic_to(&mut self, c1: PointF, c2: PointF, p: PointF) {
+        self.segments.push(Segment::CubicTo(c1, c2, p));
+    }
+
+    pub fn close(&mut self) {
+        self.segments.push(Segment::Close);
+    }
+
+    /// Aplica `transform` e achata as curvas em polilinhas (em pixels).
+    pub fn flatten(&self, transform: &Transform) -> Vec<Polyline> {
+        let mut lines = Vec::new();
+        let mut points: Vec<PointF> = Vec::new();
+        let mut start = PointF::new(0.0, 0.0);
+
+        for &segment in &self.segments {
+            // Desenho após `Z` recomeça do início do subcaminho
+            if !matches!(segment, Segment::MoveTo(_) | Segment::Close) && points.is_empty() {
+                points.push(start);
+            }
+            match segment {
+                Segment::MoveTo(p) => {
+                    finish(&mut lines, &mut points, false);
+                    start = transform.apply(p);
+                    points.push(start);
+                }
+                Segment::LineTo(p) => points.push(transform.apply(p)),
+                Segment::QuadTo(c, p) => {
+                    let p0 = points[points.len() - 1];
+                    flatten_quad(&mut points, p0, transform.apply(c), transform.apply(p));
+                }
+                Segment::CubicTo(c1, c2, p) => {
+                    let p0 = points[points.len() - 1];
+                    let (c1, c2) = (transform.apply(c1), transform.apply(c2));
+                    flatten_cubic(&mut points, p0, c1, c2, transform.apply(p));
+                }
+                Segment::Close => finish(&mut lines, &mut points, true),
+            }
+        }
+
+        finish(&mut lines, &mut points, false);
+        lines
+    }
+}
+
+/// Encerra o subcaminho atual, se houver.
+fn finish(lines: &mut Vec<Polyline>, points: &mut Vec<PointF>, closed: bool) {
+    if !points.is_empty() {
+        lines.push(Polyline {
+            points: core::mem::take(points),
+            closed,
+        });
+    }
+}
+
+// =============================================================================
+// DADOS DE CAMINHO
+// =============================================================================
+
+/// Lê o atributo `d` de um `<path>`.
+pub fn parse_path_data(data: &str) -> Path {
+    let mut path = Path::new();
+    // Erro interrompe a leitura; o que veio antes continua valendo
+    let _ = read_commands(&mut path, data);
+    path
+}
+
+/// Lê comandos até o fim ou até o primeiro erro.
+fn read_commands(path: &mut Path, data: &str) -> Option<()> {
+    let mut scanner = Scanner::new(data);
+    let mut command = 0u8;
+    let mut current = PointF::new(0.0, 0.0);
+    let mut start = current;
+    // Último controle de cúbica/quadrática (para S e T)
+    let mut last_cubic: Option<PointF> = None;
+    let mut last_quad: Option<PointF> = None;
+
+    while let Some(next) = scanner.peek() {
+        if next.is_ascii_alphabetic() {
+            command = next;
+            scanner.bump();
+        } else if command == 0 {
+            // Número sem comando (ou depois de Z)
+            return None;
+        }
+
+        let relative = command.is_ascii_lowercase();
+        let base = if relative {
+            current
+        } else {
+            PointF::new(0.0, 0.0)
+        };
+        let offset = |p: PointF| PointF::new(base.x + p.x, base.y + p.y);
+        let (mut cubic, mut quad) = (None, None);
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                current = offset(scanner.point()?);
+                start = current;
+                path.move_to(current);
+                // Pares seguintes são retas implícitas
+                command = if relative { b'l' } else { b'L' };
+            }
+            b'L' => {
+                current = offset(scanner.point()?);
+                path.line_to(current);
+            }
+            b'H' => {
+                current.x = base.x + scanner.number()?;
+                path.line_to(current);
+            }
+            b'V' => {
+                current.y = base.y + scanner.number()?;
+                path.line_to(current);
+            }
+            b'C' => {
+                let c1 = offset(scanner.point()?);
+                let c2 = offset(scanner.point()?);
+                current = offset(scanner.point()?);
+                path.cubic_to(c1, c2, current);
+                cubic = Some(c2);
+            }
+            b'S' => {
+                let c1 = reflect(last_cubic, current);
+                let c2 = offset(scanner.point()?);
+                let p = offset(scanner.point()?);
+                path.cubic_to(c1, c2, p);
+                current = p;
+                cubic = Some(c2);
+            }
+            b'Q' => {
+                let c = offset(scanner.point()?);
+                current = offset(scanner.point()?);
+                path.quad_to(c, current);
+                quad = Some(c);
+            }
+            b'T' => {
+                let c = reflect(last_quad, current);
+                current = offset(scanner.point()?);
+                path.quad_to(c, current);
+                quad = Some(c);
+            }
+            b'A' => {
+                let rx = scanner.number()?;
+                let ry = scanner.number()?;
+                let rotation = scanner.number()?;
+                let large = scanner.flag()?;
+                let sweep = scanner.flag()?;
+                let p = offset(scanner.point()?);
+                arc_to(path, current, rx, ry, rotation, large, sweep, p);
+                current = p;
+            }
+            b'Z' => {
+                path.close();
+                current = start;
+                command = 0;
+            }
+            _ => return None,
+        }
+
+        last_cubic = cubic;
+        last_quad = quad;
+    }
+
+    Some(())
+}
+
+/// Reflexo do controle anterior em torno do ponto atual.
+fn reflect(control: Option<PointF>, current: PointF) -> PointF {
+    match control {
+        Some(c) => PointF::new(2.0 * current.x - c.x, 2.0 * current.y - c.y),
+        None => current,
+    }
+}
+
+/// Arco elíptico de `from` a `to`, aproximado por cúbicas de até 90°.
+///
+/// Conversão para a parametrização por centro da especificação SVG
+/// (apêndice F.6.5), com raios ampliados se não alcançarem o destino.
+#[allow(clippy::too_many_arguments)]
+fn arc_to(
+    path: &mut Path,
+    from: PointF,
+    rx: f32,
+    ry: f32,
+    rotation: f32,
+    large: bool,
+    sweep: bool,
+    to: PointF,
+) {
+    if from == to {
+        return;
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        path.line_to(to);
+        return;
+    }
+
+    let (sin_phi, cos_phi) = sin_cos(rotation * PI / 180.0);
+    let dx2 = (from.x - to.x) / 2.0;
+    let dy2 = (from.y - to.y) / 2.0;
+    let x1p = cos_phi * dx2 + sin_phi * dy2;
+    let y1p = -sin_phi * dx2 + cos_phi * dy2;
+
+    let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+    if lambda > 1.0 {
+        let s = rdsmath::sqrtf(lambda);
+        rx *= s;
+        ry *= s;
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+    let den = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+    let sign = if large == sweep { -1.0 } else { 1.0 };
+    let coef = sign * rdsmath::sqrtf((num / den).max(0.0));
+    let cxp = coef * rx * y1p / ry;
+    let cyp = -coef * ry * x1p / rx;
+    let cx = cos_phi * cxp - sin_phi * cyp + (from.x + to.x) / 2.0;
+    let cy = sin_phi * cxp + cos_phi * cyp + (from.y + to.y) / 2.0;
+
+    let (ux, uy) = ((x1p - cxp) / rx, (y1p - cyp) / ry);
+    let (vx, vy) = ((-x1p - cxp) / rx, (-y1p - cyp) / ry);
+    let theta = atan2(uy, ux);
+    let mut delta = atan2(ux * vy - uy * vx, ux * vx + uy * vy);
+    if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    } else if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    }
+
+    let count = (ceilf(delta.abs() / (PI / 2.0)) as usize).max(1);
+    let step = delta / count as f32;
+    let (sin_q, cos_q) = sin_cos(step / 4.0);
+    let k = 4.0 / 3.0 * sin_q / cos_q;
+
+    // Ponto e derivada da elipse no ângulo `t`
+    let at = |t: f32| {
+        let (sin, cos) = sin_cos(t);
+        let point = PointF::new(
+            cx + rx * cos * cos_phi - ry * sin * sin_phi,
+            cy + rx * cos * sin_phi + ry * sin * cos_phi,
+        );
+        let tangent = PointF::new(
+            -rx * sin * cos_phi - ry * cos * sin_phi,
+            -rx * sin * sin_phi + ry * cos * cos_phi,
+        );
+        (point, tangent)
+    };
+
+    let (mut p0, mut d0) = at(theta);
+    for i in 1..=count {
+        let (p1, d1) = at(theta + step * i as f32);
+        let end = if i == count { to } else { p1 };
+        path.cubic_to(
+            PointF::new(p0.x + k * d0.x, p0.y + k * d0.y),
+            PointF::new(p1.x - k * d1.x, p1.y - k * d1.y),
+            end,
+        );
+        (p0, d0) = (p1, d1);
+    }
+}
+
+// =============================================================================
+// FORMAS
+// =============================================================================
+
+/// `<rect>`, com cantos arredondados se `rx`/`ry` > 0.
+pub fn rect(x: f32, y: f32, width: f32, height: f32, rx: f32, ry: f32) -> Path {
+    let mut path = Path::new();
+    if width <= 0.0 || height <= 0.0 {
+        return path;
+    }
+    let rx = rx.clamp(0.0, width / 2.0);
+    let ry = ry.clamp(0.0, height / 2.0);
+    let (right, bottom) = (x + width, y + height);
+
+    if rx == 0.0 || ry == 0.0 {
+        path.move_to(PointF::new(x, y));
+        path.line_to(PointF::new(right, y));
+        path.line_to(PointF::new(right, bottom));
+        path.line_to(PointF::new(x, bottom));
+        path.close();
+        return path;
+    }
+
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+    path.move_to(PointF::new(x + rx, y));
+    path.line_to(PointF::new(right - rx, y));
+    path.cubic_to(
+        PointF::new(right - rx + kx, y),
+        PointF::new(right, y + ry - ky),
+        PointF::new(right, y + ry),
+    );
+    path.line_to(PointF::new(right, bottom - ry));
+    path.cubic_to(
+        PointF::new(right, bottom - ry + ky),
+        PointF::new(right - rx + kx, bottom),
+        PointF::new(right - rx, bottom),
+    );
+    path.line_to(PointF::new(x + rx, bottom));
+    path.cubic_to(
+        PointF::new(x + rx - kx, bottom),
+        PointF::new(x, bottom - ry + ky),
+        PointF::new(x, bottom - ry),
+    );
+    path.line_to(PointF::new(x, y + ry));
+    path.cubic_to(
+        PointF::new(x, y + ry - ky),
+        PointF::new(x + rx - kx, y),
+        PointF::new(x + rx, y),
+    );
+    path.close();
+    path
+}
+
+/// `<ellipse>` (e `<circle>`, com `rx == ry`).
+pub fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> Path {
+    let mut path = Path::new();
+    if rx <= 0.0 || ry <= 0.0 {
+        return path;
+    }
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+    path.move_to(PointF::new(cx + rx, cy));
+    path.cubic_to(
+        PointF::new(cx + rx, cy + ky),
+        PointF::new(cx + kx, cy + ry),
+        PointF::new(cx, cy + ry),
+    );
+    path.cubic_to(
+        PointF::new(cx - kx, cy + ry),
+        PointF::new(cx - rx, cy + ky),
+        PointF::new(cx - rx, cy),
+    );
+    path.cubic_to(
+        PointF::new(cx - rx, cy - ky),
+        PointF::new(cx - kx, cy - ry),
+        PointF::new(cx, cy - ry),
+    );
+    path.cubic_to(
+        PointF::new(cx + kx, cy - ry),
+        PointF::new(cx + rx, cy - ky),
+        PointF::new(cx + rx, cy),
+    );
+    path.close();
+    path
+}
+
+/// `<polyline>` e `<polygon>` (`closed`) a partir do atributo `points`.
+pub fn polyline(points: &str, closed: bool) -> Path {
+    let mut path = Path::new();
+    let values = numbers(points);
+    let mut pairs = values.chunks_exact(2).map(|p| PointF::new(p[0], p[1]));
+    if let Some(first) = pairs.next() {
+        path.move_to(first);
+        for p in pairs {
+            path.line_to(p);
+        }
+        if closed {
+            path.close();
+        }
+    }
+    path
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Subcaminhos achatados de `d`: pontos e se foram fechados.
+    fn flatten(d: &str) -> Vec<(Vec<(f32, f32)>, bool)> {
+        parse_path_data(d)
+            .flatten(&Transform::IDENTITY)
+            .into_iter()
+            .map(|line| {
+                (
+                    line.points.iter().map(|p| (p.x, p.y)).collect(),
+                    line.closed,
+                )
+            })
+            .collect()
+    }
+
+    /// Pontos de um caminho com um único subcaminho.
+    fn points(d: &str) -> Vec<(f32, f32)> {
+        let mut lines = flatten(d);
+        assert_eq!(lines.len(), 1, "{}", d);
+        lines.remove(0).0
+    }
+
+    /// Confere que os dois caminhos são iguais, a menos de `tolerance`.
+    fn assert_close(a: &str, b: &str, tolerance: f32) {
+        let (a, b) = (points(a), points(b));
+        assert_eq!(a.len(), b.len());
+        for (p, q) in a.iter().zip(&b) {
+            assert!(
+                (p.0 - q.0).abs() <= tolerance && (p.1 - q.1).abs() <= tolerance,
+                "{:?} != {:?}",
+                p,
+                q
+            );
+        }
+    }
+
+    /// Confere que todos os pontos estão a `radius` de `center`.
+    fn assert_on_circle(points: &[(f32, f32)], center: (f32, f32), radius: f32) {
+        for &(x, y) in points {
+            let distance =
+                rdsmath::sqrtf((x - center.0) * (x - center.0) + (y - center.1) * (y - center.1));
+            assert!((distance - radius).abs() < 0.05, "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn reads_relative_and_implicit_commands() {
+        assert_eq!(flatten("m1 2 l3 4 h5 v-6 z"), flatten("M1 2 L4 6 H9 V0 Z"));
+        // Pares depois de M são retas; números colados e expoentes
+        assert_eq!(flatten("M0 0 10 0 10 10"), flatten("M0 0 L10 0 L10 10"));
+        assert_eq!(
+            flatten("M0,0L1e1-5.5.5-1"),
+            flatten("M0 0 L10 -5.5 L0.5 -1")
+        );
+        // Depois de Z, o próximo subcaminho parte do início do anterior
+        assert_eq!(
+            flatten("M1 1 L5 1 Z l0 4"),
+            flatten("M1 1 L5 1 Z M1 1 L1 5")
+        );
+    }
+
+    #[test]
+    fn reflects_smooth_curve_controls() {
+        assert_eq!(
+            flatten("M0 0 C0 5 5 10 10 10 S20 5 20 0"),
+            flatten("M0 0 C0 5 5 10 10 10 C15 10 20 5 20 0")
+        );
+        assert_eq!(
+            flatten("M0 0 Q5 10 10 0 t10 0"),
+            flatten("M0 0 Q5 10 10 0 Q15 -10 20 0")
+        );
+        // Sem curva anterior, o controle é o ponto atual
+        assert_eq!(flatten("M0 0 T10 0"), flatten("M0 0 Q0 0 10 0"));
+    }
+
+    #[test]
+    fn stops_at_the_first_error() {
+        assert_eq!(flatten("M0 0 L10 0 L5 x L0 10"), flatten("M0 0 L10 0"));
+        assert_eq!(flatten("M0 0 L10 0 Z 5 5"), flatten("M0 0 L10 0 Z"));
+        assert!(flatten("10 10 L20 20").is_empty());
+    }
+
+    #[test]
+    fn draws_arcs_on_the_circle() {
+        // Semicírculo de raio 5 em volta de (5, 0): por cima com sweep 1,
+        // por baixo com sweep 0
+        let above = points("M0 0 A5 5 0 0 1 10 0");
+        assert_on_circle(&above, (5.0, 0.0), 5.0);
+        assert!(above.iter().all(|p| p.1 <= 0.01));
+        assert!(above.iter().any(|p| p.1 < -4.9));
+        assert_eq!(above.last(), Some(&(10.0, 0.0)));
+
+        let below = points("M0 0 A5 5 0 0 0 10 0");
+        assert_on_circle(&below, (5.0, 0.0), 5.0);
+        assert!(below.iter().all(|p| p.1 >= -0.01));
+    }
+
+    #[test]
+    fn picks_the_large_arc() {
+        // Raio 10 entre (0, 0) e (10, 0): o arco pequeno sobe ~1.3, o grande ~18.7
+        let small = points("M0 0 A10 10 0 0 1 10 0");
+        let large = points("M0 0 A10 10 0 1 1 10 0");
+        assert_on_circle(&small, (5.0, 8.66), 10.0);
+        assert_on_circle(&large, (5.0, -8.66), 10.0);
+        let top = |points: &[(f32, f32)]| points.iter().map(|p| p.1).fold(0.0, f32::min);
+        assert!((top(&small) + 1.34).abs() < 0.05);
+        assert!((top(&large) + 18.66).abs() < 0.05);
+    }
+
+    #[test]
+    fn scales_up_short_radii() {
+        // Raios que não alcançam o destino crescem até o semicírculo
+        let arc = points("M0 0 A1 1 0 0 1 10 0");
+        assert_on_circle(&arc, (5.0, 0.0), 5.0);
+        assert_close("M0 0 A1 1 0 0 1 10 0", "M0 0 A5 5 0 0 1 10 0", 0.01);
+        // Elipse 2:1 ampliada na mesma proporção
+        assert_close("M0 0 A2 1 0 0 1 20 0", "M0 0 A10 5 0 0 1 20 0", 0.01);
+    }
+
+    #[test]
+    fn rotates_elliptical_arcs() {
+        assert_close("M0 0 A10 5 90 0 1 10 0", "M0 0 A5 10 0 0 1 10 0", 0.05);
+    }
+
+    #[test]
+    fn degenerate_arcs() {
+        // Raio zero vira reta; destino igual à origem não desenha nada
+        assert_eq!(flatten("M0 0 A0 5 0 0 1 10 0"), flatten("M0 0 L10 0"));
+        assert_eq!(flatten("M0 0 A5 0 30 1 0 10 0"), flatten("M0 0 L10 0"));
+        assert_eq!(flatten("M3 3 A5 5 0 0 1 3 3"), flatten("M3 3"));
+        // Raios negativos valem o módulo
+        assert_eq!(
+            flatten("M0 0 A-5 -5 0 0 1 10 0"),
+            flatten("M0 0 A5 5 0 0 1 10 0")
+        );
+    }
+}
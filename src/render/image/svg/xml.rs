@@ -0,0 +1,148 @@
+//! # XML
+//!
+//! Tokenizador XML mínimo: só tags e atributos.
+//!
+//! Texto, comentários, instruções de processamento, `DOCTYPE` e `CDATA`
+//! são pulados. Entidades nos valores não são expandidas.
+
+use alloc::vec::Vec;
+
+use super::super::ImageError;
+
+// =============================================================================
+// TOKENS
+// =============================================================================
+
+/// Tag de abertura ou de fechamento.
+pub enum Token<'a> {
+    /// `<name attr="value" ...>` ou `<name ... />`.
+    Start {
+        name: &'a str,
+        attrs: Vec<(&'a str, &'a str)>,
+        self_closing: bool,
+    },
+    /// `</name>`.
+    End,
+}
+
+/// Iterador de tags sobre um documento.
+pub struct Tokenizer<'a> {
+    data: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Próxima tag, `Ok(None)` no fim do documento.
+    pub fn next_token(&mut self) -> Result<Option<Token<'a>>, ImageError> {
+        loop {
+            let rest = &self.data[self.pos..];
+            let Some(start) = rest.find('<') else {
+                self.pos = self.data.len();
+                return Ok(None);
+            };
+            let rest = &rest[start..];
+            self.pos += start;
+
+            // Trechos ignorados: (abertura, fechamento)
+            let skip = [
+                ("<!--", "-->"),
+                ("<?", "?>"),
+                ("<![CDATA[", "]]>"),
+                ("<!", ">"),
+            ];
+            if let Some((open, close)) = skip.iter().find(|(open, _)| rest.starts_with(open)) {
+                let end = rest[open.len()..]
+                    .find(close)
+                    .ok_or(ImageError::Truncated)?;
+                self.pos += open.len() + end + close.len();
+                continue;
+            }
+
+            if rest.starts_with("</") {
+                let end = rest.find('>').ok_or(ImageError::Truncated)?;
+                self.pos += end + 1;
+                return Ok(Some(Token::End));
+            }
+
+            return self.start_tag().map(Some);
+        }
+    }
+
+    /// Lê `<name attrs...>` a partir de `self.pos`.
+    fn start_tag(&mut self) -> Result<Token<'a>, ImageError> {
+        let data = self.data;
+        let bytes = data.as_bytes();
+        let mut pos = self.pos + 1;
+
+        let name_end = pos
+            + data[pos..]
+                .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+                .ok_or(ImageError::Truncated)?;
+        let name = &data[pos..name_end];
+        if name.is_empty() {
+            return Err(ImageError::Corrupt);
+        }
+        pos = name_end;
+
+        let mut attrs = Vec::new();
+        loop {
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            match bytes.get(pos) {
+                None => return Err(ImageError::Truncated),
+                Some(b'>') => {
+                    self.pos = pos + 1;
+                    return Ok(Token::Start {
+                        name,
+                        attrs,
+                        self_closing: false,
+                    });
+                }
+                Some(b'/') => {
+                    if bytes.get(pos + 1) != Some(&b'>') {
+                        return Err(ImageError::Corrupt);
+                    }
+                    self.pos = pos + 2;
+                    return Ok(Token::Start {
+                        name,
+                        attrs,
+                        self_closing: true,
+                    });
+                }
+                Some(_) => {}
+            }
+
+            // nome = "valor" (ou 'valor')
+            let key_end = pos
+                + data[pos..]
+                    .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+                    .ok_or(ImageError::Truncated)?;
+            let key = &data[pos..key_end];
+            pos = key_end;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if bytes.get(pos) != Some(&b'=') || key.is_empty() {
+                return Err(ImageError::Corrupt);
+            }
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            let quote = match bytes.get(pos) {
+                Some(&q @ (b'"' | b'\'')) => q as char,
+                Some(_) => return Err(ImageError::Corrupt),
+                None => return Err(ImageError::Truncated),
+            };
+            pos += 1;
+            let value_end = pos + data[pos..].find(quote).ok_or(ImageError::Truncated)?;
+            attrs.push((key, &data[pos..value_end]));
+            pos = value_end + 1;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! # Alpha
+//!
+//! Chunk `ALPH`: canal alpha de uma imagem VP8.
+//!
+//! ```text
+//! byte 0: reservado (2) | pré-processamento (2) | filtro (2) | compressão (2)
+//! resto:  alpha cru (largura * altura bytes) ou fluxo VP8L sem cabeçalho
+//! ```
+//!
+//! No fluxo VP8L o alpha vem no canal verde. O filtro guarda cada valor
+//! como diferença para uma predição (esquerda, cima ou gradiente), desfeita
+//! aqui linha a linha.
+
+use alloc::vec::Vec;
+
+use super::{vp8l, Image, ImageError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Alpha sem compressão.
+const COMPRESSION_NONE: u8 = 0;
+
+/// Alpha comprimido como VP8L.
+const COMPRESSION_LOSSLESS: u8 = 1;
+
+/// Filtros de predição.
+const FILTER_NONE: u8 = 0;
+const FILTER_HORIZONTAL: u8 = 1;
+const FILTER_VERTICAL: u8 = 2;
+const FILTER_GRADIENT: u8 = 3;
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Decodifica o chunk e grava o alpha nos pixels (ARGB não
+/// pré-multiplicado) de `image`.
+pub fn apply(data: &[u8], image: &mut Image) -> Result<(), ImageError> {
+    let (&header, payload) = data.split_first().ok_or(ImageError::Truncated)?;
+    let compression = header & 0x03;
+    let filter = (header >> 2) & 0x03;
+    if header >> 6 != 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let (width, height) = (image.width as usize, image.height as usize);
+    let count = width * height;
+    let mut alpha: Vec<u8> = match compression {
+        COMPRESSION_NONE => payload.get(..count).ok_or(ImageError::Truncated)?.to_vec(),
+        COMPRESSION_LOSSLESS => vp8l::decode_stream(payload, image.width, image.height)?
+            .into_iter()
+            .map(|argb| (argb >> 8) as u8)
+            .collect(),
+        _ => return Err(ImageError::InvalidHeader),
+    };
+
+    unfilter(&mut alpha, width, height, filter);
+
+    for (px, &a) in image.pixels.iter_mut().zip(alpha.iter()) {
+        *px = (*px & 0x00FF_FFFF) | (a as u32) << 24;
+    }
+    Ok(())
+}
+
+/// Desfaz o filtro de predição, no lugar.
+///
+/// O primeiro pixel é previsto a partir de 0, a primeira linha a partir da
+/// esquerda e a primeira coluna a partir de cima, qualquer que seja o
+/// filtro.
+fn unfilter(alpha: &mut [u8], width: usize, height: usize, filter: u8) {
+    if filter == FILTER_NONE {
+        return;
+    }
+
+    for x in 1..width {
+        alpha[x] = alpha[x].wrapping_add(alpha[x - 1]);
+    }
+    for y in 1..height {
+        let row = y * width;
+        alpha[row] = alpha[row].wrapping_add(alpha[row - width]);
+
+        for x in 1..width {
+            let i = row + x;
+            let left = alpha[i - 1];
+            let top = alpha[i - width];
+            let prediction = match filter {
+                FILTER_HORIZONTAL => left,
+                FILTER_VERTICAL => top,
+                FILTER_GRADIENT => {
+                    let top_left = alpha[i - width - 1] as i32;
+                    (left as i32 + top as i32 - top_left).clamp(0, 255) as u8
+                }
+                _ => 0,
+            };
+            alpha[i] = alpha[i].wrapping_add(prediction);
+        }
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Alpha esperado nos testes, 3x2.
+    const ALPHA: [u8; 6] = [10, 20, 30, 40, 50, 60];
+
+    /// Aplica o chunk a uma imagem 3x2 vermelha e retorna o alpha.
+    fn apply_to_sample(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+        let mut image = Image {
+            width: 3,
+            height: 2,
+            pixels: vec![0xFFFF_0000; 6],
+        };
+        apply(data, &mut image)?;
+        assert!(image.pixels.iter().all(|&px| px & 0x00FF_FFFF == 0xFF_0000));
+        Ok(image.pixels.iter().map(|&px| (px >> 24) as u8).collect())
+    }
+
+    #[test]
+    fn reads_raw_alpha() {
+        let data = [&[COMPRESSION_NONE][..], &ALPHA].concat();
+        assert_eq!(apply_to_sample(&data).unwrap(), ALPHA);
+    }
+
+    #[test]
+    fn undoes_every_filter() {
+        // [10 20 30]   esquerda: [10 10 10]  cima: [10 10 10]  gradiente: [10 10 10]
+        // [40 50 60]             [30 10 10]        [30 30 30]             [30  0  0]
+        let cases: [(u8, [u8; 6]); 3] = [
+            (FILTER_HORIZONTAL, [10, 10, 10, 30, 10, 10]),
+            (FILTER_VERTICAL, [10, 10, 10, 30, 30, 30]),
+            (FILTER_GRADIENT, [10, 10, 10, 30, 0, 0]),
+        ];
+        for (filter, filtered) in cases {
+            let data = [&[filter << 2][..], &filtered].concat();
+            assert_eq!(apply_to_sample(&data).unwrap(), ALPHA, "filtro {}", filter);
+        }
+    }
+
+    #[test]
+    fn reads_lossless_alpha_from_green() {
+        let green: Vec<u32> = [10, 10, 200, 200, 10, 200]
+            .iter()
+            .map(|&a| a << 8)
+            .collect();
+        let data = [&[COMPRESSION_LOSSLESS][..], &vp8l::tests::stream(&green)].concat();
+        assert_eq!(apply_to_sample(&data).unwrap(), [10, 10, 200, 200, 10, 200]);
+    }
+
+    #[test]
+    fn rejects_bad_chunks() {
+        assert_eq!(apply_to_sample(&[]).err(), Some(ImageError::Truncated));
+        assert_eq!(
+            apply_to_sample(&[COMPRESSION_NONE, 1, 2, 3]).err(),
+            Some(ImageError::Truncated)
+        );
+        assert_eq!(
+            apply_to_sample(&[0x40, 0, 0, 0, 0, 0, 0]).err(),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(
+            apply_to_sample(&[2, 0, 0, 0, 0, 0, 0]).err(),
+            Some(ImageError::InvalidHeader)
+        );
+    }
+}
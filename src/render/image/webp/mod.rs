@@ -0,0 +1,297 @@
+//! # WebP
+//!
+//! Decoder WebP: container RIFF, VP8L (sem perdas), VP8 (com perdas, só
+//! quadros-chave) e canal alpha (`ALPH`).
+//!
+//! ```text
+//! RIFF <tamanho> WEBP
+//! ├── VP8L ...            # sem perdas, alpha incluso
+//! ├── VP8  ...            # com perdas, opaco
+//! └── VP8X <flags>        # estendido
+//!     ├── ALPH ...        # alpha do VP8 (opcional)
+//!     └── VP8 / VP8L
+//! ```
+//!
+//! Animações (`ANIM`/`ANMF`) não são suportadas.
+
+mod alpha;
+mod vp8;
+mod vp8_tables;
+mod vp8l;
+
+use super::{alloc_pixels, pixel_count, Image, ImageError};
+use crate::render::pixel::premultiply;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Flag de animação no chunk `VP8X`.
+const VP8X_FLAG_ANIMATION: u8 = 0x02;
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Retorna se `data` tem a assinatura de um WebP.
+pub fn is_webp(data: &[u8]) -> bool {
+    data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP"
+}
+
+/// Decodifica um arquivo WebP completo.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if !is_webp(data) {
+        return Err(ImageError::BadMagic);
+    }
+
+    // O tamanho do RIFF pode mentir; nunca lê além do buffer
+    let riff_size = read_u32(data, 4).ok_or(ImageError::Truncated)? as usize;
+    let end = data.len().min(riff_size.saturating_add(8));
+    let mut chunks = Chunks {
+        data: &data[..end],
+        pos: 12,
+    };
+
+    let first = chunks.next().ok_or(ImageError::Truncated)??;
+    let mut image = match &first.fourcc {
+        b"VP8L" => vp8l::decode(first.payload)?,
+        b"VP8 " => vp8::decode(first.payload)?,
+        b"VP8X" => decode_extended(first.payload, &mut chunks)?,
+        _ => return Err(ImageError::InvalidHeader),
+    };
+
+    for px in image.pixels.iter_mut() {
+        *px = premultiply(*px);
+    }
+    Ok(image)
+}
+
+/// Formato estendido: procura o bitstream (e o alpha) depois do `VP8X`.
+fn decode_extended(header: &[u8], chunks: &mut Chunks) -> Result<Image, ImageError> {
+    if header.len() < 10 {
+        return Err(ImageError::Truncated);
+    }
+    if header[0] & VP8X_FLAG_ANIMATION != 0 {
+        return Err(ImageError::Unsupported);
+    }
+    let canvas_width = read_u24(header, 4) + 1;
+    let canvas_height = read_u24(header, 7) + 1;
+
+    let mut alpha = None;
+    for chunk in chunks {
+        let chunk = chunk?;
+        match &chunk.fourcc {
+            b"ALPH" => alpha = Some(chunk.payload),
+            b"VP8L" | b"VP8 " => {
+                let mut image = if &chunk.fourcc == b"VP8L" {
+                    vp8l::decode(chunk.payload)?
+                } else {
+                    vp8::decode(chunk.payload)?
+                };
+                if image.width != canvas_width || image.height != canvas_height {
+                    return Err(ImageError::InvalidHeader);
+                }
+
+                // ALPH só vale para VP8; o VP8L carrega o próprio alpha
+                if let (Some(alpha), b"VP8 ") = (alpha, &chunk.fourcc) {
+                    alpha::apply(alpha, &mut image)?;
+                }
+                return Ok(image);
+            }
+            b"ANIM" | b"ANMF" => return Err(ImageError::Unsupported),
+            // ICCP, EXIF, XMP e desconhecidos são ignorados
+            _ => {}
+        }
+    }
+
+    Err(ImageError::Truncated)
+}
+
+// =============================================================================
+// CONTAINER
+// =============================================================================
+
+/// Chunk RIFF.
+struct Chunk<'a> {
+    fourcc: [u8; 4],
+    payload: &'a [u8],
+}
+
+/// Iterador sobre os chunks após o cabeçalho `RIFF....WEBP`.
+struct Chunks<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Result<Chunk<'a>, ImageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos + 8 > self.data.len() {
+            return None;
+        }
+        let fourcc = [
+            self.data[self.pos],
+            self.data[self.pos + 1],
+            self.data[self.pos + 2],
+            self.data[self.pos + 3],
+        ];
+        let size = read_u32(self.data, self.pos + 4)? as usize;
+        let start = self.pos + 8;
+        let Some(payload) = self.data.get(start..start.saturating_add(size)) else {
+            self.pos = self.data.len();
+            return Some(Err(ImageError::Truncated));
+        };
+
+        // Chunks de tamanho ímpar têm um byte de preenchimento
+        self.pos = start + size + (size & 1);
+        Some(Ok(Chunk { fourcc, payload }))
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Lê u32 little-endian.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Lê u24 little-endian (`data` já validado).
+fn read_u24(data: &[u8], offset: usize) -> u32 {
+    data[offset] as u32 | (data[offset + 1] as u32) << 8 | (data[offset + 2] as u32) << 16
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Flag de alpha no chunk `VP8X`.
+    const VP8X_FLAG_ALPHA: u8 = 0x10;
+
+    /// Monta um arquivo com `chunks` (com o byte de preenchimento).
+    fn riff(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = b"WEBP".to_vec();
+        for (fourcc, payload) in chunks {
+            body.extend_from_slice(*fourcc);
+            body.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            body.extend_from_slice(payload);
+            if payload.len() & 1 != 0 {
+                body.push(0);
+            }
+        }
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    /// Corpo do `VP8X`.
+    fn vp8x(flags: u8, width: u32, height: u32) -> Vec<u8> {
+        let mut body = vec![flags, 0, 0, 0];
+        body.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+        body.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+        body
+    }
+
+    /// Alpha cru 16x16: um valor diferente por pixel.
+    fn alph() -> Vec<u8> {
+        (0..=255).collect()
+    }
+
+    #[test]
+    fn decodes_simple_files() {
+        let image = decode(&riff(&[(b"VP8L", &vp8l::tests::sample())])).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        let expected: Vec<u32> = vp8l::tests::PIXELS.map(premultiply).to_vec();
+        assert_eq!(image.pixels, expected);
+
+        let image = decode(&riff(&[(b"VP8 ", &vp8::tests::sample())])).unwrap();
+        assert_eq!((image.width, image.height), (16, 16));
+        assert!(image.pixels.iter().all(|&px| px == 0xFF88_8888));
+    }
+
+    #[test]
+    fn applies_alpha_to_lossy() {
+        let header = vp8x(VP8X_FLAG_ALPHA, 16, 16);
+        let alpha = [&[0][..], &alph()].concat();
+        let bytes = riff(&[
+            (b"VP8X", &header),
+            (b"ALPH", &alpha),
+            (b"VP8 ", &vp8::tests::sample()),
+        ]);
+        let image = decode(&bytes).unwrap();
+        for (i, &px) in image.pixels.iter().enumerate() {
+            assert_eq!(px, premultiply((i as u32) << 24 | 0x88_8888));
+        }
+    }
+
+    #[test]
+    fn skips_unknown_chunks() {
+        // ICCP de tamanho ímpar antes do bitstream; ALPH não vale para VP8L
+        let bytes = riff(&[
+            (b"VP8X", &vp8x(VP8X_FLAG_ALPHA, 3, 2)),
+            (b"ICCP", &[1, 2, 3]),
+            (b"ALPH", &[0, 0, 0, 0, 0, 0, 0]),
+            (b"VP8L", &vp8l::tests::sample()),
+        ]);
+        let image = decode(&bytes).unwrap();
+        assert_eq!(image.pixels[0], vp8l::tests::PIXELS[0]);
+    }
+
+    #[test]
+    fn truncated_files_fail() {
+        let alpha = [&[0][..], &alph()].concat();
+        let files = [
+            riff(&[(b"VP8 ", &vp8::tests::sample())]),
+            riff(&[
+                (b"VP8X", &vp8x(VP8X_FLAG_ALPHA, 16, 16)),
+                (b"ALPH", &alpha),
+                (b"VP8 ", &vp8::tests::sample()),
+            ]),
+        ];
+        for bytes in files {
+            for len in 0..bytes.len() {
+                assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+            }
+        }
+
+        // Só o byte de preenchimento final pode faltar
+        let bytes = riff(&[(b"VP8L", &vp8l::tests::sample())]);
+        for len in 0..bytes.len() - 1 {
+            assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_files() {
+        let lossless = vp8l::tests::sample();
+        assert_eq!(
+            decode(b"RIFF\0\0\0\0WEBX").err(),
+            Some(ImageError::BadMagic)
+        );
+        assert_eq!(
+            decode(&riff(&[(b"VP8Y", &lossless)])).err(),
+            Some(ImageError::InvalidHeader)
+        );
+        assert_eq!(
+            decode(&riff(&[(b"VP8X", &vp8x(VP8X_FLAG_ANIMATION, 3, 2))])).err(),
+            Some(ImageError::Unsupported)
+        );
+        assert_eq!(
+            decode(&riff(&[(b"VP8X", &vp8x(0, 3, 2)), (b"ANIM", &[0; 6])])).err(),
+            Some(ImageError::Unsupported)
+        );
+
+        // Bitstream de tamanho diferente do canvas
+        let bytes = riff(&[(b"VP8X", &vp8x(0, 4, 2)), (b"VP8L", &lossless)]);
+        assert_eq!(decode(&bytes).err(), Some(ImageError::InvalidHeader));
+    }
+}
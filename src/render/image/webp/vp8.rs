@@ -0,0 +1,1665 @@
+//! # VP8
+//!
+//! Quadros-chave VP8 (com perdas), como definidos na RFC 6386.
+//!
+//! ```text
+//! tag (3) | 9D 01 2A | largura (2) | altura (2)
+//! partição 0: cabeçalho + modos de cada macrobloco
+//! partições 1..8: coeficientes (uma por linha de macroblocos, em rodízio)
+//! ```
+//!
+//! Cada macrobloco 16x16 é previsto a partir dos vizinhos já decodificados
+//! (16x16 inteiro ou dezesseis blocos 4x4), somado ao resíduo (DCT inversa)
+//! e guardado em planos Y, U e V. Com o quadro completo, o filtro de laço
+//! suaviza as bordas dos blocos e os planos viram ARGB, com o croma
+//! ampliado por interpolação bilinear.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::vp8_tables::{
+    CoeffProbas, AC_TABLE, BANDS, BMODES_PROBAS, BMODES_TREE, B_DC_PRED, B_HD_PRED, B_HE_PRED,
+    B_HU_PRED, B_LD_PRED, B_RD_PRED, B_TM_PRED, B_VE_PRED, B_VL_PRED, B_VR_PRED, CAT3, CAT4, CAT5,
+    CAT6, COEFF_UPDATE_PROBAS, DC_TABLE, DEFAULT_COEFF_PROBAS, NUM_BANDS, NUM_CTX, NUM_PROBAS,
+    NUM_TYPES, ZIGZAG,
+};
+use super::{pixel_count, Image, ImageError};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Código de início de quadro-chave.
+const START_CODE: [u8; 3] = [0x9D, 0x01, 0x2A];
+
+/// Máximo de partições de coeficientes.
+const MAX_PARTITIONS: usize = 8;
+
+/// Segmentos (conjuntos de quantização e filtro).
+const NUM_SEGMENTS: usize = 4;
+
+/// Largura de linha dos buffers de trabalho de um macrobloco.
+const BPS: usize = 32;
+
+/// Origem do bloco nos buffers de trabalho: uma linha de borda em cima e
+/// a borda esquerda na coluna 7.
+const ORIGIN: usize = BPS + 8;
+
+/// Bytes lidos além do fim de uma partição antes de considerá-la truncada.
+const MAX_OVERRUN: usize = 4;
+
+// =============================================================================
+// DECODER BOOLEANO
+// =============================================================================
+
+/// Decodificador aritmético binário (RFC 6386, seção 7).
+struct BoolDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Janela de 2 bytes alinhada com `range`.
+    value: u32,
+    range: u32,
+    /// Bits deslocados desde o último byte carregado.
+    bit_count: u32,
+}
+
+impl<'a> BoolDecoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        let mut decoder = Self {
+            data,
+            pos: 0,
+            value: 0,
+            range: 255,
+            bit_count: 0,
+        };
+        decoder.value = (decoder.next_byte() << 8) | decoder.next_byte();
+        decoder
+    }
+
+    /// Próximo byte (zero depois do fim).
+    #[inline]
+    fn next_byte(&mut self) -> u32 {
+        let byte = self.data.get(self.pos).copied().unwrap_or(0);
+        self.pos += 1;
+        byte as u32
+    }
+
+    /// Retorna se a leitura foi muito além do fim dos dados.
+    fn exhausted(&self) -> bool {
+        self.pos > self.data.len() + MAX_OVERRUN
+    }
+
+    /// Lê um bit com probabilidade `prob / 256` de ser zero.
+    #[inline]
+    fn read_bool(&mut self, prob: u8) -> bool {
+        let split = 1 + (((self.range - 1) * prob as u32) >> 8);
+        let big_split = split << 8;
+        let bit = if self.value >= big_split {
+            self.range -= split;
+            self.value -= big_split;
+            true
+        } else {
+            self.range = split;
+            false
+        };
+
+        while self.range < 128 {
+            self.value <<= 1;
+            self.range <<= 1;
+            self.bit_count += 1;
+            if self.bit_count == 8 {
+                self.bit_count = 0;
+                self.value |= self.next_byte();
+            }
+        }
+        bit
+    }
+
+    /// Bit com probabilidade 1/2.
+    #[inline]
+    fn read_flag(&mut self) -> bool {
+        self.read_bool(128)
+    }
+
+    /// Inteiro sem sinal de `bits` bits (MSB primeiro).
+    fn read_literal(&mut self, bits: u32) -> u32 {
+        let mut value = 0;
+        for _ in 0..bits {
+            value = (value << 1) | self.read_flag() as u32;
+        }
+        value
+    }
+
+    /// Magnitude de `bits` bits seguida do sinal.
+    fn read_signed(&mut self, bits: u32) -> i32 {
+        let value = self.read_literal(bits) as i32;
+        if self.read_flag() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Valor com sinal precedido de uma flag de presença (0 se ausente).
+    fn read_optional_signed(&mut self, bits: u32) -> i32 {
+        if self.read_flag() {
+            self.read_signed(bits)
+        } else {
+            0
+        }
+    }
+}
+
+// =============================================================================
+// CABEÇALHO
+// =============================================================================
+
+/// Segmentação do quadro.
+#[derive(Default)]
+struct Segments {
+    /// Lê o segmento de cada macrobloco.
+    update_map: bool,
+    /// Valores absolutos (senão, deltas sobre os globais).
+    absolute: bool,
+    quantizer: [i32; NUM_SEGMENTS],
+    filter_level: [i32; NUM_SEGMENTS],
+    /// Probabilidades da árvore de segmento.
+    probas: [u8; 3],
+}
+
+/// Parâmetros do filtro de laço.
+#[derive(Default)]
+struct FilterHeader {
+    simple: bool,
+    level: i32,
+    sharpness: i32,
+    use_deltas: bool,
+    /// Delta do quadro de referência (só o intra, índice 0, importa aqui).
+    ref_delta: [i32; 4],
+    /// Delta por modo (índice 0: blocos 4x4).
+    mode_delta: [i32; 4],
+}
+
+/// Passos de quantização (DC, AC) de um segmento.
+#[derive(Clone, Copy, Default)]
+struct Quant {
+    y1: [i32; 2],
+    y2: [i32; 2],
+    uv: [i32; 2],
+}
+
+/// Filtro já resolvido para um segmento e tipo de macrobloco.
+#[derive(Clone, Copy, Default)]
+struct FilterParams {
+    /// Limite das bordas internas (0 = sem filtro).
+    limit: i32,
+    /// Limite das diferenças internas.
+    interior: i32,
+    /// Limiar de variação alta.
+    hev_threshold: i32,
+}
+
+/// O que o filtro precisa saber de cada macrobloco.
+#[derive(Clone, Copy, Default)]
+struct MacroblockInfo {
+    segment: u8,
+    is_i4x4: bool,
+    has_coeffs: bool,
+}
+
+/// Blocos com coeficientes não nulos na borda de um macrobloco (contexto
+/// do próximo).
+#[derive(Clone, Copy, Default)]
+struct NonZero {
+    y: [bool; 4],
+    u: [bool; 2],
+    v: [bool; 2],
+    dc: bool,
+}
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Decodifica um chunk `VP8 `.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if data.len() < 10 {
+        return Err(ImageError::Truncated);
+    }
+    let tag = data[0] as u32 | (data[1] as u32) << 8 | (data[2] as u32) << 16;
+    let key_frame = tag & 1 == 0;
+    let profile = (tag >> 1) & 7;
+    let first_partition_size = (tag >> 5) as usize;
+    if !key_frame {
+        return Err(ImageError::Unsupported);
+    }
+    if profile > 3 || data[3..6] != START_CODE {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    // Os 2 bits altos de cada dimensão são escala (ignorada)
+    let width = u16::from_le_bytes([data[6], data[7]]) as u32 & 0x3FFF;
+    let height = u16::from_le_bytes([data[8], data[9]]) as u32 & 0x3FFF;
+    pixel_count(width, height)?;
+
+    let rest = &data[10..];
+    if first_partition_size > rest.len() {
+        return Err(ImageError::Truncated);
+    }
+    let (first, tail) = rest.split_at(first_partition_size);
+    let mut br = BoolDecoder::new(first);
+    let mut decoder = Decoder::new(width, height, &mut br, tail)?;
+    decoder.decode_macroblocks(&mut br)?;
+    decoder.filter_frame();
+
+    Ok(Image {
+        width,
+        height,
+        pixels: decoder.to_argb(),
+    })
+}
+
+/// Estado de decodificação de um quadro.
+struct Decoder<'a> {
+    width: usize,
+    height: usize,
+    mb_width: usize,
+    mb_height: usize,
+
+    segments: Segments,
+    filter: FilterHeader,
+    quant: [Quant; NUM_SEGMENTS],
+    probas: CoeffProbas,
+    skip_proba: Option<u8>,
+    partitions: Vec<BoolDecoder<'a>>,
+
+    /// Planos reconstruídos (múltiplos de 16 / 8).
+    y: Vec<u8>,
+    u: Vec<u8>,
+    v: Vec<u8>,
+    y_stride: usize,
+    uv_stride: usize,
+
+    /// Modos 4x4 da última linha de cada macrobloco (contexto).
+    intra_top: Vec<u8>,
+    intra_left: [u8; 4],
+    nz_top: Vec<NonZero>,
+    nz_left: NonZero,
+    info: Vec<MacroblockInfo>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Lê o cabeçalho da partição 0 e localiza as partições de
+    /// coeficientes em `tail`.
+    fn new(
+        width: u32,
+        height: u32,
+        br: &mut BoolDecoder,
+        tail: &'a [u8],
+    ) -> Result<Self, ImageError> {
+        let (width, height) = (width as usize, height as usize);
+        let mb_width = width.div_ceil(16);
+        let mb_height = height.div_ceil(16);
+
+        let _color_space = br.read_flag();
+        let _clamping = br.read_flag();
+
+        let mut segments = Segments::default();
+        let segmentation = br.read_flag();
+        if segmentation {
+            segments.update_map = br.read_flag();
+            if br.read_flag() {
+                segments.absolute = br.read_flag();
+                for q in segments.quantizer.iter_mut() {
+                    *q = br.read_optional_signed(7);
+                }
+                for level in segments.filter_level.iter_mut() {
+                    *level = br.read_optional_signed(6);
+                }
+            }
+            if segments.update_map {
+                for proba in segments.probas.iter_mut() {
+                    *proba = if br.read_flag() {
+                        br.read_literal(8) as u8
+                    } else {
+                        255
+                    };
+                }
+            }
+        }
+
+        let mut filter = FilterHeader {
+            simple: br.read_flag(),
+            level: br.read_literal(6) as i32,
+            sharpness: br.read_literal(3) as i32,
+            use_deltas: br.read_flag(),
+            ..FilterHeader::default()
+        };
+        if filter.use_deltas && br.read_flag() {
+            for delta in filter.ref_delta.iter_mut() {
+                if br.read_flag() {
+                    *delta = br.read_signed(6);
+                }
+            }
+            for delta in filter.mode_delta.iter_mut() {
+                if br.read_flag() {
+                    *delta = br.read_signed(6);
+                }
+            }
+        }
+
+        let num_partitions = 1usize << br.read_literal(2);
+        let partitions = split_partitions(tail, num_partitions)?;
+
+        // Quantização
+        let base = br.read_literal(7) as i32;
+        let y1_dc = br.read_optional_signed(4);
+        let y2_dc = br.read_optional_signed(4);
+        let y2_ac = br.read_optional_signed(4);
+        let uv_dc = br.read_optional_signed(4);
+        let uv_ac = br.read_optional_signed(4);
+        let mut quant = [Quant::default(); NUM_SEGMENTS];
+        for (s, q) in quant.iter_mut().enumerate() {
+            let index = match (segmentation, segments.absolute) {
+                (false, _) => base,
+                (true, true) => segments.quantizer[s],
+                (true, false) => base + segments.quantizer[s],
+            };
+            let dc = |delta: i32, max: i32| DC_TABLE[(index + delta).clamp(0, max) as usize] as i32;
+            let ac = |delta: i32| AC_TABLE[(index + delta).clamp(0, 127) as usize] as i32;
+            q.y1 = [dc(y1_dc, 127), ac(0)];
+            q.y2 = [dc(y2_dc, 127) * 2, ((ac(y2_ac) * 101581) >> 16).max(8)];
+            q.uv = [dc(uv_dc, 117), ac(uv_ac)];
+        }
+
+        let _refresh_entropy = br.read_flag();
+
+        let mut probas = DEFAULT_COEFF_PROBAS;
+        for t in 0..NUM_TYPES {
+            for b in 0..NUM_BANDS {
+                for c in 0..NUM_CTX {
+                    for p in 0..NUM_PROBAS {
+                        if br.read_bool(COEFF_UPDATE_PROBAS[t][b][c][p]) {
+                            probas[t][b][c][p] = br.read_literal(8) as u8;
+                        }
+                    }
+                }
+            }
+        }
+
+        let skip_proba = if br.read_flag() {
+            Some(br.read_literal(8) as u8)
+        } else {
+            None
+        };
+
+        if br.exhausted() {
+            return Err(ImageError::Truncated);
+        }
+
+        let y_stride = mb_width * 16;
+        let uv_stride = mb_width * 8;
+        Ok(Self {
+            width,
+            height,
+            mb_width,
+            mb_height,
+            segments,
+            filter,
+            quant,
+            probas,
+            skip_proba,
+            partitions,
+            y: vec![0; y_stride * mb_height * 16],
+            u: vec![0; uv_stride * mb_height * 8],
+            v: vec![0; uv_stride * mb_height * 8],
+            y_stride,
+            uv_stride,
+            intra_top: vec![B_DC_PRED; mb_width * 4],
+            intra_left: [B_DC_PRED; 4],
+            nz_top: vec![NonZero::default(); mb_width],
+            nz_left: NonZero::default(),
+            info: vec![MacroblockInfo::default(); mb_width * mb_height],
+        })
+    }
+
+    /// Decodifica todos os macroblocos, em ordem de varredura.
+    fn decode_macroblocks(&mut self, br: &mut BoolDecoder) -> Result<(), ImageError> {
+        let mut partitions = core::mem::take(&mut self.partitions);
+        let mask = partitions.len() - 1;
+
+        for mb_y in 0..self.mb_height {
+            self.intra_left = [B_DC_PRED; 4];
+            self.nz_left = NonZero::default();
+
+            let tokens = &mut partitions[mb_y & mask];
+            for mb_x in 0..self.mb_width {
+                self.decode_macroblock(br, tokens, mb_x, mb_y);
+            }
+            if tokens.exhausted() || br.exhausted() {
+                return Err(ImageError::Truncated);
+            }
+        }
+        Ok(())
+    }
+
+    /// Modos, coeficientes e reconstrução de um macrobloco.
+    fn decode_macroblock(
+        &mut self,
+        br: &mut BoolDecoder,
+        tokens: &mut BoolDecoder,
+        mb_x: usize,
+        mb_y: usize,
+    ) {
+        let segment = if self.segments.update_map {
+            let p = self.segments.probas;
+            if !br.read_bool(p[0]) {
+                br.read_bool(p[1]) as u8
+            } else {
+                2 + br.read_bool(p[2]) as u8
+            }
+        } else {
+            0
+        };
+        let skip = self.skip_proba.is_some_and(|p| br.read_bool(p));
+
+        // Modos de predição do luma
+        let is_i4x4 = !br.read_bool(145);
+        let mut y_mode = B_DC_PRED;
+        let mut b_modes = [B_DC_PRED; 16];
+        let top = &mut self.intra_top[mb_x * 4..mb_x * 4 + 4];
+        if !is_i4x4 {
+            y_mode = if br.read_bool(156) {
+                if br.read_bool(128) {
+                    B_TM_PRED
+                } else {
+                    B_HE_PRED
+                }
+            } else if br.read_bool(163) {
+                B_VE_PRED
+            } else {
+                B_DC_PRED
+            };
+            top.fill(y_mode);
+            self.intra_left = [y_mode; 4];
+        } else {
+            for y in 0..4 {
+                let mut left = self.intra_left[y];
+                for x in 0..4 {
+                    let probas = &BMODES_PROBAS[top[x] as usize][left as usize];
+                    let mut i = BMODES_TREE[br.read_bool(probas[0]) as usize];
+                    while i > 0 {
+                        let i2 = 2 * i as usize;
+                        i = BMODES_TREE[i2 + br.read_bool(probas[i as usize]) as usize];
+                    }
+                    left = (-i) as u8;
+                    top[x] = left;
+                    b_modes[y * 4 + x] = left;
+                }
+                self.intra_left[y] = left;
+            }
+        }
+
+        let uv_mode = if !br.read_bool(142) {
+            B_DC_PRED
+        } else if !br.read_bool(114) {
+            B_VE_PRED
+        } else if br.read_bool(183) {
+            B_TM_PRED
+        } else {
+            B_HE_PRED
+        };
+
+        // Coeficientes: 16 blocos Y, 4 U, 4 V
+        let mut coeffs = [0i32; 384];
+        let has_coeffs = if skip {
+            let dc = (self.nz_top[mb_x].dc, self.nz_left.dc);
+            self.nz_top[mb_x] = NonZero::default();
+            self.nz_left = NonZero::default();
+            if is_i4x4 {
+                (self.nz_top[mb_x].dc, self.nz_left.dc) = dc;
+            }
+            false
+        } else {
+            self.read_residuals(tokens, mb_x, segment, is_i4x4, &mut coeffs)
+        };
+
+        self.reconstruct(mb_x, mb_y, is_i4x4, y_mode, &b_modes, uv_mode, &coeffs);
+        self.info[mb_y * self.mb_width + mb_x] = MacroblockInfo {
+            segment,
+            is_i4x4,
+            has_coeffs,
+        };
+    }
+
+    /// Lê e dequantiza os coeficientes. Retorna se algum é não nulo.
+    fn read_residuals(
+        &mut self,
+        br: &mut BoolDecoder,
+        mb_x: usize,
+        segment: u8,
+        is_i4x4: bool,
+        coeffs: &mut [i32; 384],
+    ) -> bool {
+        let q = self.quant[segment as usize];
+        let mut top = self.nz_top[mb_x];
+        let mut left = self.nz_left;
+
+        // Sem 4x4, os DCs dos blocos Y vêm de um bloco Y2 (Walsh-Hadamard)
+        let (first, y_type) = if !is_i4x4 {
+            let mut dc = [0i32; 16];
+            let ctx = top.dc as usize + left.dc as usize;
+            let nz = read_coeffs(br, &self.probas[1], ctx, q.y2, 0, &mut dc);
+            top.dc = nz > 0;
+            left.dc = nz > 0;
+            if nz > 0 {
+                inverse_wht(&dc, coeffs);
+            }
+            (1, 0)
+        } else {
+            (0, 3)
+        };
+
+        for y in 0..4 {
+            let mut l = left.y[y];
+            for x in 0..4 {
+                let ctx = l as usize + top.y[x] as usize;
+                let block = &mut coeffs[(y * 4 + x) * 16..][..16];
+                let nz = read_coeffs(br, &self.probas[y_type], ctx, q.y1, first, block);
+                l = nz > first;
+                top.y[x] = l;
+            }
+            left.y[y] = l;
+        }
+
+        for (plane, offset) in [(0, 256), (1, 320)] {
+            let (top_nz, left_nz) = if plane == 0 {
+                (&mut top.u, &mut left.u)
+            } else {
+                (&mut top.v, &mut left.v)
+            };
+            for y in 0..2 {
+                let mut l = left_nz[y];
+                for x in 0..2 {
+                    let ctx = l as usize + top_nz[x] as usize;
+                    let block = &mut coeffs[offset + (y * 2 + x) * 16..][..16];
+                    let nz = read_coeffs(br, &self.probas[2], ctx, q.uv, 0, block);
+                    l = nz > 0;
+                    top_nz[x] = l;
+                }
+                left_nz[y] = l;
+            }
+        }
+
+        self.nz_top[mb_x] = top;
+        self.nz_left = left;
+        coeffs.iter().any(|&c| c != 0)
+    }
+
+    // =========================================================================
+    // RECONSTRUÇÃO
+    // =========================================================================
+
+    /// Predição + resíduo de um macrobloco, gravados nos planos.
+    #[allow(clippy::too_many_arguments)]
+    fn reconstruct(
+        &mut self,
+        mb_x: usize,
+        mb_y: usize,
+        is_i4x4: bool,
+        y_mode: u8,
+        b_modes: &[u8; 16],
+        uv_mode: u8,
+        coeffs: &[i32; 384],
+    ) {
+        // Luma, com 4 pixels extras à direita da borda de cima para os
+        // modos 4x4 diagonais
+        let mut ybuf = [0u8; BPS * 17];
+        load_borders(&mut ybuf, &self.y, self.y_stride, mb_x, mb_y, 16);
+        let top_right = ORIGIN - BPS + 16;
+        if mb_y == 0 {
+            ybuf[top_right..top_right + 4].fill(127);
+        } else {
+            let row = (mb_y * 16 - 1) * self.y_stride;
+            let x = mb_x * 16 + 16;
+            if mb_x + 1 < self.mb_width {
+                ybuf[top_right..top_right + 4].copy_from_slice(&self.y[row + x..row + x + 4]);
+            } else {
+                ybuf[top_right..top_right + 4].fill(self.y[row + x - 1]);
+            }
+        }
+        // Os blocos 4x4 da última coluna reusam o canto do macrobloco
+        for k in 1..4 {
+            let dst = top_right + 4 * k * BPS;
+            ybuf.copy_within(top_right..top_right + 4, dst);
+        }
+
+        if is_i4x4 {
+            for (n, &mode) in b_modes.iter().enumerate() {
+                let offset = ORIGIN + (n / 4) * 4 * BPS + (n % 4) * 4;
+                predict_4x4(&mut ybuf, offset, mode);
+                inverse_dct_add(&coeffs[n * 16..][..16], &mut ybuf, offset);
+            }
+        } else {
+            predict_block(&mut ybuf, 16, y_mode, mb_x > 0, mb_y > 0);
+            for n in 0..16 {
+                let offset = ORIGIN + (n / 4) * 4 * BPS + (n % 4) * 4;
+                inverse_dct_add(&coeffs[n * 16..][..16], &mut ybuf, offset);
+            }
+        }
+        store_block(&ybuf, &mut self.y, self.y_stride, mb_x, mb_y, 16);
+
+        // Croma
+        for (plane, offset) in [(0, 256), (1, 320)] {
+            let data = if plane == 0 { &mut self.u } else { &mut self.v };
+            let mut buf = [0u8; BPS * 9];
+            load_borders(&mut buf, data, self.uv_stride, mb_x, mb_y, 8);
+            predict_block(&mut buf, 8, uv_mode, mb_x > 0, mb_y > 0);
+            for n in 0..4 {
+                let dst = ORIGIN + (n / 2) * 4 * BPS + (n % 2) * 4;
+                inverse_dct_add(&coeffs[offset + n * 16..][..16], &mut buf, dst);
+            }
+            store_block(&buf, data, self.uv_stride, mb_x, mb_y, 8);
+        }
+    }
+
+    // =========================================================================
+    // FILTRO DE LAÇO
+    // =========================================================================
+
+    /// Filtra as bordas de todos os macroblocos, em ordem de varredura.
+    fn filter_frame(&mut self) {
+        if self.filter.level == 0 {
+            return;
+        }
+        let params = self.filter_params();
+
+        for mb_y in 0..self.mb_height {
+            for mb_x in 0..self.mb_width {
+                let info = self.info[mb_y * self.mb_width + mb_x];
+                let p = params[info.segment as usize][info.is_i4x4 as usize];
+                if p.limit == 0 {
+                    continue;
+                }
+                let inner = info.is_i4x4 || info.has_coeffs;
+                if self.filter.simple {
+                    self.filter_simple(mb_x, mb_y, p, inner);
+                } else {
+                    self.filter_normal(mb_x, mb_y, p, inner);
+                }
+            }
+        }
+    }
+
+    /// Limites por segmento e por tipo de macrobloco (16x16, 4x4).
+    fn filter_params(&self) -> [[FilterParams; 2]; NUM_SEGMENTS] {
+        let mut params = [[FilterParams::default(); 2]; NUM_SEGMENTS];
+        for (s, segment_params) in params.iter_mut().enumerate() {
+            let mut base = self.filter.level;
+            if self.segments.update_map || self.segments.filter_level[s] != 0 {
+                base = if self.segments.absolute {
+                    self.segments.filter_level[s]
+                } else {
+                    base + self.segments.filter_level[s]
+                };
+            }
+
+            for (i4x4, p) in segment_params.iter_mut().enumerate() {
+                let mut level = base;
+                if self.filter.use_deltas {
+                    level += self.filter.ref_delta[0];
+                    if i4x4 == 1 {
+                        level += self.filter.mode_delta[0];
+                    }
+                }
+                let level = level.clamp(0, 63);
+                if level == 0 {
+                    continue;
+                }
+
+                let mut interior = level;
+                if self.filter.sharpness > 0 {
+                    interior >>= if self.filter.sharpness > 4 { 2 } else { 1 };
+                    interior = interior.min(9 - self.filter.sharpness);
+                }
+                let interior = interior.max(1);
+                *p = FilterParams {
+                    limit: 2 * level + interior,
+                    interior,
+                    hev_threshold: match level {
+                        40.. => 2,
+                        15.. => 1,
+                        _ => 0,
+                    },
+                };
+            }
+        }
+        params
+    }
+
+    /// Filtro simples (só luma).
+    fn filter_simple(&mut self, mb_x: usize, mb_y: usize, p: FilterParams, inner: bool) {
+        let stride = self.y_stride;
+        let origin = mb_y * 16 * stride + mb_x * 16;
+        let edge = 2 * (p.limit + 4) + 1;
+        let interior = 2 * p.limit + 1;
+
+        if mb_x > 0 {
+            simple_edge(&mut self.y, origin, 1, stride, edge);
+        }
+        if inner {
+            for k in 1..4 {
+                simple_edge(&mut self.y, origin + 4 * k, 1, stride, interior);
+            }
+        }
+        if mb_y > 0 {
+            simple_edge(&mut self.y, origin, stride, 1, edge);
+        }
+        if inner {
+            for k in 1..4 {
+                simple_edge(&mut self.y, origin + 4 * k * stride, stride, 1, interior);
+            }
+        }
+    }
+
+    /// Filtro normal (luma e croma).
+    fn filter_normal(&mut self, mb_x: usize, mb_y: usize, p: FilterParams, inner: bool) {
+        let (ys, uvs) = (self.y_stride, self.uv_stride);
+        let y_origin = mb_y * 16 * ys + mb_x * 16;
+        let uv_origin = mb_y * 8 * uvs + mb_x * 8;
+        let edge = EdgeParams {
+            threshold: 2 * (p.limit + 4) + 1,
+            interior: p.interior,
+            hev_threshold: p.hev_threshold,
+            macroblock: true,
+        };
+        let sub = EdgeParams {
+            threshold: 2 * p.limit + 1,
+            macroblock: false,
+            ..edge
+        };
+
+        // Bordas verticais (esquerda e internas)
+        if mb_x > 0 {
+            normal_edge(&mut self.y, y_origin, 1, ys, 16, edge);
+            normal_edge(&mut self.u, uv_origin, 1, uvs, 8, edge);
+            normal_edge(&mut self.v, uv_origin, 1, uvs, 8, edge);
+        }
+        if inner {
+            for k in 1..4 {
+                normal_edge(&mut self.y, y_origin + 4 * k, 1, ys, 16, sub);
+            }
+            normal_edge(&mut self.u, uv_origin + 4, 1, uvs, 8, sub);
+            normal_edge(&mut self.v, uv_origin + 4, 1, uvs, 8, sub);
+        }
+
+        // Bordas horizontais (topo e internas)
+        if mb_y > 0 {
+            normal_edge(&mut self.y, y_origin, ys, 1, 16, edge);
+            normal_edge(&mut self.u, uv_origin, uvs, 1, 8, edge);
+            normal_edge(&mut self.v, uv_origin, uvs, 1, 8, edge);
+        }
+        if inner {
+            for k in 1..4 {
+                normal_edge(&mut self.y, y_origin + 4 * k * ys, ys, 1, 16, sub);
+            }
+            normal_edge(&mut self.u, uv_origin + 4 * uvs, uvs, 1, 8, sub);
+            normal_edge(&mut self.v, uv_origin + 4 * uvs, uvs, 1, 8, sub);
+        }
+    }
+
+    // =========================================================================
+    // CONVERSÃO
+    // =========================================================================
+
+    /// Converte os planos para ARGB opaco, ampliando o croma com
+    /// interpolação bilinear (pesos 9-3-3-1).
+    fn to_argb(&self) -> Vec<u32> {
+        let (w, h) = (self.width, self.height);
+        let last_x = w.div_ceil(2) - 1;
+        let last_y = h.div_ceil(2) - 1;
+        let mut pixels = Vec::with_capacity(w * h);
+
+        for y in 0..h {
+            // Linha de croma mais próxima e a vizinha do outro lado
+            let near_y = y / 2;
+            let far_y = if y & 1 == 0 {
+                near_y.saturating_sub(1)
+            } else {
+                (near_y + 1).min(last_y)
+            };
+            let near_row = near_y * self.uv_stride;
+            let far_row = far_y * self.uv_stride;
+            let y_row = y * self.y_stride;
+
+            for x in 0..w {
+                let near_x = x / 2;
+                let far_x = if x & 1 == 0 {
+                    near_x.saturating_sub(1)
+                } else {
+                    (near_x + 1).min(last_x)
+                };
+                let sample = |plane: &[u8]| {
+                    let a = plane[near_row + near_x] as i32;
+                    let b = plane[near_row + far_x] as i32;
+                    let c = plane[far_row + near_x] as i32;
+                    let d = plane[far_row + far_x] as i32;
+                    (9 * a + 3 * (b + c) + d + 8) >> 4
+                };
+                pixels.push(yuv_to_argb(
+                    self.y[y_row + x] as i32,
+                    sample(&self.u),
+                    sample(&self.v),
+                ));
+            }
+        }
+        pixels
+    }
+}
+
+/// Separa as partições de coeficientes (tamanhos de 3 bytes antes dos
+/// dados; a última ocupa o resto).
+fn split_partitions(tail: &[u8], count: usize) -> Result<Vec<BoolDecoder<'_>>, ImageError> {
+    debug_assert!(count <= MAX_PARTITIONS);
+    let sizes_len = 3 * (count - 1);
+    if tail.len() < sizes_len {
+        return Err(ImageError::Truncated);
+    }
+    let (sizes, mut data) = tail.split_at(sizes_len);
+
+    let mut partitions = Vec::with_capacity(count);
+    for size in sizes.chunks_exact(3) {
+        let size = size[0] as usize | (size[1] as usize) << 8 | (size[2] as usize) << 16;
+        if size > data.len() {
+            return Err(ImageError::Truncated);
+        }
+        let (partition, rest) = data.split_at(size);
+        partitions.push(BoolDecoder::new(partition));
+        data = rest;
+    }
+    partitions.push(BoolDecoder::new(data));
+    Ok(partitions)
+}
+
+// =============================================================================
+// COEFICIENTES
+// =============================================================================
+
+/// Lê os coeficientes de um bloco 4x4 a partir da posição `first`,
+/// dequantizados e em ordem natural.
+///
+/// Retorna a posição após o último coeficiente não nulo.
+fn read_coeffs(
+    br: &mut BoolDecoder,
+    probas: &[[[u8; NUM_PROBAS]; NUM_CTX]; NUM_BANDS],
+    ctx: usize,
+    dq: [i32; 2],
+    first: usize,
+    out: &mut [i32],
+) -> usize {
+    let mut n = first;
+    let mut p = &probas[BANDS[n]][ctx];
+    while n < 16 {
+        // Fim de bloco
+        if !br.read_bool(p[0]) {
+            return n;
+        }
+        // Sequência de zeros
+        while !br.read_bool(p[1]) {
+            n += 1;
+            if n == 16 {
+                return 16;
+            }
+            p = &probas[BANDS[n]][0];
+        }
+
+        let (value, next_ctx) = if !br.read_bool(p[2]) {
+            (1, 1)
+        } else {
+            (read_large_value(br, p), 2)
+        };
+        let value = if br.read_flag() { -value } else { value };
+        // Coeficientes têm 16 bits, como no decoder de referência
+        out[ZIGZAG[n]] = (value * dq[(n > 0) as usize]) as i16 as i32;
+
+        n += 1;
+        p = &probas[BANDS[n]][next_ctx];
+    }
+    16
+}
+
+/// Magnitude de um coeficiente maior que 1.
+fn read_large_value(br: &mut BoolDecoder, p: &[u8; NUM_PROBAS]) -> i32 {
+    if !br.read_bool(p[3]) {
+        if !br.read_bool(p[4]) {
+            2
+        } else {
+            3 + br.read_bool(p[5]) as i32
+        }
+    } else if !br.read_bool(p[6]) {
+        if !br.read_bool(p[7]) {
+            5 + br.read_bool(159) as i32
+        } else {
+            7 + 2 * br.read_bool(165) as i32 + br.read_bool(145) as i32
+        }
+    } else {
+        let bit1 = br.read_bool(p[8]) as usize;
+        let bit0 = br.read_bool(p[9 + bit1]) as usize;
+        let category = 2 * bit1 + bit0;
+        let table = [CAT3, CAT4, CAT5, CAT6][category];
+        let mut value = 0;
+        for &prob in table {
+            value = 2 * value + br.read_bool(prob) as i32;
+        }
+        value + 3 + (8 << category)
+    }
+}
+
+/// Walsh-Hadamard inversa: os 16 DCs de Y2 para o coeficiente 0 de cada
+/// bloco Y.
+fn inverse_wht(input: &[i32; 16], coeffs: &mut [i32; 384]) {
+    let mut tmp = [0i32; 16];
+    for i in 0..4 {
+        let a0 = input[i] + input[12 + i];
+        let a1 = input[4 + i] + input[8 + i];
+        let a2 = input[4 + i] - input[8 + i];
+        let a3 = input[i] - input[12 + i];
+        tmp[i] = a0 + a1;
+        tmp[8 + i] = a0 - a1;
+        tmp[4 + i] = a3 + a2;
+        tmp[12 + i] = a3 - a2;
+    }
+    for i in 0..4 {
+        let dc = tmp[i * 4] + 3;
+        let a0 = dc + tmp[3 + i * 4];
+        let a1 = tmp[1 + i * 4] + tmp[2 + i * 4];
+        let a2 = tmp[1 + i * 4] - tmp[2 + i * 4];
+        let a3 = dc - tmp[3 + i * 4];
+        let out = i * 4 * 16;
+        coeffs[out] = (a0 + a1) >> 3;
+        coeffs[out + 16] = (a3 + a2) >> 3;
+        coeffs[out + 32] = (a0 - a1) >> 3;
+        coeffs[out + 48] = (a3 - a2) >> 3;
+    }
+}
+
+/// DCT inversa de um bloco 4x4, somada aos pixels em `buf[offset..]`.
+fn inverse_dct_add(input: &[i32], buf: &mut [u8], offset: usize) {
+    if input.iter().all(|&c| c == 0) {
+        return;
+    }
+
+    // (a * sqrt(2) * cos(pi/8)) e (a * sqrt(2) * sin(pi/8)) em ponto fixo
+    let mul1 = |a: i32| (a.wrapping_mul(20091) >> 16).wrapping_add(a);
+    let mul2 = |a: i32| a.wrapping_mul(35468) >> 16;
+
+    let mut tmp = [0i32; 16];
+    for i in 0..4 {
+        let a = input[i] + input[8 + i];
+        let b = input[i] - input[8 + i];
+        let c = mul2(input[4 + i]) - mul1(input[12 + i]);
+        let d = mul1(input[4 + i]) + mul2(input[12 + i]);
+        tmp[i * 4] = a + d;
+        tmp[i * 4 + 1] = b + c;
+        tmp[i * 4 + 2] = b - c;
+        tmp[i * 4 + 3] = a - d;
+    }
+    for i in 0..4 {
+        let dc = tmp[i] + 4;
+        let a = dc + tmp[8 + i];
+        let b = dc - tmp[8 + i];
+        let c = mul2(tmp[4 + i]) - mul1(tmp[12 + i]);
+        let d = mul1(tmp[4 + i]) + mul2(tmp[12 + i]);
+        let row = offset + i * BPS;
+        for (x, v) in [a + d, b + c, b - c, a - d].into_iter().enumerate() {
+            buf[row + x] = clip_u8(buf[row + x] as i32 + (v >> 3));
+        }
+    }
+}
+
+// =============================================================================
+// PREDIÇÃO
+// =============================================================================
+
+/// Copia as bordas de cima e da esquerda de um bloco `size x size` para o
+/// buffer de trabalho.
+///
+/// Fora do quadro, a borda de cima vale 127 e a esquerda 129; o canto
+/// segue a de cima na primeira linha e a esquerda na primeira coluna.
+fn load_borders(
+    buf: &mut [u8],
+    plane: &[u8],
+    stride: usize,
+    mb_x: usize,
+    mb_y: usize,
+    size: usize,
+) {
+    let (x0, y0) = (mb_x * size, mb_y * size);
+    let top = ORIGIN - BPS;
+
+    if mb_y == 0 {
+        buf[top - 1..top + size].fill(127);
+    } else {
+        let row = (y0 - 1) * stride;
+        buf[top - 1] = if mb_x == 0 { 129 } else { plane[row + x0 - 1] };
+        buf[top..top + size].copy_from_slice(&plane[row + x0..row + x0 + size]);
+    }
+
+    for j in 0..size {
+        buf[ORIGIN + j * BPS - 1] = if mb_x == 0 {
+            129
+        } else {
+            plane[(y0 + j) * stride + x0 - 1]
+        };
+    }
+}
+
+/// Copia o bloco reconstruído do buffer de trabalho para o plano.
+fn store_block(buf: &[u8], plane: &mut [u8], stride: usize, mb_x: usize, mb_y: usize, size: usize) {
+    for j in 0..size {
+        let dst = (mb_y * size + j) * stride + mb_x * size;
+        let src = ORIGIN + j * BPS;
+        plane[dst..dst + size].copy_from_slice(&buf[src..src + size]);
+    }
+}
+
+/// Predição de um bloco inteiro (16x16 luma ou 8x8 croma).
+///
+/// Só o DC trata bordas ausentes; os outros modos usam os valores fixos
+/// da borda.
+fn predict_block(buf: &mut [u8], size: usize, mode: u8, has_left: bool, has_top: bool) {
+    let top = ORIGIN - BPS;
+    match mode {
+        B_VE_PRED => {
+            for j in 0..size {
+                buf.copy_within(top..top + size, ORIGIN + j * BPS);
+            }
+        }
+        B_HE_PRED => {
+            for j in 0..size {
+                let row = ORIGIN + j * BPS;
+                let left = buf[row - 1];
+                buf[row..row + size].fill(left);
+            }
+        }
+        B_TM_PRED => true_motion(buf, ORIGIN, size),
+        _ => {
+            let shift = size.trailing_zeros();
+            let top_sum: u32 = buf[top..top + size].iter().map(|&v| v as u32).sum();
+            let left_sum: u32 = (0..size).map(|j| buf[ORIGIN + j * BPS - 1] as u32).sum();
+            let dc = match (has_top, has_left) {
+                (true, true) => (top_sum + left_sum + size as u32) >> (shift + 1),
+                (true, false) => (top_sum + (size as u32 >> 1)) >> shift,
+                (false, true) => (left_sum + (size as u32 >> 1)) >> shift,
+                (false, false) => 128,
+            };
+            for j in 0..size {
+                let row = ORIGIN + j * BPS;
+                buf[row..row + size].fill(dc as u8);
+            }
+        }
+    }
+}
+
+/// `esquerda + cima - canto`, limitado.
+fn true_motion(buf: &mut [u8], offset: usize, size: usize) {
+    let top = offset - BPS;
+    let corner = buf[top - 1] as i32;
+    for j in 0..size {
+        let row = offset + j * BPS;
+        let left = buf[row - 1] as i32 - corner;
+        for x in 0..size {
+            buf[row + x] = clip_u8(buf[top + x] as i32 + left);
+        }
+    }
+}
+
+/// Predição de um bloco 4x4 em `offset`.
+fn predict_4x4(buf: &mut [u8], offset: usize, mode: u8) {
+    let top = offset - BPS;
+    // Cima (A..H, com os 4 da direita), esquerda (I..L) e canto (X)
+    let a: [i32; 8] = core::array::from_fn(|i| buf[top + i] as i32);
+    let l: [i32; 4] = core::array::from_fn(|j| buf[offset + j * BPS - 1] as i32);
+    let x = buf[top - 1] as i32;
+
+    let avg3 = |a: i32, b: i32, c: i32| ((a + 2 * b + c + 2) >> 2) as u8;
+    let avg2 = |a: i32, b: i32| ((a + b + 1) >> 1) as u8;
+    let mut out = [[0u8; 4]; 4];
+
+    match mode {
+        B_VE_PRED => {
+            let row = [
+                avg3(x, a[0], a[1]),
+                avg3(a[0], a[1], a[2]),
+                avg3(a[1], a[2], a[3]),
+                avg3(a[2], a[3], a[4]),
+            ];
+            out = [row; 4];
+        }
+        B_HE_PRED => {
+            let rows = [
+                avg3(x, l[0], l[1]),
+                avg3(l[0], l[1], l[2]),
+                avg3(l[1], l[2], l[3]),
+                avg3(l[2], l[3], l[3]),
+            ];
+            for (row, value) in out.iter_mut().zip(rows) {
+                *row = [value; 4];
+            }
+        }
+        B_TM_PRED => return true_motion(buf, offset, 4),
+        B_RD_PRED => {
+            // Diagonal de baixo-esquerda para cima-direita: L K J I X A B C D
+            let edge = [l[3], l[2], l[1], l[0], x, a[0], a[1], a[2], a[3]];
+            for (y, row) in out.iter_mut().enumerate() {
+                for (x, px) in row.iter_mut().enumerate() {
+                    let i = 3 - y + x;
+                    *px = avg3(edge[i], edge[i + 1], edge[i + 2]);
+                }
+            }
+        }
+        B_LD_PRED => {
+            for (y, row) in out.iter_mut().enumerate() {
+                for (x, px) in row.iter_mut().enumerate() {
+                    let i = x + y;
+                    *px = avg3(a[i], a[i + 1], a[(i + 2).min(7)]);
+                }
+            }
+        }
+        B_VR_PRED => {
+            out[0] = [
+                avg2(x, a[0]),
+                avg2(a[0], a[1]),
+                avg2(a[1], a[2]),
+                avg2(a[2], a[3]),
+            ];
+            out[1] = [
+                avg3(l[0], x, a[0]),
+                avg3(x, a[0], a[1]),
+                avg3(a[0], a[1], a[2]),
+                avg3(a[1], a[2], a[3]),
+            ];
+            out[2] = [avg3(l[1], l[0], x), out[0][0], out[0][1], out[0][2]];
+            out[3] = [avg3(l[2], l[1], l[0]), out[1][0], out[1][1], out[1][2]];
+        }
+        B_VL_PRED => {
+            out[0] = [
+                avg2(a[0], a[1]),
+                avg2(a[1], a[2]),
+                avg2(a[2], a[3]),
+                avg2(a[3], a[4]),
+            ];
+            out[1] = [
+                avg3(a[0], a[1], a[2]),
+                avg3(a[1], a[2], a[3]),
+                avg3(a[2], a[3], a[4]),
+                avg3(a[3], a[4], a[5]),
+            ];
+            out[2] = [out[0][1], out[0][2], out[0][3], avg3(a[4], a[5], a[6])];
+            out[3] = [out[1][1], out[1][2], out[1][3], avg3(a[5], a[6], a[7])];
+        }
+        B_HD_PRED => {
+            out[0] = [
+                avg2(l[0], x),
+                avg3(l[0], x, a[0]),
+                avg3(x, a[0], a[1]),
+                avg3(a[0], a[1], a[2]),
+            ];
+            out[1] = [avg2(l[1], l[0]), avg3(l[1], l[0], x), out[0][0], out[0][1]];
+            out[2] = [
+                avg2(l[2], l[1]),
+                avg3(l[2], l[1], l[0]),
+                out[1][0],
+                out[1][1],
+            ];
+            out[3] = [
+                avg2(l[3], l[2]),
+                avg3(l[3], l[2], l[1]),
+                out[2][0],
+                out[2][1],
+            ];
+        }
+        B_HU_PRED => {
+            let last = l[3] as u8;
+            out[0] = [
+                avg2(l[0], l[1]),
+                avg3(l[0], l[1], l[2]),
+                avg2(l[1], l[2]),
+                avg3(l[1], l[2], l[3]),
+            ];
+            out[1] = [
+                out[0][2],
+                out[0][3],
+                avg2(l[2], l[3]),
+                avg3(l[2], l[3], l[3]),
+            ];
+            out[2] = [out[1][2], out[1][3], last, last];
+            out[3] = [last; 4];
+        }
+        _ => {
+            let sum: i32 = a[..4].iter().sum::<i32>() + l.iter().sum::<i32>();
+            out = [[((sum + 4) >> 3) as u8; 4]; 4];
+        }
+    }
+
+    for (j, row) in out.iter().enumerate() {
+        let dst = offset + j * BPS;
+        buf[dst..dst + 4].copy_from_slice(row);
+    }
+}
+
+// =============================================================================
+// FILTROS DE BORDA
+// =============================================================================
+
+/// Parâmetros de uma borda do filtro normal.
+#[derive(Clone, Copy)]
+struct EdgeParams {
+    /// Limite de `4 * |p0 - q0| + |p1 - q1|`.
+    threshold: i32,
+    /// Limite das diferenças entre pixels vizinhos do mesmo lado.
+    interior: i32,
+    /// Acima disso a borda tem variação alta e só `p0`/`q0` mudam.
+    hev_threshold: i32,
+    /// Borda de macrobloco (filtro de 6 pixels) ou interna (4).
+    macroblock: bool,
+}
+
+/// Filtro simples ao longo de 16 pixels.
+///
+/// `step` cruza a borda; `advance` anda ao longo dela.
+fn simple_edge(plane: &mut [u8], mut pos: usize, step: usize, advance: usize, threshold: i32) {
+    for _ in 0..16 {
+        let p1 = plane[pos - 2 * step] as i32;
+        let p0 = plane[pos - step] as i32;
+        let q0 = plane[pos] as i32;
+        let q1 = plane[pos + step] as i32;
+        if 4 * (p0 - q0).abs() + (p1 - q1).abs() <= threshold {
+            filter2(plane, pos, step);
+        }
+        pos += advance;
+    }
+}
+
+/// Filtro normal ao longo de `count` pixels.
+fn normal_edge(
+    plane: &mut [u8],
+    mut pos: usize,
+    step: usize,
+    advance: usize,
+    count: usize,
+    params: EdgeParams,
+) {
+    for _ in 0..count {
+        let px = |k: isize| plane[(pos as isize + k * step as isize) as usize] as i32;
+        let (p3, p2, p1, p0) = (px(-4), px(-3), px(-2), px(-1));
+        let (q0, q1, q2, q3) = (px(0), px(1), px(2), px(3));
+
+        let it = params.interior;
+        let needs_filter = 4 * (p0 - q0).abs() + (p1 - q1).abs() <= params.threshold
+            && (p3 - p2).abs() <= it
+            && (p2 - p1).abs() <= it
+            && (p1 - p0).abs() <= it
+            && (q3 - q2).abs() <= it
+            && (q2 - q1).abs() <= it
+            && (q1 - q0).abs() <= it;
+
+        if needs_filter {
+            let hev =
+                (p1 - p0).abs() > params.hev_threshold || (q1 - q0).abs() > params.hev_threshold;
+            if hev {
+                filter2(plane, pos, step);
+            } else if params.macroblock {
+                filter6(plane, pos, step);
+            } else {
+                filter4(plane, pos, step);
+            }
+        }
+        pos += advance;
+    }
+}
+
+/// Ajusta `p0` e `q0`.
+fn filter2(plane: &mut [u8], pos: usize, step: usize) {
+    let p1 = plane[pos - 2 * step] as i32;
+    let p0 = plane[pos - step] as i32;
+    let q0 = plane[pos] as i32;
+    let q1 = plane[pos + step] as i32;
+    let a = 3 * (q0 - p0) + clamp_i8(p1 - q1);
+    let a1 = ((a + 4) >> 3).clamp(-16, 15);
+    let a2 = ((a + 3) >> 3).clamp(-16, 15);
+    plane[pos - step] = clip_u8(p0 + a2);
+    plane[pos] = clip_u8(q0 - a1);
+}
+
+/// Ajusta `p1..q1` (bordas internas sem variação alta).
+fn filter4(plane: &mut [u8], pos: usize, step: usize) {
+    let p1 = plane[pos - 2 * step] as i32;
+    let p0 = plane[pos - step] as i32;
+    let q0 = plane[pos] as i32;
+    let q1 = plane[pos + step] as i32;
+    let a = 3 * (q0 - p0);
+    let a1 = ((a + 4) >> 3).clamp(-16, 15);
+    let a2 = ((a + 3) >> 3).clamp(-16, 15);
+    let a3 = (a1 + 1) >> 1;
+    plane[pos - 2 * step] = clip_u8(p1 + a3);
+    plane[pos - step] = clip_u8(p0 + a2);
+    plane[pos] = clip_u8(q0 - a1);
+    plane[pos + step] = clip_u8(q1 - a3);
+}
+
+/// Ajusta `p2..q2` (bordas de macrobloco sem variação alta).
+fn filter6(plane: &mut [u8], pos: usize, step: usize) {
+    let p2 = plane[pos - 3 * step] as i32;
+    let p1 = plane[pos - 2 * step] as i32;
+    let p0 = plane[pos - step] as i32;
+    let q0 = plane[pos] as i32;
+    let q1 = plane[pos + step] as i32;
+    let q2 = plane[pos + 2 * step] as i32;
+    let a = clamp_i8(3 * (q0 - p0) + clamp_i8(p1 - q1));
+    let a1 = (27 * a + 63) >> 7;
+    let a2 = (18 * a + 63) >> 7;
+    let a3 = (9 * a + 63) >> 7;
+    plane[pos - 3 * step] = clip_u8(p2 + a3);
+    plane[pos - 2 * step] = clip_u8(p1 + a2);
+    plane[pos - step] = clip_u8(p0 + a1);
+    plane[pos] = clip_u8(q0 - a1);
+    plane[pos + step] = clip_u8(q1 - a2);
+    plane[pos + 2 * step] = clip_u8(q2 - a3);
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Limita a 0-255.
+#[inline]
+fn clip_u8(v: i32) -> u8 {
+    v.clamp(0, 255) as u8
+}
+
+/// Limita a -128..127.
+#[inline]
+fn clamp_i8(v: i32) -> i32 {
+    v.clamp(-128, 127)
+}
+
+/// YUV (BT.601, faixa limitada) para ARGB opaco, em ponto fixo de 14 bits.
+#[inline]
+fn yuv_to_argb(y: i32, u: i32, v: i32) -> u32 {
+    let mul = |v: i32, coeff: i32| (v * coeff) >> 8;
+    let clip = |v: i32| (v >> 6).clamp(0, 255) as u32;
+    let luma = mul(y, 19077);
+    let r = clip(luma + mul(v, 26149) - 14234);
+    let g = clip(luma - mul(u, 6419) - mul(v, 13320) + 8708);
+    let b = clip(luma + mul(u, 33050) - 17685);
+    0xFF00_0000 | r << 16 | g << 8 | b
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Codificador booleano (RFC 6386, seção 7.3), espelho do
+    /// [`BoolDecoder`].
+    struct BoolEncoder {
+        bytes: Vec<u8>,
+        range: u32,
+        bottom: u32,
+        bit_count: u32,
+    }
+
+    impl BoolEncoder {
+        fn new() -> Self {
+            Self {
+                bytes: Vec::new(),
+                range: 255,
+                bottom: 0,
+                bit_count: 24,
+            }
+        }
+
+        fn write_bool(&mut self, prob: u8, bit: bool) {
+            let split = 1 + (((self.range - 1) * prob as u32) >> 8);
+            if bit {
+                self.bottom = self.bottom.wrapping_add(split);
+                self.range -= split;
+            } else {
+                self.range = split;
+            }
+
+            while self.range < 128 {
+                self.range <<= 1;
+                if self.bottom & (1 << 31) != 0 {
+                    self.carry();
+                }
+                self.bottom <<= 1;
+                self.bit_count -= 1;
+                if self.bit_count == 0 {
+                    self.bytes.push((self.bottom >> 24) as u8);
+                    self.bottom &= (1 << 24) - 1;
+                    self.bit_count = 8;
+                }
+            }
+        }
+
+        /// Propaga o "vai um" pelos bytes já escritos.
+        fn carry(&mut self) {
+            for byte in self.bytes.iter_mut().rev() {
+                *byte = byte.wrapping_add(1);
+                if *byte != 0 {
+                    break;
+                }
+            }
+        }
+
+        fn write_flag(&mut self, bit: bool) {
+            self.write_bool(128, bit);
+        }
+
+        fn write_literal(&mut self, value: u32, bits: u32) {
+            for i in (0..bits).rev() {
+                self.write_flag(value >> i & 1 != 0);
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bottom & (1 << (32 - self.bit_count)) != 0 {
+                self.carry();
+            }
+            let mut value = self.bottom << (self.bit_count & 7);
+            for _ in 0..self.bit_count >> 3 {
+                value <<= 8;
+            }
+            self.bytes.extend_from_slice(&value.to_be_bytes());
+            self.bytes
+        }
+    }
+
+    /// Macrobloco de teste: modos 16x16 de luma e croma e o sinal do único
+    /// coeficiente, o DC do bloco Y2 (0 = macrobloco pulado).
+    type Mb = (u8, u8, i32);
+
+    /// Monta um quadro-chave `width x height`, com quantizador `q` e sem
+    /// filtro de laço.
+    fn encode(width: u16, height: u16, q: u32, mbs: &[Mb]) -> Vec<u8> {
+        let mut header = BoolEncoder::new();
+        let mut tokens = BoolEncoder::new();
+
+        // Espaço de cor, clamping, segmentação; filtro simples, nível,
+        // nitidez, deltas; uma partição de coeficientes
+        header.write_literal(0, 3);
+        header.write_literal(0, 1 + 6 + 3 + 1);
+        header.write_literal(0, 2);
+        header.write_literal(q, 7);
+        header.write_literal(0, 5);
+        header.write_flag(false);
+        for t in 0..NUM_TYPES {
+            for b in 0..NUM_BANDS {
+                for c in 0..NUM_CTX {
+                    for p in 0..NUM_PROBAS {
+                        header.write_bool(COEFF_UPDATE_PROBAS[t][b][c][p], false);
+                    }
+                }
+            }
+        }
+        header.write_flag(true);
+        header.write_literal(128, 8);
+
+        let mb_width = (width as usize).div_ceil(16);
+        let mut top_dc = vec![false; mb_width];
+        for (i, &(y_mode, uv_mode, dc)) in mbs.iter().enumerate() {
+            let mb_x = i % mb_width;
+            let left_dc = mb_x > 0 && mbs[i - 1].2 != 0;
+
+            header.write_bool(128, dc == 0);
+            header.write_bool(145, true);
+            let (a, b) = match y_mode {
+                B_DC_PRED => (false, false),
+                B_VE_PRED => (false, true),
+                B_HE_PRED => (true, false),
+                _ => (true, true),
+            };
+            header.write_bool(156, a);
+            header.write_bool(if a { 128 } else { 163 }, b);
+            match uv_mode {
+                B_DC_PRED => header.write_bool(142, false),
+                B_VE_PRED => {
+                    header.write_bool(142, true);
+                    header.write_bool(114, false);
+                }
+                mode => {
+                    header.write_bool(142, true);
+                    header.write_bool(114, true);
+                    header.write_bool(183, mode == B_TM_PRED);
+                }
+            }
+
+            if dc != 0 {
+                // Y2: um coeficiente de magnitude 1 e fim de bloco
+                let ctx = top_dc[mb_x] as usize + left_dc as usize;
+                let p = &DEFAULT_COEFF_PROBAS[1][BANDS[0]][ctx];
+                tokens.write_bool(p[0], true);
+                tokens.write_bool(p[1], true);
+                tokens.write_bool(p[2], false);
+                tokens.write_flag(dc < 0);
+                tokens.write_bool(DEFAULT_COEFF_PROBAS[1][BANDS[1]][1][0], false);
+
+                // 16 blocos Y sem AC, 8 de croma vazios
+                for _ in 0..16 {
+                    tokens.write_bool(DEFAULT_COEFF_PROBAS[0][BANDS[1]][0][0], false);
+                }
+                for _ in 0..8 {
+                    tokens.write_bool(DEFAULT_COEFF_PROBAS[2][BANDS[0]][0][0], false);
+                }
+            }
+            top_dc[mb_x] = dc != 0;
+        }
+
+        let first = header.finish();
+        let tag = (first.len() as u32) << 5 | 0x10;
+        let mut bytes = tag.to_le_bytes()[..3].to_vec();
+        bytes.extend_from_slice(&START_CODE);
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&first);
+        bytes.extend_from_slice(&tokens.finish());
+        bytes
+    }
+
+    /// Decodifica e confere que todos os pixels são `expected`.
+    fn assert_solid(bytes: &[u8], expected: u32) {
+        let image = decode(bytes).unwrap();
+        assert!(
+            image.pixels.iter().all(|&px| px == expected),
+            "{:08X} != {:08X}",
+            image.pixels[0],
+            expected
+        );
+    }
+
+    /// Quadro 16x16 usado também pelos testes do container: luma 133.
+    pub(crate) fn sample() -> Vec<u8> {
+        encode(16, 16, 127, &[(B_DC_PRED, B_DC_PRED, 1)])
+    }
+
+    #[test]
+    fn decodes_predicted_macroblocks() {
+        // Sem vizinhos: DC = 128, bordas de cima 127 e da esquerda 129
+        let solid = |y_mode, uv_mode| encode(16, 16, 0, &[(y_mode, uv_mode, 0)]);
+        assert_solid(&solid(B_DC_PRED, B_DC_PRED), 0xFF82_8282);
+        assert_solid(&solid(B_VE_PRED, B_DC_PRED), 0xFF81_8181);
+        assert_solid(&solid(B_HE_PRED, B_DC_PRED), 0xFF84_8484);
+        assert_solid(&solid(B_DC_PRED, B_VE_PRED), 0xFF81_8480);
+        assert_solid(&solid(B_DC_PRED, B_HE_PRED), 0xFF84_8184);
+    }
+
+    #[test]
+    fn adds_dequantized_residuals() {
+        // DC de Y2 = 157 * 2: luma 128 +- 5 depois das transformadas
+        assert_solid(&sample(), 0xFF88_8888);
+        assert_solid(
+            &encode(16, 16, 127, &[(B_DC_PRED, B_DC_PRED, -1)]),
+            0xFF7D_7D7D,
+        );
+
+        // O segundo macrobloco prevê 133 a partir do primeiro, com contexto 1
+        let pair = [(B_DC_PRED, B_DC_PRED, 1), (B_DC_PRED, B_DC_PRED, 1)];
+        let image = decode(&encode(32, 16, 127, &pair)).unwrap();
+        assert_eq!(
+            (image.pixels[0], image.pixels[16]),
+            (0xFF88_8888, 0xFF8E_8E8E)
+        );
+    }
+
+    #[test]
+    fn crops_partial_macroblocks() {
+        let mbs = [
+            (B_DC_PRED, B_DC_PRED, 1),
+            (B_VE_PRED, B_DC_PRED, 0),
+            (B_HE_PRED, B_DC_PRED, 0),
+            (B_TM_PRED, B_DC_PRED, 0),
+        ];
+        let image = decode(&encode(20, 18, 127, &mbs)).unwrap();
+        assert_eq!((image.width, image.height), (20, 18));
+        assert_eq!(image.pixels.len(), 20 * 18);
+
+        // Luma 133 (DC + resíduo), 127 (borda de cima), 129 (borda da
+        // esquerda) e 129 + 127 - 133 (TM)
+        for (i, &px) in image.pixels.iter().enumerate() {
+            let (x, y) = (i % 20, i / 20);
+            let expected = match (x < 16, y < 16) {
+                (true, true) => 0xFF88_8888,
+                (false, true) => 0xFF81_8181,
+                (true, false) => 0xFF84_8484,
+                (false, false) => 0xFF7D_7D7D,
+            };
+            assert_eq!(px, expected, "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn truncated_frames_fail() {
+        let bytes = encode(20, 18, 127, &[(B_DC_PRED, B_DC_PRED, 1); 4]);
+        // A partição de coeficientes pode ser lida um pouco além do fim
+        let first = (u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) >> 5) as usize;
+        for len in 0..10 + first {
+            assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_frames() {
+        let mut bytes = sample();
+        bytes[0] |= 1;
+        assert_eq!(decode(&bytes).err(), Some(ImageError::Unsupported));
+
+        let mut bytes = sample();
+        bytes[3] = 0;
+        assert_eq!(decode(&bytes).err(), Some(ImageError::InvalidHeader));
+    }
+}
@@ -0,0 +1,841 @@
+//! # VP8L
+//!
+//! Bitstream sem perdas.
+//!
+//! ```text
+//! 0x2F | largura-1 (14) | altura-1 (14) | alpha (1) | versão (3)
+//! transformações* | cache de cores | códigos de prefixo | pixels
+//! ```
+//!
+//! Os pixels são símbolos de prefixo (literal ARGB, referência LZ77 ou
+//! índice no cache de cores), possivelmente com um conjunto de códigos por
+//! bloco ("meta códigos"). Depois de decodificados, as transformações são
+//! desfeitas na ordem inversa: predição, correlação de cores, subtração do
+//! verde e paleta.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{alloc_pixels, pixel_count, Image, ImageError};
+use crate::render::image::bits::BitReader;
+use crate::render::image::huffman::PrefixCode;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Primeiro byte do bitstream.
+const SIGNATURE: u8 = 0x2F;
+
+/// Símbolos de comprimento LZ77 no alfabeto verde (após os 256 literais).
+const NUM_LENGTH_CODES: usize = 24;
+
+/// Símbolos do alfabeto de distâncias.
+const NUM_DISTANCE_CODES: usize = 40;
+
+/// Maior cache de cores (2^11 entradas).
+const MAX_CACHE_BITS: u32 = 11;
+
+/// Ordem dos comprimentos do código de comprimentos.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    17, 18, 0, 1, 2, 3, 4, 5, 16, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+];
+
+/// As 120 distâncias curtas: `dy << 4 | (8 - dx)`, em ordem de código.
+const DISTANCE_MAP: [u8; 120] = [
+    0x18, 0x07, 0x17, 0x19, 0x28, 0x06, 0x27, 0x29, 0x16, 0x1a, 0x26, 0x2a, 0x38, 0x05, 0x37, 0x39,
+    0x15, 0x1b, 0x36, 0x3a, 0x25, 0x2b, 0x48, 0x04, 0x47, 0x49, 0x14, 0x1c, 0x35, 0x3b, 0x46, 0x4a,
+    0x24, 0x2c, 0x58, 0x45, 0x4b, 0x34, 0x3c, 0x03, 0x57, 0x59, 0x13, 0x1d, 0x56, 0x5a, 0x23, 0x2d,
+    0x44, 0x4c, 0x55, 0x5b, 0x33, 0x3d, 0x68, 0x02, 0x67, 0x69, 0x12, 0x1e, 0x66, 0x6a, 0x22, 0x2e,
+    0x54, 0x5c, 0x43, 0x4d, 0x65, 0x6b, 0x32, 0x3e, 0x78, 0x01, 0x77, 0x79, 0x53, 0x5d, 0x11, 0x1f,
+    0x64, 0x6c, 0x42, 0x4e, 0x76, 0x7a, 0x21, 0x2f, 0x75, 0x7b, 0x31, 0x3f, 0x63, 0x6d, 0x52, 0x5e,
+    0x00, 0x74, 0x7c, 0x41, 0x4f, 0x10, 0x20, 0x62, 0x6e, 0x30, 0x73, 0x7d, 0x51, 0x5f, 0x40, 0x72,
+    0x7e, 0x61, 0x6f, 0x50, 0x71, 0x7f, 0x60, 0x70,
+];
+
+// =============================================================================
+// DECODER
+// =============================================================================
+
+/// Decodifica um chunk `VP8L`.
+pub fn decode(data: &[u8]) -> Result<Image, ImageError> {
+    if data.len() < 5 {
+        return Err(ImageError::Truncated);
+    }
+    if data[0] != SIGNATURE {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let mut br = BitReader::new(&data[1..]);
+    let width = br.read(14) + 1;
+    let height = br.read(14) + 1;
+    let _alpha_used = br.read_bit();
+    if br.read(3) != 0 {
+        return Err(ImageError::InvalidHeader);
+    }
+
+    let pixels = decode_image(&mut br, width, height)?;
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Decodifica um fluxo VP8L sem cabeçalho (usado pelo chunk `ALPH`).
+pub fn decode_stream(data: &[u8], width: u32, height: u32) -> Result<Vec<u32>, ImageError> {
+    decode_image(&mut BitReader::new(data), width, height)
+}
+
+/// Transformação a desfazer depois dos pixels.
+enum Transform {
+    /// Cada bloco `2^bits` prevê o pixel a partir dos vizinhos.
+    Predictor { bits: u32, modes: Vec<u32> },
+    /// Vermelho e azul corrigidos a partir do verde (e azul do vermelho).
+    Color { bits: u32, elements: Vec<u32> },
+    /// Verde subtraído de vermelho e azul.
+    SubtractGreen,
+    /// Índices de paleta, vários por pixel se a paleta é pequena.
+    ColorIndexing { bits: u32, palette: Vec<u32> },
+}
+
+/// Imagem principal: transformações e pixels.
+fn decode_image(br: &mut BitReader, width: u32, height: u32) -> Result<Vec<u32>, ImageError> {
+    pixel_count(width, height)?;
+
+    // Cada transformação guarda a largura da imagem sobre a qual foi lida
+    let mut transforms: Vec<(Transform, u32)> = Vec::new();
+    let mut seen = 0u32;
+    let mut xsize = width;
+    while br.read_bit() {
+        let kind = br.read(2);
+        if seen & (1 << kind) != 0 {
+            return Err(ImageError::Corrupt);
+        }
+        seen |= 1 << kind;
+
+        let transform = match kind {
+            0 | 1 => {
+                let bits = br.read(3) + 2;
+                let data = decode_entropy_image(
+                    br,
+                    div_round_up(xsize, bits),
+                    div_round_up(height, bits),
+                )?;
+                if kind == 0 {
+                    Transform::Predictor { bits, modes: data }
+                } else {
+                    Transform::Color {
+                        bits,
+                        elements: data,
+                    }
+                }
+            }
+            2 => Transform::SubtractGreen,
+            _ => {
+                let size = br.read(8) + 1;
+                let mut palette = decode_entropy_image(br, size, 1)?;
+                for i in 1..palette.len() {
+                    palette[i] = add_pixels(palette[i], palette[i - 1]);
+                }
+                // Índices fora da paleta viram preto transparente
+                palette.resize(256, 0);
+                let bits = match size {
+                    0..=2 => 3,
+                    3..=4 => 2,
+                    5..=16 => 1,
+                    _ => 0,
+                };
+                Transform::ColorIndexing { bits, palette }
+            }
+        };
+
+        let reduced = match transform {
+            Transform::ColorIndexing { bits, .. } => div_round_up(xsize, bits),
+            _ => xsize,
+        };
+        transforms.push((transform, xsize));
+        xsize = reduced;
+    }
+
+    let mut pixels = decode_pixels(br, xsize, height, true)?;
+    if br.overflowed() {
+        return Err(ImageError::Truncated);
+    }
+
+    for (transform, width) in transforms.iter().rev() {
+        let width = *width;
+        match transform {
+            Transform::Predictor { bits, modes } => {
+                apply_predictor(&mut pixels, width, height, *bits, modes)
+            }
+            Transform::Color { bits, elements } => {
+                apply_color(&mut pixels, width, height, *bits, elements)
+            }
+            Transform::SubtractGreen => apply_subtract_green(&mut pixels),
+            Transform::ColorIndexing { bits, palette } => {
+                pixels = apply_color_indexing(&pixels, width, height, *bits, palette)
+            }
+        }
+    }
+
+    Ok(pixels)
+}
+
+/// Sub-imagem (dados de transformação, meta códigos, paleta).
+fn decode_entropy_image(
+    br: &mut BitReader,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u32>, ImageError> {
+    decode_pixels(br, width, height, false)
+}
+
+/// Códigos de prefixo de um grupo: verde/comprimento/cache, vermelho,
+/// azul, alpha e distância.
+type Group = [PrefixCode; 5];
+
+/// Cache de cores, lido antes dos códigos de prefixo.
+fn read_cache_bits(br: &mut BitReader) -> Result<u32, ImageError> {
+    if !br.read_bit() {
+        return Ok(0);
+    }
+    let bits = br.read(4);
+    if bits == 0 || bits > MAX_CACHE_BITS {
+        return Err(ImageError::Corrupt);
+    }
+    Ok(bits)
+}
+
+/// Decodifica `width x height` pixels codificados por entropia.
+///
+/// Só a imagem principal (`main`) pode ter meta códigos.
+fn decode_pixels(
+    br: &mut BitReader,
+    width: u32,
+    height: u32,
+    main: bool,
+) -> Result<Vec<u32>, ImageError> {
+    let mut pixels = alloc_pixels(width, height)?;
+    let cache_bits = read_cache_bits(br)?;
+    let cache_size = if cache_bits > 0 { 1 << cache_bits } else { 0 };
+
+    // Meta códigos: cada bloco 2^group_bits escolhe um grupo
+    let mut group_bits = 0;
+    let mut group_map = Vec::new();
+    let mut num_groups = 1;
+    if main && br.read_bit() {
+        group_bits = br.read(3) + 2;
+        group_map = decode_entropy_image(
+            br,
+            div_round_up(width, group_bits),
+            div_round_up(height, group_bits),
+        )?;
+        for entry in group_map.iter_mut() {
+            *entry = (*entry >> 8) & 0xFFFF;
+            num_groups = num_groups.max(*entry as usize + 1);
+        }
+    }
+
+    // Grupos nunca referenciados são lidos e descartados
+    let mut dense = vec![u32::MAX; num_groups];
+    if group_map.is_empty() {
+        dense[0] = 0;
+    }
+    for &entry in &group_map {
+        dense[entry as usize] = 0;
+    }
+    let mut groups: Vec<Group> = Vec::new();
+    for slot in dense.iter_mut() {
+        let group = read_group(br, cache_size)?;
+        if *slot != u32::MAX {
+            *slot = groups.len() as u32;
+            groups.push(group);
+        }
+    }
+    for entry in group_map.iter_mut() {
+        *entry = dense[*entry as usize];
+    }
+
+    let mut cache = vec![0u32; cache_size];
+    let cache_shift = 32 - cache_bits;
+    let groups_width = div_round_up(width, group_bits) as usize;
+    let width = width as usize;
+    let total = pixels.len();
+    let mut pos = 0;
+    while pos < total {
+        let group = if group_bits > 0 {
+            let (x, y) = (pos % width, pos / width);
+            &groups[group_map[(y >> group_bits) * groups_width + (x >> group_bits)] as usize]
+        } else {
+            &groups[0]
+        };
+
+        let symbol = group[0].read(br) as usize;
+        if symbol < 256 {
+            let red = group[1].read(br) as u32;
+            let blue = group[2].read(br) as u32;
+            let alpha = group[3].read(br) as u32;
+            pixels[pos] = alpha << 24 | red << 16 | (symbol as u32) << 8 | blue;
+            pos += 1;
+        } else if symbol < 256 + NUM_LENGTH_CODES {
+            let length = prefix_value(br, (symbol - 256) as u32) as usize;
+            let distance_symbol = group[4].read(br) as u32;
+            let distance = plane_distance(width, prefix_value(br, distance_symbol) as usize);
+            if distance > pos || length > total - pos {
+                return Err(ImageError::Corrupt);
+            }
+            for i in pos..pos + length {
+                pixels[i] = pixels[i - distance];
+            }
+            if cache_size > 0 {
+                for &px in &pixels[pos..pos + length] {
+                    cache[cache_index(px, cache_shift)] = px;
+                }
+            }
+            pos += length;
+            if br.overflowed() {
+                return Err(ImageError::Truncated);
+            }
+            continue;
+        } else {
+            let index = symbol - 256 - NUM_LENGTH_CODES;
+            pixels[pos] = *cache.get(index).ok_or(ImageError::Corrupt)?;
+            pos += 1;
+        }
+
+        if cache_size > 0 {
+            let px = pixels[pos - 1];
+            cache[cache_index(px, cache_shift)] = px;
+        }
+    }
+
+    if br.overflowed() {
+        return Err(ImageError::Truncated);
+    }
+    Ok(pixels)
+}
+
+/// Posição de `argb` no cache de cores.
+#[inline]
+fn cache_index(argb: u32, shift: u32) -> usize {
+    (argb.wrapping_mul(0x1E35_A7BD) >> shift) as usize
+}
+
+/// Lê os cinco códigos de um grupo.
+fn read_group(br: &mut BitReader, cache_size: usize) -> Result<Group, ImageError> {
+    Ok([
+        read_code(br, 256 + NUM_LENGTH_CODES + cache_size)?,
+        read_code(br, 256)?,
+        read_code(br, 256)?,
+        read_code(br, 256)?,
+        read_code(br, NUM_DISTANCE_CODES)?,
+    ])
+}
+
+/// Lê um código de prefixo (simples ou normal) de `alphabet` símbolos.
+fn read_code(br: &mut BitReader, alphabet: usize) -> Result<PrefixCode, ImageError> {
+    let mut lengths = vec![0u8; alphabet];
+
+    if br.read_bit() {
+        // Simples: um ou dois símbolos de comprimento 1
+        let count = br.read(1) + 1;
+        let first_bits = if br.read_bit() { 8 } else { 1 };
+        let first = br.read(first_bits) as usize;
+        *lengths.get_mut(first).ok_or(ImageError::Corrupt)? = 1;
+        if count == 2 {
+            let second = br.read(8) as usize;
+            *lengths.get_mut(second).ok_or(ImageError::Corrupt)? = 1;
+        }
+    } else {
+        let mut code_length_lengths = [0u8; 19];
+        let count = br.read(4) as usize + 4;
+        for &symbol in &CODE_LENGTH_ORDER[..count] {
+            code_length_lengths[symbol] = br.read(3) as u8;
+        }
+        let code_lengths = PrefixCode::new(&code_length_lengths)?;
+
+        let mut max_symbols = if br.read_bit() {
+            let bits = 2 + 2 * br.read(3);
+            let max = 2 + br.read(bits) as usize;
+            if max > alphabet {
+                return Err(ImageError::Corrupt);
+            }
+            max
+        } else {
+            alphabet
+        };
+
+        let mut previous = 8;
+        let mut symbol = 0;
+        while symbol < alphabet && max_symbols > 0 {
+            max_symbols -= 1;
+            let code = code_lengths.read(br) as u8;
+            if code < 16 {
+                lengths[symbol] = code;
+                symbol += 1;
+                if code != 0 {
+                    previous = code;
+                }
+                continue;
+            }
+
+            let (extra, base, value) = match code {
+                16 => (2, 3, previous),
+                17 => (3, 3, 0),
+                _ => (7, 11, 0),
+            };
+            let repeat = br.read(extra) as usize + base;
+            if symbol + repeat > alphabet {
+                return Err(ImageError::Corrupt);
+            }
+            lengths[symbol..symbol + repeat].fill(value);
+            symbol += repeat;
+        }
+    }
+
+    if br.overflowed() {
+        return Err(ImageError::Truncated);
+    }
+    PrefixCode::new(&lengths)
+}
+
+/// Valor de um símbolo de comprimento/distância LZ77 (com bits extras).
+#[inline]
+fn prefix_value(br: &mut BitReader, symbol: u32) -> u32 {
+    if symbol < 4 {
+        return symbol + 1;
+    }
+    let extra = (symbol - 2) >> 1;
+    let offset = (2 + (symbol & 1)) << extra;
+    offset + br.read(extra) + 1
+}
+
+/// Converte código de distância em deslocamento linear.
+///
+/// Os 120 primeiros códigos são vizinhos 2D próximos; os demais, a
+/// distância menos 120.
+#[inline]
+fn plane_distance(width: usize, code: usize) -> usize {
+    if code > DISTANCE_MAP.len() {
+        return code - DISTANCE_MAP.len();
+    }
+    let entry = DISTANCE_MAP[code - 1] as isize;
+    let dy = entry >> 4;
+    let dx = 8 - (entry & 0xF);
+    (dy * width as isize + dx).max(1) as usize
+}
+
+// =============================================================================
+// TRANSFORMAÇÕES
+// =============================================================================
+
+/// Desfaz a predição espacial.
+fn apply_predictor(pixels: &mut [u32], width: u32, height: u32, bits: u32, modes: &[u32]) {
+    let w = width as usize;
+    let blocks_width = div_round_up(width, bits) as usize;
+
+    // Primeira linha: pixel 0 prevê preto opaco, os outros o da esquerda
+    pixels[0] = add_pixels(pixels[0], 0xFF00_0000);
+    for x in 1..w {
+        pixels[x] = add_pixels(pixels[x], pixels[x - 1]);
+    }
+
+    for y in 1..height as usize {
+        let row = y * w;
+        let block_row = (y >> bits) * blocks_width;
+
+        // Primeira coluna prevê o pixel de cima
+        pixels[row] = add_pixels(pixels[row], pixels[row - w]);
+        for x in 1..w {
+            let i = row + x;
+            let mode = (modes[block_row + (x >> bits)] >> 8) & 0xF;
+            let left = pixels[i - 1];
+            let top = pixels[i - w];
+            let top_left = pixels[i - w - 1];
+            // No fim da linha, TR é o primeiro pixel da linha atual
+            let top_right = pixels[i - w + 1];
+
+            let prediction = match mode {
+                1 => left,
+                2 => top,
+                3 => top_right,
+                4 => top_left,
+                5 => average2(average2(left, top_right), top),
+                6 => average2(left, top_left),
+                7 => average2(left, top),
+                8 => average2(top_left, top),
+                9 => average2(top, top_right),
+                10 => average2(average2(left, top_left), average2(top, top_right)),
+                11 => select(left, top, top_left),
+                12 => clamp_add_subtract_full(left, top, top_left),
+                13 => clamp_add_subtract_half(average2(left, top), top_left),
+                _ => 0xFF00_0000,
+            };
+            pixels[i] = add_pixels(pixels[i], prediction);
+        }
+    }
+}
+
+/// Desfaz a correlação entre canais.
+fn apply_color(pixels: &mut [u32], width: u32, height: u32, bits: u32, elements: &[u32]) {
+    let w = width as usize;
+    let blocks_width = div_round_up(width, bits) as usize;
+
+    for y in 0..height as usize {
+        let block_row = (y >> bits) * blocks_width;
+        for (x, px) in pixels[y * w..(y + 1) * w].iter_mut().enumerate() {
+            let element = elements[block_row + (x >> bits)];
+            let green_to_red = element as i8;
+            let green_to_blue = (element >> 8) as i8;
+            let red_to_blue = (element >> 16) as i8;
+
+            let green = (*px >> 8) as i8;
+            let red = ((*px >> 16) as i32 + color_delta(green_to_red, green)) & 0xFF;
+            let blue = (*px as i32
+                + color_delta(green_to_blue, green)
+                + color_delta(red_to_blue, red as u8 as i8))
+                & 0xFF;
+            *px = (*px & 0xFF00_FF00) | (red as u32) << 16 | blue as u32;
+        }
+    }
+}
+
+/// Soma o verde de volta ao vermelho e ao azul.
+fn apply_subtract_green(pixels: &mut [u32]) {
+    for px in pixels.iter_mut() {
+        let green = (*px >> 8) & 0xFF;
+        let red_blue = ((*px & 0x00FF_00FF) + (green << 16 | green)) & 0x00FF_00FF;
+        *px = (*px & 0xFF00_FF00) | red_blue;
+    }
+}
+
+/// Expande índices (empacotados em `8 >> bits` bits) pela paleta.
+fn apply_color_indexing(
+    packed: &[u32],
+    width: u32,
+    height: u32,
+    bits: u32,
+    palette: &[u32],
+) -> Vec<u32> {
+    let w = width as usize;
+    let packed_width = div_round_up(width, bits) as usize;
+    let index_bits = 8 >> bits;
+    let index_mask = (1 << index_bits) - 1;
+    let per_pixel_mask = (1 << bits) - 1;
+
+    let mut pixels = Vec::with_capacity(w * height as usize);
+    for y in 0..height as usize {
+        let row = &packed[y * packed_width..(y + 1) * packed_width];
+        for x in 0..w {
+            let indices = (row[x >> bits] >> 8) & 0xFF;
+            let shift = (x & per_pixel_mask) as u32 * index_bits;
+            pixels.push(palette[((indices >> shift) & index_mask) as usize]);
+        }
+    }
+    pixels
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// `ceil(size / 2^bits)`.
+#[inline]
+fn div_round_up(size: u32, bits: u32) -> u32 {
+    (size + (1 << bits) - 1) >> bits
+}
+
+/// Soma canal a canal, módulo 256.
+#[inline]
+fn add_pixels(a: u32, b: u32) -> u32 {
+    let alpha_green = (a & 0xFF00_FF00).wrapping_add(b & 0xFF00_FF00) & 0xFF00_FF00;
+    let red_blue = (a & 0x00FF_00FF).wrapping_add(b & 0x00FF_00FF) & 0x00FF_00FF;
+    alpha_green | red_blue
+}
+
+/// Média canal a canal (arredondada para baixo).
+#[inline]
+fn average2(a: u32, b: u32) -> u32 {
+    (((a ^ b) & 0xFEFE_FEFE) >> 1) + (a & b)
+}
+
+/// Canal `shift` de `pixel`.
+#[inline]
+fn channel(pixel: u32, shift: u32) -> i32 {
+    ((pixel >> shift) & 0xFF) as i32
+}
+
+/// Escolhe `left` ou `top`, o mais próximo do gradiente `left + top - top_left`.
+fn select(left: u32, top: u32, top_left: u32) -> u32 {
+    let mut distance_left = 0;
+    let mut distance_top = 0;
+    for shift in [24, 16, 8, 0] {
+        distance_left += (channel(top, shift) - channel(top_left, shift)).abs();
+        distance_top += (channel(left, shift) - channel(top_left, shift)).abs();
+    }
+    if distance_left < distance_top {
+        left
+    } else {
+        top
+    }
+}
+
+/// `a + b - c` limitado a 0-255, canal a canal.
+fn clamp_add_subtract_full(a: u32, b: u32, c: u32) -> u32 {
+    let mut out = 0;
+    for shift in [24, 16, 8, 0] {
+        let value = channel(a, shift) + channel(b, shift) - channel(c, shift);
+        out |= (value.clamp(0, 255) as u32) << shift;
+    }
+    out
+}
+
+/// `a + (a - b) / 2` limitado a 0-255, canal a canal.
+fn clamp_add_subtract_half(a: u32, b: u32) -> u32 {
+    let mut out = 0;
+    for shift in [24, 16, 8, 0] {
+        let (ca, cb) = (channel(a, shift), channel(b, shift));
+        out |= ((ca + (ca - cb) / 2).clamp(0, 255) as u32) << shift;
+    }
+    out
+}
+
+/// Correção de um canal pela transformação de cores.
+#[inline]
+fn color_delta(transform: i8, color: i8) -> i32 {
+    (transform as i32 * color as i32) >> 5
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Escritor de bits LSB-first, espelho do [`BitReader`].
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        count: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u32, bits: u32) {
+            for i in 0..bits {
+                if self.count.is_multiple_of(8) {
+                    self.bytes.push(0);
+                }
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= ((value >> i & 1) as u8) << (self.count % 8);
+                self.count += 1;
+            }
+        }
+    }
+
+    /// Escreve `pixels` com códigos simples (no máximo dois valores por
+    /// canal), sem cache de cores e sem meta códigos.
+    fn write_pixels(w: &mut BitWriter, pixels: &[u32], main: bool) {
+        w.write(0, 1);
+        if main {
+            w.write(0, 1);
+        }
+
+        // Verde, vermelho, azul, alpha; distância nunca usada
+        let mut codes = Vec::new();
+        for shift in [8, 16, 0, 24] {
+            let mut symbols: Vec<u32> = pixels.iter().map(|&px| px >> shift & 0xFF).collect();
+            symbols.sort_unstable();
+            symbols.dedup();
+            assert!(symbols.len() <= 2);
+
+            w.write(1, 1);
+            w.write(symbols.len() as u32 - 1, 1);
+            w.write(1, 1);
+            for &symbol in &symbols {
+                w.write(symbol, 8);
+            }
+            codes.push((shift, symbols));
+        }
+        w.write(0b0001, 4);
+
+        // Com dois símbolos, o menor tem o código 0
+        for &px in pixels {
+            for (shift, symbols) in &codes {
+                if symbols.len() == 2 {
+                    w.write((px >> shift & 0xFF == symbols[1]) as u32, 1);
+                }
+            }
+        }
+    }
+
+    /// Fluxo sem cabeçalho, sem transformações.
+    pub(crate) fn stream(pixels: &[u32]) -> Vec<u8> {
+        let mut w = BitWriter::default();
+        w.write(0, 1);
+        write_pixels(&mut w, pixels, true);
+        w.bytes
+    }
+
+    /// Transformação aplicada pelo [`sample`].
+    #[derive(Clone, Copy, PartialEq)]
+    enum Coding {
+        Plain,
+        SubtractGreen,
+        /// Paleta de até duas cores.
+        Palette,
+    }
+
+    /// Monta um chunk `VP8L` `width x height` com `pixels`.
+    fn encode(width: u32, height: u32, pixels: &[u32], coding: Coding) -> Vec<u8> {
+        let mut w = BitWriter::default();
+        w.write(SIGNATURE as u32, 8);
+        w.write(width - 1, 14);
+        w.write(height - 1, 14);
+        w.write(1, 1);
+        w.write(0, 3);
+
+        let coded: Vec<u32> = match coding {
+            Coding::Plain => {
+                w.write(0, 1);
+                pixels.to_vec()
+            }
+            Coding::SubtractGreen => {
+                w.write(1, 1);
+                w.write(2, 2);
+                w.write(0, 1);
+                pixels
+                    .iter()
+                    .map(|&px| {
+                        let green = px >> 8 & 0xFF;
+                        let red = (px >> 16).wrapping_sub(green) & 0xFF;
+                        let blue = px.wrapping_sub(green) & 0xFF;
+                        px & 0xFF00_FF00 | red << 16 | blue
+                    })
+                    .collect()
+            }
+            Coding::Palette => {
+                let mut palette = pixels.to_vec();
+                palette.sort_unstable();
+                palette.dedup();
+                assert!(palette.len() <= 2);
+
+                w.write(1, 1);
+                w.write(3, 2);
+                w.write(palette.len() as u32 - 1, 8);
+                let deltas: Vec<u32> = (0..palette.len())
+                    .map(|i| match i {
+                        0 => palette[0],
+                        _ => subtract_pixels(palette[i], palette[i - 1]),
+                    })
+                    .collect();
+                write_pixels(&mut w, &deltas, false);
+                w.write(0, 1);
+
+                // Oito índices de 1 bit por pixel, no verde
+                let mut packed = Vec::new();
+                for row in pixels.chunks(width as usize) {
+                    for group in row.chunks(8) {
+                        let mut green = 0;
+                        for (i, px) in group.iter().enumerate() {
+                            let index = palette.iter().position(|c| c == px).unwrap();
+                            green |= (index as u32) << i;
+                        }
+                        packed.push(green << 8);
+                    }
+                }
+                packed
+            }
+        };
+        write_pixels(&mut w, &coded, true);
+        w.bytes
+    }
+
+    /// `a - b`, canal a canal, módulo 256.
+    fn subtract_pixels(a: u32, b: u32) -> u32 {
+        let mut out = 0;
+        for shift in [24, 16, 8, 0] {
+            out |= ((a >> shift).wrapping_sub(b >> shift) & 0xFF) << shift;
+        }
+        out
+    }
+
+    /// Imagem 3x2 de duas cores, usada também pelos testes do container.
+    pub(crate) const PIXELS: [u32; 6] = [
+        0xFFE0_4010,
+        0x8020_40F0,
+        0x8020_40F0,
+        0xFFE0_4010,
+        0xFFE0_4010,
+        0x8020_40F0,
+    ];
+
+    /// Chunk `VP8L` com [`PIXELS`].
+    pub(crate) fn sample() -> Vec<u8> {
+        encode(3, 2, &PIXELS, Coding::Plain)
+    }
+
+    #[test]
+    fn decodes_literal_pixels() {
+        let image = decode(&sample()).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.pixels, PIXELS);
+    }
+
+    #[test]
+    fn undoes_subtract_green() {
+        let pixels = [0xFF10_8030, 0x40F0_20A0, 0xFF10_8030, 0xFF10_8030];
+        let image = decode(&encode(2, 2, &pixels, Coding::SubtractGreen)).unwrap();
+        assert_eq!(image.pixels, pixels);
+    }
+
+    #[test]
+    fn expands_palette_indices() {
+        // Mais de oito pixels por linha: dois pixels empacotados
+        let (a, b) = (0xFF00_80FF, 0x4020_1000);
+        let row = [a, b, b, a, a, a, b, a, b, a];
+        let pixels = [row, row].concat();
+        let image = decode(&encode(10, 2, &pixels, Coding::Palette)).unwrap();
+        assert_eq!(image.pixels, pixels);
+    }
+
+    #[test]
+    fn decodes_headerless_streams() {
+        let alpha = [0x0000_1000, 0x0000_F000, 0x0000_1000];
+        assert_eq!(decode_stream(&stream(&alpha), 3, 1).unwrap(), alpha);
+    }
+
+    #[test]
+    fn truncated_streams_fail() {
+        for coding in [Coding::Plain, Coding::SubtractGreen, Coding::Palette] {
+            let bytes = encode(3, 2, &PIXELS, coding);
+            for len in 0..bytes.len() {
+                assert!(decode(&bytes[..len]).is_err(), "{} bytes", len);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut bytes = sample();
+        bytes[0] = 0x2E;
+        assert_eq!(decode(&bytes).err(), Some(ImageError::InvalidHeader));
+
+        // Versão 1
+        let mut bytes = sample();
+        bytes[4] |= 0x20;
+        assert_eq!(decode(&bytes).err(), Some(ImageError::InvalidHeader));
+
+        // Subtração do verde duas vezes
+        let mut w = BitWriter::default();
+        w.write(SIGNATURE as u32, 8);
+        w.write(0, 32);
+        for _ in 0..2 {
+            w.write(1, 1);
+            w.write(2, 2);
+        }
+        assert_eq!(decode(&w.bytes).err(), Some(ImageError::Corrupt));
+    }
+}
@@ -0,0 +1,247 @@
+//! # Text Layout
+//!
+//! Medição, truncamento com reticências e quebra de linhas.
+//!
+//! ## Uso
+//!
+//! ```text
+//! // Uma linha, truncada com "…" se passar de 120px
+//! let layout = TextLayout::single_line(font, "Editor de Texto Avançado", 120);
+//!
+//! // Várias linhas, no máximo 3 (a última recebe "…" se sobrar texto)
+//! let layout = TextLayout::wrapped(font, description, 240, 3);
+//!
+//! canvas.draw_layout(&layout, bounds, TextAlign::Center, &style);
+//! ```
+//!
+//! Todas as larguras usam o avanço real de cada glifo da fonte.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::font::TextFont;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Reticências (U+2026).
+pub const ELLIPSIS: &str = "\u{2026}";
+
+/// Reticências para fontes sem U+2026.
+const ELLIPSIS_FALLBACK: &str = "...";
+
+// =============================================================================
+// TIPOS
+// =============================================================================
+
+/// Alinhamento horizontal do texto.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextAlign {
+    /// Alinhado à esquerda.
+    #[default]
+    Left,
+    /// Centralizado.
+    Center,
+    /// Alinhado à direita.
+    Right,
+}
+
+impl TextAlign {
+    /// Deslocamento X de uma linha de largura `width` em `available` pixels.
+    pub fn offset(self, width: u32, available: u32) -> i32 {
+        let free = available.saturating_sub(width) as i32;
+        match self {
+            TextAlign::Left => 0,
+            TextAlign::Center => free / 2,
+            TextAlign::Right => free,
+        }
+    }
+}
+
+/// Linha de texto já medida.
+#[derive(Debug, Clone, Default)]
+pub struct TextLine {
+    /// Conteúdo da linha.
+    pub text: String,
+    /// Largura em pixels.
+    pub width: u32,
+}
+
+// =============================================================================
+// FUNÇÕES
+// =============================================================================
+
+/// Reticências suportadas pela fonte.
+pub fn ellipsis(font: &dyn TextFont) -> &'static str {
+    if font.has_glyph('\u{2026}') {
+        ELLIPSIS
+    } else {
+        ELLIPSIS_FALLBACK
+    }
+}
+
+/// Trunca `text` com reticências para caber em `max_width`.
+///
+/// Retorna o texto original (sem cópia) quando ele já cabe.
+pub fn truncate<'t>(font: &dyn TextFont, text: &'t str, max_width: u32) -> Cow<'t, str> {
+    if font.measure(text) <= max_width {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(ellipsize(font, text, max_width))
+    }
+}
+
+/// Corta `text` e acrescenta reticências, sempre, cabendo em `max_width`.
+///
+/// Se nem as reticências couberem, retorna string vazia.
+fn ellipsize(font: &dyn TextFont, text: &str, max_width: u32) -> String {
+    let mark = ellipsis(font);
+    let mark_width = font.measure(mark);
+    if mark_width > max_width {
+        return String::new();
+    }
+
+    let budget = max_width - mark_width;
+    let mut width = 0;
+    let mut end = 0;
+
+    for (i, c) in text.char_indices() {
+        let advance = font.advance(c);
+        if width + advance > budget {
+            break;
+        }
+        width += advance;
+        end = i + c.len_utf8();
+    }
+
+    let mut out = String::from(text[..end].trim_end());
+    out.push_str(mark);
+    out
+}
+
+// =============================================================================
+// TEXT LAYOUT
+// =============================================================================
+
+/// Texto dividido em linhas prontas para desenho.
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    /// Linhas, de cima para baixo.
+    lines: Vec<TextLine>,
+    /// Distância entre linhas consecutivas.
+    line_height: u32,
+    /// Altura de uma linha de glifos.
+    glyph_height: u32,
+}
+
+impl TextLayout {
+    /// Layout de uma linha, truncada com reticências em `max_width`.
+    ///
+    /// Quebras de linha são tratadas como espaço.
+    pub fn single_line(font: &dyn TextFont, text: &str, max_width: u32) -> Self {
+        let text = if text.contains('\n') {
+            Cow::Owned(text.replace('\n', " "))
+        } else {
+            Cow::Borrowed(text)
+        };
+        let text = truncate(font, &text, max_width).into_owned();
+        let width = font.measure(&text);
+
+        Self {
+            lines: alloc::vec![TextLine { text, width }],
+            line_height: font.line_height(),
+            glyph_height: font.height(),
+        }
+    }
+
+    /// Layout com quebra de palavras em `max_width`.
+    ///
+    /// `\n` força nova linha. Palavras maiores que `max_width` são
+    /// quebradas entre caracteres. Com mais de `max_lines` linhas, o texto
+    /// é cortado e a última linha recebe reticências.
+    pub fn wrapped(font: &dyn TextFont, text: &str, max_width: u32, max_lines: usize) -> Self {
+        let mut lines = Vec::new();
+        let space = font.advance(' ');
+
+        'paragraphs: for paragraph in text.split('\n') {
+            let mut line = TextLine::default();
+
+            for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+                let word_width = font.measure(word);
+
+                // Cabe na linha atual
+                if line.text.is_empty() && word_width <= max_width {
+                    line.text.push_str(word);
+                    line.width = word_width;
+                    continue;
+                }
+                if !line.text.is_empty() && line.width + space + word_width <= max_width {
+                    line.text.push(' ');
+                    line.text.push_str(word);
+                    line.width += space + word_width;
+                    continue;
+                }
+
+                if !line.text.is_empty() {
+                    lines.push(core::mem::take(&mut line));
+                    if lines.len() > max_lines {
+                        break 'paragraphs;
+                    }
+                }
+
+                // Palavra longa: quebrar entre caracteres
+                for c in word.chars() {
+                    let advance = font.advance(c);
+                    if line.width + advance > max_width && !line.text.is_empty() {
+                        lines.push(core::mem::take(&mut line));
+                        if lines.len() > max_lines {
+                            break 'paragraphs;
+                        }
+                    }
+                    line.text.push(c);
+                    line.width += advance;
+                }
+            }
+
+            lines.push(line);
+            if lines.len() > max_lines {
+                break;
+            }
+        }
+
+        // Sobrou texto: cortar e marcar a última linha visível
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                last.text = ellipsize(font, &last.text, max_width);
+                last.width = font.measure(&last.text);
+            }
+        }
+
+        Self {
+            lines,
+            line_height: font.line_height(),
+            glyph_height: font.height(),
+        }
+    }
+
+    /// Linhas do layout.
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Distância entre linhas consecutivas.
+    pub fn line_height(&self) -> u32 {
+        self.line_height
+    }
+
+    /// Altura total (da primeira à última linha de glifos).
+    pub fn height(&self) -> u32 {
+        match self.lines.len() {
+            0 => 0,
+            n => (n as u32 - 1) * self.line_height + self.glyph_height,
+        }
+    }
+}
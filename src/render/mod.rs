@@ -1,9 +1,50 @@
 //! # Render Module
 //!
 //! Renderização do Shell.
+//!
+//! ## Componentes
+//!
+//! - **blur**: Blur e saturação para vidro fosco
+//! - **canvas**: Superfície de desenho com clip e translação
+//! - **damage**: Regiões danificadas para redesenho parcial
+//! - **font**: Fontes bitmap (PSF2, BDF e 8x8 embutida) e TrueType
+//! - **glyph_cache**: Cache de glifos rasterizados
+//! - **image**: Decoders de imagem (PNG, QOI, BMP, WebP e SVG) e escala
+//! - **layout**: Medição, truncamento e quebra de linhas
+//! - **paint**: Tintas (cor sólida, gradientes e imagem)
+//! - **pixel**: Blend em ARGB pré-multiplicado, sem divisão
+//! - **raster**: Rasterizador de contornos com anti-aliasing
+//! - **shadow**: Sombras projetadas montadas de um canto pré-desfocado
+//! - **shape**: Retângulos arredondados com anti-aliasing
+//! - **surface**: Buffers offscreen compostos com opacidade
+//! - **text**: Desenho de strings
 
+pub mod blur;
+pub mod canvas;
+pub mod damage;
 pub mod font;
+pub mod glyph_cache;
+pub mod image;
+pub mod layout;
+pub mod paint;
+pub mod pixel;
+pub mod raster;
+pub mod shadow;
+pub mod shape;
+pub mod surface;
+pub mod text;
+
+pub use canvas::Canvas;
+pub use damage::DamageRegion;
+pub use font::FontSet;
+pub use image::Image;
+pub use layout::{TextAlign, TextLayout};
+pub use paint::{GradientStop, LinearGradient, Paint, RadialGradient};
+pub use shadow::ShadowKernel;
+pub use shape::CornerRadii;
+pub use surface::Surface;
+pub use text::{TextBaseline, TextStyle};
 
 // TODO: Revisar no futuro
 #[allow(unused)]
-pub use font::Font;
+pub use font::{Font, TextFont};
@@ -0,0 +1,356 @@
+//! # Paint
+//!
+//! Como uma área é pintada: cor sólida, gradiente ou imagem.
+//!
+//! ```text
+//! const SHEEN: &[GradientStop] = &[
+//!     GradientStop::new(0.0, Color(0x30FFFFFF)),
+//!     GradientStop::new(0.4, Color(0x00FFFFFF)),
+//! ];
+//!
+//! let paint = Paint::LinearGradient(LinearGradient::new(180.0, SHEEN));
+//! canvas.fill_rounded_paint(rect, 16, &paint);
+//! ```
+//!
+//! Gradientes são avaliados em coordenadas relativas ao retângulo pintado,
+//! então o mesmo `Paint` serve para qualquer tamanho. Antes de pintar, o
+//! gradiente vira um [`Shader`] com uma tabela de 256 cores já
+//! interpoladas. A interpolação é em alpha pré-multiplicado, o formato do
+//! canvas, então não há franjas escuras entre uma cor e o transparente.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use gfx_types::color::Color;
+use gfx_types::geometry::{Point, Rect, Size};
+
+use super::pixel::premultiply;
+use super::raster::floorf;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Entradas da tabela de cores de um gradiente.
+const GRADIENT_LUT_SIZE: usize = 256;
+
+// =============================================================================
+// PAINT
+// =============================================================================
+
+/// Tinta de preenchimento.
+#[derive(Debug, Clone)]
+pub enum Paint {
+    /// Cor única.
+    Solid(Color),
+    /// Gradiente ao longo de uma direção.
+    LinearGradient(LinearGradient),
+    /// Gradiente a partir de um centro.
+    RadialGradient(RadialGradient),
+    /// Imagem repetida.
+    ///
+    /// Nenhum estilo do tema usa textura ainda; a variante existe para
+    /// temas com imagem de fundo.
+    #[allow(dead_code)]
+    Pattern(ImagePattern),
+}
+
+impl Paint {
+    /// Nada pintado.
+    pub const NONE: Self = Self::Solid(Color(0x00000000));
+
+    /// Retorna se a tinta não cobre nada (cor sólida transparente).
+    pub fn is_transparent(&self) -> bool {
+        matches!(self, Self::Solid(color) if color.alpha() == 0)
+    }
+
+    /// Prepara a tinta para pintar `rect`.
+    pub fn shader(&self, rect: Rect) -> Shader<'_> {
+        match self {
+            Self::Solid(color) => Shader::Solid(premultiply(color.as_u32())),
+            Self::LinearGradient(gradient) => gradient.shader(rect),
+            Self::RadialGradient(gradient) => gradient.shader(rect),
+            Self::Pattern(pattern) => Shader::Pattern(pattern),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+/// Parada de cor de um gradiente.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientStop {
+    /// Posição ao longo do gradiente (0.0 - 1.0).
+    pub offset: f32,
+    /// Cor na posição.
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Cria parada em `offset` com `color`.
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// Gradiente linear.
+#[derive(Debug, Clone, Copy)]
+pub struct LinearGradient {
+    /// Direção em graus, como no CSS: 0 = para cima, 90 = para a direita,
+    /// 180 = para baixo.
+    pub angle: f32,
+    /// Paradas em ordem crescente de `offset`.
+    pub stops: &'static [GradientStop],
+}
+
+impl LinearGradient {
+    /// Cria gradiente na direção `angle` (graus).
+    pub const fn new(angle: f32, stops: &'static [GradientStop]) -> Self {
+        Self { angle, stops }
+    }
+
+    /// Prepara para `rect`.
+    ///
+    /// A linha do gradiente passa pelo centro e tem o comprimento que faz
+    /// os cantos caírem exatamente em 0.0 e 1.0.
+    fn shader(&self, rect: Rect) -> Shader<'static> {
+        let (sin, cos) = sin_cos_degrees(self.angle);
+        let (dx, dy) = (sin, -cos);
+        let (w, h) = (rect.width as f32, rect.height as f32);
+        let length = (w * dx).abs() + (h * dy).abs();
+        if length <= 0.0 {
+            return Shader::Solid(first_color(self.stops));
+        }
+
+        // t = ((p - centro) . dir) / length + 0.5, com p no centro do pixel
+        let (step_x, step_y) = (dx / length, dy / length);
+        let start = 0.5 - (w / 2.0 - 0.5) * step_x - (h / 2.0 - 0.5) * step_y;
+        Shader::Linear {
+            lut: Box::new(gradient_lut(self.stops)),
+            start,
+            step_x,
+            step_y,
+        }
+    }
+}
+
+/// Gradiente radial (circular).
+#[derive(Debug, Clone, Copy)]
+pub struct RadialGradient {
+    /// Centro relativo ao retângulo (0.0 - 1.0 em cada eixo).
+    pub center: (f32, f32),
+    /// Raio como fração da distância do centro ao canto mais distante.
+    pub radius: f32,
+    /// Paradas em ordem crescente de `offset`.
+    pub stops: &'static [GradientStop],
+}
+
+impl RadialGradient {
+    /// Cria gradiente centrado em `center` (relativo) até o canto mais
+    /// distante.
+    pub const fn new(center: (f32, f32), stops: &'static [GradientStop]) -> Self {
+        Self {
+            center,
+            radius: 1.0,
+            stops,
+        }
+    }
+
+    /// Prepara para `rect`.
+    fn shader(&self, rect: Rect) -> Shader<'static> {
+        let (w, h) = (rect.width as f32, rect.height as f32);
+        let cx = self.center.0 * w;
+        let cy = self.center.1 * h;
+        let far_x = cx.max(w - cx);
+        let far_y = cy.max(h - cy);
+        let radius = rdsmath::sqrtf(far_x * far_x + far_y * far_y) * self.radius;
+        if radius <= 0.0 {
+            return Shader::Solid(first_color(self.stops));
+        }
+
+        Shader::Radial {
+            lut: Box::new(gradient_lut(self.stops)),
+            cx,
+            cy,
+            inv_radius: 1.0 / radius,
+        }
+    }
+}
+
+/// Imagem repetida lado a lado.
+#[derive(Debug, Clone)]
+pub struct ImagePattern {
+    /// Pixels ARGB pré-multiplicados, linha a linha.
+    pub pixels: Rc<[u32]>,
+    /// Dimensões da imagem.
+    pub size: Size,
+    /// Deslocamento da primeira repetição em relação ao retângulo.
+    pub offset: Point,
+}
+
+impl ImagePattern {
+    /// Cria padrão de `pixels` (`size.width * size.height`).
+    #[allow(dead_code)] // Ver `Paint::Pattern`
+    pub fn new(pixels: Rc<[u32]>, size: Size) -> Self {
+        Self {
+            pixels,
+            size,
+            offset: Point::new(0, 0),
+        }
+    }
+}
+
+// =============================================================================
+// SHADER
+// =============================================================================
+
+/// Tinta pronta para um retângulo: cor (pré-multiplicada) de cada pixel
+/// relativo a ele.
+pub enum Shader<'a> {
+    /// Cor única.
+    Solid(u32),
+    /// `t = start + x * step_x + y * step_y`.
+    Linear {
+        lut: Box<[u32; GRADIENT_LUT_SIZE]>,
+        start: f32,
+        step_x: f32,
+        step_y: f32,
+    },
+    /// `t = distância ao centro / raio`.
+    Radial {
+        lut: Box<[u32; GRADIENT_LUT_SIZE]>,
+        cx: f32,
+        cy: f32,
+        inv_radius: f32,
+    },
+    /// Imagem repetida.
+    Pattern(&'a ImagePattern),
+}
+
+impl Shader<'_> {
+    /// Cor ARGB pré-multiplicada do pixel `(x, y)`, relativo ao retângulo.
+    #[inline]
+    pub fn color_at(&self, x: i32, y: i32) -> u32 {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Linear {
+                lut,
+                start,
+                step_x,
+                step_y,
+            } => lut[lut_index(start + x as f32 * step_x + y as f32 * step_y)],
+            Self::Radial {
+                lut,
+                cx,
+                cy,
+                inv_radius,
+            } => {
+                let dx = x as f32 + 0.5 - cx;
+                let dy = y as f32 + 0.5 - cy;
+                lut[lut_index(rdsmath::sqrtf(dx * dx + dy * dy) * inv_radius)]
+            }
+            Self::Pattern(pattern) => {
+                let (w, h) = (pattern.size.width as i32, pattern.size.height as i32);
+                if w == 0 || h == 0 {
+                    return 0;
+                }
+                let px = (x - pattern.offset.x).rem_euclid(w);
+                let py = (y - pattern.offset.y).rem_euclid(h);
+                pattern
+                    .pixels
+                    .get((py * w + px) as usize)
+                    .copied()
+                    .unwrap_or(0)
+            }
+        }
+    }
+}
+
+/// Índice da tabela para `t`, limitado a `[0, 1]`.
+#[inline]
+fn lut_index(t: f32) -> usize {
+    (t.clamp(0.0, 1.0) * (GRADIENT_LUT_SIZE - 1) as f32 + 0.5) as usize
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Cor pré-multiplicada da primeira parada (transparente sem paradas).
+fn first_color(stops: &[GradientStop]) -> u32 {
+    stops
+        .first()
+        .map_or(0, |stop| premultiply(stop.color.as_u32()))
+}
+
+/// Tabela de cores pré-multiplicadas de `stops`, amostrada uniformemente
+/// em `[0, 1]`.
+///
+/// Antes da primeira e depois da última parada a cor é estendida.
+fn gradient_lut(stops: &[GradientStop]) -> [u32; GRADIENT_LUT_SIZE] {
+    let mut lut = [0u32; GRADIENT_LUT_SIZE];
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return lut;
+    };
+
+    let mut segment = 0;
+    for (i, entry) in lut.iter_mut().enumerate() {
+        let t = i as f32 / (GRADIENT_LUT_SIZE - 1) as f32;
+        *entry = if t <= first.offset {
+            premultiply(first.color.as_u32())
+        } else if t >= last.offset {
+            premultiply(last.color.as_u32())
+        } else {
+            while segment + 2 < stops.len() && t > stops[segment + 1].offset {
+                segment += 1;
+            }
+            let (a, b) = (stops[segment], stops[segment + 1]);
+            let span = b.offset - a.offset;
+            let f = if span > 0.0 {
+                (t - a.offset) / span
+            } else {
+                1.0
+            };
+            lerp(
+                premultiply(a.color.as_u32()),
+                premultiply(b.color.as_u32()),
+                f,
+            )
+        };
+    }
+    lut
+}
+
+/// Interpola canal a canal.
+fn lerp(a: u32, b: u32, f: f32) -> u32 {
+    let mix = |shift: u32| {
+        let ca = ((a >> shift) & 0xFF) as f32;
+        let cb = ((b >> shift) & 0xFF) as f32;
+        ((ca + (cb - ca) * f + 0.5) as u32).min(255) << shift
+    };
+    mix(24) | mix(16) | mix(8) | mix(0)
+}
+
+/// Seno e cosseno de `degrees`, sem libm.
+///
+/// Reduz ao primeiro quadrante e usa Taylor até o 9º grau (erro < 1e-5).
+fn sin_cos_degrees(degrees: f32) -> (f32, f32) {
+    let turns = degrees / 360.0;
+    let degrees = (turns - floorf(turns)) * 360.0;
+    let quadrant = (degrees / 90.0) as u32 % 4;
+    let x = (degrees - quadrant as f32 * 90.0) * (core::f32::consts::PI / 180.0);
+
+    let x2 = x * x;
+    let sin = x * (1.0 - x2 / 6.0 * (1.0 - x2 / 20.0 * (1.0 - x2 / 42.0 * (1.0 - x2 / 72.0))));
+    let cos = 1.0 - x2 / 2.0 * (1.0 - x2 / 12.0 * (1.0 - x2 / 30.0 * (1.0 - x2 / 56.0)));
+
+    match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
@@ -0,0 +1,163 @@
+//! # Pixel
+//!
+//! Operações por pixel e por trecho de linha (span), em ARGB
+//! pré-multiplicado.
+//!
+//! Todo buffer desenhado pelo [`Canvas`](super::Canvas) guarda cores
+//! pré-multiplicadas: cada canal já vem multiplicado pelo alpha. O
+//! source-over fica `dst * (255 - sa) / 255 + src`, igual em todos os
+//! canais (inclusive o alpha), e pixels opacos são idênticos nos dois
+//! formatos.
+//!
+//! ## Sem divisão
+//!
+//! `x * a / 255` é feito como `((x * a + 0x80) * 257) >> 16`, que dá o
+//! mesmo resultado arredondado para `x, a <= 255`. Dois canais são
+//! processados por operação (`0x00RR00BB` e `0x00AA00GG`).
+//!
+//! Não há caminho SIMD: o alvo do Shell desliga SSE (soft-float), então
+//! os spans usam o mesmo caminho escalar em todo build.
+
+// =============================================================================
+// PIXEL
+// =============================================================================
+
+/// Máscara dos canais pares (R e B, ou A e G após `>> 8`).
+const CHANNEL_MASK: u32 = 0x00FF_00FF;
+
+/// `x / 255` arredondado, sem divisão (`x <= 255 * 255`).
+#[inline]
+pub fn div255(x: u32) -> u32 {
+    ((x + 0x80) * 257) >> 16
+}
+
+/// Multiplica os 4 canais de `pixel` por `factor / 255`.
+#[inline]
+pub fn scale(pixel: u32, factor: u32) -> u32 {
+    let rb = (pixel & CHANNEL_MASK) * factor + 0x0080_0080;
+    let rb = ((rb + ((rb >> 8) & CHANNEL_MASK)) >> 8) & CHANNEL_MASK;
+    let ag = ((pixel >> 8) & CHANNEL_MASK) * factor + 0x0080_0080;
+    let ag = (ag + ((ag >> 8) & CHANNEL_MASK)) & !CHANNEL_MASK;
+    rb | ag
+}
+
+/// Converte ARGB direto para pré-multiplicado.
+#[inline]
+pub fn premultiply(color: u32) -> u32 {
+    scale(color | 0xFF00_0000, color >> 24)
+}
+
+/// Source-over de `src` sobre `dst` (ambos pré-multiplicados).
+#[inline]
+pub fn over(dst: u32, src: u32) -> u32 {
+    match src >> 24 {
+        0 => dst,
+        255 => src,
+        sa => src + scale(dst, 255 - sa),
+    }
+}
+
+// =============================================================================
+// SPANS
+// =============================================================================
+
+/// Combina `color` (pré-multiplicado) sobre todos os pixels de `span`.
+pub fn blend_span(span: &mut [u32], color: u32) {
+    match color >> 24 {
+        0 => {}
+        255 => span.fill(color),
+        _ => {
+            for px in span {
+                *px = over(*px, color);
+            }
+        }
+    }
+}
+
+/// Combina `src` sobre `span` pixel a pixel (mesmo comprimento).
+pub fn blend_span_from(span: &mut [u32], src: &[u32]) {
+    let len = span.len().min(src.len());
+    let (span, src) = (&mut span[..len], &src[..len]);
+    for (px, &s) in span.iter_mut().zip(src) {
+        *px = over(*px, s);
+    }
+}
+
+/// Combina `src` com opacidade `opacity` (0-255) sobre `span`.
+pub fn blend_span_from_scaled(span: &mut [u32], src: &[u32], opacity: u8) {
+    match opacity {
+        0 => {}
+        255 => blend_span_from(span, src),
+        _ => {
+            let len = span.len().min(src.len());
+            let (span, src) = (&mut span[..len], &src[..len]);
+            let opacity = opacity as u32;
+            for (px, &s) in span.iter_mut().zip(src) {
+                *px = over(*px, scale(s, opacity));
+            }
+        }
+    }
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `x * a / 255` arredondado, com divisão.
+    fn reference(x: u32, a: u32) -> u32 {
+        (x * a + 127) / 255
+    }
+
+    #[test]
+    fn div255_matches_division() {
+        for x in 0..=255 {
+            for a in 0..=255 {
+                assert_eq!(div255(x * a), reference(x, a), "{} * {}", x, a);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_matches_per_channel() {
+        let pixel = 0xC0_80_3F_01;
+        for factor in 0..=255 {
+            let expected = [24, 16, 8, 0]
+                .iter()
+                .map(|&shift| reference((pixel >> shift) & 0xFF, factor) << shift)
+                .fold(0, |acc, channel| acc | channel);
+            assert_eq!(scale(pixel, factor), expected, "fator {}", factor);
+        }
+    }
+
+    #[test]
+    fn spans_match_single_pixels() {
+        let dst: [u32; 7] =
+            core::array::from_fn(|i| premultiply(0x8000_0000 | (i as u32 * 0x0020_3040)));
+        let src: [u32; 7] =
+            core::array::from_fn(|i| premultiply(((i as u32 * 40) << 24) | 0x00C0_4020));
+        let color = premultiply(0x8040_80C0);
+
+        let mut span = dst;
+        blend_span(&mut span, color);
+        assert!(span
+            .iter()
+            .zip(&dst)
+            .all(|(&out, &d)| out == over(d, color)));
+
+        let mut span = dst;
+        blend_span_from_scaled(&mut span, &src, 100);
+        for i in 0..dst.len() {
+            assert_eq!(span[i], over(dst[i], scale(src[i], 100)));
+        }
+
+        // Opacidade 255 é o blend direto; `src` mais curto limita o span
+        let mut span = dst;
+        blend_span_from_scaled(&mut span, &src[..3], 255);
+        assert_eq!(span[2], over(dst[2], src[2]));
+        assert_eq!(span[3..], dst[3..]);
+    }
+}
@@ -0,0 +1,268 @@
+//! # Rasterizer
+//!
+//! Rasterizador de contornos com anti-aliasing por cobertura.
+//!
+//! ## Algoritmo
+//!
+//! Cada segmento de reta deposita, em um acumulador `f32` por pixel, a
+//! área assinada que ocupa em cada linha. Uma soma corrida sobre o
+//! acumulador produz a cobertura final (0.0 - 1.0) de cada pixel, já
+//! anti-aliased, sem supersampling. Curvas são achatadas em retas antes
+//! de serem depositadas.
+//!
+//! O preenchimento segue a regra nonzero (contornos com orientação oposta
+//! formam buracos) ou, opcionalmente, evenodd.
+
+use alloc::vec::Vec;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Tolerância de achatamento de curvas (maior = mais segmentos).
+const FLATTEN_TOLERANCE: f32 = 3.0;
+
+/// Desvio abaixo do qual uma curva é desenhada como reta.
+const FLATTEN_MIN_DEVIATION: f32 = 0.333;
+
+/// Limite de segmentos por curva.
+const MAX_CURVE_SEGMENTS: usize = 64;
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// `floor` para `f32` sem libm.
+#[inline]
+pub fn floorf(x: f32) -> f32 {
+    let i = x as i32 as f32;
+    if i > x {
+        i - 1.0
+    } else {
+        i
+    }
+}
+
+/// `ceil` para `f32` sem libm.
+#[inline]
+pub fn ceilf(x: f32) -> f32 {
+    let i = x as i32 as f32;
+    if i < x {
+        i + 1.0
+    } else {
+        i
+    }
+}
+
+// =============================================================================
+// RASTERIZER
+// =============================================================================
+
+/// Ponto em coordenadas de pixel (y para baixo).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointF {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl PointF {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    fn lerp(self, other: PointF, t: f32) -> PointF {
+        PointF::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+}
+
+/// Acumulador de cobertura para uma área `width x height`.
+pub struct Rasterizer {
+    width: usize,
+    height: usize,
+    /// Área assinada por pixel (+ folga para a última coluna).
+    acc: Vec<f32>,
+    /// Ponto atual do caminho.
+    current: PointF,
+    /// Início do contorno atual.
+    start: PointF,
+}
+
+impl Rasterizer {
+    /// Cria rasterizador para uma área `width x height`.
+    pub fn new(width: u32, height: u32) -> Self {
+        let width = width as usize;
+        let height = height as usize;
+        Self {
+            width,
+            height,
+            acc: alloc::vec![0.0; width * height + 4],
+            current: PointF::new(0.0, 0.0),
+            start: PointF::new(0.0, 0.0),
+        }
+    }
+
+    /// Inicia um novo contorno (fechando o anterior).
+    pub fn move_to(&mut self, p: PointF) {
+        self.close();
+        self.current = p;
+        self.start = p;
+    }
+
+    /// Reta até `p`.
+    pub fn line_to(&mut self, p: PointF) {
+        self.draw_line(self.current, p);
+        self.current = p;
+    }
+
+    /// Bézier quadrática até `p` com controle `c`.
+    pub fn quad_to(&mut self, c: PointF, p: PointF) {
+        let p0 = self.current;
+        let ddx = p0.x - 2.0 * c.x + p.x;
+        let ddy = p0.y - 2.0 * c.y + p.y;
+        let dev = ddx * ddx + ddy * ddy;
+
+        if dev < FLATTEN_MIN_DEVIATION {
+            self.line_to(p);
+            return;
+        }
+
+        let n = Self::segments(rdsmath::sqrtf(rdsmath::sqrtf(FLATTEN_TOLERANCE * dev)));
+        let step = 1.0 / n as f32;
+        let mut prev = p0;
+
+        for i in 1..n {
+            let t = i as f32 * step;
+            let next = p0.lerp(c, t).lerp(c.lerp(p, t), t);
+            self.draw_line(prev, next);
+            prev = next;
+        }
+
+        self.draw_line(prev, p);
+        self.current = p;
+    }
+
+    /// Fecha o contorno atual com uma reta até o início.
+    pub fn close(&mut self) {
+        if self.current != self.start {
+            self.draw_line(self.current, self.start);
+        }
+        self.current = self.start;
+    }
+
+    /// Número de segmentos para achatar uma curva.
+    fn segments(estimate: f32) -> usize {
+        (1 + estimate as usize).min(MAX_CURVE_SEGMENTS)
+    }
+
+    /// Deposita a área assinada de uma reta no acumulador.
+    pub fn draw_line(&mut self, p0: PointF, p1: PointF) {
+        if p0.y == p1.y || self.width == 0 {
+            return;
+        }
+
+        let (dir, p0, p1) = if p0.y < p1.y {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+
+        let max_x = self.width as f32;
+        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+        let mut x = p0.x;
+
+        if p0.y < 0.0 {
+            x -= p0.y * dxdy;
+        }
+
+        let y_start = p0.y.max(0.0) as usize;
+        let y_end = (ceilf(p1.y).max(0.0) as usize).min(self.height);
+
+        for y in y_start..y_end {
+            let row = y * self.width;
+            let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
+            let x_next = x + dxdy * dy;
+            let d = dy * dir;
+
+            // Trechos fora da área horizontal acumulam na borda
+            let xa = x.clamp(0.0, max_x);
+            let xb = x_next.clamp(0.0, max_x);
+            let (x0, x1) = if xa < xb { (xa, xb) } else { (xb, xa) };
+
+            let x0_floor = floorf(x0);
+            let x0i = x0_floor as usize;
+            let x1_ceil = ceilf(x1);
+            let x1i = x1_ceil as usize;
+
+            if x1i <= x0i + 1 {
+                // Segmento dentro de um único pixel
+                let xmf = 0.5 * (xa + xb) - x0_floor;
+                self.acc[row + x0i] += d - d * xmf;
+                self.acc[row + x0i + 1] += d * xmf;
+            } else {
+                let s = 1.0 / (x1 - x0);
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+
+                self.acc[row + x0i] += d * a0;
+
+                if x1i == x0i + 2 {
+                    self.acc[row + x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    self.acc[row + x0i + 1] += d * (a1 - a0);
+
+                    for xi in x0i + 2..x1i - 1 {
+                        self.acc[row + xi] += d * s;
+                    }
+
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    self.acc[row + x1i - 1] += d * (1.0 - a2 - am);
+                }
+
+                self.acc[row + x1i] += d * am;
+            }
+
+            x = x_next;
+        }
+    }
+
+    /// Finaliza e retorna a cobertura (0-255) de cada pixel.
+    pub fn into_coverage(self) -> Vec<u8> {
+        self.coverage(false)
+    }
+
+    /// Como [`into_coverage`](Self::into_coverage), com a regra evenodd
+    /// (toda sobreposição de contornos alterna entre dentro e fora).
+    pub fn into_coverage_even_odd(self) -> Vec<u8> {
+        self.coverage(true)
+    }
+
+    fn coverage(mut self, even_odd: bool) -> Vec<u8> {
+        self.close();
+
+        let len = self.width * self.height;
+        let mut coverage = Vec::with_capacity(len);
+        let mut sum = 0.0f32;
+
+        for &a in &self.acc[..len] {
+            sum += a;
+            let mut c = if sum < 0.0 { -sum } else { sum };
+            if even_odd {
+                // Dobra a cobertura em [0, 1]: 1.5 -> 0.5, 2.0 -> 0.0
+                c -= 2.0 * floorf(c * 0.5);
+                if c > 1.0 {
+                    c = 2.0 - c;
+                }
+            }
+            coverage.push((c.min(1.0) * 255.0 + 0.5) as u8);
+        }
+
+        coverage
+    }
+}
@@ -0,0 +1,96 @@
+//! # Shadow
+//!
+//! Sombras projetadas de retângulos arredondados.
+//!
+//! Desfocar a silhueta a cada frame custaria tanto quanto o blur de fundo.
+//! Só que a sombra de um retângulo arredondado só varia perto dos cantos:
+//! um [`ShadowKernel`] guarda um canto já desfocado (máscara 0-255) e a
+//! sombra de qualquer tamanho é montada em nove fatias:
+//!
+//! ```text
+//! +----+-----------+----+
+//! | TL |  borda ↑  | TR |   cantos: o kernel espelhado
+//! +----+-----------+----+
+//! | ←  |   miolo   |  → |   bordas: última linha/coluna do kernel
+//! +----+-----------+----+
+//! | BL |  borda ↓  | BR |   miolo: última amostra (sólido)
+//! +----+-----------+----+
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::blur::{self, BOX_BLUR_PASSES};
+use super::shape::{self, CornerRadii};
+
+// =============================================================================
+// SHADOW KERNEL
+// =============================================================================
+
+/// Canto desfocado de uma silhueta com raio e blur fixos.
+pub struct ShadowKernel {
+    /// Raio do blur: a sombra passa `blur` pixels da silhueta.
+    blur: u32,
+    /// Lado do canto (`raio + 2 * blur`); a amostra `size` já está fora da
+    /// influência do arco.
+    size: u32,
+    /// Cobertura do canto superior esquerdo, `(size + 1)^2` bytes.
+    corner: Vec<u8>,
+}
+
+impl ShadowKernel {
+    /// Pré-calcula o canto de uma silhueta de raio `radius` desfocada por
+    /// `blur` pixels.
+    pub fn new(radius: u32, blur: u32) -> Self {
+        let size = radius + 2 * blur;
+        let side = 2 * size + 1;
+        let shape_side = side - 2 * blur;
+        let radii = CornerRadii::all(radius).clamped(shape_side, shape_side);
+
+        // Silhueta com margem `blur` em volta; a cobertura vai no canal azul
+        let mut mask = vec![0u32; (side * side) as usize];
+        for y in 0..shape_side {
+            let row = ((y + blur) * side + blur) as usize;
+            for x in 0..shape_side {
+                let coverage = shape::coverage(shape_side, shape_side, &radii, x as i32, y as i32);
+                mask[row + x as usize] = (coverage * 255.0 + 0.5) as u32;
+            }
+        }
+
+        // Três passadas de raio blur/3 espalham por ~blur pixels
+        let pass_radius = blur / BOX_BLUR_PASSES;
+        if pass_radius > 0 {
+            blur::box_blur(&mut mask, side as usize, side as usize, pass_radius);
+        }
+
+        let mut corner = Vec::with_capacity(((size + 1) * (size + 1)) as usize);
+        for y in 0..=size {
+            let row = (y * side) as usize;
+            corner.extend(mask[row..row + size as usize + 1].iter().map(|&v| v as u8));
+        }
+
+        Self { blur, size, corner }
+    }
+
+    /// Raio do blur.
+    pub fn blur(&self) -> u32 {
+        self.blur
+    }
+
+    /// Cobertura (0-255) do pixel `(x, y)` da sombra de uma silhueta
+    /// `width x height`.
+    ///
+    /// As coordenadas são relativas à área da sombra, que começa `blur`
+    /// pixels antes da silhueta e tem `2 * blur` a mais em cada dimensão.
+    pub fn alpha(&self, x: u32, y: u32, width: u32, height: u32) -> u8 {
+        let (w, h) = (width + 2 * self.blur, height + 2 * self.blur);
+        if x >= w || y >= h {
+            return 0;
+        }
+
+        // Distância à borda mais próxima, limitada ao tamanho do kernel
+        let tx = x.min(w - 1 - x).min(self.size);
+        let ty = y.min(h - 1 - y).min(self.size);
+        self.corner[(ty * (self.size + 1) + tx) as usize]
+    }
+}
@@ -0,0 +1,163 @@
+//! # Shape
+//!
+//! Geometria de retângulos arredondados com anti-aliasing.
+//!
+//! A cobertura de um pixel é estimada pela distância do seu centro ao arco
+//! do canto: `0.5 - d`, limitada a `[0, 1]`. Fora dos cantos o retângulo
+//! é sólido. Preenchimento, borda e clip usam as mesmas funções, então a
+//! borda sempre coincide com o fundo.
+
+use super::raster::ceilf;
+
+// =============================================================================
+// CORNER RADII
+// =============================================================================
+
+/// Raios dos quatro cantos de um retângulo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CornerRadii {
+    /// Canto superior esquerdo.
+    pub top_left: u32,
+    /// Canto superior direito.
+    pub top_right: u32,
+    /// Canto inferior direito.
+    pub bottom_right: u32,
+    /// Canto inferior esquerdo.
+    pub bottom_left: u32,
+}
+
+impl CornerRadii {
+    /// Sem arredondamento.
+    pub const ZERO: Self = Self::all(0);
+
+    /// Mesmo raio nos quatro cantos.
+    pub const fn all(radius: u32) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    /// Raios em sentido horário, a partir do superior esquerdo.
+    pub const fn new(top_left: u32, top_right: u32, bottom_right: u32, bottom_left: u32) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    /// Retorna se nenhum canto é arredondado.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// Limita cada raio à metade do menor lado de `width x height`.
+    pub fn clamped(self, width: u32, height: u32) -> Self {
+        let max = width.min(height) / 2;
+        Self::new(
+            self.top_left.min(max),
+            self.top_right.min(max),
+            self.bottom_right.min(max),
+            self.bottom_left.min(max),
+        )
+    }
+
+    /// Raios da borda interna de um contorno de espessura `amount`.
+    pub fn inset(self, amount: u32) -> Self {
+        Self::new(
+            self.top_left.saturating_sub(amount),
+            self.top_right.saturating_sub(amount),
+            self.bottom_right.saturating_sub(amount),
+            self.bottom_left.saturating_sub(amount),
+        )
+    }
+
+    /// Raios (esquerdo, direito) que afetam a linha `y` de um retângulo de
+    /// altura `height`; 0 fora dos cantos.
+    pub fn row(&self, y: u32, height: u32) -> (u32, u32) {
+        let side = |top: u32, bottom: u32| {
+            if y < top {
+                top
+            } else if y + bottom >= height {
+                bottom
+            } else {
+                0
+            }
+        };
+        (
+            side(self.top_left, self.bottom_left),
+            side(self.top_right, self.bottom_right),
+        )
+    }
+
+    /// Pixels (esquerda, direita) excluídos da linha `y` pelo clip: os com
+    /// centro fora do arco, ou seja, cobertura abaixo de 50%.
+    pub fn row_insets(&self, y: u32, height: u32) -> (u32, u32) {
+        let side = |top: u32, bottom: u32| {
+            if y < top {
+                corner_inset(top, y)
+            } else if y + bottom >= height {
+                corner_inset(bottom, height - 1 - y)
+            } else {
+                0
+            }
+        };
+        (
+            side(self.top_left, self.bottom_left),
+            side(self.top_right, self.bottom_right),
+        )
+    }
+}
+
+impl From<u32> for CornerRadii {
+    fn from(radius: u32) -> Self {
+        Self::all(radius)
+    }
+}
+
+// =============================================================================
+// COBERTURA
+// =============================================================================
+
+/// Cobertura (0.0 - 1.0) do pixel `(x, y)` de um retângulo `width x height`
+/// com cantos `radii` (já limitados por [`CornerRadii::clamped`]).
+pub fn coverage(width: u32, height: u32, radii: &CornerRadii, x: i32, y: i32) -> f32 {
+    let (w, h) = (width as i32, height as i32);
+    if x < 0 || y < 0 || x >= w || y >= h {
+        return 0.0;
+    }
+
+    let (tl, tr) = (radii.top_left as i32, radii.top_right as i32);
+    let (br, bl) = (radii.bottom_right as i32, radii.bottom_left as i32);
+
+    // Centro do arco do canto que contém o pixel
+    let (cx, cy, r) = if x < tl && y < tl {
+        (tl, tl, tl)
+    } else if x >= w - tr && y < tr {
+        (w - tr, tr, tr)
+    } else if x >= w - br && y >= h - br {
+        (w - br, h - br, br)
+    } else if x < bl && y >= h - bl {
+        (bl, h - bl, bl)
+    } else {
+        return 1.0;
+    };
+
+    let dx = x as f32 + 0.5 - cx as f32;
+    let dy = y as f32 + 0.5 - cy as f32;
+    let distance = rdsmath::sqrtf(dx * dx + dy * dy) - r as f32;
+    (0.5 - distance).clamp(0.0, 1.0)
+}
+
+/// Pixels com centro fora de um arco de raio `radius`, na linha `row`
+/// contada a partir da borda (0 = linha mais externa).
+pub fn corner_inset(radius: u32, row: u32) -> u32 {
+    if row >= radius {
+        return 0;
+    }
+
+    let r = radius as f32;
+    let v = r - (row as f32 + 0.5);
+    let chord = rdsmath::sqrtf(r * r - v * v);
+    // Primeiro x com centro (x + 0.5) >= r - chord
+    ceilf(r - chord - 0.5).max(0.0) as u32
+}
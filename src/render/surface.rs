@@ -0,0 +1,68 @@
+//! # Surface
+//!
+//! Buffer de pixels fora da tela (offscreen).
+//!
+//! Uma [`Surface`] é desenhada uma vez através de um [`Canvas`] e depois
+//! composta quantas vezes for preciso, com deslocamento e opacidade:
+//!
+//! ```text
+//! let mut layer = Surface::new(Size::new(320, 200));
+//! GlassRenderer::draw_rect(&mut layer.canvas(), Rect::new(0, 0, 320, 200), &style);
+//!
+//! // Em cada frame da animação
+//! canvas.draw_surface(&layer, Point::new(x, y), opacity);
+//! ```
+//!
+//! Os pixels começam transparentes, então o que não for desenhado não
+//! cobre o destino. Como no canvas, ficam em ARGB pré-multiplicado.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use gfx_types::geometry::Size;
+
+use super::canvas::Canvas;
+
+// =============================================================================
+// SURFACE
+// =============================================================================
+
+/// Buffer ARGB próprio, com stride igual à largura.
+pub struct Surface {
+    /// Pixels ARGB pré-multiplicados (`size.width * size.height`).
+    pixels: Vec<u32>,
+    /// Dimensões.
+    size: Size,
+}
+
+impl Surface {
+    /// Cria surface transparente de tamanho `size`.
+    pub fn new(size: Size) -> Self {
+        Self {
+            pixels: vec![0; size.width as usize * size.height as usize],
+            size,
+        }
+    }
+
+    /// Dimensões.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Pixels ARGB pré-multiplicados, linha a linha.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// Canvas para desenhar na surface.
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        Canvas::new(&mut self.pixels, self.size)
+    }
+
+    /// Redimensiona, descartando o conteúdo (fica transparente).
+    pub fn resize(&mut self, size: Size) {
+        self.pixels.clear();
+        self.pixels
+            .resize(size.width as usize * size.height as usize, 0);
+        self.size = size;
+    }
+}
@@ -0,0 +1,161 @@
+//! # Text Renderer
+//!
+//! Desenho de strings usando qualquer [`TextFont`] (bitmap ou TrueType).
+//!
+//! A cobertura de cada pixel do glifo multiplica o alpha da cor do texto,
+//! e o resultado é combinado com o fundo por [`Canvas::blend_pixel`],
+//! respeitando o clip e a origem do canvas.
+//!
+//! ## Alinhamento Vertical
+//!
+//! O ponto `(x, y)` passado para [`TextRenderer::draw`] é interpretado
+//! conforme [`TextBaseline`]: topo ou centro da linha.
+
+use gfx_types::color::Color;
+use gfx_types::geometry::{Point, Rect};
+
+use super::canvas::Canvas;
+use super::font::TextFont;
+use super::layout::{TextAlign, TextLayout};
+
+// =============================================================================
+// TIPOS
+// =============================================================================
+
+/// Referência vertical do ponto de origem do texto.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextBaseline {
+    /// `y` é o topo da linha.
+    Top,
+    /// `y` é o centro vertical da linha.
+    Middle,
+}
+
+/// Estilo de texto.
+#[derive(Clone, Copy)]
+pub struct TextStyle<'a> {
+    /// Fonte.
+    pub font: &'a dyn TextFont,
+    /// Cor do texto.
+    pub color: Color,
+    /// Alinhamento vertical.
+    pub baseline: TextBaseline,
+}
+
+impl<'a> TextStyle<'a> {
+    /// Cria estilo com fonte, cor e alinhamento pelo topo.
+    pub const fn new(font: &'a dyn TextFont, color: Color) -> Self {
+        Self {
+            font,
+            color,
+            baseline: TextBaseline::Top,
+        }
+    }
+
+    /// Define alinhamento vertical.
+    pub const fn with_baseline(mut self, baseline: TextBaseline) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Converte `y` de origem no topo da linha.
+    pub fn top(&self, y: i32) -> i32 {
+        let height = self.font.height() as i32;
+        match self.baseline {
+            TextBaseline::Top => y,
+            TextBaseline::Middle => y - height / 2,
+        }
+    }
+}
+
+// =============================================================================
+// TEXT RENDERER
+// =============================================================================
+
+/// Renderiza texto em um [`Canvas`].
+pub struct TextRenderer;
+
+impl TextRenderer {
+    /// Desenha `text` em `origin`.
+    ///
+    /// Retorna a coordenada X logo após o último caractere.
+    pub fn draw(canvas: &mut Canvas, text: &str, origin: Point, style: &TextStyle) -> i32 {
+        let clip = canvas.clip_rect();
+        let top = style.top(origin.y);
+        let rgb = style.color.as_u32() & 0x00FF_FFFF;
+        let alpha = style.color.alpha() as u32;
+        let mut pen_x = origin.x;
+
+        for c in text.chars() {
+            if pen_x >= clip.right() {
+                break;
+            }
+
+            let glyph = style.font.glyph_image(c);
+            let gx = pen_x + glyph.left;
+            let gy = top + glyph.top;
+
+            for row in 0..glyph.height {
+                let py = gy + row as i32;
+                if py < clip.y || py >= clip.bottom() {
+                    continue;
+                }
+
+                for col in 0..glyph.width {
+                    let px = gx + col as i32;
+                    if px < clip.x || px >= clip.right() {
+                        continue;
+                    }
+
+                    let coverage = glyph.coverage(col, row) as u32;
+                    if coverage != 0 {
+                        let a = alpha * coverage / 255;
+                        canvas.blend_pixel(px, py, Color((a << 24) | rgb));
+                    }
+                }
+            }
+
+            pen_x += glyph.advance as i32;
+        }
+
+        pen_x
+    }
+
+    /// Desenha texto centralizado horizontalmente em `cx`.
+    pub fn draw_centered(
+        canvas: &mut Canvas,
+        text: &str,
+        cx: i32,
+        y: i32,
+        style: &TextStyle,
+    ) -> i32 {
+        let width = style.font.measure(text) as i32;
+        Self::draw(canvas, text, Point::new(cx - width / 2, y), style)
+    }
+
+    /// Desenha um [`TextLayout`] dentro de `bounds`.
+    ///
+    /// Cada linha é alinhada horizontalmente por `align`. O bloco inteiro
+    /// é posicionado verticalmente em `bounds` conforme `style.baseline`:
+    /// `Top` no topo, `Middle` no centro.
+    pub fn draw_layout(
+        canvas: &mut Canvas,
+        layout: &TextLayout,
+        bounds: Rect,
+        align: TextAlign,
+        style: &TextStyle,
+    ) {
+        let free = bounds.height as i32 - layout.height() as i32;
+        let mut y = match style.baseline {
+            TextBaseline::Top => bounds.y,
+            TextBaseline::Middle => bounds.y + free / 2,
+        };
+
+        let line_style = style.with_baseline(TextBaseline::Top);
+        for line in layout.lines() {
+            let x = bounds.x + align.offset(line.width, bounds.width);
+            Self::draw(canvas, &line.text, Point::new(x, y), &line_style);
+            y += layout.line_height() as i32;
+        }
+    }
+}
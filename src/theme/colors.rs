@@ -74,6 +74,9 @@ pub const TASKBAR_HIGHLIGHT: Color = Color(0x15FFFFFF);
 /// Fundo do menu
 pub const MENU_BG: Color = Color(0xE5181822);
 
+/// Fundo do menu na base (tingido com o destaque)
+pub const MENU_BG_TINT: Color = Color(0xE5261A1C);
+
 /// Item do menu hover
 pub const MENU_ITEM_HOVER: Color = Color(0xFF2A2A3C);
 
@@ -137,3 +140,6 @@ pub const WALLPAPER_GRADIENT_TOP: Color = Color(0xFFFF4500);
 
 /// Cor do fundo do gradiente
 pub const WALLPAPER_GRADIENT_BOTTOM: Color = Color(0xFFCC3500);
+
+/// Fundo das áreas não cobertas pela imagem (modos fit, center e tile)
+pub const WALLPAPER_BACKGROUND: Color = Color(0xFF1A1A2E);
@@ -1,51 +1,206 @@
 //! # Glass Effects
 //!
 //! Efeitos visuais de vidro/blur para a UI.
+//!
+//! O vidro é desenhado em três etapas:
+//!
+//! 1. **Shadow**: sombra projetada em volta, conforme a [`Elevation`]
+//! 2. **Backdrop**: o que já está sob o retângulo é desfocado e saturado
+//! 3. **Fill**: fundo translúcido, borda e highlight por cima
+//!
+//! Quem desenha o conteúdo em uma camada separada (painéis animados) aplica
+//! sombra e backdrop no destino e o fill na camada.
+//!
+//! A sombra sai do retângulo: quem danifica a área de um vidro deve usar
+//! [`GlassStyle::visual_bounds`].
 
 use gfx_types::color::Color;
-use gfx_types::geometry::{Rect, Size};
+use gfx_types::geometry::Rect;
 
 use super::colors;
+use crate::render::canvas::{union, with_opacity};
+use crate::render::{
+    blur, pixel, Canvas, CornerRadii, GradientStop, LinearGradient, Paint, RadialGradient,
+    ShadowKernel,
+};
+
+// =============================================================================
+// GRADIENTES
+// =============================================================================
+
+/// Brilho do topo do vidro, sumindo até um terço da altura.
+const SHEEN: &[GradientStop] = &[
+    GradientStop::new(0.0, colors::GLASS_BORDER_LIGHT),
+    GradientStop::new(0.35, Color(0x00FFFFFF)),
+];
+
+/// Brilho suave dos botões em hover.
+const BUTTON_SHEEN: &[GradientStop] = &[
+    GradientStop::new(0.0, Color(0x10FFFFFF)),
+    GradientStop::new(0.5, Color(0x00FFFFFF)),
+];
+
+/// Fundo dos painéis, tingido com o destaque a partir do centro da base.
+const PANEL_BACKGROUND: &[GradientStop] = &[
+    GradientStop::new(0.0, colors::MENU_BG_TINT),
+    GradientStop::new(0.8, colors::MENU_BG),
+];
+
+// =============================================================================
+// SOMBRA
+// =============================================================================
+
+/// Sombra projetada por um vidro.
+#[derive(Clone, Copy, Debug)]
+pub struct Shadow {
+    /// Deslocamento horizontal.
+    pub offset_x: i32,
+    /// Deslocamento vertical (positivo = para baixo).
+    pub offset_y: i32,
+    /// Quanto a sombra se espalha além da silhueta, em pixels.
+    pub blur_radius: u32,
+    /// Expansão (ou contração, se negativo) da silhueta.
+    pub spread: i32,
+    /// Cor no miolo da sombra.
+    pub color: Color,
+}
+
+impl Shadow {
+    /// Sem sombra.
+    pub const NONE: Self = Self {
+        offset_x: 0,
+        offset_y: 0,
+        blur_radius: 0,
+        spread: 0,
+        color: Color(0x00000000),
+    };
+
+    /// Retorna se a sombra não desenha nada.
+    pub fn is_none(&self) -> bool {
+        self.color.alpha() == 0
+    }
+
+    /// Silhueta da sombra de `rect`: deslocada e expandida por `spread`.
+    pub fn silhouette(&self, rect: Rect) -> Rect {
+        let grow = |size: u32| (size as i32 + 2 * self.spread).max(0) as u32;
+        Rect::new(
+            rect.x + self.offset_x - self.spread,
+            rect.y + self.offset_y - self.spread,
+            grow(rect.width),
+            grow(rect.height),
+        )
+    }
+
+    /// Área pintada pela sombra de `rect` (silhueta + blur).
+    pub fn extent(&self, rect: Rect) -> Rect {
+        let silhouette = self.silhouette(rect);
+        let blur = self.blur_radius as i32;
+        Rect::new(
+            silhouette.x - blur,
+            silhouette.y - blur,
+            silhouette.width + 2 * self.blur_radius,
+            silhouette.height + 2 * self.blur_radius,
+        )
+    }
+
+    /// Pré-calcula o kernel para silhuetas com cantos `radii`.
+    ///
+    /// O kernel usa um raio só (o maior), expandido pelo `spread`.
+    pub fn kernel(&self, radii: CornerRadii) -> ShadowKernel {
+        let radius = radii
+            .top_left
+            .max(radii.top_right)
+            .max(radii.bottom_right)
+            .max(radii.bottom_left);
+        let radius = (radius as i32 + self.spread).max(0) as u32;
+        ShadowKernel::new(radius, self.blur_radius)
+    }
+}
+
+/// Nível de elevação de um vidro: quanto mais alto, maior e mais escura a
+/// sombra.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Elevation {
+    /// Levemente acima do fundo (barras da taskbar).
+    Raised,
+    /// Flutuando sobre tudo (painéis e menus).
+    Floating,
+}
+
+impl Elevation {
+    /// Sombra deste nível.
+    pub const fn shadow(self) -> Shadow {
+        match self {
+            Self::Raised => Shadow {
+                offset_x: 0,
+                offset_y: 2,
+                blur_radius: 9,
+                spread: 0,
+                color: Color(0x50000000),
+            },
+            Self::Floating => Shadow {
+                offset_x: 0,
+                offset_y: 10,
+                blur_radius: 24,
+                spread: -2,
+                color: Color(0x80000000),
+            },
+        }
+    }
+}
 
 // =============================================================================
 // GLASS STYLE
 // =============================================================================
 
 /// Estilo de glass effect.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct GlassStyle {
-    /// Cor de fundo.
-    pub background: Color,
+    /// Tinta do fundo.
+    pub background: Paint,
     /// Cor de borda.
     pub border: Color,
-    /// Cor de highlight (topo).
-    pub highlight: Color,
-    /// Raio dos cantos.
-    pub corner_radius: u32,
-    /// Espessura da borda.
+    /// Brilho pintado sobre o fundo (normalmente um gradiente que some a
+    /// partir do topo).
+    pub highlight: Paint,
+    /// Raios dos cantos.
+    pub corner_radii: CornerRadii,
+    /// Espessura da borda em pixels (0 = sem borda).
     pub border_thickness: u32,
+    /// Raio do blur do fundo (0 = sem blur).
+    pub blur_radius: u32,
+    /// Saturação do fundo em % (100 = inalterada).
+    pub saturation: u16,
+    /// Sombra projetada.
+    pub shadow: Shadow,
 }
 
 impl GlassStyle {
     /// Estilo padrão para barras.
     pub const fn bar() -> Self {
         Self {
-            background: colors::GLASS_BG,
+            background: Paint::Solid(colors::GLASS_BG),
             border: colors::GLASS_BORDER,
-            highlight: colors::GLASS_BORDER_LIGHT,
-            corner_radius: 12,
+            highlight: Paint::LinearGradient(LinearGradient::new(180.0, SHEEN)),
+            corner_radii: CornerRadii::all(12),
             border_thickness: 1,
+            blur_radius: 6,
+            saturation: 140,
+            shadow: Elevation::Raised.shadow(),
         }
     }
 
     /// Estilo para painéis/menus.
     pub const fn panel() -> Self {
         Self {
-            background: colors::MENU_BG,
+            background: Paint::RadialGradient(RadialGradient::new((0.5, 1.0), PANEL_BACKGROUND)),
             border: colors::GLASS_BORDER,
-            highlight: colors::GLASS_BORDER_LIGHT,
-            corner_radius: 16,
+            highlight: Paint::LinearGradient(LinearGradient::new(180.0, SHEEN)),
+            corner_radii: CornerRadii::all(16),
             border_thickness: 1,
+            blur_radius: 10,
+            saturation: 140,
+            shadow: Elevation::Floating.shadow(),
         }
     }
 
@@ -54,11 +209,14 @@ impl GlassStyle {
     /// Estilo para botões.
     pub const fn button() -> Self {
         Self {
-            background: Color(0x00000000), // Transparente por padrão
+            background: Paint::NONE, // Transparente por padrão
             border: Color(0x00000000),
-            highlight: Color(0x00000000),
-            corner_radius: 8,
+            highlight: Paint::NONE,
+            corner_radii: CornerRadii::all(8),
             border_thickness: 0,
+            blur_radius: 0,
+            saturation: 100,
+            shadow: Shadow::NONE,
         }
     }
 
@@ -67,11 +225,14 @@ impl GlassStyle {
     /// Estilo hover.
     pub const fn button_hover() -> Self {
         Self {
-            background: colors::GLASS_BG_HOVER,
+            background: Paint::Solid(colors::GLASS_BG_HOVER),
             border: Color(0x20FFFFFF),
-            highlight: Color(0x10FFFFFF),
-            corner_radius: 8,
+            highlight: Paint::LinearGradient(LinearGradient::new(180.0, BUTTON_SHEEN)),
+            corner_radii: CornerRadii::all(8),
             border_thickness: 1,
+            blur_radius: 0,
+            saturation: 100,
+            shadow: Shadow::NONE,
         }
     }
 
@@ -80,295 +241,122 @@ impl GlassStyle {
     /// Estilo ativo/pressionado.
     pub const fn button_active() -> Self {
         Self {
-            background: colors::GLASS_BG_ACTIVE,
+            background: Paint::Solid(colors::GLASS_BG_ACTIVE),
             border: colors::ACCENT,
-            highlight: Color(0x05FFFFFF),
-            corner_radius: 8,
+            highlight: Paint::NONE,
+            corner_radii: CornerRadii::all(8),
             border_thickness: 1,
+            blur_radius: 0,
+            saturation: 100,
+            shadow: Shadow::NONE,
+        }
+    }
+
+    /// Área pintada por um vidro em `rect`, incluindo a sombra.
+    pub fn visual_bounds(&self, rect: Rect) -> Rect {
+        if self.shadow.is_none() {
+            rect
+        } else {
+            union(rect, self.shadow.extent(rect))
         }
     }
+
+    /// Kernel da sombra deste estilo (ver [`Shadow::kernel`]).
+    pub fn shadow_kernel(&self) -> ShadowKernel {
+        self.shadow.kernel(self.corner_radii)
+    }
 }
 
 // =============================================================================
 // GLASS RENDERER
 // =============================================================================
 
-/// Renderiza efeitos glass em um buffer.
+/// Renderiza efeitos glass em um [`Canvas`].
 pub struct GlassRenderer;
 
 impl GlassRenderer {
-    /// Desenha um retângulo com efeito glass.
-    pub fn draw_rect(buffer: &mut [u32], buffer_size: Size, rect: Rect, style: &GlassStyle) {
-        let stride = buffer_size.width as usize;
-
-        // Preencher fundo com cantos arredondados
-        Self::fill_rounded_rect(
-            buffer,
-            stride,
-            buffer_size,
-            rect,
-            style.background,
-            style.corner_radius,
-        );
-
-        // Desenhar borda se tiver espessura
-        if style.border_thickness > 0 {
-            Self::stroke_rounded_rect(
-                buffer,
-                stride,
-                buffer_size,
-                rect,
-                style.border,
-                style.corner_radius,
-            );
-        }
-
-        // Highlight no topo (efeito vidro)
-        if style.highlight.alpha() > 0 {
-            let highlight_rect = Rect::new(
-                rect.x + style.corner_radius as i32,
-                rect.y + 1,
-                rect.width - style.corner_radius * 2,
-                1,
-            );
-            Self::fill_rect_blend(buffer, stride, buffer_size, highlight_rect, style.highlight);
-        }
+    /// Desenha um retângulo com efeito glass (backdrop + fill), sem sombra.
+    pub fn draw_rect(canvas: &mut Canvas, rect: Rect, style: &GlassStyle) {
+        Self::draw_backdrop(canvas, rect, style, 255);
+        Self::draw_fill(canvas, rect, style);
     }
 
-    /// Preenche retângulo com cantos arredondados.
-    fn fill_rounded_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
+    /// Desenha a sombra de um vidro em `rect`, com opacidade `opacity`.
+    ///
+    /// `kernel` vem de [`GlassStyle::shadow_kernel`] e é guardado por quem
+    /// desenha, para não ser recalculado a cada frame. A área sob o próprio
+    /// vidro não é escurecida.
+    pub fn draw_shadow(
+        canvas: &mut Canvas,
         rect: Rect,
-        color: Color,
-        radius: u32,
+        style: &GlassStyle,
+        kernel: &ShadowKernel,
+        opacity: u8,
     ) {
-        let r = radius as i32;
-
-        for y in 0..rect.height as i32 {
-            let sy = rect.y + y;
-            if sy < 0 || sy >= size.height as i32 {
-                continue;
-            }
-
-            // Calcular inset horizontal baseado na posição vertical
-            let inset = if y < r {
-                Self::corner_inset(r, r - 1 - y)
-            } else if y >= (rect.height as i32 - r) {
-                Self::corner_inset(r, y - (rect.height as i32 - r))
-            } else {
-                0
-            };
-
-            let x_start = (rect.x + inset).max(0);
-            let x_end = (rect.x + rect.width as i32 - inset).min(size.width as i32);
-
-            if x_start < x_end {
-                let row_start = sy as usize * stride + x_start as usize;
-                let row_end = sy as usize * stride + x_end as usize;
-
-                if row_end <= buffer.len() {
-                    let color_u32 = color.as_u32();
-                    let alpha = color.alpha();
-
-                    if alpha == 255 {
-                        buffer[row_start..row_end].fill(color_u32);
-                    } else if alpha > 0 {
-                        for px in &mut buffer[row_start..row_end] {
-                            *px = Self::blend(*px, color_u32);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    /// Desenha borda de retângulo arredondado.
-    fn stroke_rounded_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        rect: Rect,
-        color: Color,
-        radius: u32,
-    ) {
-        let r = radius as i32;
-        let color_u32 = color.as_u32();
-
-        // Bordas horizontais (topo e fundo)
-        for x in r..(rect.width as i32 - r) {
-            let px_top = rect.x + x;
-            let px_bot = rect.x + x;
-            let py_top = rect.y;
-            let py_bot = rect.y + rect.height as i32 - 1;
-
-            Self::put_pixel_blend(buffer, stride, size, px_top, py_top, color_u32);
-            Self::put_pixel_blend(buffer, stride, size, px_bot, py_bot, color_u32);
-        }
-
-        // Bordas verticais (esquerda e direita)
-        for y in r..(rect.height as i32 - r) {
-            let px_left = rect.x;
-            let px_right = rect.x + rect.width as i32 - 1;
-            let py = rect.y + y;
-
-            Self::put_pixel_blend(buffer, stride, size, px_left, py, color_u32);
-            Self::put_pixel_blend(buffer, stride, size, px_right, py, color_u32);
+        let shadow = &style.shadow;
+        if shadow.is_none() || opacity == 0 || !canvas.is_visible(shadow.extent(rect)) {
+            return;
         }
 
-        // Cantos arredondados
-        Self::draw_corner_arc(
-            buffer,
-            stride,
-            size,
-            rect.x + r,
-            rect.y + r,
-            r,
-            color_u32,
-            0,
-        ); // Top-left
-        Self::draw_corner_arc(
-            buffer,
-            stride,
-            size,
-            rect.x + rect.width as i32 - r - 1,
-            rect.y + r,
-            r,
-            color_u32,
-            1,
-        ); // Top-right
-        Self::draw_corner_arc(
-            buffer,
-            stride,
-            size,
-            rect.x + r,
-            rect.y + rect.height as i32 - r - 1,
-            r,
-            color_u32,
-            2,
-        ); // Bottom-left
-        Self::draw_corner_arc(
-            buffer,
-            stride,
-            size,
-            rect.x + rect.width as i32 - r - 1,
-            rect.y + rect.height as i32 - r - 1,
-            r,
-            color_u32,
-            3,
-        ); // Bottom-right
-    }
-
-    /// Desenha arco de canto.
-    fn draw_corner_arc(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        cx: i32,
-        cy: i32,
-        r: i32,
-        color: u32,
-        quadrant: u8,
-    ) {
-        // Bresenham circle adapted for quarter
-        let mut x = 0;
-        let mut y = r;
-        let mut d = 3 - 2 * r;
-
-        while x <= y {
-            let points = match quadrant {
-                0 => [(-x, -y), (-y, -x)], // Top-left
-                1 => [(x, -y), (y, -x)],   // Top-right
-                2 => [(-x, y), (-y, x)],   // Bottom-left
-                3 => [(x, y), (y, x)],     // Bottom-right
-                _ => return,
-            };
-
-            for (dx, dy) in points {
-                Self::put_pixel_blend(buffer, stride, size, cx + dx, cy + dy, color);
-            }
-
-            if d < 0 {
-                d += 4 * x + 6;
-            } else {
-                d += 4 * (x - y) + 10;
-                y -= 1;
-            }
-            x += 1;
-        }
+        let color = Color(with_opacity(shadow.color.as_u32(), opacity));
+        canvas.draw_shadow(
+            shadow.silhouette(rect),
+            kernel,
+            color,
+            Some((rect, style.corner_radii)),
+        );
     }
 
-    /// Calcula inset para cantos arredondados.
-    fn corner_inset(radius: i32, distance: i32) -> i32 {
-        if distance >= radius {
-            return radius;
+    /// Desfoca e satura o que já está desenhado sob `rect`, com opacidade
+    /// `opacity` (abaixo de 255, o resultado é misturado ao original).
+    ///
+    /// Só os pixels dentro do clip são amostrados: para um resultado
+    /// estável, o retângulo inteiro deve estar sendo redesenhado.
+    pub fn draw_backdrop(canvas: &mut Canvas, rect: Rect, style: &GlassStyle, opacity: u8) {
+        if (style.blur_radius == 0 && style.saturation == 100)
+            || opacity == 0
+            || !canvas.is_visible(rect)
+        {
+            return;
         }
-        // Aproximação circular
-        let r = radius as f32;
-        let d = distance as f32;
-        let inset = r - rdsmath::sqrtf(r * r - d * d);
-        inset as i32
-    }
 
-    /// Preenche retângulo com blend.
-    fn fill_rect_blend(buffer: &mut [u32], stride: usize, size: Size, rect: Rect, color: Color) {
-        let color_u32 = color.as_u32();
+        canvas.save();
+        canvas.clip_rounded(rect, style.corner_radii);
+        canvas.filter_rect(rect, |pixels, width, height| {
+            let original = (opacity < 255).then(|| pixels.to_vec());
+            blur::box_blur(pixels, width, height, style.blur_radius);
+            blur::saturate(pixels, style.saturation);
 
-        for y in 0..rect.height as i32 {
-            let sy = rect.y + y;
-            if sy < 0 || sy >= size.height as i32 {
-                continue;
+            if let Some(mut original) = original {
+                pixel::blend_span_from_scaled(&mut original, pixels, opacity);
+                pixels.copy_from_slice(&original);
             }
-
-            for x in 0..rect.width as i32 {
-                let sx = rect.x + x;
-                if sx < 0 || sx >= size.width as i32 {
-                    continue;
-                }
-
-                let idx = sy as usize * stride + sx as usize;
-                if idx < buffer.len() {
-                    buffer[idx] = Self::blend(buffer[idx], color_u32);
-                }
-            }
-        }
+        });
+        canvas.restore();
     }
 
-    /// Coloca pixel com blend.
-    fn put_pixel_blend(buffer: &mut [u32], stride: usize, size: Size, x: i32, y: i32, color: u32) {
-        if x < 0 || y < 0 || x >= size.width as i32 || y >= size.height as i32 {
+    /// Desenha fundo translúcido, highlight e borda.
+    pub fn draw_fill(canvas: &mut Canvas, rect: Rect, style: &GlassStyle) {
+        if !canvas.is_visible(rect) {
             return;
         }
-        let idx = y as usize * stride + x as usize;
-        if idx < buffer.len() {
-            buffer[idx] = Self::blend(buffer[idx], color);
-        }
-    }
-
-    /// Alpha blend.
-    fn blend(dst: u32, src: u32) -> u32 {
-        let sa = (src >> 24) & 0xFF;
-        if sa == 0 {
-            return dst;
-        }
-        if sa == 255 {
-            return src;
-        }
-
-        let sr = (src >> 16) & 0xFF;
-        let sg = (src >> 8) & 0xFF;
-        let sb = src & 0xFF;
 
-        let dr = (dst >> 16) & 0xFF;
-        let dg = (dst >> 8) & 0xFF;
-        let db = dst & 0xFF;
+        // Preencher fundo com cantos arredondados
+        canvas.fill_rounded_paint(rect, style.corner_radii, &style.background);
 
-        let inv_sa = 255 - sa;
-        let out_r = (sr * sa + dr * inv_sa) / 255;
-        let out_g = (sg * sa + dg * inv_sa) / 255;
-        let out_b = (sb * sa + db * inv_sa) / 255;
+        // Brilho (efeito vidro) sobre o fundo, sob a borda
+        if !style.highlight.is_transparent() {
+            canvas.fill_rounded_paint(rect, style.corner_radii, &style.highlight);
+        }
 
-        0xFF000000 | (out_r << 16) | (out_g << 8) | out_b
+        // Desenhar borda se tiver espessura
+        if style.border_thickness > 0 {
+            canvas.stroke_rounded_rect(
+                rect,
+                style.corner_radii,
+                style.border_thickness,
+                style.border,
+            );
+        }
     }
 }
@@ -53,6 +53,12 @@ pub const ICON_SIZE: u32 = 24;
 /// Espaçamento entre ícones.
 pub const ICON_GAP: u32 = 4;
 
+/// Largura de uma entrada de janela na taskbar (ícone + título).
+pub const WINDOW_ENTRY_WIDTH: u32 = 160;
+
+/// Tamanho do ícone de uma entrada de janela.
+pub const WINDOW_ICON_SIZE: u32 = 32;
+
 // =============================================================================
 // PAINÉIS
 // =============================================================================
@@ -96,6 +102,9 @@ pub const APP_ICON_SIZE: u32 = 40;
 /// Espaçamento entre ícone e texto.
 pub const APP_ICON_GAP: u32 = 12;
 
+/// Largura do botão "Atualizar" no cabeçalho do menu.
+pub const MENU_REFRESH_WIDTH: u32 = 80;
+
 // =============================================================================
 // GRID DE APPS
 // =============================================================================
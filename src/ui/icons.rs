@@ -0,0 +1,371 @@
+//! # Icons
+//!
+//! Cache de ícones de apps, indexado por `(id do app, tamanho)`.
+//!
+//! Cada ícone é resolvido uma única vez, na primeira vez que é pedido:
+//!
+//! 1. memória;
+//! 2. cópia já rasterizada em `/state/cache/icons`, se ainda corresponder
+//!    ao conteúdo do arquivo do ícone (`AppInfo::icon_path`);
+//! 3. o próprio arquivo, decodificado no tamanho pedido e gravado no cache
+//!    em disco;
+//! 4. avatar gerado: a inicial do app sobre uma cor estável derivada do id.
+//!
+//! ## Cache em disco
+//!
+//! Só existe com a feature `fs-write` (o SDK publicado ainda não grava
+//! arquivos); sem ela os passos 2 e 3 não tocam o disco e cada ícone é
+//! decodificado uma vez por sessão.
+//!
+//! Um arquivo por `(id, tamanho)`, em `<id>-<tamanho>.icon`:
+//!
+//! ```text
+//! magic "FICN" | versão u32 | largura u32 | altura u32 | hash do conteúdo u32
+//! pixels ARGB pré-multiplicados (u32 LE), largura * altura
+//! ```
+//!
+//! O hash é do conteúdo do arquivo de origem: a cópia deixa de valer quando
+//! o app aponta para outro ícone ou quando uma atualização troca o arquivo
+//! no mesmo caminho. Ler o arquivo é bem mais barato que decodificá-lo (SVG
+//! em especial), que é o que a cópia evita.
+//!
+//! [`IconCache::invalidate`] apaga as cópias de um app (usado quando a lista
+//! de apps é relida e o app foi removido, reinstalado ou mudou de ícone).
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::cell::RefCell;
+use gfx_types::color::Color;
+use gfx_types::geometry::{Rect, Size};
+
+use crate::app::{AppIcon, AppInfo};
+use crate::render::image::{ImageError, MAX_IMAGE_FILE_SIZE};
+use crate::render::{FontSet, Surface, TextBaseline, TextStyle};
+use crate::theme::colors;
+use crate::util::fs::{read_file, ReadError};
+use crate::util::hash::{fnv1a, FNV1A_SEED};
+
+#[cfg(feature = "fs-write")]
+use {
+    crate::util::fs::{create_dir_all, remove_file, write_atomic},
+    alloc::vec::Vec,
+    core::cell::Cell,
+    redpowder::fs::list_dir,
+};
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Diretório do cache de ícones rasterizados.
+#[cfg(feature = "fs-write")]
+pub const ICON_CACHE_DIR: &str = "/state/cache/icons";
+
+/// Assinatura dos arquivos do cache.
+#[cfg(feature = "fs-write")]
+const ICON_MAGIC: &[u8; 4] = b"FICN";
+
+/// Versão do formato do cache (2: hash do conteúdo, não do caminho).
+#[cfg(feature = "fs-write")]
+const ICON_VERSION: u32 = 2;
+
+/// Tamanho do cabeçalho, em bytes.
+#[cfg(feature = "fs-write")]
+const HEADER_SIZE: usize = 20;
+
+/// Maior tamanho de ícone aceito.
+const MAX_ICON_SIZE: u32 = 256;
+
+/// Paleta dos avatares.
+const AVATAR_COLORS: [Color; 6] = [
+    Color(0xFF4A90D9), // Azul
+    Color(0xFF3FB950), // Verde
+    Color(0xFFE53935), // Vermelho
+    Color(0xFFF0B429), // Amarelo
+    Color(0xFF9C27B0), // Roxo
+    Color(0xFFFF6B35), // Laranja
+];
+
+/// Tamanho a partir do qual o avatar usa a fonte grande.
+const AVATAR_LARGE_FONT_SIZE: u32 = 40;
+
+// =============================================================================
+// ICON CACHE
+// =============================================================================
+
+/// Chave de um ícone no cache.
+type IconKey = (String, u32);
+
+/// Cache compartilhado de ícones de apps.
+pub struct IconCache {
+    entries: RefCell<BTreeMap<IconKey, Rc<AppIcon>>>,
+    /// Diretório do cache em disco já criado nesta sessão.
+    #[cfg(feature = "fs-write")]
+    dir_ready: Cell<bool>,
+}
+
+impl IconCache {
+    /// Cria cache vazio.
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(BTreeMap::new()),
+            #[cfg(feature = "fs-write")]
+            dir_ready: Cell::new(false),
+        }
+    }
+
+    /// Obtém o ícone de `app` em `size`×`size`, carregando se ausente.
+    ///
+    /// Nunca falha: sem ícone legível, devolve o avatar com a inicial.
+    pub fn get(&self, app: &AppInfo, size: u32, fonts: &FontSet) -> Rc<AppIcon> {
+        let size = size.clamp(1, MAX_ICON_SIZE);
+        let key = (app.id.clone(), size);
+        if let Some(icon) = self.entries.borrow().get(&key) {
+            return icon.clone();
+        }
+
+        let icon = Rc::new(
+            self.load(app, size)
+                .unwrap_or_else(|| avatar(app, size, fonts)),
+        );
+        self.entries.borrow_mut().insert(key, icon.clone());
+        icon
+    }
+
+    /// Descarta os ícones em memória (o cache em disco é mantido).
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    /// Descarta os ícones de `app_id`, em memória e em disco (todos os
+    /// tamanhos).
+    pub fn invalidate(&self, app_id: &str) {
+        self.entries.borrow_mut().retain(|(id, _), _| id != app_id);
+
+        #[cfg(feature = "fs-write")]
+        remove_copies(app_id);
+    }
+
+    /// Carrega do disco ou decodifica o arquivo do ícone.
+    fn load(&self, app: &AppInfo, size: u32) -> Option<AppIcon> {
+        let source = app.icon_path.as_deref()?;
+        let data = match read_file(source, MAX_IMAGE_FILE_SIZE) {
+            Ok(data) => data,
+            Err(ReadError::TooLarge) => {
+                redpowder::println!("[Icons] ERRO: Icone {} grande demais", source);
+                return None;
+            }
+            Err(_) => return None,
+        };
+
+        #[cfg(feature = "fs-write")]
+        let copy = DiskCopy::new(&app.id, size, &data);
+        #[cfg(feature = "fs-write")]
+        if let Some(icon) = copy.read() {
+            return Some(icon);
+        }
+
+        let icon = match AppIcon::decode(&data, size) {
+            Ok(icon) => icon,
+            Err(ImageError::BadMagic) => return None,
+            Err(e) => {
+                redpowder::println!("[Icons] ERRO: Icone {} invalido: {:?}", source, e);
+                return None;
+            }
+        };
+
+        #[cfg(feature = "fs-write")]
+        {
+            self.ensure_dir();
+            if !copy.write(&icon) {
+                redpowder::println!("[Icons] WARN: Falha ao gravar {}", copy.path);
+            }
+        }
+        Some(icon)
+    }
+
+    /// Cria o diretório do cache em disco (uma vez por sessão).
+    #[cfg(feature = "fs-write")]
+    fn ensure_dir(&self) {
+        if self.dir_ready.replace(true) {
+            return;
+        }
+        create_dir_all(ICON_CACHE_DIR);
+    }
+}
+
+impl Default for IconCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// AVATAR
+// =============================================================================
+
+/// Cor do avatar de um app, estável entre execuções.
+fn avatar_color(app_id: &str) -> Color {
+    AVATAR_COLORS[hash(app_id) as usize % AVATAR_COLORS.len()]
+}
+
+/// Gera o avatar: inicial do nome sobre a cor do app.
+fn avatar(app: &AppInfo, size: u32, fonts: &FontSet) -> AppIcon {
+    let mut surface = Surface::new(Size::new(size, size));
+    {
+        let mut canvas = surface.canvas();
+        let rect = Rect::new(0, 0, size, size);
+        canvas.fill_rounded_rect(rect, size / 5, avatar_color(&app.id));
+
+        let mut initial_buf = [0u8; 4];
+        let initial = app
+            .name
+            .chars()
+            .next()
+            .map(|c| {
+                &*c.to_uppercase()
+                    .next()
+                    .unwrap_or(c)
+                    .encode_utf8(&mut initial_buf)
+            })
+            .unwrap_or("?");
+        let font = if size >= AVATAR_LARGE_FONT_SIZE {
+            fonts.large()
+        } else {
+            fonts.regular()
+        };
+        canvas.draw_text_centered(
+            initial,
+            size as i32 / 2,
+            size as i32 / 2,
+            &TextStyle::new(font, colors::TEXT_ON_ACCENT).with_baseline(TextBaseline::Middle),
+        );
+    }
+
+    AppIcon {
+        width: size,
+        height: size,
+        data: surface.pixels().to_vec(),
+    }
+}
+
+// =============================================================================
+// CACHE EM DISCO
+// =============================================================================
+
+/// Cópia rasterizada de um ícone em `(id, tamanho)`.
+#[cfg(feature = "fs-write")]
+struct DiskCopy {
+    path: String,
+    size: u32,
+    /// Hash do conteúdo do arquivo de origem.
+    source_hash: u32,
+}
+
+#[cfg(feature = "fs-write")]
+impl DiskCopy {
+    /// Cópia de `(app_id, size)` para a origem com conteúdo `source`.
+    fn new(app_id: &str, size: u32, source: &[u8]) -> Self {
+        Self {
+            path: alloc::format!("{}/{}-{}.icon", ICON_CACHE_DIR, cache_name(app_id), size),
+            size,
+            source_hash: fnv1a(FNV1A_SEED, source),
+        }
+    }
+
+    /// Lê a cópia, se existir e ainda valer para a origem.
+    fn read(&self) -> Option<AppIcon> {
+        let max_len = HEADER_SIZE + (self.size * self.size) as usize * 4;
+        let data = read_file(&self.path, max_len).ok()?;
+        if data.len() < HEADER_SIZE || &data[0..4] != ICON_MAGIC {
+            return None;
+        }
+
+        let field = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let (version, width, height, hash) = (field(4), field(8), field(12), field(16));
+        if version != ICON_VERSION
+            || hash != self.source_hash
+            || width > self.size
+            || height > self.size
+        {
+            return None;
+        }
+        let pixels = &data[HEADER_SIZE..];
+        if pixels.len() != (width * height) as usize * 4 {
+            return None;
+        }
+
+        Some(AppIcon {
+            width,
+            height,
+            data: pixels
+                .chunks_exact(4)
+                .map(|p| u32::from_le_bytes([p[0], p[1], p[2], p[3]]))
+                .collect(),
+        })
+    }
+
+    /// Grava `icon`, via arquivo temporário + rename.
+    fn write(&self, icon: &AppIcon) -> bool {
+        let mut data = Vec::with_capacity(HEADER_SIZE + icon.data.len() * 4);
+        data.extend_from_slice(ICON_MAGIC);
+        for field in [ICON_VERSION, icon.width, icon.height, self.source_hash] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        for pixel in &icon.data {
+            data.extend_from_slice(&pixel.to_le_bytes());
+        }
+
+        // Um arquivo truncado nunca fica com o nome final
+        write_atomic(&self.path, &data)
+    }
+}
+
+/// Apaga as cópias de `app_id` em todos os tamanhos.
+#[cfg(feature = "fs-write")]
+fn remove_copies(app_id: &str) {
+    let Ok(entries) = list_dir(ICON_CACHE_DIR) else {
+        return;
+    };
+    let prefix = alloc::format!("{}-", cache_name(app_id));
+    for entry in entries {
+        // "<nome>-<tamanho>.icon": o tamanho separa "a.b" de "a.b-c"
+        let is_copy = entry
+            .name()
+            .strip_prefix(prefix.as_str())
+            .and_then(|rest| rest.strip_suffix(".icon"))
+            .is_some_and(|size| !size.is_empty() && size.bytes().all(|b| b.is_ascii_digit()));
+        if is_copy {
+            let path = alloc::format!("{}/{}", ICON_CACHE_DIR, entry.name());
+            if !remove_file(&path) {
+                redpowder::println!("[Icons] WARN: Falha ao remover {}", path);
+            }
+        }
+    }
+}
+
+/// Nome de `app_id` nos arquivos do cache.
+#[cfg(feature = "fs-write")]
+fn cache_name(app_id: &str) -> String {
+    // Ids são "vendor.name"; qualquer outro caractere vira '_'
+    app_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Hash de um texto (estável entre execuções).
+fn hash(text: &str) -> u32 {
+    fnv1a(FNV1A_SEED, text.as_bytes())
+}
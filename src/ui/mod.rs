@@ -4,14 +4,17 @@
 //!
 //! ## Componentes
 //!
-//! - **wallpaper**: Papel de parede (webp ou gradiente)
+//! - **wallpaper**: Papel de parede (imagem ou gradiente)
 //! - **taskbar**: Barras flutuantes na parte inferior
+//! - **icons**: Cache de ícones de apps (memória e disco)
 //! - **panels**: Painéis popup (widgets, quick settings, start menu)
 
+mod icons;
 pub mod panels;
 mod taskbar;
 mod wallpaper;
 
+pub use icons::IconCache;
 pub use panels::{Panel, PanelType, QuickSettingsPanel, StartMenuPanel, WidgetPanel};
 pub use taskbar::{Taskbar, TaskbarAction};
-pub use wallpaper::Wallpaper;
+pub use wallpaper::{SlideshowConfig, Wallpaper, WallpaperConfig, WallpaperMode};
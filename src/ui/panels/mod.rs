@@ -16,7 +16,11 @@ pub use quick_settings::QuickSettingsPanel;
 pub use start_menu::{StartMenuAction, StartMenuPanel};
 pub use widget_panel::WidgetPanel;
 
-use gfx_types::geometry::Rect;
+use core::cell::RefCell;
+use gfx_types::geometry::{Point, Rect, Size};
+
+use crate::render::{Canvas, FontSet, ShadowKernel, Surface};
+use crate::theme::{GlassRenderer, GlassStyle};
 
 // =============================================================================
 // PANEL TRAIT
@@ -45,12 +49,18 @@ pub trait Panel {
     }
 
     /// Retorna bounds.
-    // TODO: Revisar no futuro
-    #[allow(unused)]
     fn bounds(&self) -> Rect;
 
+    /// Retorna bounds na posição atual da animação.
+    fn animated_bounds(&self) -> Rect;
+
+    /// Área pintada na posição atual da animação, incluindo a sombra.
+    fn visual_bounds(&self) -> Rect {
+        GlassStyle::panel().visual_bounds(self.animated_bounds())
+    }
+
     /// Desenha o painel.
-    fn draw(&self, buffer: &mut [u32], buffer_size: gfx_types::geometry::Size);
+    fn draw(&self, canvas: &mut Canvas, fonts: &FontSet);
 
     /// Processa clique. Retorna true se consumiu.
     fn handle_click(&mut self, x: i32, y: i32) -> bool;
@@ -58,3 +68,77 @@ pub trait Panel {
     /// Atualiza animação. Retorna true se ainda animando.
     fn update_animation(&mut self) -> bool;
 }
+
+/// Bounds de um painel que sobe da base da tela.
+///
+/// Com `progress` 0.0 o painel está logo abaixo da tela; com 1.0, em `bounds`.
+fn slide_up(bounds: Rect, screen_height: u32, progress: f32) -> Rect {
+    let start_y = screen_height as i32;
+    let current_y = start_y + ((bounds.y - start_y) as f32 * progress) as i32;
+    Rect::new(bounds.x, current_y, bounds.width, bounds.height)
+}
+
+// =============================================================================
+// PANEL LAYER
+// =============================================================================
+
+/// Camada offscreen de um painel.
+///
+/// Durante a animação o painel é renderizado uma vez na camada e composto a
+/// cada frame na posição animada, com opacidade proporcional ao progresso.
+/// Totalmente aberto, o painel é desenhado direto no canvas.
+struct PanelLayer {
+    surface: RefCell<Option<Surface>>,
+    /// Sombra do painel, pré-calculada.
+    shadow: ShadowKernel,
+}
+
+impl PanelLayer {
+    /// Cria camada vazia.
+    fn new() -> Self {
+        Self {
+            surface: RefCell::new(None),
+            shadow: GlassStyle::panel().shadow_kernel(),
+        }
+    }
+
+    /// Descarta o conteúdo (refeito no próximo desenho animado).
+    fn invalidate(&mut self) {
+        *self.surface.get_mut() = None;
+    }
+
+    /// Desenha o painel em `bounds`, com sombra e blur de fundo.
+    ///
+    /// `render` desenha fundo e conteúdo em coordenadas relativas ao painel.
+    fn draw(
+        &self,
+        canvas: &mut Canvas,
+        bounds: Rect,
+        progress: f32,
+        render: impl FnOnce(&mut Canvas),
+    ) {
+        // Sombra e blur do que está atrás ficam no destino, não na camada;
+        // os dois acompanham a opacidade da camada
+        let style = GlassStyle::panel();
+        let opacity = (progress.clamp(0.0, 1.0) * 255.0) as u8;
+        GlassRenderer::draw_shadow(canvas, bounds, &style, &self.shadow, opacity);
+        GlassRenderer::draw_backdrop(canvas, bounds, &style, opacity);
+
+        if progress >= 1.0 {
+            canvas.save();
+            canvas.translate(bounds.x, bounds.y);
+            render(canvas);
+            canvas.restore();
+            return;
+        }
+
+        let mut slot = self.surface.borrow_mut();
+        let surface = slot.get_or_insert_with(|| {
+            let mut surface = Surface::new(Size::new(bounds.width, bounds.height));
+            render(&mut surface.canvas());
+            surface
+        });
+
+        canvas.draw_surface(surface, Point::new(bounds.x, bounds.y), opacity);
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Painel de configurações rápidas.
 
-use gfx_types::geometry::{Point, Rect, Size};
+use gfx_types::color::Color;
+use gfx_types::geometry::{Point, Rect};
 
+use crate::render::{Canvas, FontSet};
 use crate::theme::{colors, metrics, GlassRenderer, GlassStyle};
 
-use super::Panel;
+use super::{slide_up, Panel, PanelLayer};
 
 // =============================================================================
 // QUICK SETTINGS
@@ -44,6 +46,8 @@ pub struct QuickSettingsPanel {
     screen_height: u32,
     /// Itens.
     items: [QuickSettingItem; 6],
+    /// Camada usada durante a animação.
+    layer: PanelLayer,
 }
 
 impl QuickSettingsPanel {
@@ -88,12 +92,27 @@ impl QuickSettingsPanel {
                     active: false,
                 },
             ],
+            layer: PanelLayer::new(),
         }
     }
 
-    /// Desenha o grid de configurações.
-    fn draw_settings_grid(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
+    /// Desenha fundo e grid (coordenadas relativas ao painel).
+    fn draw_contents(&self, canvas: &mut Canvas) {
+        let bounds = Rect::new(0, 0, self.bounds.width, self.bounds.height);
+
+        // Fundo glass
+        let style = GlassStyle::panel();
+        GlassRenderer::draw_fill(canvas, bounds, &style);
+
+        // Grid de configurações
+        canvas.save();
+        canvas.clip_rounded(bounds, style.corner_radii);
+        self.draw_settings_grid(canvas);
+        canvas.restore();
+    }
+
+    /// Desenha o grid de configurações (coordenadas relativas ao painel).
+    fn draw_settings_grid(&self, canvas: &mut Canvas) {
         let padding = metrics::PANEL_PADDING as i32;
 
         let cell_size = 80i32;
@@ -104,40 +123,32 @@ impl QuickSettingsPanel {
             let col = (i % cols) as i32;
             let row = (i / cols) as i32;
 
-            let x = self.bounds.x + padding + col * (cell_size + gap);
-            let y = self.bounds.y + padding + row * (cell_size + gap);
+            let x = padding + col * (cell_size + gap);
+            let y = padding + row * (cell_size + gap);
 
             // Fundo do botão
             let bg_color = if item.active {
-                colors::ACCENT.as_u32()
+                colors::ACCENT
             } else {
-                colors::BG_MEDIUM.as_u32()
+                colors::BG_MEDIUM
             };
 
             // Desenhar botão arredondado
-            Self::fill_rounded_rect(
-                buffer,
-                stride,
-                buffer_size,
-                x,
-                y,
-                cell_size as u32,
-                cell_size as u32,
-                bg_color,
+            canvas.fill_rounded_rect(
+                Rect::new(x, y, cell_size as u32, cell_size as u32),
                 12,
+                bg_color,
             );
 
             // Ícone
             let icon_color = if item.active {
-                colors::TEXT_ON_ACCENT.as_u32()
+                colors::TEXT_ON_ACCENT
             } else {
-                colors::ICON_NORMAL.as_u32()
+                colors::ICON_NORMAL
             };
 
-            self.draw_setting_icon(
-                buffer,
-                stride,
-                buffer_size,
+            Self::draw_setting_icon(
+                canvas,
                 x + cell_size / 2,
                 y + cell_size / 2 - 8,
                 item.item_type,
@@ -148,100 +159,58 @@ impl QuickSettingsPanel {
 
     /// Desenha ícone de configuração.
     fn draw_setting_icon(
-        &self,
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
+        canvas: &mut Canvas,
         cx: i32,
         cy: i32,
         item_type: QuickSettingType,
-        color: u32,
+        color: Color,
     ) {
+        let mut bar = |x: i32, y: i32, w: u32, h: u32| {
+            canvas.fill_rect(Rect::new(cx + x, cy + y, w, h), color);
+        };
+
         match item_type {
             QuickSettingType::Wifi => {
                 // Arcos de wifi
-                Self::fill_rect(buffer, stride, size, cx - 12, cy - 8, 24, 2, color);
-                Self::fill_rect(buffer, stride, size, cx - 8, cy - 2, 16, 2, color);
-                Self::fill_rect(buffer, stride, size, cx - 4, cy + 4, 8, 2, color);
-                Self::fill_rect(buffer, stride, size, cx - 1, cy + 8, 2, 4, color);
+                bar(-12, -8, 24, 2);
+                bar(-8, -2, 16, 2);
+                bar(-4, 4, 8, 2);
+                bar(-1, 8, 2, 4);
             }
             QuickSettingType::Bluetooth => {
                 // B estilizado
-                Self::fill_rect(buffer, stride, size, cx - 2, cy - 10, 4, 20, color);
-                Self::fill_rect(buffer, stride, size, cx + 2, cy - 8, 4, 2, color);
-                Self::fill_rect(buffer, stride, size, cx + 2, cy - 2, 4, 2, color);
-                Self::fill_rect(buffer, stride, size, cx + 2, cy + 4, 4, 2, color);
+                bar(-2, -10, 4, 20);
+                bar(2, -8, 4, 2);
+                bar(2, -2, 4, 2);
+                bar(2, 4, 4, 2);
             }
             QuickSettingType::Volume => {
                 // Alto-falante
-                Self::fill_rect(buffer, stride, size, cx - 8, cy - 4, 6, 8, color);
-                Self::fill_rect(buffer, stride, size, cx - 2, cy - 8, 4, 16, color);
-                Self::fill_rect(buffer, stride, size, cx + 4, cy - 6, 2, 12, color);
-                Self::fill_rect(buffer, stride, size, cx + 8, cy - 8, 2, 16, color);
+                bar(-8, -4, 6, 8);
+                bar(-2, -8, 4, 16);
+                bar(4, -6, 2, 12);
+                bar(8, -8, 2, 16);
             }
             QuickSettingType::Brightness => {
                 // Sol
-                Self::fill_rect(buffer, stride, size, cx - 4, cy - 4, 8, 8, color);
-                Self::fill_rect(buffer, stride, size, cx - 1, cy - 10, 2, 4, color);
-                Self::fill_rect(buffer, stride, size, cx - 1, cy + 6, 2, 4, color);
-                Self::fill_rect(buffer, stride, size, cx - 10, cy - 1, 4, 2, color);
-                Self::fill_rect(buffer, stride, size, cx + 6, cy - 1, 4, 2, color);
+                bar(-4, -4, 8, 8);
+                bar(-1, -10, 2, 4);
+                bar(-1, 6, 2, 4);
+                bar(-10, -1, 4, 2);
+                bar(6, -1, 4, 2);
             }
             QuickSettingType::DoNotDisturb => {
                 // Círculo com linha
-                Self::fill_rect(buffer, stride, size, cx - 8, cy - 1, 16, 2, color);
+                bar(-8, -1, 16, 2);
             }
             QuickSettingType::AirplaneMode => {
                 // Avião simplificado
-                Self::fill_rect(buffer, stride, size, cx - 1, cy - 10, 2, 20, color);
-                Self::fill_rect(buffer, stride, size, cx - 10, cy - 2, 20, 4, color);
-                Self::fill_rect(buffer, stride, size, cx - 4, cy + 6, 8, 3, color);
+                bar(-1, -10, 2, 20);
+                bar(-10, -2, 20, 4);
+                bar(-4, 6, 8, 3);
             }
         }
     }
-
-    fn fill_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        x: i32,
-        y: i32,
-        w: u32,
-        h: u32,
-        color: u32,
-    ) {
-        for dy in 0..h as i32 {
-            let py = y + dy;
-            if py < 0 || py >= size.height as i32 {
-                continue;
-            }
-            for dx in 0..w as i32 {
-                let px = x + dx;
-                if px < 0 || px >= size.width as i32 {
-                    continue;
-                }
-                let idx = py as usize * stride + px as usize;
-                if idx < buffer.len() {
-                    buffer[idx] = color;
-                }
-            }
-        }
-    }
-
-    fn fill_rounded_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        x: i32,
-        y: i32,
-        w: u32,
-        h: u32,
-        color: u32,
-        _radius: u32,
-    ) {
-        // Versão simplificada (sem cantos arredondados por enquanto)
-        Self::fill_rect(buffer, stride, size, x, y, w, h, color);
-    }
 }
 
 impl Panel for QuickSettingsPanel {
@@ -257,31 +226,26 @@ impl Panel for QuickSettingsPanel {
         self.bounds
     }
 
-    fn draw(&self, buffer: &mut [u32], buffer_size: Size) {
+    fn animated_bounds(&self) -> Rect {
+        slide_up(self.bounds, self.screen_height, self.animation_progress)
+    }
+
+    fn draw(&self, canvas: &mut Canvas, _fonts: &FontSet) {
         if !self.is_visible() {
             return;
         }
 
-        // Calcular posição animada
-        let target_y = self.bounds.y;
-        let start_y = self.screen_height as i32;
-        let current_y = start_y + ((target_y - start_y) as f32 * self.animation_progress) as i32;
+        // Fora da região sendo redesenhada
+        if !canvas.is_visible(self.visual_bounds()) {
+            return;
+        }
 
-        let animated_bounds = Rect::new(
-            self.bounds.x,
-            current_y,
-            self.bounds.width,
-            self.bounds.height,
+        self.layer.draw(
+            canvas,
+            self.animated_bounds(),
+            self.animation_progress,
+            |canvas| self.draw_contents(canvas),
         );
-
-        // Fundo glass
-        let style = GlassStyle::panel();
-        GlassRenderer::draw_rect(buffer, buffer_size, animated_bounds, &style);
-
-        // Grid de configurações
-        if self.animation_progress > 0.5 {
-            self.draw_settings_grid(buffer, buffer_size);
-        }
     }
 
     fn handle_click(&mut self, x: i32, y: i32) -> bool {
@@ -291,6 +255,7 @@ impl Panel for QuickSettingsPanel {
 
         if self.bounds.contains_point(Point::new(x, y)) {
             // TODO: Toggle item clicado
+            self.layer.invalidate();
             return true;
         }
 
@@ -303,6 +268,7 @@ impl Panel for QuickSettingsPanel {
 
         if (self.animation_progress - target).abs() < 0.01 {
             self.animation_progress = target;
+            self.layer.invalidate();
             return false;
         }
 
@@ -1,15 +1,25 @@
 //! # Start Menu Panel
 //!
 //! Menu iniciar com lista de aplicativos.
+//!
+//! Os ícones vêm do [`IconCache`] compartilhado, no tamanho
+//! [`metrics::APP_ICON_SIZE`]; apps sem ícone legível aparecem com o avatar
+//! gerado (inicial sobre a cor do app).
+//!
+//! O botão "Atualizar" no cabeçalho pede ao desktop que releia a lista de
+//! apps ([`StartMenuAction::RefreshApps`]).
 
+use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
 use gfx_types::geometry::{Point, Rect, Size};
 
-use crate::app::AppInfo;
+use crate::app::{AppIcon, AppInfo};
+use crate::render::{Canvas, FontSet, TextAlign, TextBaseline, TextLayout, TextStyle};
 use crate::theme::{colors, metrics, GlassRenderer, GlassStyle};
+use crate::ui::IconCache;
 
-use super::Panel;
+use super::{slide_up, Panel, PanelLayer};
 
 // =============================================================================
 // START MENU
@@ -19,7 +29,8 @@ use super::Panel;
 #[derive(Debug, Clone)]
 pub enum StartMenuAction {
     None,
-    LaunchApp(String), // Path do app
+    LaunchApp(String), // Id do app
+    RefreshApps,
 }
 
 /// Painel do menu iniciar.
@@ -34,17 +45,21 @@ pub struct StartMenuPanel {
     screen_height: u32,
     /// Apps disponíveis.
     apps: Vec<AppInfo>,
+    /// Cache de ícones compartilhado.
+    icons: Rc<IconCache>,
     /// Item hover.
     hover_index: Option<usize>,
     /// Última ação.
     last_action: StartMenuAction,
     /// Scroll offset.
     scroll_offset: i32,
+    /// Camada usada durante a animação.
+    layer: PanelLayer,
 }
 
 impl StartMenuPanel {
     /// Cria novo painel.
-    pub fn new(screen_width: u32, screen_height: u32) -> Self {
+    pub fn new(screen_width: u32, screen_height: u32, icons: Rc<IconCache>) -> Self {
         let margin = metrics::TASKBAR_MARGIN;
         let taskbar_y = screen_height as i32 - metrics::TASKBAR_HEIGHT as i32 - margin as i32;
 
@@ -62,15 +77,26 @@ impl StartMenuPanel {
             animation_progress: 0.0,
             screen_height,
             apps: Vec::new(),
+            icons,
             hover_index: None,
             last_action: StartMenuAction::None,
             scroll_offset: 0,
+            layer: PanelLayer::new(),
         }
     }
 
-    /// Define apps disponíveis.
+    /// Define apps disponíveis (apps `hidden` ficam de fora).
+    ///
+    /// O item em hover é mantido pelo id do app, mesmo que mude de posição.
     pub fn set_apps(&mut self, apps: Vec<AppInfo>) {
-        self.apps = apps;
+        let hovered = self
+            .hover_index
+            .and_then(|i| self.apps.get(i))
+            .map(|app| app.id.clone());
+
+        self.apps = apps.into_iter().filter(|app| !app.hidden).collect();
+        self.hover_index = hovered.and_then(|id| self.apps.iter().position(|app| app.id == id));
+        self.layer.invalidate();
     }
 
     /// Retorna última ação e limpa.
@@ -78,36 +104,52 @@ impl StartMenuPanel {
         core::mem::replace(&mut self.last_action, StartMenuAction::None)
     }
 
-    /// Desenha lista de apps.
-    fn draw_app_list(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
+    /// Desenha fundo e lista (coordenadas relativas ao painel).
+    fn draw_contents(&self, canvas: &mut Canvas, fonts: &FontSet) {
+        let bounds = Rect::new(0, 0, self.bounds.width, self.bounds.height);
+
+        // Fundo glass
+        let style = GlassStyle::panel();
+        GlassRenderer::draw_fill(canvas, bounds, &style);
+
+        // Lista de apps
+        canvas.save();
+        canvas.clip_rounded(bounds, style.corner_radii);
+        self.draw_app_list(canvas, fonts);
+        canvas.restore();
+    }
+
+    /// Desenha lista de apps (coordenadas relativas ao painel).
+    fn draw_app_list(&self, canvas: &mut Canvas, fonts: &FontSet) {
         let padding = metrics::PANEL_PADDING as i32;
+        let bounds = Rect::new(0, 0, self.bounds.width, self.bounds.height);
 
         // Título "Aplicativos"
-        let title_y = self.bounds.y + padding;
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            self.bounds.x + padding,
-            title_y,
-            100,
-            3,
-            colors::TEXT_PRIMARY.as_u32(),
+        let title_y = bounds.y + padding;
+        canvas.draw_text(
+            "Aplicativos",
+            Point::new(bounds.x + padding, title_y + 8),
+            &TextStyle::new(fonts.regular(), colors::TEXT_PRIMARY)
+                .with_baseline(TextBaseline::Middle),
+        );
+
+        // Botão "Atualizar"
+        let refresh = self.refresh_rect();
+        let label = TextLayout::single_line(fonts.regular(), "Atualizar", refresh.width);
+        canvas.draw_layout(
+            &label,
+            refresh,
+            TextAlign::Right,
+            &TextStyle::new(fonts.regular(), colors::TEXT_SECONDARY)
+                .with_baseline(TextBaseline::Middle),
         );
 
         // Separador
         let sep_y = title_y + 24;
-        let sep_width = (self.bounds.width as i32 - padding * 2).max(0) as u32;
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            self.bounds.x + padding,
-            sep_y,
-            sep_width,
-            1,
-            colors::MENU_SEPARATOR.as_u32(),
+        let sep_width = (bounds.width as i32 - padding * 2).max(0) as u32;
+        canvas.blend_rect(
+            Rect::new(bounds.x + padding, sep_y, sep_width, 1),
+            colors::MENU_SEPARATOR,
         );
 
         // Lista de apps
@@ -115,75 +157,56 @@ impl StartMenuPanel {
         let item_height = metrics::APP_ITEM_HEIGHT as i32;
         let icon_size = metrics::APP_ICON_SIZE as i32;
 
-        let visible_height = self.bounds.height as i32 - (list_y - self.bounds.y) - padding;
+        let visible_height = bounds.height as i32 - (list_y - bounds.y) - padding;
         let max_visible = (visible_height / item_height) as usize;
 
         for (i, app) in self.apps.iter().take(max_visible).enumerate() {
             let item_y = list_y + (i as i32) * item_height - self.scroll_offset;
 
-            if item_y + item_height < self.bounds.y || item_y > self.bounds.bottom() {
+            if item_y + item_height < bounds.y || item_y > bounds.bottom() {
                 continue;
             }
 
             // Fundo do item (hover)
             if self.hover_index == Some(i) {
-                Self::fill_rect(
-                    buffer,
-                    stride,
-                    buffer_size,
-                    self.bounds.x + padding / 2,
-                    item_y,
-                    self.bounds.width - padding as u32,
-                    item_height as u32,
-                    colors::MENU_ITEM_HOVER.as_u32(),
+                canvas.blend_rect(
+                    Rect::new(
+                        bounds.x + padding / 2,
+                        item_y,
+                        bounds.width - padding as u32,
+                        item_height as u32,
+                    ),
+                    colors::MENU_ITEM_HOVER,
                 );
             }
 
-            // Placeholder do ícone
-            let icon_x = self.bounds.x + padding;
+            // Ícone (ou avatar)
+            let icon_x = bounds.x + padding;
             let icon_y = item_y + (item_height - icon_size) / 2;
+            let icon_rect = Rect::new(icon_x, icon_y, icon_size as u32, icon_size as u32);
 
-            // Quadrado colorido como placeholder do ícone
-            let icon_color = self.get_app_color(i);
-            Self::fill_rect(
-                buffer,
-                stride,
-                buffer_size,
-                icon_x,
-                icon_y,
-                icon_size as u32,
-                icon_size as u32,
-                icon_color,
-            );
-
-            // Inicial do app no ícone
-            let initial_x = icon_x + icon_size / 2 - 4;
-            let initial_y = icon_y + icon_size / 2 - 4;
-            Self::fill_rect(
-                buffer,
-                stride,
-                buffer_size,
-                initial_x,
-                initial_y,
-                8,
-                8,
-                colors::TEXT_ON_ACCENT.as_u32(),
-            );
+            let icon = self.icons.get(app, metrics::APP_ICON_SIZE, fonts);
+            Self::draw_icon(canvas, &icon, icon_rect);
 
-            // Nome do app (placeholder - linha)
+            // Nome do app
             let name_x = icon_x + icon_size + metrics::APP_ICON_GAP as i32;
-            let name_y = item_y + item_height / 2 - 2;
-            let name_width = (app.name.len() * 8).min(200) as u32;
-            Self::fill_rect(
-                buffer,
-                stride,
-                buffer_size,
+            let name_rect = Rect::new(
                 name_x,
-                name_y,
-                name_width,
-                4,
-                colors::TEXT_PRIMARY.as_u32(),
+                item_y,
+                (bounds.right() - padding - name_x).max(0) as u32,
+                item_height as u32,
+            );
+            let name = TextLayout::single_line(fonts.regular(), &app.name, name_rect.width);
+            canvas.save();
+            canvas.clip(name_rect);
+            canvas.draw_layout(
+                &name,
+                name_rect,
+                TextAlign::Left,
+                &TextStyle::new(fonts.regular(), colors::TEXT_PRIMARY)
+                    .with_baseline(TextBaseline::Middle),
             );
+            canvas.restore();
         }
 
         // Indicador de scroll se necessário
@@ -195,58 +218,37 @@ impl StartMenuPanel {
             let scrollbar_y =
                 list_y + (scroll_ratio * (visible_height - scrollbar_height) as f32) as i32;
 
-            Self::fill_rect(
-                buffer,
-                stride,
-                buffer_size,
-                self.bounds.right() - 6,
-                scrollbar_y,
-                3,
-                scrollbar_height as u32,
-                colors::GLASS_BORDER.as_u32(),
+            canvas.blend_rect(
+                Rect::new(bounds.right() - 6, scrollbar_y, 3, scrollbar_height as u32),
+                colors::GLASS_BORDER,
             );
         }
     }
 
-    /// Cor do app baseada no índice.
-    fn get_app_color(&self, index: usize) -> u32 {
-        const COLORS: [u32; 6] = [
-            0xFF4A90D9, // Azul
-            0xFF3FB950, // Verde
-            0xFFE53935, // Vermelho
-            0xFFF0B429, // Amarelo
-            0xFF9C27B0, // Roxo
-            0xFFFF6B35, // Laranja
-        ];
-        COLORS[index % COLORS.len()]
+    /// Desenha um ícone centralizado em `rect` (cortado se maior).
+    fn draw_icon(canvas: &mut Canvas, icon: &AppIcon, rect: Rect) {
+        let x = rect.x + (rect.width as i32 - icon.width as i32) / 2;
+        let y = rect.y + (rect.height as i32 - icon.height as i32) / 2;
+        canvas.save();
+        canvas.clip(rect);
+        canvas.blit_blend(
+            &icon.data,
+            Size::new(icon.width, icon.height),
+            Point::new(x, y),
+        );
+        canvas.restore();
     }
 
-    fn fill_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        x: i32,
-        y: i32,
-        w: u32,
-        h: u32,
-        color: u32,
-    ) {
-        for dy in 0..h as i32 {
-            let py = y + dy;
-            if py < 0 || py >= size.height as i32 {
-                continue;
-            }
-            for dx in 0..w as i32 {
-                let px = x + dx;
-                if px < 0 || px >= size.width as i32 {
-                    continue;
-                }
-                let idx = py as usize * stride + px as usize;
-                if idx < buffer.len() {
-                    buffer[idx] = color;
-                }
-            }
-        }
+    /// Área do botão "Atualizar" (coordenadas relativas ao painel).
+    fn refresh_rect(&self) -> Rect {
+        let padding = metrics::PANEL_PADDING as i32;
+        let width = metrics::MENU_REFRESH_WIDTH;
+        Rect::new(
+            self.bounds.width as i32 - padding - width as i32,
+            padding - 4,
+            width,
+            24,
+        )
     }
 
     /// Encontra app pelo ponto.
@@ -284,6 +286,7 @@ impl Panel for StartMenuPanel {
         if !visible {
             self.hover_index = None;
             self.scroll_offset = 0;
+            self.layer.invalidate();
         }
     }
 
@@ -291,31 +294,26 @@ impl Panel for StartMenuPanel {
         self.bounds
     }
 
-    fn draw(&self, buffer: &mut [u32], buffer_size: Size) {
+    fn animated_bounds(&self) -> Rect {
+        slide_up(self.bounds, self.screen_height, self.animation_progress)
+    }
+
+    fn draw(&self, canvas: &mut Canvas, fonts: &FontSet) {
         if !self.is_visible() {
             return;
         }
 
-        // Calcular posição animada
-        let target_y = self.bounds.y;
-        let start_y = self.screen_height as i32;
-        let current_y = start_y + ((target_y - start_y) as f32 * self.animation_progress) as i32;
+        // Fora da região sendo redesenhada
+        if !canvas.is_visible(self.visual_bounds()) {
+            return;
+        }
 
-        let animated_bounds = Rect::new(
-            self.bounds.x,
-            current_y,
-            self.bounds.width,
-            self.bounds.height,
+        self.layer.draw(
+            canvas,
+            self.animated_bounds(),
+            self.animation_progress,
+            |canvas| self.draw_contents(canvas, fonts),
         );
-
-        // Fundo glass
-        let style = GlassStyle::panel();
-        GlassRenderer::draw_rect(buffer, buffer_size, animated_bounds, &style);
-
-        // Lista de apps
-        if self.animation_progress > 0.5 {
-            self.draw_app_list(buffer, buffer_size);
-        }
     }
 
     fn handle_click(&mut self, x: i32, y: i32) -> bool {
@@ -327,10 +325,22 @@ impl Panel for StartMenuPanel {
             return false;
         }
 
+        let refresh = self.refresh_rect();
+        let refresh = Rect::new(
+            self.bounds.x + refresh.x,
+            self.bounds.y + refresh.y,
+            refresh.width,
+            refresh.height,
+        );
+        if refresh.contains_point(Point::new(x, y)) {
+            self.last_action = StartMenuAction::RefreshApps;
+            return true;
+        }
+
         if let Some(index) = self.app_at_point(x, y) {
             if index < self.apps.len() {
-                let path = self.apps[index].path.clone();
-                self.last_action = StartMenuAction::LaunchApp(path);
+                let id = self.apps[index].id.clone();
+                self.last_action = StartMenuAction::LaunchApp(id);
                 self.set_visible(false);
             }
         }
@@ -344,6 +354,7 @@ impl Panel for StartMenuPanel {
 
         if (self.animation_progress - target).abs() < 0.01 {
             self.animation_progress = target;
+            self.layer.invalidate();
             return false;
         }
 
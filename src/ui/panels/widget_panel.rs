@@ -2,11 +2,12 @@
 //!
 //! Painel de widgets que abre da esquerda.
 
-use gfx_types::geometry::{Rect, Size};
+use gfx_types::geometry::Rect;
 
+use crate::render::{Canvas, FontSet, TextAlign, TextLayout, TextStyle};
 use crate::theme::{colors, metrics, GlassRenderer, GlassStyle};
 
-use super::Panel;
+use super::{slide_up, Panel, PanelLayer};
 
 // =============================================================================
 // WIDGET PANEL
@@ -22,6 +23,8 @@ pub struct WidgetPanel {
     animation_progress: f32,
     /// Altura da tela.
     screen_height: u32,
+    /// Camada usada durante a animação.
+    layer: PanelLayer,
 }
 
 impl WidgetPanel {
@@ -42,17 +45,30 @@ impl WidgetPanel {
             visible: false,
             animation_progress: 0.0,
             screen_height,
+            layer: PanelLayer::new(),
         }
     }
 
-    /// Desenha texto "Ainda não há widgets".
-    fn draw_empty_message(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
-        let color = colors::TEXT_SECONDARY.as_u32();
+    /// Desenha fundo e conteúdo (coordenadas relativas ao painel).
+    fn draw_contents(&self, canvas: &mut Canvas, fonts: &FontSet) {
+        let bounds = Rect::new(0, 0, self.bounds.width, self.bounds.height);
 
+        // Desenhar fundo glass
+        let style = GlassStyle::panel();
+        GlassRenderer::draw_fill(canvas, bounds, &style);
+
+        // Desenhar conteúdo
+        canvas.save();
+        canvas.clip_rounded(bounds, style.corner_radii);
+        self.draw_empty_message(canvas, fonts);
+        canvas.restore();
+    }
+
+    /// Desenha texto "Ainda não há widgets" (coordenadas relativas ao painel).
+    fn draw_empty_message(&self, canvas: &mut Canvas, fonts: &FontSet) {
         // Posição central
-        let cx = self.bounds.x + self.bounds.width as i32 / 2;
-        let cy = self.bounds.y + self.bounds.height as i32 / 2;
+        let cx = self.bounds.width as i32 / 2;
+        let cy = self.bounds.height as i32 / 2;
 
         // Ícone de widgets (4 quadrados grandes)
         let icon_size = 48;
@@ -61,89 +77,48 @@ impl WidgetPanel {
         let half = icon_size / 2 - 4;
         let gap = 8;
 
-        let icon_color = colors::ICON_DISABLED.as_u32();
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            ix,
-            iy,
-            half as u32,
-            half as u32,
-            icon_color,
-        );
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            ix + half + gap,
-            iy,
-            half as u32,
-            half as u32,
-            icon_color,
-        );
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            ix,
-            iy + half + gap,
-            half as u32,
-            half as u32,
-            icon_color,
-        );
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            ix + half + gap,
-            iy + half + gap,
-            half as u32,
-            half as u32,
-            icon_color,
-        );
+        let icon_color = colors::ICON_DISABLED;
+        for (dx, dy) in [
+            (0, 0),
+            (half + gap, 0),
+            (0, half + gap),
+            (half + gap, half + gap),
+        ] {
+            canvas.fill_rect(
+                Rect::new(ix + dx, iy + dy, half as u32, half as u32),
+                icon_color,
+            );
+        }
 
-        // Texto placeholder
+        // Mensagem
+        let padding = metrics::PANEL_PADDING as i32;
+        let text_width = (self.bounds.width as i32 - padding * 2).max(0) as u32;
         let text_y = cy + 30;
-        Self::fill_rect(buffer, stride, buffer_size, cx - 80, text_y, 160, 2, color);
-        Self::fill_rect(
-            buffer,
-            stride,
-            buffer_size,
-            cx - 60,
-            text_y + 10,
-            120,
+        let title = TextLayout::single_line(fonts.regular(), "Ainda não há widgets", text_width);
+        let hint = TextLayout::wrapped(
+            fonts.small(),
+            "Adicione widgets para vê-los aqui",
+            text_width,
             2,
-            color,
         );
-    }
 
-    fn fill_rect(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        x: i32,
-        y: i32,
-        w: u32,
-        h: u32,
-        color: u32,
-    ) {
-        for dy in 0..h as i32 {
-            let py = y + dy;
-            if py < 0 || py >= size.height as i32 {
-                continue;
-            }
-            for dx in 0..w as i32 {
-                let px = x + dx;
-                if px < 0 || px >= size.width as i32 {
-                    continue;
-                }
-                let idx = py as usize * stride + px as usize;
-                if idx < buffer.len() {
-                    buffer[idx] = color;
-                }
-            }
-        }
+        canvas.draw_layout(
+            &title,
+            Rect::new(padding, text_y, text_width, title.height()),
+            TextAlign::Center,
+            &TextStyle::new(fonts.regular(), colors::TEXT_SECONDARY),
+        );
+        canvas.draw_layout(
+            &hint,
+            Rect::new(
+                padding,
+                text_y + fonts.regular().line_height() as i32,
+                text_width,
+                hint.height(),
+            ),
+            TextAlign::Center,
+            &TextStyle::new(fonts.small(), colors::TEXT_DISABLED),
+        );
     }
 }
 
@@ -160,31 +135,26 @@ impl Panel for WidgetPanel {
         self.bounds
     }
 
-    fn draw(&self, buffer: &mut [u32], buffer_size: Size) {
+    fn animated_bounds(&self) -> Rect {
+        slide_up(self.bounds, self.screen_height, self.animation_progress)
+    }
+
+    fn draw(&self, canvas: &mut Canvas, fonts: &FontSet) {
         if !self.is_visible() {
             return;
         }
 
-        // Calcular posição animada (slide up)
-        let target_y = self.bounds.y;
-        let start_y = self.screen_height as i32;
-        let current_y = start_y + ((target_y - start_y) as f32 * self.animation_progress) as i32;
+        // Fora da região sendo redesenhada
+        if !canvas.is_visible(self.visual_bounds()) {
+            return;
+        }
 
-        let animated_bounds = Rect::new(
-            self.bounds.x,
-            current_y,
-            self.bounds.width,
-            self.bounds.height,
+        self.layer.draw(
+            canvas,
+            self.animated_bounds(),
+            self.animation_progress,
+            |canvas| self.draw_contents(canvas, fonts),
         );
-
-        // Desenhar fundo glass
-        let style = GlassStyle::panel();
-        GlassRenderer::draw_rect(buffer, buffer_size, animated_bounds, &style);
-
-        // Desenhar conteúdo (com offset)
-        if self.animation_progress > 0.5 {
-            self.draw_empty_message(buffer, buffer_size);
-        }
     }
 
     fn handle_click(&mut self, x: i32, y: i32) -> bool {
@@ -201,6 +171,7 @@ impl Panel for WidgetPanel {
 
         if (self.animation_progress - target).abs() < 0.01 {
             self.animation_progress = target;
+            self.layer.invalidate();
             return false;
         }
 
@@ -1,15 +1,21 @@
 //! # Taskbar
 //!
 //! Barra de tarefas com 3 barras flutuantes.
+//!
+//! Janelas cujo título coincide com o nome de um app conhecido mostram o
+//! ícone do app (via [`IconCache`]); as demais, a inicial do título.
 
-// TODO: Revisar no futuro
-#[allow(unused)]
 use gfx_types::color::Color;
 use gfx_types::geometry::{Point, Rect, Size};
 
 use crate::app::AppInfo;
+use crate::render::{
+    Canvas, FontSet, ShadowKernel, TextAlign, TextBaseline, TextLayout, TextStyle,
+};
 use crate::theme::{colors, metrics, GlassRenderer, GlassStyle};
+use crate::ui::IconCache;
 
+use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -20,14 +26,14 @@ use alloc::vec::Vec;
 // TODO: Revisar no futuro
 #[allow(unused)]
 /// Ação retornada pelo tratamento de clique.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TaskbarAction {
     None,
     ToggleWidgetPanel,
     ToggleStartMenu,
     ToggleQuickSettings,
     ToggleWindow(u32),
-    LaunchApp(usize),
+    LaunchApp(String), // Id do app
 }
 
 // TODO: Revisar no futuro
@@ -58,12 +64,16 @@ pub struct Taskbar {
     center_bar: Rect,
     /// Rect da barra de status (direita).
     status_bar: Rect,
+    /// Sombra das barras, pré-calculada.
+    shadow: ShadowKernel,
 
     // Estado
     /// Janelas abertas.
     entries: Vec<WindowEntry>,
     /// Apps disponíveis.
     pub available_apps: Vec<AppInfo>,
+    /// Cache de ícones compartilhado.
+    icons: Rc<IconCache>,
     /// Uptime em segundos.
     uptime_secs: u64,
 
@@ -76,15 +86,17 @@ pub struct Taskbar {
 
 impl Taskbar {
     /// Cria nova taskbar.
-    pub fn new(screen_width: u32, screen_height: u32) -> Self {
+    pub fn new(screen_width: u32, screen_height: u32, icons: Rc<IconCache>) -> Self {
         let mut taskbar = Self {
             screen_width,
             screen_height,
             widget_bar: Rect::ZERO,
             center_bar: Rect::ZERO,
             status_bar: Rect::ZERO,
+            shadow: GlassStyle::bar().shadow_kernel(),
             entries: Vec::new(),
             available_apps: Vec::new(),
+            icons,
             uptime_secs: 0,
             hover_bar: 0,
         };
@@ -158,11 +170,54 @@ impl Taskbar {
             .map(|e| e.minimized)
     }
 
-    /// Atualiza uptime.
-    pub fn update_uptime(&mut self) {
-        if let Ok(ms) = redpowder::time::clock() {
-            self.uptime_secs = ms / 1000;
-        }
+    /// Atualiza uptime. Retorna true se o valor exibido mudou.
+    pub fn update_uptime(&mut self) -> bool {
+        let Ok(ms) = redpowder::time::clock() else {
+            return false;
+        };
+        let secs = ms / 1000;
+        let changed = secs != self.uptime_secs;
+        self.uptime_secs = secs;
+        changed
+    }
+
+    // =========================================================================
+    // REGIÕES
+    // =========================================================================
+
+    /// Áreas das três barras.
+    pub fn bar_bounds(&self) -> [Rect; 3] {
+        [self.widget_bar, self.center_bar, self.status_bar]
+    }
+
+    /// Área da barra central (menu e janelas abertas).
+    pub fn center_bounds(&self) -> Rect {
+        self.center_bar
+    }
+
+    /// Área da barra de status.
+    pub fn status_bounds(&self) -> Rect {
+        self.status_bar
+    }
+
+    /// Área da entrada da janela `id`, se presente.
+    pub fn entry_bounds(&self, id: u32) -> Option<Rect> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        let entry_width = metrics::WINDOW_ENTRY_WIDTH as i32;
+        let x = self.entries_x() + index as i32 * (entry_width + metrics::ICON_GAP as i32);
+
+        Some(Rect::new(
+            x,
+            self.center_bar.y,
+            entry_width as u32,
+            self.center_bar.height,
+        ))
+    }
+
+    /// X onde começa a primeira entrada de janela.
+    fn entries_x(&self) -> i32 {
+        // Ícone do menu (28px) + separador (12px)
+        self.center_bar.x + metrics::TASKBAR_PADDING as i32 + 28 + 12
     }
 
     // =========================================================================
@@ -170,24 +225,27 @@ impl Taskbar {
     // =========================================================================
 
     /// Desenha a taskbar.
-    pub fn draw(&mut self, buffer: &mut [u32], buffer_size: Size) {
-        self.update_uptime();
-
+    pub fn draw(&self, canvas: &mut Canvas, fonts: &FontSet) {
         let style = GlassStyle::bar();
 
+        // Sombras primeiro, para nenhuma escurecer a barra vizinha
+        for bar in self.bar_bounds() {
+            GlassRenderer::draw_shadow(canvas, bar, &style, &self.shadow, 255);
+        }
+
         // Desenhar as 3 barras
-        GlassRenderer::draw_rect(buffer, buffer_size, self.widget_bar, &style);
-        GlassRenderer::draw_rect(buffer, buffer_size, self.center_bar, &style);
-        GlassRenderer::draw_rect(buffer, buffer_size, self.status_bar, &style);
+        for bar in self.bar_bounds() {
+            GlassRenderer::draw_rect(canvas, bar, &style);
+        }
 
         // Conteúdo das barras
-        self.draw_widget_button(buffer, buffer_size);
-        self.draw_center_content(buffer, buffer_size);
-        self.draw_status_content(buffer, buffer_size);
+        self.draw_widget_button(canvas);
+        self.draw_center_content(canvas, fonts);
+        self.draw_status_content(canvas, fonts);
     }
 
     /// Desenha botão de widgets.
-    fn draw_widget_button(&self, buffer: &mut [u32], buffer_size: Size) {
+    fn draw_widget_button(&self, canvas: &mut Canvas) {
         // Ícone de grid (4 quadrados)
         let icon_size = 20;
         let cx = self.widget_bar.x + (self.widget_bar.width as i32 - icon_size) / 2;
@@ -195,308 +253,178 @@ impl Taskbar {
 
         let half = icon_size / 2 - 2;
         let gap = 3;
-        let color = colors::ICON_NORMAL.as_u32();
-        let stride = buffer_size.width as usize;
+        let color = colors::ICON_NORMAL;
 
         // 4 quadrados
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            cx,
-            cy,
-            half as u32,
-            half as u32,
-            color,
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            cx + half + gap,
-            cy,
-            half as u32,
-            half as u32,
-            color,
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            cx,
-            cy + half + gap,
-            half as u32,
-            half as u32,
-            color,
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            cx + half + gap,
-            cy + half + gap,
-            half as u32,
-            half as u32,
-            color,
-        );
+        for (dx, dy) in [
+            (0, 0),
+            (half + gap, 0),
+            (0, half + gap),
+            (half + gap, half + gap),
+        ] {
+            canvas.fill_rect(Rect::new(cx + dx, cy + dy, half as u32, half as u32), color);
+        }
     }
 
     /// Desenha conteúdo central (ícone do menu + apps abertos).
-    fn draw_center_content(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
+    fn draw_center_content(&self, canvas: &mut Canvas, fonts: &FontSet) {
         let padding = metrics::TASKBAR_PADDING as i32;
 
         // Ícone do menu (3 linhas horizontais)
         let menu_x = self.center_bar.x + padding;
         let menu_y = self.center_bar.y + (self.center_bar.height as i32 - 16) / 2;
-        let color = colors::ICON_NORMAL.as_u32();
+        let color = colors::ICON_NORMAL;
 
         // 3 linhas
-        Self::fill_rect_simple(buffer, stride, buffer_size, menu_x, menu_y, 18, 2, color);
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            menu_x,
-            menu_y + 6,
-            18,
-            2,
-            color,
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            menu_x,
-            menu_y + 12,
-            18,
-            2,
-            color,
-        );
+        for dy in [0, 6, 12] {
+            canvas.fill_rect(Rect::new(menu_x, menu_y + dy, 18, 2), color);
+        }
 
         // Separador
         let sep_x = menu_x + 28;
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            sep_x,
-            self.center_bar.y + 8,
-            1,
-            self.center_bar.height - 16,
-            colors::MENU_SEPARATOR.as_u32(),
+        canvas.blend_rect(
+            Rect::new(sep_x, self.center_bar.y + 8, 1, self.center_bar.height - 16),
+            colors::MENU_SEPARATOR,
         );
 
         // Ícones das janelas abertas
-        let mut icon_x = sep_x + 12;
-        let icon_size = 32u32;
+        let mut icon_x = self.entries_x();
+        let icon_size = metrics::WINDOW_ICON_SIZE;
         let icon_y = self.center_bar.y + (self.center_bar.height as i32 - icon_size as i32) / 2;
+        let entry_width = metrics::WINDOW_ENTRY_WIDTH as i32;
 
         for entry in &self.entries {
-            if icon_x + icon_size as i32 > self.center_bar.right() - padding {
+            if icon_x + entry_width > self.center_bar.right() - padding {
                 break;
             }
 
-            // Fundo do ícone
-            let icon_color = if entry.minimized {
-                colors::BG_MEDIUM.as_u32()
+            // Ícone do app (ou inicial do título)
+            let icon_rect = Rect::new(icon_x, icon_y, icon_size, icon_size);
+            let text_color = if entry.minimized {
+                colors::TEXT_SECONDARY
             } else {
-                colors::GLASS_BG_ACTIVE.as_u32()
+                colors::TEXT_PRIMARY
             };
 
-            Self::fill_rect_simple(
-                buffer,
-                stride,
-                buffer_size,
-                icon_x,
-                icon_y,
-                icon_size,
-                icon_size,
-                icon_color,
-            );
+            if let Some(app) = self.app_for_window(entry) {
+                let icon = self.icons.get(app, icon_size, fonts);
+                canvas.blit_blend(
+                    &icon.data,
+                    Size::new(icon.width, icon.height),
+                    Point::new(
+                        icon_x + (icon_size as i32 - icon.width as i32) / 2,
+                        icon_y + (icon_size as i32 - icon.height as i32) / 2,
+                    ),
+                );
+            } else {
+                Self::draw_initial(canvas, fonts, entry, icon_rect, text_color);
+            }
 
             // Indicador de ativo
             if !entry.minimized {
-                Self::fill_rect_simple(
-                    buffer,
-                    stride,
-                    buffer_size,
-                    icon_x + 4,
-                    icon_y + icon_size as i32 - 3,
-                    icon_size - 8,
-                    2,
-                    colors::ACCENT.as_u32(),
+                canvas.fill_rect(
+                    Rect::new(icon_x + 4, icon_y + icon_size as i32 - 3, icon_size - 8, 2),
+                    colors::ACCENT,
                 );
             }
 
-            icon_x += icon_size as i32 + metrics::ICON_GAP as i32;
+            // Título (truncado)
+            let title_x = icon_x + icon_size as i32 + 8;
+            let title_rect = Rect::new(
+                title_x,
+                self.center_bar.y,
+                (icon_x + entry_width - title_x).max(0) as u32,
+                self.center_bar.height,
+            );
+            let title = TextLayout::single_line(fonts.regular(), &entry.title, title_rect.width);
+            canvas.save();
+            canvas.clip(title_rect);
+            canvas.draw_layout(
+                &title,
+                title_rect,
+                TextAlign::Left,
+                &TextStyle::new(fonts.regular(), text_color).with_baseline(TextBaseline::Middle),
+            );
+            canvas.restore();
+
+            icon_x += entry_width + metrics::ICON_GAP as i32;
         }
     }
 
-    /// Desenha conteúdo de status (uptime).
-    fn draw_status_content(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
+    /// App cujo nome coincide com o título da janela.
+    fn app_for_window(&self, entry: &WindowEntry) -> Option<&AppInfo> {
+        self.available_apps
+            .iter()
+            .find(|app| app.name.eq_ignore_ascii_case(&entry.title))
+    }
+
+    /// Desenha a inicial do título sobre um fundo neutro.
+    fn draw_initial(
+        canvas: &mut Canvas,
+        fonts: &FontSet,
+        entry: &WindowEntry,
+        rect: Rect,
+        text_color: Color,
+    ) {
+        let background = if entry.minimized {
+            colors::BG_MEDIUM
+        } else {
+            colors::GLASS_BG_ACTIVE
+        };
+        canvas.blend_rect(rect, background);
+
+        let mut initial_buf = [0u8; 4];
+        let initial = entry
+            .title
+            .chars()
+            .next()
+            .map(|c| {
+                &*c.to_uppercase()
+                    .next()
+                    .unwrap_or(c)
+                    .encode_utf8(&mut initial_buf)
+            })
+            .unwrap_or("?");
+        canvas.save();
+        canvas.clip(rect);
+        canvas.draw_text_centered(
+            initial,
+            rect.x + rect.width as i32 / 2,
+            rect.y + rect.height as i32 / 2,
+            &TextStyle::new(fonts.regular(), text_color).with_baseline(TextBaseline::Middle),
+        );
+        canvas.restore();
+    }
 
+    /// Desenha conteúdo de status (uptime).
+    fn draw_status_content(&self, canvas: &mut Canvas, fonts: &FontSet) {
         // Formatar uptime como HH:MM:SS
-        let hours = self.uptime_secs / 3600;
+        let hours = (self.uptime_secs / 3600) % 100;
         let minutes = (self.uptime_secs % 3600) / 60;
         let seconds = self.uptime_secs % 60;
 
-        // Desenhar cada dígito
-        let text_y = self.status_bar.y + (self.status_bar.height as i32 - 12) / 2;
-        let mut x = self.status_bar.x + 12;
-
-        // HH
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (hours / 10) as u8);
-        x += 10;
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (hours % 10) as u8);
-        x += 12;
-
-        // :
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            x,
-            text_y + 2,
-            2,
-            2,
-            colors::TEXT_PRIMARY.as_u32(),
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            x,
-            text_y + 8,
-            2,
-            2,
-            colors::TEXT_PRIMARY.as_u32(),
-        );
-        x += 6;
-
-        // MM
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (minutes / 10) as u8);
-        x += 10;
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (minutes % 10) as u8);
-        x += 12;
-
-        // :
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            x,
-            text_y + 2,
-            2,
-            2,
-            colors::TEXT_PRIMARY.as_u32(),
-        );
-        Self::fill_rect_simple(
-            buffer,
-            stride,
-            buffer_size,
-            x,
-            text_y + 8,
-            2,
-            2,
-            colors::TEXT_PRIMARY.as_u32(),
-        );
-        x += 6;
-
-        // SS
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (seconds / 10) as u8);
-        x += 10;
-        self.draw_digit(buffer, stride, buffer_size, x, text_y, (seconds % 10) as u8);
-    }
-
-    /// Desenha um dígito simples (7-segment style).
-    fn draw_digit(&self, buffer: &mut [u32], stride: usize, size: Size, x: i32, y: i32, digit: u8) {
-        let color = colors::TEXT_PRIMARY.as_u32();
-        let w = 6u32;
-        let h = 12u32;
-        let t = 2u32;
-
-        // Segmentos: top, top-left, top-right, middle, bottom-left, bottom-right, bottom
-        let segments: [u8; 10] = [
-            0b1110111, // 0
-            0b0010010, // 1
-            0b1011101, // 2
-            0b1011011, // 3
-            0b0111010, // 4
-            0b1101011, // 5
-            0b1101111, // 6
-            0b1010010, // 7
-            0b1111111, // 8
-            0b1111011, // 9
+        let digits = [
+            b'0' + (hours / 10) as u8,
+            b'0' + (hours % 10) as u8,
+            b':',
+            b'0' + (minutes / 10) as u8,
+            b'0' + (minutes % 10) as u8,
+            b':',
+            b'0' + (seconds / 10) as u8,
+            b'0' + (seconds % 10) as u8,
         ];
-
-        let seg = segments[digit as usize % 10];
-
-        if seg & 0b1000000 != 0 {
-            // top
-            Self::fill_rect_simple(buffer, stride, size, x, y, w, t, color);
-        }
-        if seg & 0b0100000 != 0 {
-            // top-left
-            Self::fill_rect_simple(buffer, stride, size, x, y, t, h / 2, color);
-        }
-        if seg & 0b0010000 != 0 {
-            // top-right
-            Self::fill_rect_simple(
-                buffer,
-                stride,
-                size,
-                x + w as i32 - t as i32,
-                y,
-                t,
-                h / 2,
-                color,
-            );
-        }
-        if seg & 0b0001000 != 0 {
-            // middle
-            Self::fill_rect_simple(
-                buffer,
-                stride,
-                size,
-                x,
-                y + h as i32 / 2 - t as i32 / 2,
-                w,
-                t,
-                color,
-            );
-        }
-        if seg & 0b0000100 != 0 {
-            // bottom-left
-            Self::fill_rect_simple(buffer, stride, size, x, y + h as i32 / 2, t, h / 2, color);
-        }
-        if seg & 0b0000010 != 0 {
-            // bottom-right
-            Self::fill_rect_simple(
-                buffer,
-                stride,
-                size,
-                x + w as i32 - t as i32,
-                y + h as i32 / 2,
-                t,
-                h / 2,
-                color,
-            );
-        }
-        if seg & 0b0000001 != 0 {
-            // bottom
-            Self::fill_rect_simple(
-                buffer,
-                stride,
-                size,
-                x,
-                y + h as i32 - t as i32,
-                w,
-                t,
-                color,
-            );
-        }
+        let text = core::str::from_utf8(&digits).unwrap_or("");
+
+        canvas.save();
+        canvas.clip(self.status_bar);
+        canvas.draw_text_centered(
+            text,
+            self.status_bar.x + self.status_bar.width as i32 / 2,
+            self.status_bar.y + self.status_bar.height as i32 / 2,
+            &TextStyle::new(fonts.regular(), colors::TEXT_PRIMARY)
+                .with_baseline(TextBaseline::Middle),
+        );
+        canvas.restore();
     }
 
     // =========================================================================
@@ -524,15 +452,14 @@ impl Taskbar {
             }
 
             // Verificar clique em janela
-            let sep_x = menu_area_end + 12;
-            let icon_size = 32i32;
-            let mut icon_x = sep_x;
+            let entry_width = metrics::WINDOW_ENTRY_WIDTH as i32;
+            let mut entry_x = self.entries_x();
 
             for entry in &self.entries {
-                if x >= icon_x && x < icon_x + icon_size {
+                if x >= entry_x && x < entry_x + entry_width {
                     return TaskbarAction::ToggleWindow(entry.id);
                 }
-                icon_x += icon_size + metrics::ICON_GAP as i32;
+                entry_x += entry_width + metrics::ICON_GAP as i32;
             }
         }
 
@@ -546,38 +473,4 @@ impl Taskbar {
             || self.center_bar.contains_point(point)
             || self.status_bar.contains_point(point)
     }
-
-    // =========================================================================
-    // HELPERS
-    // =========================================================================
-
-    fn fill_rect_simple(
-        buffer: &mut [u32],
-        stride: usize,
-        size: Size,
-        x: i32,
-        y: i32,
-        w: u32,
-        h: u32,
-        color: u32,
-    ) {
-        for dy in 0..h as i32 {
-            let py = y + dy;
-            if py < 0 || py >= size.height as i32 {
-                continue;
-            }
-
-            for dx in 0..w as i32 {
-                let px = x + dx;
-                if px < 0 || px >= size.width as i32 {
-                    continue;
-                }
-
-                let idx = py as usize * stride + px as usize;
-                if idx < buffer.len() {
-                    buffer[idx] = color;
-                }
-            }
-        }
-    }
 }
@@ -1,23 +1,164 @@
 //! # Wallpaper
 //!
 //! Componente de papel de parede.
-//! Tenta carregar webp, fallback para gradiente.
-// TODO: Revisar no futuro
-#[allow(unused)]
+//! Tenta carregar a imagem (PNG, QOI, BMP ou WebP), fallback para
+//! gradiente (arquivo ausente, grande demais ou inválido).
+//!
+//! O wallpaper é desenhado uma única vez em uma camada própria; cada frame
+//! apenas copia dessa camada as regiões danificadas. A imagem é escalada
+//! conforme o [`WallpaperMode`] só ao montar a camada (carga, mudança de
+//! modo ou de tamanho).
+//!
+//! Caminho, modo e cores vêm de um [`WallpaperConfig`], que pode ser
+//! trocado em tempo de execução. Com slideshow, as imagens de um diretório
+//! se alternam num intervalo, com cross-fade entre a camada anterior e a
+//! nova.
+use alloc::string::String;
+use alloc::vec::Vec;
 use gfx_types::color::Color;
-use gfx_types::geometry::{Rect, Size};
+use gfx_types::geometry::{Point, Rect, Size};
+use redpowder::fs::list_dir;
 
+use crate::render::image::{self, scale, ImageError};
+use crate::render::{Canvas, Image, Surface};
 use crate::theme::colors;
 
 // =============================================================================
 // CONSTANTES
 // =============================================================================
 
-// TODO: Revisar no futuro
-#[allow(unused)]
 /// Caminho do wallpaper padrão.
 const DEFAULT_WALLPAPER_PATH: &str = "/system/resources/wallpapers/default.webp";
 
+/// Intervalo padrão do slideshow (ms).
+const DEFAULT_SLIDESHOW_INTERVAL_MS: u64 = 5 * 60 * 1000;
+
+/// Duração padrão do cross-fade (ms).
+const DEFAULT_FADE_MS: u64 = 1000;
+
+// =============================================================================
+// MODO
+// =============================================================================
+
+/// Como a imagem ocupa a tela.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WallpaperMode {
+    /// Cobre a tela mantendo a proporção; o excesso é cortado.
+    #[default]
+    Fill,
+    /// Cabe inteira na tela mantendo a proporção; sobram faixas com a cor
+    /// de fundo.
+    Fit,
+    /// Ocupa a tela exata, distorcendo a proporção.
+    Stretch,
+    /// Tamanho original, centralizada.
+    Center,
+    /// Tamanho original, repetida a partir do canto superior esquerdo.
+    Tile,
+}
+
+impl WallpaperMode {
+    /// Modo pelo nome usado na configuração (`fill`, `fit`...).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fill" => Some(Self::Fill),
+            "fit" => Some(Self::Fit),
+            "stretch" => Some(Self::Stretch),
+            "center" => Some(Self::Center),
+            "tile" => Some(Self::Tile),
+            _ => None,
+        }
+    }
+
+    /// Modo pelo código usado no IPC (1 = fill ... 5 = tile).
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            1 => Some(Self::Fill),
+            2 => Some(Self::Fit),
+            3 => Some(Self::Stretch),
+            4 => Some(Self::Center),
+            5 => Some(Self::Tile),
+            _ => None,
+        }
+    }
+}
+
+// =============================================================================
+// CONFIGURAÇÃO
+// =============================================================================
+
+/// Configuração do wallpaper.
+#[derive(Debug, Clone)]
+pub struct WallpaperConfig {
+    /// Imagem (ignorada se o slideshow tiver imagens).
+    pub path: String,
+    /// Modo de escala.
+    pub mode: WallpaperMode,
+    /// Cor das áreas não cobertas pela imagem.
+    pub background: Color,
+    /// Topo do gradiente fallback.
+    pub gradient_top: Color,
+    /// Base do gradiente fallback.
+    pub gradient_bottom: Color,
+    /// Slideshow (desligado se `None`).
+    pub slideshow: Option<SlideshowConfig>,
+}
+
+impl Default for WallpaperConfig {
+    fn default() -> Self {
+        Self {
+            path: String::from(DEFAULT_WALLPAPER_PATH),
+            mode: WallpaperMode::default(),
+            background: colors::WALLPAPER_BACKGROUND,
+            gradient_top: colors::WALLPAPER_GRADIENT_TOP,
+            gradient_bottom: colors::WALLPAPER_GRADIENT_BOTTOM,
+            slideshow: None,
+        }
+    }
+}
+
+/// Configuração do slideshow.
+#[derive(Debug, Clone)]
+pub struct SlideshowConfig {
+    /// Diretório com as imagens (em ordem alfabética).
+    pub dir: String,
+    /// Tempo de cada imagem (ms).
+    pub interval_ms: u64,
+    /// Duração do cross-fade (ms, 0 = troca direta).
+    pub fade_ms: u64,
+}
+
+impl SlideshowConfig {
+    /// Slideshow de `dir` com intervalo e fade padrão.
+    pub fn new(dir: String) -> Self {
+        Self {
+            dir,
+            interval_ms: DEFAULT_SLIDESHOW_INTERVAL_MS,
+            fade_ms: DEFAULT_FADE_MS,
+        }
+    }
+}
+
+/// Estado do slideshow.
+struct Slideshow {
+    /// Imagens do diretório.
+    paths: Vec<String>,
+    /// Imagem atual.
+    index: usize,
+    /// Instante da próxima troca (definido no primeiro `update`).
+    next_at: Option<u64>,
+}
+
+/// Cross-fade em andamento.
+struct Fade {
+    /// Camada anterior, desenhada por baixo.
+    previous: Surface,
+    /// Início (definido no primeiro `update`).
+    start: Option<u64>,
+    /// Opacidade atual da camada nova.
+    opacity: u8,
+}
+
 // =============================================================================
 // WALLPAPER
 // =============================================================================
@@ -27,128 +168,273 @@ pub struct Wallpaper {
     /// Bounds do wallpaper.
     bounds: Rect,
     /// Imagem carregada (se disponível).
-    image_data: Option<WallpaperImage>,
+    image_data: Option<Image>,
     /// Usa gradiente fallback.
     use_gradient: bool,
-}
-
-/// Dados da imagem de wallpaper.
-struct WallpaperImage {
-    width: u32,
-    height: u32,
-    pixels: alloc::vec::Vec<u32>,
+    /// Configuração atual.
+    config: WallpaperConfig,
+    /// Camada pré-renderizada.
+    layer: Surface,
+    /// Slideshow ativo.
+    slideshow: Option<Slideshow>,
+    /// Cross-fade em andamento.
+    fade: Option<Fade>,
 }
 
 impl Wallpaper {
-    /// Cria wallpaper com bounds.
-    pub fn new(width: u32, height: u32) -> Self {
+    /// Cria wallpaper com bounds e configuração.
+    pub fn new(width: u32, height: u32, config: WallpaperConfig) -> Self {
         let mut wallpaper = Self {
             bounds: Rect::new(0, 0, width, height),
             image_data: None,
             use_gradient: true,
+            config: WallpaperConfig::default(),
+            layer: Surface::new(Size::new(width, height)),
+            slideshow: None,
+            fade: None,
         };
 
-        // Tentar carregar imagem
-        wallpaper.try_load_image();
-
+        wallpaper.apply_config(config);
         wallpaper
     }
 
-    /// Tenta carregar a imagem do wallpaper.
-    fn try_load_image(&mut self) {
-        // TODO: Implementar carregamento de webp quando tivermos decoder
-        // Por enquanto, sempre usa gradiente
-        redpowder::println!("[Wallpaper] Usando gradiente fallback (webp não suportado ainda)");
-        self.use_gradient = true;
+    /// Configuração atual.
+    pub fn config(&self) -> &WallpaperConfig {
+        &self.config
+    }
+
+    /// Aplica uma configuração: recarrega a imagem (ou o slideshow) e
+    /// remonta a camada, sem fade.
+    pub fn apply_config(&mut self, config: WallpaperConfig) {
+        self.config = config;
+        self.fade = None;
+        self.slideshow = self.config.slideshow.as_ref().and_then(Slideshow::scan);
+
+        let path = match self.slideshow {
+            Some(ref slideshow) => slideshow.paths[0].clone(),
+            None => self.config.path.clone(),
+        };
+        self.load_image(&path);
+        self.render_layer();
     }
 
-    /// Desenha o wallpaper no buffer.
-    pub fn draw(&self, buffer: &mut [u32], buffer_size: Size) {
-        if let Some(ref image) = self.image_data {
-            self.draw_image(buffer, buffer_size, image);
-        } else {
-            self.draw_gradient(buffer, buffer_size);
+    /// Avança slideshow e fade.
+    ///
+    /// Retorna `true` se a tela do wallpaper precisa ser redesenhada.
+    pub fn update(&mut self, now_ms: u64) -> bool {
+        let mut changed = false;
+
+        if let Some(ref mut fade) = self.fade {
+            let duration = self.config.slideshow.as_ref().map_or(0, |s| s.fade_ms);
+            let elapsed = now_ms.saturating_sub(*fade.start.get_or_insert(now_ms));
+            if elapsed >= duration {
+                self.fade = None;
+                changed = true;
+            } else {
+                let opacity = (elapsed * 255 / duration) as u8;
+                if opacity != fade.opacity {
+                    fade.opacity = opacity;
+                    changed = true;
+                }
+            }
+        }
+
+        let interval = self.config.slideshow.as_ref().map_or(0, |s| s.interval_ms);
+        if let Some(ref mut slideshow) = self.slideshow {
+            let next_at = *slideshow.next_at.get_or_insert(now_ms + interval);
+            if now_ms >= next_at && slideshow.paths.len() > 1 {
+                slideshow.next_at = Some(now_ms + interval);
+                slideshow.index = (slideshow.index + 1) % slideshow.paths.len();
+                let path = slideshow.paths[slideshow.index].clone();
+                changed |= self.show_next(&path);
+            }
         }
+
+        changed
     }
 
-    /// Desenha imagem.
-    fn draw_image(&self, buffer: &mut [u32], buffer_size: Size, image: &WallpaperImage) {
-        let stride = buffer_size.width as usize;
+    /// Troca para a próxima imagem do slideshow, com fade.
+    ///
+    /// Se a imagem falhar, a atual continua na tela até a próxima troca.
+    fn show_next(&mut self, path: &str) -> bool {
+        let previous_image = self.image_data.take();
+        if !self.load_image(path) {
+            self.image_data = previous_image;
+            self.use_gradient = self.image_data.is_none();
+            return false;
+        }
 
-        // Escalar/centralizar imagem conforme necessário
-        for y in 0..self.bounds.height.min(image.height) {
-            let src_y = y as usize;
-            let dst_y = (self.bounds.y as u32 + y) as usize;
+        let fade_ms = self.config.slideshow.as_ref().map_or(0, |s| s.fade_ms);
+        let previous = core::mem::replace(&mut self.layer, Surface::new(Size::new(0, 0)));
+        self.render_layer();
+        self.fade = (fade_ms > 0).then_some(Fade {
+            previous,
+            start: None,
+            opacity: 0,
+        });
+        true
+    }
 
-            if dst_y >= buffer_size.height as usize {
-                continue;
+    /// Carrega a imagem do wallpaper.
+    ///
+    /// Retorna `false` (e liga o gradiente fallback) se falhar.
+    fn load_image(&mut self, path: &str) -> bool {
+        self.image_data = None;
+        self.use_gradient = true;
+
+        match image::load(path) {
+            Ok(image) => {
+                redpowder::println!(
+                    "[Wallpaper] {} carregado ({}x{})",
+                    path,
+                    image.width,
+                    image.height
+                );
+                self.image_data = Some(image);
+                self.use_gradient = false;
+                true
+            }
+            Err(ImageError::Io) => {
+                redpowder::println!("[Wallpaper] Usando gradiente fallback");
+                false
+            }
+            Err(e) => {
+                redpowder::println!("[Wallpaper] ERRO: Falha ao carregar {}: {:?}", path, e);
+                redpowder::println!("[Wallpaper] Usando gradiente fallback");
+                false
             }
+        }
+    }
 
-            for x in 0..self.bounds.width.min(image.width) {
-                let src_x = x as usize;
-                let dst_x = (self.bounds.x as u32 + x) as usize;
+    /// Desenha o wallpaper no canvas (copia da camada, respeitando o clip).
+    ///
+    /// Durante o cross-fade, a camada nova é composta sobre a anterior.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let origin = Point::new(self.bounds.x, self.bounds.y);
+        match self.fade {
+            Some(ref fade) => {
+                canvas.blit(fade.previous.pixels(), fade.previous.size(), origin);
+                canvas.draw_surface(&self.layer, origin, fade.opacity);
+            }
+            None => canvas.blit(self.layer.pixels(), self.layer.size(), origin),
+        }
+    }
 
-                if dst_x >= buffer_size.width as usize {
-                    continue;
-                }
+    /// Renderiza imagem ou gradiente na camada.
+    ///
+    /// Só precisa ser chamado quando o tamanho ou a imagem mudam.
+    fn render_layer(&mut self) {
+        let mut layer = core::mem::replace(&mut self.layer, Surface::new(Size::new(0, 0)));
+        layer.resize(Size::new(self.bounds.width, self.bounds.height));
+
+        {
+            let mut canvas = layer.canvas();
+            canvas.translate(-self.bounds.x, -self.bounds.y);
+            if let Some(ref image) = self.image_data {
+                self.draw_image(&mut canvas, image);
+            } else {
+                self.draw_gradient(&mut canvas);
+            }
+        }
 
-                let src_idx = src_y * image.width as usize + src_x;
-                let dst_idx = dst_y * stride + dst_x;
+        self.layer = layer;
+    }
 
-                if src_idx < image.pixels.len() && dst_idx < buffer.len() {
-                    buffer[dst_idx] = image.pixels[src_idx];
+    /// Desenha imagem conforme o modo.
+    fn draw_image(&self, canvas: &mut Canvas, image: &Image) {
+        let bounds = self.bounds;
+        if bounds.width == 0 || bounds.height == 0 {
+            return;
+        }
+        canvas.save();
+        canvas.clip(bounds);
+        // Fundo para faixas e pixels transparentes
+        canvas.fill_rect(bounds, self.config.background);
+
+        match self.config.mode {
+            WallpaperMode::Fill => {
+                let region = fill_region(image, bounds.width, bounds.height);
+                let scaled = scale::resize_region(image, region, bounds.width, bounds.height);
+                self.blit_scaled(canvas, scaled, Point::new(bounds.x, bounds.y));
+            }
+            WallpaperMode::Fit => {
+                let (width, height) = fit_size(image, bounds.width, bounds.height);
+                let origin = centered(bounds, width, height);
+                self.blit_scaled(canvas, scale::resize(image, width, height), origin);
+            }
+            WallpaperMode::Stretch => {
+                let scaled = scale::resize(image, bounds.width, bounds.height);
+                self.blit_scaled(canvas, scaled, Point::new(bounds.x, bounds.y));
+            }
+            WallpaperMode::Center => {
+                let origin = centered(bounds, image.width, image.height);
+                canvas.blit_blend(&image.pixels, image_size(image), origin);
+            }
+            WallpaperMode::Tile => {
+                for y in (0..bounds.height).step_by(image.height as usize) {
+                    for x in (0..bounds.width).step_by(image.width as usize) {
+                        canvas.blit_blend(
+                            &image.pixels,
+                            image_size(image),
+                            Point::new(bounds.x + x as i32, bounds.y + y as i32),
+                        );
+                    }
                 }
             }
         }
+
+        canvas.restore();
     }
 
-    /// Desenha gradiente vertical (fallback).
-    fn draw_gradient(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
+    /// Desenha o resultado de uma escala (ou registra a falha).
+    fn blit_scaled(&self, canvas: &mut Canvas, scaled: Result<Image, ImageError>, origin: Point) {
+        match scaled {
+            Ok(image) => canvas.blit_blend(&image.pixels, image_size(&image), origin),
+            Err(e) => {
+                redpowder::println!(
+                    "[Wallpaper] ERRO: Falha ao escalar para {:?}: {:?}",
+                    self.config.mode,
+                    e
+                );
+            }
+        }
+    }
 
-        let top = colors::WALLPAPER_GRADIENT_TOP;
-        let bottom = colors::WALLPAPER_GRADIENT_BOTTOM;
+    /// Desenha gradiente vertical (fallback).
+    fn draw_gradient(&self, canvas: &mut Canvas) {
+        let top = self.config.gradient_top;
+        let bottom = self.config.gradient_bottom;
 
         for y in 0..self.bounds.height {
-            let dst_y = (self.bounds.y as u32 + y) as usize;
-            if dst_y >= buffer_size.height as usize {
-                continue;
-            }
-
             // Interpolação linear
             let t = y as f32 / self.bounds.height as f32;
-            let color = top.lerp(&bottom, t).as_u32();
-
-            let row_start = dst_y * stride + self.bounds.x as usize;
-            let row_end = (row_start + self.bounds.width as usize).min(buffer.len());
-
-            if row_start < buffer.len() {
-                buffer[row_start..row_end].fill(color);
-            }
+            let color = top.lerp(&bottom, t);
+
+            canvas.fill_rect(
+                Rect::new(
+                    self.bounds.x,
+                    self.bounds.y + y as i32,
+                    self.bounds.width,
+                    1,
+                ),
+                color,
+            );
         }
 
         // Adicionar sutil noise/pattern para não ficar flat
-        self.add_subtle_pattern(buffer, buffer_size);
+        self.add_subtle_pattern(canvas);
     }
 
     /// Adiciona pattern sutil ao gradiente.
-    fn add_subtle_pattern(&self, buffer: &mut [u32], buffer_size: Size) {
-        let stride = buffer_size.width as usize;
-
+    fn add_subtle_pattern(&self, canvas: &mut Canvas) {
         // Pattern diagonal sutil
         for y in (0..self.bounds.height).step_by(3) {
             for x in (0..self.bounds.width).step_by(3) {
-                let dst_x = (self.bounds.x as u32 + x) as usize;
-                let dst_y = (self.bounds.y as u32 + y) as usize;
-
-                if dst_x >= buffer_size.width as usize || dst_y >= buffer_size.height as usize {
-                    continue;
-                }
+                let px = self.bounds.x + x as i32;
+                let py = self.bounds.y + y as i32;
 
-                let idx = dst_y * stride + dst_x;
-                if idx < buffer.len() {
+                if let Some(pixel) = canvas.get_pixel(px, py) {
                     // Sutil variação (+/- 5 em brightness)
-                    let pixel = buffer[idx];
                     let variation = if ((x + y) / 3) % 2 == 0 { 5 } else { -5i32 };
 
                     let r = ((pixel >> 16) & 0xFF) as i32;
@@ -159,16 +445,185 @@ impl Wallpaper {
                     let g = (g + variation).clamp(0, 255) as u32;
                     let b = (b + variation).clamp(0, 255) as u32;
 
-                    buffer[idx] = 0xFF000000 | (r << 16) | (g << 8) | b;
+                    canvas.put_pixel(px, py, Color(0xFF000000 | (r << 16) | (g << 8) | b));
                 }
             }
         }
     }
 
+    // TODO: Revisar no futuro
+    #[allow(unused)]
+    /// Define o modo de escala da imagem.
+    pub fn set_mode(&mut self, mode: WallpaperMode) {
+        if self.config.mode != mode {
+            self.config.mode = mode;
+            self.render_layer();
+        }
+    }
+
+    // TODO: Revisar no futuro
+    #[allow(unused)]
+    /// Define a cor das áreas não cobertas pela imagem.
+    pub fn set_background(&mut self, color: Color) {
+        if self.config.background.0 != color.0 {
+            self.config.background = color;
+            self.render_layer();
+        }
+    }
+
     // TODO: Revisar no futuro
     #[allow(unused)]
     /// Define bounds.
     pub fn set_bounds(&mut self, x: i32, y: i32, width: u32, height: u32) {
         self.bounds = Rect::new(x, y, width, height);
+        self.fade = None;
+        self.render_layer();
+    }
+}
+
+impl Slideshow {
+    /// Lista as imagens do diretório do slideshow.
+    ///
+    /// Retorna `None` (slideshow desligado) se o diretório não puder ser
+    /// lido ou não tiver arquivos. O formato só é conferido ao carregar.
+    fn scan(config: &SlideshowConfig) -> Option<Self> {
+        let entries = match list_dir(&config.dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                redpowder::println!("[Wallpaper] ERRO: Falha ao listar {}: {:?}", config.dir, e);
+                return None;
+            }
+        };
+
+        let mut paths: Vec<String> = entries
+            .iter()
+            .filter(|entry| !entry.is_dir())
+            .map(|entry| alloc::format!("{}/{}", config.dir.trim_end_matches('/'), entry.name()))
+            .collect();
+        if paths.is_empty() {
+            redpowder::println!("[Wallpaper] Slideshow: nenhuma imagem em {}", config.dir);
+            return None;
+        }
+        paths.sort();
+
+        redpowder::println!(
+            "[Wallpaper] Slideshow: {} imagens em {}",
+            paths.len(),
+            config.dir
+        );
+        Some(Self {
+            paths,
+            index: 0,
+            next_at: None,
+        })
+    }
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Tamanho da imagem como [`Size`].
+fn image_size(image: &Image) -> Size {
+    Size::new(image.width, image.height)
+}
+
+/// Região central da imagem com a proporção da tela (modo fill).
+fn fill_region(image: &Image, width: u32, height: u32) -> Rect {
+    let (iw, ih) = (image.width as u64, image.height as u64);
+    let (w, h) = (width as u64, height as u64);
+    if iw * h > ih * w {
+        // Imagem mais larga que a tela: corta as laterais
+        let crop = (ih * w / h).max(1) as u32;
+        Rect::new(((image.width - crop) / 2) as i32, 0, crop, image.height)
+    } else {
+        // Imagem mais alta que a tela: corta topo e base
+        let crop = (iw * h / w).max(1) as u32;
+        Rect::new(0, ((image.height - crop) / 2) as i32, image.width, crop)
+    }
+}
+
+/// Maior tamanho com a proporção da imagem que cabe na tela (modo fit).
+fn fit_size(image: &Image, width: u32, height: u32) -> (u32, u32) {
+    let (iw, ih) = (image.width as u64, image.height as u64);
+    let (w, h) = (width as u64, height as u64);
+    if iw * h > ih * w {
+        (width, (ih * w / iw).max(1) as u32)
+    } else {
+        ((iw * h / ih).max(1) as u32, height)
+    }
+}
+
+/// Origem que centraliza `width` x `height` em `bounds`.
+fn centered(bounds: Rect, width: u32, height: u32) -> Point {
+    Point::new(
+        bounds.x + (bounds.width as i32 - width as i32) / 2,
+        bounds.y + (bounds.height as i32 - height as i32) / 2,
+    )
+}
+
+// =============================================================================
+// TESTES
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Imagem vazia `width x height` (só o tamanho importa).
+    fn image(width: u32, height: u32) -> Image {
+        Image {
+            width,
+            height,
+            pixels: Vec::new(),
+        }
+    }
+
+    /// [`fill_region`] como `(x, y, largura, altura)`.
+    fn crop(
+        width: u32,
+        height: u32,
+        screen_width: u32,
+        screen_height: u32,
+    ) -> (i32, i32, u32, u32) {
+        let r = fill_region(&image(width, height), screen_width, screen_height);
+        (r.x, r.y, r.width, r.height)
+    }
+
+    #[test]
+    fn fill_crops_to_the_screen_ratio() {
+        // Mais larga que a tela: corta as laterais
+        assert_eq!(crop(200, 100, 100, 100), (50, 0, 100, 100));
+        assert_eq!(crop(2560, 1080, 1920, 1080), (320, 0, 1920, 1080));
+
+        // Mais alta: corta topo e base
+        assert_eq!(crop(100, 200, 100, 100), (0, 50, 100, 100));
+        assert_eq!(crop(1600, 1200, 1920, 1080), (0, 150, 1600, 900));
+
+        // Mesma proporção: a imagem inteira
+        assert_eq!(crop(200, 100, 400, 200), (0, 0, 200, 100));
+    }
+
+    #[test]
+    fn fit_letterboxes_inside_the_screen() {
+        // Mais larga: faixas em cima e embaixo
+        assert_eq!(fit_size(&image(200, 100), 100, 100), (100, 50));
+        assert_eq!(fit_size(&image(2560, 1080), 1920, 1080), (1920, 810));
+
+        // Mais alta: faixas nas laterais
+        assert_eq!(fit_size(&image(100, 200), 100, 100), (50, 100));
+        assert_eq!(fit_size(&image(1600, 1200), 1920, 1080), (1440, 1080));
+
+        // Mesma proporção: a tela inteira, ampliando ou reduzindo
+        assert_eq!(fit_size(&image(200, 100), 400, 200), (400, 200));
+        assert_eq!(fit_size(&image(3840, 2160), 1920, 1080), (1920, 1080));
+    }
+
+    #[test]
+    fn extreme_ratios_never_collapse() {
+        assert_eq!(crop(1, 1000, 1000, 1), (0, 499, 1, 1));
+        assert_eq!(crop(1000, 1, 1, 1000), (499, 0, 1, 1));
+        assert_eq!(fit_size(&image(1, 1000), 1000, 1), (1, 1));
+        assert_eq!(fit_size(&image(1000, 1), 1, 1000), (1, 1));
     }
 }
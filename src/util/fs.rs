@@ -0,0 +1,108 @@
+//! # FS
+//!
+//! Leitura e gravação de arquivos inteiros sobre `redpowder::fs`.
+//!
+//! Leituras são feitas em blocos, com um teto de tamanho escolhido por
+//! quem chama. Gravações que substituem um arquivo passam por um arquivo
+//! temporário + rename, então um arquivo pela metade nunca fica no lugar
+//! do anterior.
+//!
+//! A gravação usa funções que o `redpowder` publicado ainda não tem
+//! (`File::create`, `File::write`, `create_dir`, `rename`, `remove_file`) e
+//! só existe com a feature `fs-write`.
+
+use alloc::vec::Vec;
+use redpowder::fs::File;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Tamanho do bloco de leitura.
+const READ_CHUNK_SIZE: usize = 4096;
+
+// =============================================================================
+// ERROS
+// =============================================================================
+
+/// Erro ao ler um arquivo inteiro.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReadError {
+    /// Arquivo ausente ou sem permissão.
+    Open,
+    /// Falha durante a leitura.
+    Read,
+    /// Arquivo maior que o limite pedido.
+    TooLarge,
+}
+
+// =============================================================================
+// LEITURA
+// =============================================================================
+
+/// Lê um arquivo inteiro, desistindo se passar de `max_len` bytes.
+pub fn read_file(path: &str, max_len: usize) -> Result<Vec<u8>, ReadError> {
+    let file = File::open(path).map_err(|_| ReadError::Open)?;
+    let mut data = Vec::new();
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+
+    loop {
+        match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                if data.len() + n > max_len {
+                    return Err(ReadError::TooLarge);
+                }
+                data.extend_from_slice(&chunk[..n]);
+            }
+            Err(_) => return Err(ReadError::Read),
+        }
+    }
+
+    Ok(data)
+}
+
+// =============================================================================
+// GRAVAÇÃO
+// =============================================================================
+
+/// Grava `data` em `path` via arquivo temporário + rename.
+#[cfg(feature = "fs-write")]
+pub fn write_atomic(path: &str, data: &[u8]) -> bool {
+    let tmp = alloc::format!("{}.tmp", path);
+    write_file(&tmp, data) && redpowder::fs::rename(&tmp, path).is_ok()
+}
+
+/// Grava `data` inteiro em `path` (o arquivo é fechado ao retornar).
+#[cfg(feature = "fs-write")]
+pub fn write_file(path: &str, data: &[u8]) -> bool {
+    let Ok(file) = File::create(path) else {
+        return false;
+    };
+    let mut written = 0;
+    while written < data.len() {
+        match file.write(&data[written..]) {
+            Ok(0) | Err(_) => return false,
+            Ok(n) => written += n,
+        }
+    }
+    true
+}
+
+/// Remove o arquivo `path` (false se não existir ou não puder ser removido).
+#[cfg(feature = "fs-write")]
+pub fn remove_file(path: &str) -> bool {
+    redpowder::fs::remove_file(path).is_ok()
+}
+
+/// Cria `path` e os diretórios pais que faltarem.
+///
+/// Erros são ignorados ("já existe" é o caso comum); quem grava depois
+/// reporta o que de fato falhou.
+#[cfg(feature = "fs-write")]
+pub fn create_dir_all(path: &str) {
+    for (i, _) in path.match_indices('/').skip(1) {
+        let _ = redpowder::fs::create_dir(&path[..i]);
+    }
+    let _ = redpowder::fs::create_dir(path);
+}
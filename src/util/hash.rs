@@ -0,0 +1,20 @@
+//! # Hash
+//!
+//! FNV-1a de 32 bits: rápido, sem tabelas e estável entre execuções, então
+//! serve para chaves gravadas em disco (checksum do `apps.cache`, cópias
+//! de ícones, cor dos avatares). Não é criptográfico.
+
+/// Valor inicial do hash.
+pub const FNV1A_SEED: u32 = 0x811C_9DC5;
+
+/// Primo multiplicador do FNV de 32 bits.
+const FNV1A_PRIME: u32 = 0x0100_0193;
+
+/// Acumula `bytes` no hash `hash` (começando em [`FNV1A_SEED`]).
+///
+/// Alimentar os dados em partes dá o mesmo resultado que de uma vez.
+pub fn fnv1a(hash: u32, bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(hash, |h, &b| (h ^ b as u32).wrapping_mul(FNV1A_PRIME))
+}
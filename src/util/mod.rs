@@ -0,0 +1,11 @@
+//! # Util Module
+//!
+//! Utilitários compartilhados pelos demais módulos.
+//!
+//! ## Componentes
+//!
+//! - **fs**: Leitura e gravação de arquivos inteiros
+//! - **hash**: Hash FNV-1a estável (checksums e chaves em disco)
+
+pub mod fs;
+pub mod hash;
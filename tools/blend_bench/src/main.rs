@@ -0,0 +1,210 @@
+//! # Blend Bench
+//!
+//! Compara o pipeline de blend atual (`render/pixel.rs`, pré-multiplicado,
+//! sem divisão, dois canais por operação) com a implementação anterior
+//! (ARGB direto, três divisões por 255 e índice checado por pixel).
+//!
+//! ```text
+//! cd tools/blend_bench
+//! cargo run --release
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// O benchmark usa só parte das operações
+#[allow(dead_code)]
+#[path = "../../../src/render/pixel.rs"]
+mod pixel;
+
+// =============================================================================
+// CONSTANTES
+// =============================================================================
+
+/// Resolução dos buffers (1080p).
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+
+/// Repetições de cada cenário; o resultado é a média.
+const ITERATIONS: u32 = 50;
+
+// =============================================================================
+// IMPLEMENTAÇÃO ANTERIOR
+// =============================================================================
+
+/// Blend e preenchimento como eram antes do pipeline pré-multiplicado.
+mod legacy {
+    /// Source-over em ARGB direto.
+    pub fn blend(dst: u32, src: u32) -> u32 {
+        let sa = (src >> 24) & 0xFF;
+        if sa == 0 {
+            return dst;
+        }
+        if sa == 255 {
+            return src;
+        }
+
+        let da = (dst >> 24) & 0xFF;
+        if da != 255 {
+            return blend_translucent(dst, src);
+        }
+
+        let inv_sa = 255 - sa;
+        let channel = |shift: u32| {
+            let s = (src >> shift) & 0xFF;
+            let d = (dst >> shift) & 0xFF;
+            (s * sa + d * inv_sa) / 255
+        };
+        0xFF000000 | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+    }
+
+    fn blend_translucent(dst: u32, src: u32) -> u32 {
+        let sa = (src >> 24) & 0xFF;
+        let da = (dst >> 24) & 0xFF;
+        let dw = da * (255 - sa) / 255;
+        let out_a = sa + dw;
+        if out_a == 0 {
+            return 0;
+        }
+        let channel = |shift: u32| {
+            let sc = (src >> shift) & 0xFF;
+            let dc = (dst >> shift) & 0xFF;
+            (sc * sa + dc * dw) / out_a
+        };
+        (out_a << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+    }
+
+    /// Retângulo com blend, checando limites pixel a pixel.
+    pub fn fill_rect_blend(buffer: &mut [u32], width: usize, height: usize, color: u32) {
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+                    let idx = y as usize * width + x as usize;
+                    buffer[idx] = blend(buffer[idx], color);
+                }
+            }
+        }
+    }
+
+    /// Composição de camada com opacidade.
+    pub fn composite(buffer: &mut [u32], layer: &[u32], opacity: u8) {
+        for (px, &s) in buffer.iter_mut().zip(layer) {
+            let a = ((s >> 24) & 0xFF) * opacity as u32 / 255;
+            *px = blend(*px, (s & 0x00FFFFFF) | (a << 24));
+        }
+    }
+}
+
+// =============================================================================
+// MAIN
+// =============================================================================
+
+fn main() {
+    let background: Vec<u32> = (0..WIDTH * HEIGHT)
+        .map(|i| 0xFF000000 | (i as u32).wrapping_mul(2654435761) >> 8)
+        .collect();
+
+    // Camada de painel: translúcida, como um vidro desenhado offscreen
+    let layer_straight: Vec<u32> = (0..WIDTH * HEIGHT)
+        .map(|i| 0xC0000000 | (i as u32).wrapping_mul(40503) & 0x00FFFFFF)
+        .collect();
+    let layer_premultiplied: Vec<u32> = layer_straight
+        .iter()
+        .map(|&c| pixel::premultiply(c))
+        .collect();
+
+    let color = 0xD0181822;
+    let color_premultiplied = pixel::premultiply(color);
+
+    println!(
+        "blend_bench: {}x{}, {} iterações",
+        WIDTH, HEIGHT, ITERATIONS
+    );
+    println!();
+    println!(
+        "{:<34} {:>10} {:>10} {:>8}",
+        "cenário", "anterior", "atual", "ganho"
+    );
+
+    compare(
+        "retângulo translúcido",
+        &background,
+        |buffer| legacy::fill_rect_blend(buffer, WIDTH, HEIGHT, color),
+        |buffer| {
+            let (rows, _) = buffer.as_chunks_mut::<WIDTH>();
+            for row in rows {
+                pixel::blend_span(row, color_premultiplied);
+            }
+        },
+    );
+
+    compare(
+        "camada (opacidade 255)",
+        &background,
+        |buffer| legacy::composite(buffer, &layer_straight, 255),
+        |buffer| {
+            let (rows, _) = buffer.as_chunks_mut::<WIDTH>();
+            let (layer_rows, _) = layer_premultiplied.as_chunks::<WIDTH>();
+            for (row, src) in rows.iter_mut().zip(layer_rows) {
+                pixel::blend_span_from(row, src);
+            }
+        },
+    );
+
+    compare(
+        "camada (opacidade 128)",
+        &background,
+        |buffer| legacy::composite(buffer, &layer_straight, 128),
+        |buffer| {
+            let (rows, _) = buffer.as_chunks_mut::<WIDTH>();
+            let (layer_rows, _) = layer_premultiplied.as_chunks::<WIDTH>();
+            for (row, src) in rows.iter_mut().zip(layer_rows) {
+                pixel::blend_span_from_scaled(row, src, 128);
+            }
+        },
+    );
+
+    compare(
+        "pixel a pixel (escalar, sem span)",
+        &background,
+        |buffer| {
+            for px in buffer.iter_mut() {
+                *px = legacy::blend(*px, color);
+            }
+        },
+        |buffer| {
+            for px in buffer.iter_mut() {
+                *px = pixel::over(*px, color_premultiplied);
+            }
+        },
+    );
+}
+
+/// Mede `old` e `new` sobre cópias de `background` e imprime a linha.
+fn compare(name: &str, background: &[u32], old: impl Fn(&mut [u32]), new: impl Fn(&mut [u32])) {
+    let old_time = measure(background, old);
+    let new_time = measure(background, new);
+    println!(
+        "{:<34} {:>8.2}ms {:>8.2}ms {:>7.1}x",
+        name,
+        old_time.as_secs_f64() * 1000.0,
+        new_time.as_secs_f64() * 1000.0,
+        old_time.as_secs_f64() / new_time.as_secs_f64()
+    );
+}
+
+/// Tempo médio de `f` sobre uma cópia de `background`.
+fn measure(background: &[u32], f: impl Fn(&mut [u32])) -> Duration {
+    let mut buffer = background.to_vec();
+    f(&mut buffer); // aquecimento
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        buffer.copy_from_slice(background);
+        let start = Instant::now();
+        f(black_box(&mut buffer));
+        total += start.elapsed();
+        black_box(&buffer);
+    }
+    total / ITERATIONS
+}